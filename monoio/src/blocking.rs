@@ -1,6 +1,16 @@
 //! Blocking tasks related.
 
-use std::{future::Future, task::Poll};
+use std::{
+    cell::RefCell,
+    future::Future,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    task::Poll,
+    time::{Duration, Instant},
+};
 
 use threadpool::{Builder as ThreadPoolBuilder, ThreadPool as ThreadPoolImpl};
 
@@ -15,6 +25,16 @@ pub trait ThreadPool {
     /// Monoio runtime will call `schedule_task` on `spawn_blocking`.
     /// ThreadPool impl must execute it now or later.
     fn schedule_task(&self, task: BlockingTask);
+
+    /// Monoio runtime will call `shutdown` on `Runtime::shutdown`.
+    /// ThreadPool impl should stop accepting tasks, wait up to `timeout` for the
+    /// queued and running ones, then release its threads. Returns whether all
+    /// tasks finished in time.
+    /// The default impl does nothing and returns true.
+    fn shutdown(&self, timeout: Duration) -> bool {
+        let _ = timeout;
+        true
+    }
 }
 
 /// Error on waiting blocking task.
@@ -89,12 +109,14 @@ pub enum BlockingStrategy {
 /// Users can also set `BlockingStrategy` for a runtime when there is no thread pool.
 /// WARNING: DO NOT USE THIS FOR ASYNC TASK! Async tasks will not be executed but only built the
 /// future!
-pub fn spawn_blocking<F, R>(func: F) -> JoinHandle<Result<R, JoinError>>
+/// The returned handle can cancel the task, see [`BlockingJoinHandle::cancel`].
+pub fn spawn_blocking<F, R>(func: F) -> BlockingJoinHandle<R>
 where
     F: FnOnce() -> R + Send + 'static,
     R: Send + 'static,
 {
-    let fut = BlockingFuture(Some(func));
+    let canceled = Arc::new(AtomicBool::new(false));
+    let fut = BlockingFuture(Some(func), canceled.clone());
     let (task, join) = new_task(DEFAULT_THREAD_ID, fut, NoopScheduler);
     crate::runtime::CURRENT.with(|inner| {
         let handle = &inner.blocking_handle;
@@ -115,31 +137,277 @@ where
         }
     });
 
-    join
+    BlockingJoinHandle { join, canceled }
+}
+
+/// Handle to a task spawned with [`spawn_blocking`], awaiting it returns the output of
+/// the task.
+pub struct BlockingJoinHandle<R> {
+    join: JoinHandle<Result<R, JoinError>>,
+    canceled: Arc<AtomicBool>,
+}
+
+impl<R> BlockingJoinHandle<R> {
+    /// Cancel the task, the handle then returns `JoinError::Canceled`.
+    /// If the task has not started yet, it will never run. If it is running, it is
+    /// detached: it runs to completion on its thread and its output is dropped.
+    pub fn cancel(&self) {
+        self.canceled.store(true, Ordering::Release);
+    }
+
+    /// Returns true if the task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.join.is_finished()
+    }
+
+    /// Wait for the task at most `timeout`, then cancel it, see
+    /// [`cancel`](Self::cancel). The timer is required to be enabled.
+    pub async fn timeout(self, timeout: Duration) -> Result<R, JoinError> {
+        let canceled = self.canceled.clone();
+        match crate::time::timeout(timeout, self).await {
+            Ok(res) => res,
+            Err(_) => {
+                canceled.store(true, Ordering::Release);
+                Err(JoinError::Canceled)
+            }
+        }
+    }
+}
+
+impl<R> Future for BlockingJoinHandle<R> {
+    type Output = Result<R, JoinError>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Self::Output> {
+        if let Poll::Ready(res) = std::pin::Pin::new(&mut self.join).poll(cx) {
+            return Poll::Ready(res);
+        }
+        if self.canceled.load(Ordering::Acquire) {
+            return Poll::Ready(Err(JoinError::Canceled));
+        }
+        Poll::Pending
+    }
+}
+
+impl<R> std::fmt::Debug for BlockingJoinHandle<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingJoinHandle")
+            .field("canceled", &self.canceled.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Run blocking tasks borrowing from the enclosing async frame, like
+/// `std::thread::scope` does for threads.
+///
+/// `f` spawns the tasks with [`Scope::spawn_blocking`], then the returned future
+/// waits for all of them before returning the output of `f`. If the future is
+/// dropped early, the drop blocks the thread until the running tasks finished.
+///
+/// ```
+/// # fn main() {
+/// # let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+/// #     .attach_thread_pool(Box::new(monoio::blocking::DefaultThreadPool::new(2)))
+/// #     .build()
+/// #     .unwrap();
+/// # rt.block_on(async {
+/// let data = vec![1, 2, 3, 4];
+/// let (a, b) = unsafe {
+///     monoio::blocking::scope(|s| {
+///         let (left, right) = data.split_at(2);
+///         (
+///             s.spawn_blocking(|| left.iter().sum::<i32>()),
+///             s.spawn_blocking(|| right.iter().sum::<i32>()),
+///         )
+///     })
+/// }
+/// .await;
+/// assert_eq!(a.join().unwrap() + b.join().unwrap(), 10);
+/// # });
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// The returned future must not be leaked, with `std::mem::forget` or a reference
+/// cycle, once it has been polled: the tasks would keep using the borrowed data
+/// after it is freed. Dropping it or polling it to completion is fine.
+pub async unsafe fn scope<'env, F, T>(f: F) -> T
+where
+    F: FnOnce(&Scope<'env>) -> T,
+{
+    let scope = Scope {
+        jobs: RefCell::new(Vec::new()),
+        pending: Default::default(),
+        _env: PhantomData,
+    };
+    let _wait = WaitPending(scope.pending.clone());
+    let out = f(&scope);
+    let jobs = std::mem::take(&mut *scope.jobs.borrow_mut());
+    for job in jobs {
+        let _ = job.await;
+    }
+    out
+}
+
+/// Scope to spawn blocking tasks borrowing data, see [`scope`].
+pub struct Scope<'env> {
+    jobs: RefCell<Vec<BlockingJoinHandle<()>>>,
+    pending: Arc<Pending>,
+    _env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawn a blocking task which may borrow data living as long as the scope,
+    /// see [`spawn_blocking`].
+    pub fn spawn_blocking<F, R>(&self, func: F) -> ScopedJoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'env,
+        R: Send + 'static,
+    {
+        let output = Arc::new(Mutex::new(None));
+        let slot = output.clone();
+        let guard = PendingGuard::new(self.pending.clone());
+        let job: Box<dyn FnOnce() + Send + 'env> = Box::new(move || {
+            let _guard = guard;
+            *slot.lock().unwrap() = Some(func());
+        });
+        // Safety: the scope waits for the job, which is dropped once it ran or was
+        // canceled, before the borrowed data goes away.
+        let job: Box<dyn FnOnce() + Send + 'static> = unsafe { std::mem::transmute(job) };
+        self.jobs.borrow_mut().push(spawn_blocking(job));
+        ScopedJoinHandle { output }
+    }
+}
+
+/// Handle to a task spawned with [`Scope::spawn_blocking`].
+#[derive(Debug)]
+pub struct ScopedJoinHandle<R> {
+    output: Arc<Mutex<Option<R>>>,
+}
+
+impl<R> ScopedJoinHandle<R> {
+    /// Returns the output of the task, once the scope returned.
+    /// `JoinError::Canceled` is returned if the task did not complete.
+    pub fn join(self) -> Result<R, JoinError> {
+        self.output
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or(JoinError::Canceled)
+    }
+}
+
+// Number of scoped tasks not dropped yet.
+#[derive(Default)]
+struct Pending {
+    count: Mutex<usize>,
+    zero: Condvar,
+}
+
+struct PendingGuard(Arc<Pending>);
+
+impl PendingGuard {
+    fn new(pending: Arc<Pending>) -> Self {
+        *pending.count.lock().unwrap() += 1;
+        Self(pending)
+    }
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        let mut count = self.0.count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.0.zero.notify_all();
+        }
+    }
+}
+
+// Blocks until the scoped tasks are dropped, when the scope is dropped before
+// it completed.
+struct WaitPending(Arc<Pending>);
+
+impl Drop for WaitPending {
+    fn drop(&mut self) {
+        let count = self.0.count.lock().unwrap();
+        let _count = self.0.zero.wait_while(count, |count| *count > 0).unwrap();
+    }
 }
 
 /// DefaultThreadPool is a simple wrapped `threadpool::ThreadPool` that implement
 /// `monoio::blocking::ThreadPool`. You may use this implementation, or you can use your own thread
 /// pool implementation.
+///
+/// The pool is shut down in three phases: new tasks are canceled, queued and running
+/// tasks are waited for until the deadline, then the threads exit once the last
+/// clone of the pool is dropped. Tasks still queued at the deadline are canceled,
+/// running ones are detached.
 #[derive(Clone)]
 pub struct DefaultThreadPool {
     pool: ThreadPoolImpl,
+    state: Arc<PoolState>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    // No more tasks are accepted.
+    closed: AtomicBool,
+    // Queued tasks are dropped instead of run.
+    abandoned: AtomicBool,
 }
 
 impl DefaultThreadPool {
-    /// Create a new DefaultThreadPool.
+    /// Create a new DefaultThreadPool, its threads are named `monoio-blocking`.
     pub fn new(num_threads: usize) -> Self {
+        Self::with_thread_name(num_threads, "monoio-blocking")
+    }
+
+    /// Create a new DefaultThreadPool whose threads are named `name`.
+    pub fn with_thread_name(num_threads: usize, name: impl Into<String>) -> Self {
         let pool = ThreadPoolBuilder::default()
             .num_threads(num_threads)
+            .thread_name(name.into())
             .build();
-        Self { pool }
+        Self {
+            pool,
+            state: Default::default(),
+        }
     }
 }
 
 impl ThreadPool for DefaultThreadPool {
     #[inline]
     fn schedule_task(&self, task: BlockingTask) {
-        self.pool.execute(move || task.run());
+        // Dropping the task cancels it.
+        if self.state.closed.load(Ordering::Acquire) {
+            return;
+        }
+        let state = self.state.clone();
+        self.pool.execute(move || {
+            if !state.abandoned.load(Ordering::Acquire) {
+                task.run();
+            }
+        });
+    }
+
+    fn shutdown(&self, timeout: Duration) -> bool {
+        self.state.closed.store(true, Ordering::Release);
+
+        // A worker counts a task as active before removing it from the queue, so
+        // the sum does not drop to 0 while a task is handed over.
+        let deadline = Instant::now() + timeout;
+        while self.pool.queued_count() + self.pool.active_count() > 0 {
+            if Instant::now() >= deadline {
+                self.state.abandoned.store(true, Ordering::Release);
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        self.pool.join();
+        true
     }
 }
 
@@ -160,13 +428,22 @@ pub(crate) enum BlockingHandle {
     Empty(BlockingStrategy),
 }
 
+impl BlockingHandle {
+    pub(crate) fn shutdown(&self, timeout: Duration) -> bool {
+        match self {
+            BlockingHandle::Attached(pool) => pool.shutdown(timeout),
+            BlockingHandle::Empty(_) => true,
+        }
+    }
+}
+
 impl From<BlockingStrategy> for BlockingHandle {
     fn from(value: BlockingStrategy) -> Self {
         Self::Empty(value)
     }
 }
 
-struct BlockingFuture<F>(Option<F>);
+struct BlockingFuture<F>(Option<F>, Arc<AtomicBool>);
 
 impl<T> Unpin for BlockingFuture<T> {}
 
@@ -183,6 +460,9 @@ where
     ) -> std::task::Poll<Self::Output> {
         let me = &mut *self;
         let func = me.0.take().expect("blocking task ran twice.");
+        if me.1.load(Ordering::Acquire) {
+            return Poll::Ready(Err(JoinError::Canceled));
+        }
         Poll::Ready(Ok(func()))
     }
 }
@@ -233,6 +513,21 @@ mod tests {
         });
     }
 
+    #[test]
+    fn pool_thread_name() {
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(DefaultThreadPool::with_thread_name(
+                1,
+                "blocking-test",
+            )))
+            .build()
+            .unwrap();
+        let name = rt.block_on(async {
+            crate::spawn_blocking(|| std::thread::current().name().map(str::to_string)).await
+        });
+        assert_eq!(name.unwrap().as_deref(), Some("blocking-test"));
+    }
+
     #[test]
     #[should_panic]
     fn blocking_panic() {
@@ -325,4 +620,160 @@ mod tests {
             assert_eq!(result4.unwrap(), "hello spawn_blocking4!");
         });
     }
+
+    #[test]
+    fn shutdown_drain() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let pool = DefaultThreadPool::new(1);
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(pool.clone()))
+            .build()
+            .unwrap();
+        let done = Arc::new(AtomicUsize::new(0));
+        rt.block_on(async {
+            for _ in 0..2 {
+                let done = done.clone();
+                // The join handles are dropped, the tasks keep running.
+                drop(crate::spawn_blocking(move || {
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                    done.fetch_add(1, Ordering::Relaxed);
+                }));
+            }
+        });
+        assert!(rt.shutdown(std::time::Duration::from_secs(5)));
+        assert_eq!(done.load(Ordering::Relaxed), 2);
+
+        // The pool does not accept tasks anymore.
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(pool))
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let ret = crate::spawn_blocking(|| 1).await;
+            assert!(matches!(ret, Err(super::JoinError::Canceled)));
+        });
+    }
+
+    #[test]
+    fn shutdown_timeout() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+            .build()
+            .unwrap();
+        let done = Arc::new(AtomicUsize::new(0));
+        rt.block_on(async {
+            for _ in 0..2 {
+                let done = done.clone();
+                drop(crate::spawn_blocking(move || {
+                    std::thread::sleep(std::time::Duration::from_millis(200));
+                    done.fetch_add(1, Ordering::Relaxed);
+                }));
+            }
+        });
+        let begin = std::time::Instant::now();
+        assert!(!rt.shutdown(std::time::Duration::from_millis(50)));
+        assert!(begin.elapsed() < std::time::Duration::from_millis(200));
+
+        // The running task is detached, the queued one is canceled.
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        assert_eq!(done.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn cancel_queued() {
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+            .build()
+            .unwrap();
+        let ran = Arc::new(AtomicBool::new(false));
+        rt.block_on(async {
+            let busy = crate::spawn_blocking(|| {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            });
+            let ran2 = ran.clone();
+            let queued = crate::spawn_blocking(move || ran2.store(true, Ordering::Relaxed));
+            queued.cancel();
+            assert!(matches!(queued.await, Err(super::JoinError::Canceled)));
+            busy.await.unwrap();
+        });
+        assert!(rt.shutdown(std::time::Duration::from_secs(5)));
+        assert!(!ran.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn timeout_detach() {
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+            .enable_timer()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let begin = std::time::Instant::now();
+            let ret = crate::spawn_blocking(|| {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+                1
+            })
+            .timeout(std::time::Duration::from_millis(50))
+            .await;
+            assert!(matches!(ret, Err(super::JoinError::Canceled)));
+            assert!(begin.elapsed() < std::time::Duration::from_millis(300));
+
+            let ret = crate::spawn_blocking(|| 2)
+                .timeout(std::time::Duration::from_secs(5))
+                .await;
+            assert_eq!(ret.unwrap(), 2);
+        });
+    }
+
+    #[test]
+    fn scope_borrow() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .attach_thread_pool(Box::new(DefaultThreadPool::new(2)))
+            .enable_timer()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut data = vec![1, 2, 3];
+            let sum = unsafe {
+                super::scope(|s| {
+                    let sum = s.spawn_blocking(|| data.iter().sum::<i32>());
+                    s.spawn_blocking(|| assert_eq!(data.len(), 3));
+                    sum
+                })
+            }
+            .await;
+            assert_eq!(sum.join().unwrap(), 6);
+            data.push(4);
+
+            // Dropping the scope waits for the running tasks.
+            let done = AtomicBool::new(false);
+            let scope = unsafe {
+                super::scope(|s| {
+                    s.spawn_blocking(|| {
+                        std::thread::sleep(std::time::Duration::from_millis(100));
+                        done.store(true, Ordering::Relaxed);
+                    });
+                })
+            };
+            let ret = crate::time::timeout(std::time::Duration::from_millis(10), scope).await;
+            assert!(ret.is_err());
+            assert!(done.load(Ordering::Relaxed));
+        });
+    }
 }
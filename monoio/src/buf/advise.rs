@@ -0,0 +1,41 @@
+use std::io;
+
+use super::IoBuf;
+use crate::{driver::op::Op, fs::Advice, BufResult};
+
+/// Give the kernel a hint about how the memory of `buf` will be accessed,
+/// like `madvise(2)`.
+///
+/// Only the whole pages inside the initialized part of the buffer are
+/// advised, so the memory around it is never affected. Small buffers may
+/// therefore not be advised at all. [`Advice::NoReuse`] is not supported for
+/// memory and returns an `InvalidInput` error.
+///
+/// Note that [`Advice::DontNeed`] discards the contents of anonymous memory,
+/// which is read back as zeros afterwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{buf::advise, fs::Advice};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let buf = vec![0u8; 1 << 20];
+///     let (res, _buf) = advise(buf, Advice::WillNeed).await;
+///     res
+/// }
+/// ```
+pub async fn advise<T: IoBuf>(buf: T, advice: Advice) -> BufResult<(), T> {
+    let Some(advice) = advice.madvise() else {
+        return (
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "advice not supported for memory",
+            )),
+            buf,
+        );
+    };
+    let completion = Op::madvise(buf, advice).unwrap().await;
+    (completion.meta.result.map(|_| ()), completion.data.buf)
+}
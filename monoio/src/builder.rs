@@ -18,13 +18,46 @@ use crate::{
 pub struct RuntimeBuilder<D> {
     // iouring entries
     entries: Option<u32>,
+    // timer wheel levels
+    timer_levels: usize,
+    // timer clock source
+    clock: Clock,
+    // driver picked by FusionDriver
+    driver: crate::utils::DriverKind,
 
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     urb: io_uring::Builder,
+    // register the ring fd if supported
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    register_ring_fd: bool,
+    // when queued SQEs are submitted
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    submit_policy: crate::driver::SubmitPolicy,
+    // how long to wait for completions on park
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    wait_policy: crate::driver::WaitPolicy,
+    // punt ops to io-wq by default
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    async_punt: bool,
+    // opcodes the ring is restricted to
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    restrictions: Option<Vec<u8>>,
+    // ring for bulk ops and its entries
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    bulk_ring: Option<(io_uring::Builder, u32)>,
 
     // blocking handle
     #[cfg(feature = "sync")]
     blocking_handle: crate::blocking::BlockingHandle,
+    // op interceptor
+    #[cfg(feature = "interceptor")]
+    interceptor: Option<std::sync::Arc<dyn crate::interceptor::Interceptor>>,
+    // cgroup v2 the runtime thread is moved into
+    #[cfg(target_os = "linux")]
+    cgroup: Option<std::path::PathBuf>,
+    // name given to the runtime thread
+    #[cfg(target_os = "linux")]
+    thread_name: Option<String>,
     // driver mark
     _mark: PhantomData<D>,
 }
@@ -45,12 +78,33 @@ impl<T> RuntimeBuilder<T> {
     pub fn new() -> Self {
         Self {
             entries: None,
+            timer_levels: crate::time::driver::DEFAULT_LEVELS,
+            clock: Clock::new(),
+            driver: crate::utils::DriverKind::Auto,
 
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb: io_uring::IoUring::builder(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: true,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy: Default::default(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            wait_policy: Default::default(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            async_punt: false,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            restrictions: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            bulk_ring: None,
 
             #[cfg(feature = "sync")]
             blocking_handle: crate::blocking::BlockingStrategy::Panic.into(),
+            #[cfg(feature = "interceptor")]
+            interceptor: None,
+            #[cfg(target_os = "linux")]
+            cgroup: None,
+            #[cfg(target_os = "linux")]
+            thread_name: None,
             _mark: PhantomData,
         }
     }
@@ -87,6 +141,13 @@ direct_build!(TimeDriver<LegacyDriver>);
 
 // ===== builder impl =====
 
+#[cfg(all(target_os = "linux", any(feature = "legacy", feature = "iouring")))]
+fn set_current_thread_name(name: &str) -> io::Result<()> {
+    let name = std::ffi::CString::new(name)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "thread name contains a nul"))?;
+    crate::syscall!(prctl(libc::PR_SET_NAME, name.as_ptr())).map(|_| ())
+}
+
 #[cfg(feature = "legacy")]
 impl Buildable for LegacyDriver {
     fn build(this: RuntimeBuilder<Self>) -> io::Result<Runtime<LegacyDriver>> {
@@ -94,15 +155,30 @@ impl Buildable for LegacyDriver {
         #[cfg(feature = "sync")]
         let blocking_handle = this.blocking_handle;
 
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &this.cgroup {
+            crate::utils::cgroup::move_current_thread(cgroup)?;
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(name) = &this.thread_name {
+            set_current_thread_name(name)?;
+        }
+
         BUILD_THREAD_ID.set(&thread_id, || {
             let driver = match this.entries {
                 Some(entries) => LegacyDriver::new_with_entries(entries)?,
                 None => LegacyDriver::new()?,
             };
             #[cfg(feature = "sync")]
-            let context = crate::runtime::Context::new(blocking_handle);
+            #[allow(unused_mut)]
+            let mut context = crate::runtime::Context::new(blocking_handle);
             #[cfg(not(feature = "sync"))]
-            let context = crate::runtime::Context::new();
+            #[allow(unused_mut)]
+            let mut context = crate::runtime::Context::new();
+            #[cfg(feature = "interceptor")]
+            {
+                context.interceptor = this.interceptor;
+            }
             Ok(Runtime::new(context, driver))
         })
     }
@@ -115,15 +191,52 @@ impl Buildable for IoUringDriver {
         #[cfg(feature = "sync")]
         let blocking_handle = this.blocking_handle;
 
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &this.cgroup {
+            crate::utils::cgroup::move_current_thread(cgroup)?;
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(name) = &this.thread_name {
+            set_current_thread_name(name)?;
+        }
+
+        // Restrictions can only be registered while the ring is disabled.
+        let mut urb = this.urb;
+        if this.restrictions.is_some() {
+            urb.setup_r_disabled();
+        }
+
         BUILD_THREAD_ID.set(&thread_id, || {
             let driver = match this.entries {
-                Some(entries) => IoUringDriver::new_with_entries(&this.urb, entries)?,
-                None => IoUringDriver::new(&this.urb)?,
+                Some(entries) => IoUringDriver::new_with_entries(&urb, entries)?,
+                None => IoUringDriver::new(&urb)?,
             };
+            if let Some((urb, entries)) = &this.bulk_ring {
+                let mut urb = urb.clone();
+                if this.restrictions.is_some() {
+                    urb.setup_r_disabled();
+                }
+                driver.enable_bulk_ring(&urb, *entries)?;
+            }
+            if this.register_ring_fd {
+                driver.register_ring_fd();
+            }
+            if let Some(opcodes) = &this.restrictions {
+                driver.restrict(opcodes)?;
+            }
+            driver.set_submit_policy(this.submit_policy);
+            driver.set_wait_policy(this.wait_policy);
+            driver.set_async_punt(this.async_punt);
             #[cfg(feature = "sync")]
-            let context = crate::runtime::Context::new(blocking_handle);
+            #[allow(unused_mut)]
+            let mut context = crate::runtime::Context::new(blocking_handle);
             #[cfg(not(feature = "sync"))]
-            let context = crate::runtime::Context::new();
+            #[allow(unused_mut)]
+            let mut context = crate::runtime::Context::new();
+            #[cfg(feature = "interceptor")]
+            {
+                context.interceptor = this.interceptor;
+            }
             Ok(Runtime::new(context, driver))
         })
     }
@@ -144,6 +257,46 @@ impl<D> RuntimeBuilder<D> {
         self
     }
 
+    /// Set the interceptor run around every op of the runtime, see
+    /// [`interceptor`](crate::interceptor).
+    #[cfg(feature = "interceptor")]
+    #[must_use]
+    pub fn with_interceptor(
+        mut self,
+        interceptor: std::sync::Arc<dyn crate::interceptor::Interceptor>,
+    ) -> Self {
+        self.interceptor = Some(interceptor);
+        self
+    }
+
+    /// Move the runtime thread into the cgroup v2 at `path` when building
+    /// the runtime, before the driver is created.
+    ///
+    /// The SQPOLL thread and io_uring workers of the runtime, as well as the
+    /// threads it spawns afterwards, start in the same cgroup. The cgroup
+    /// must be threaded, see
+    /// [`move_current_thread`](crate::utils::cgroup::move_current_thread).
+    #[cfg(target_os = "linux")]
+    #[must_use]
+    pub fn with_cgroup(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.cgroup = Some(path.into());
+        self
+    }
+
+    /// Name the runtime thread `name` when building the runtime, e.g.
+    /// `monoio-worker-0` for the first of a set of per-core runtimes.
+    ///
+    /// The name is shown by `top -H`, `ps -L` and in `/proc`, the kernel
+    /// truncates it to 15 bytes. The SQPOLL thread of the runtime is named
+    /// `iou-sqp-<tid>` by the kernel, after the
+    /// [`os_thread_id`](crate::Runtime::os_thread_id) of the runtime.
+    #[cfg(target_os = "linux")]
+    #[must_use]
+    pub fn with_thread_name(mut self, name: impl Into<String>) -> Self {
+        self.thread_name = Some(name.into());
+        self
+    }
+
     /// Replaces the default [`io_uring::Builder`], which controls the settings for the
     /// inner `io_uring` API.
     ///
@@ -155,6 +308,135 @@ impl<D> RuntimeBuilder<D> {
         self.urb = urb;
         self
     }
+
+    /// Set whether to register the ring fd(`IORING_REGISTER_RING_FDS`), which
+    /// saves an fd lookup on every `io_uring_enter`.
+    ///
+    /// It is enabled by default, and silently skipped if not supported by the
+    /// kernel(requires Linux 5.18+).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_registered_ring_fd(mut self, enabled: bool) -> Self {
+        self.register_ring_fd = enabled;
+        self
+    }
+
+    /// Set when the io_uring driver submits queued SQEs to the kernel, see
+    /// [`SubmitPolicy`](crate::SubmitPolicy). The legacy driver ignores it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_submit_policy(mut self, policy: crate::SubmitPolicy) -> Self {
+        self.submit_policy = policy;
+        self
+    }
+
+    /// Set how long the io_uring driver waits for completions when the
+    /// runtime parks, see [`WaitPolicy`](crate::WaitPolicy). The legacy
+    /// driver ignores it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_wait_policy(mut self, policy: crate::WaitPolicy) -> Self {
+        self.wait_policy = policy;
+        self
+    }
+
+    /// Set whether ops are submitted with `IOSQE_ASYNC` by default, which
+    /// makes the kernel punt them to its io-wq workers at once instead of
+    /// trying a nonblocking attempt first.
+    ///
+    /// It is disabled by default. Punting is worth it when most attempts
+    /// would block anyway, like buffered reads of cold data. It can be
+    /// overridden for some ops with [`async_punt`](crate::io::async_punt).
+    /// The legacy driver ignores it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_async_punt(mut self, enabled: bool) -> Self {
+        self.async_punt = enabled;
+        self
+    }
+
+    /// Restrict the io_uring to the given opcodes(`IORING_REGISTER_RESTRICTIONS`),
+    /// like [`io_uring::opcode::Read::CODE`], before the runtime is handed
+    /// to code which is not trusted. Ops with other opcodes fail with
+    /// `EACCES`, and so do all `io_uring_register` calls.
+    ///
+    /// The opcodes the driver needs for itself, to cancel ops, time out
+    /// parking and wake up the thread, are always allowed. That includes
+    /// `Read` and `PollAdd` with the `sync` feature, which reads an eventfd
+    /// through the ring. Building the
+    /// runtime fails if not supported by the kernel(requires Linux 5.10+).
+    /// The legacy driver ignores it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn restrict_opcodes(mut self, opcodes: &[u8]) -> Self {
+        self.restrictions = Some(opcodes.to_vec());
+        self
+    }
+
+    /// Add a second io_uring for ops tagged [`Qos::Bulk`](crate::io::Qos::Bulk)
+    /// with [`with_qos`](crate::io::with_qos), built from `urb` with
+    /// `entries`.
+    ///
+    /// Background io like compaction then has its own queues, and never
+    /// delays the completions of the latency critical ops on the main ring.
+    /// The bulk ring may use a different setup, like SQPOLL to save the
+    /// submission syscalls. The legacy driver ignores it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_bulk_ring(mut self, urb: io_uring::Builder, entries: u32) -> Self {
+        self.bulk_ring = Some((urb, entries));
+        self
+    }
+
+    /// Set the size of the io_uring completion queue(`IORING_SETUP_CQSIZE`),
+    /// which defaults to twice the entries.
+    ///
+    /// A larger completion queue avoids overflows when many ops complete
+    /// between two parks. The size must not be less than the entries, or
+    /// building the ring fails.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_cq_size(mut self, size: u32) -> Self {
+        self.urb.setup_cqsize(size);
+        self
+    }
+
+    /// Enable `IORING_SETUP_COOP_TASKRUN` and `IORING_SETUP_TASKRUN_FLAG`.
+    ///
+    /// The kernel no longer interrupts the runtime thread with an IPI to run
+    /// completion work, which is processed on the next kernel transition
+    /// instead. Requires Linux 5.19+, or building the ring fails.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_coop_taskrun(mut self) -> Self {
+        self.urb.setup_coop_taskrun().setup_taskrun_flag();
+        self
+    }
+
+    /// Enable `IORING_SETUP_SINGLE_ISSUER`.
+    ///
+    /// Hint the kernel that only the runtime thread submits to the ring.
+    /// Requires Linux 6.0+, or building the ring fails.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_single_issuer(mut self) -> Self {
+        self.urb.setup_single_issuer();
+        self
+    }
+
+    /// Enable `IORING_SETUP_DEFER_TASKRUN`, which implies
+    /// `IORING_SETUP_SINGLE_ISSUER`.
+    ///
+    /// Completion work is deferred until the runtime thread asks for events,
+    /// so it runs in batches and never interrupts running tasks. The driver
+    /// always enters the ring with `IORING_ENTER_GETEVENTS` in this mode.
+    /// Requires Linux 6.1+, or building the ring fails.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn with_defer_taskrun(mut self) -> Self {
+        self.urb.setup_single_issuer().setup_defer_taskrun();
+        self
+    }
 }
 
 // ===== FusionDriver =====
@@ -165,15 +447,42 @@ pub struct FusionDriver;
 
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
 impl RuntimeBuilder<FusionDriver> {
+    /// Set the driver to build, [`DriverKind::Auto`](crate::utils::DriverKind::Auto)
+    /// by default which picks io_uring if the kernel supports it and falls
+    /// back to the legacy driver otherwise.
+    ///
+    /// Building fails with `Unsupported` error if the driver is not enabled
+    /// in this build.
+    #[must_use]
+    pub fn with_driver(mut self, driver: crate::utils::DriverKind) -> Self {
+        self.driver = driver;
+        self
+    }
+
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<IoUringDriver, LegacyDriver>> {
-        if crate::utils::detect_uring() {
+        if self.use_uring() {
             let builder = RuntimeBuilder::<IoUringDriver> {
                 entries: self.entries,
+                timer_levels: self.timer_levels,
+                clock: self.clock,
+                driver: self.driver,
                 urb: self.urb,
+                register_ring_fd: self.register_ring_fd,
+                submit_policy: self.submit_policy,
+                wait_policy: self.wait_policy,
+                async_punt: self.async_punt,
+                restrictions: self.restrictions,
+                bulk_ring: self.bulk_ring,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
+                #[cfg(feature = "interceptor")]
+                interceptor: self.interceptor,
+                #[cfg(target_os = "linux")]
+                cgroup: self.cgroup,
+                #[cfg(target_os = "linux")]
+                thread_name: self.thread_name,
                 _mark: PhantomData,
             };
             info!("io_uring driver built");
@@ -181,9 +490,24 @@ impl RuntimeBuilder<FusionDriver> {
         } else {
             let builder = RuntimeBuilder::<LegacyDriver> {
                 entries: self.entries,
+                timer_levels: self.timer_levels,
+                clock: self.clock,
+                driver: self.driver,
                 urb: self.urb,
+                register_ring_fd: self.register_ring_fd,
+                submit_policy: self.submit_policy,
+                wait_policy: self.wait_policy,
+                async_punt: self.async_punt,
+                restrictions: self.restrictions,
+                bulk_ring: self.bulk_ring,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
+                #[cfg(feature = "interceptor")]
+                interceptor: self.interceptor,
+                #[cfg(target_os = "linux")]
+                cgroup: self.cgroup,
+                #[cfg(target_os = "linux")]
+                thread_name: self.thread_name,
                 _mark: PhantomData,
             };
             info!("legacy driver built");
@@ -194,10 +518,20 @@ impl RuntimeBuilder<FusionDriver> {
     /// Build the runtime.
     #[cfg(not(all(target_os = "linux", feature = "iouring")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<LegacyDriver>> {
+        self.check_driver(crate::utils::DriverKind::Legacy)?;
         let builder = RuntimeBuilder::<LegacyDriver> {
             entries: self.entries,
+            timer_levels: self.timer_levels,
+            clock: self.clock,
+            driver: self.driver,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor: self.interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup: self.cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name: self.thread_name,
             _mark: PhantomData,
         };
         Ok(builder.build()?.into())
@@ -206,11 +540,27 @@ impl RuntimeBuilder<FusionDriver> {
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", not(feature = "legacy")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<IoUringDriver>> {
+        self.check_driver(crate::utils::DriverKind::IoUring)?;
         let builder = RuntimeBuilder::<IoUringDriver> {
             entries: self.entries,
+            timer_levels: self.timer_levels,
+            clock: self.clock,
+            driver: self.driver,
             urb: self.urb,
+            register_ring_fd: self.register_ring_fd,
+            submit_policy: self.submit_policy,
+            wait_policy: self.wait_policy,
+            async_punt: self.async_punt,
+            restrictions: self.restrictions,
+            bulk_ring: self.bulk_ring,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor: self.interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup: self.cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name: self.thread_name,
             _mark: PhantomData,
         };
         Ok(builder.build()?.into())
@@ -219,17 +569,44 @@ impl RuntimeBuilder<FusionDriver> {
 
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
 impl RuntimeBuilder<TimeDriver<FusionDriver>> {
+    /// Set the driver to build, [`DriverKind::Auto`](crate::utils::DriverKind::Auto)
+    /// by default which picks io_uring if the kernel supports it and falls
+    /// back to the legacy driver otherwise.
+    ///
+    /// Building fails with `Unsupported` error if the driver is not enabled
+    /// in this build.
+    #[must_use]
+    pub fn with_driver(mut self, driver: crate::utils::DriverKind) -> Self {
+        self.driver = driver;
+        self
+    }
+
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
     pub fn build(
         self,
     ) -> io::Result<crate::FusionRuntime<TimeDriver<IoUringDriver>, TimeDriver<LegacyDriver>>> {
-        if crate::utils::detect_uring() {
+        if self.use_uring() {
             let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
                 entries: self.entries,
+                timer_levels: self.timer_levels,
+                clock: self.clock,
+                driver: self.driver,
                 urb: self.urb,
+                register_ring_fd: self.register_ring_fd,
+                submit_policy: self.submit_policy,
+                wait_policy: self.wait_policy,
+                async_punt: self.async_punt,
+                restrictions: self.restrictions,
+                bulk_ring: self.bulk_ring,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
+                #[cfg(feature = "interceptor")]
+                interceptor: self.interceptor,
+                #[cfg(target_os = "linux")]
+                cgroup: self.cgroup,
+                #[cfg(target_os = "linux")]
+                thread_name: self.thread_name,
                 _mark: PhantomData,
             };
             info!("io_uring driver with timer built");
@@ -237,9 +614,24 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
         } else {
             let builder = RuntimeBuilder::<TimeDriver<LegacyDriver>> {
                 entries: self.entries,
+                timer_levels: self.timer_levels,
+                clock: self.clock,
+                driver: self.driver,
                 urb: self.urb,
+                register_ring_fd: self.register_ring_fd,
+                submit_policy: self.submit_policy,
+                wait_policy: self.wait_policy,
+                async_punt: self.async_punt,
+                restrictions: self.restrictions,
+                bulk_ring: self.bulk_ring,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
+                #[cfg(feature = "interceptor")]
+                interceptor: self.interceptor,
+                #[cfg(target_os = "linux")]
+                cgroup: self.cgroup,
+                #[cfg(target_os = "linux")]
+                thread_name: self.thread_name,
                 _mark: PhantomData,
             };
             info!("legacy driver with timer built");
@@ -250,10 +642,20 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
     /// Build the runtime.
     #[cfg(not(all(target_os = "linux", feature = "iouring")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<TimeDriver<LegacyDriver>>> {
+        self.check_driver(crate::utils::DriverKind::Legacy)?;
         let builder = RuntimeBuilder::<TimeDriver<LegacyDriver>> {
             entries: self.entries,
+            timer_levels: self.timer_levels,
+            clock: self.clock,
+            driver: self.driver,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor: self.interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup: self.cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name: self.thread_name,
             _mark: PhantomData,
         };
         Ok(builder.build()?.into())
@@ -262,17 +664,57 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
     /// Build the runtime.
     #[cfg(all(target_os = "linux", feature = "iouring", not(feature = "legacy")))]
     pub fn build(self) -> io::Result<crate::FusionRuntime<TimeDriver<IoUringDriver>>> {
+        self.check_driver(crate::utils::DriverKind::IoUring)?;
         let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
             entries: self.entries,
+            timer_levels: self.timer_levels,
+            clock: self.clock,
+            driver: self.driver,
             urb: self.urb,
+            register_ring_fd: self.register_ring_fd,
+            submit_policy: self.submit_policy,
+            wait_policy: self.wait_policy,
+            async_punt: self.async_punt,
+            restrictions: self.restrictions,
+            bulk_ring: self.bulk_ring,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor: self.interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup: self.cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name: self.thread_name,
             _mark: PhantomData,
         };
         Ok(builder.build()?.into())
     }
 }
 
+#[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
+impl<D> RuntimeBuilder<D> {
+    #[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
+    fn use_uring(&self) -> bool {
+        match self.driver {
+            crate::utils::DriverKind::Auto => crate::utils::detect_uring(),
+            crate::utils::DriverKind::IoUring => true,
+            crate::utils::DriverKind::Legacy => false,
+        }
+    }
+
+    // With a single driver enabled, fail if another one is asked for.
+    #[cfg(not(all(target_os = "linux", feature = "iouring", feature = "legacy")))]
+    fn check_driver(&self, enabled: crate::utils::DriverKind) -> io::Result<()> {
+        if self.driver != crate::utils::DriverKind::Auto && self.driver != enabled {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "driver is not enabled in this build",
+            ));
+        }
+        Ok(())
+    }
+}
+
 // ===== enable_timer related =====
 mod time_wrap {
     pub trait TimeWrapable {}
@@ -296,14 +738,35 @@ where
             mut context,
         } = Buildable::build(RuntimeBuilder::<D> {
             entries: this.entries,
+            timer_levels: this.timer_levels,
+            clock: this.clock.clone(),
+            driver: this.driver,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb: this.urb,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: this.register_ring_fd,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy: this.submit_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            wait_policy: this.wait_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            async_punt: this.async_punt,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            restrictions: this.restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            bulk_ring: this.bulk_ring,
             #[cfg(feature = "sync")]
             blocking_handle: this.blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor: this.interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup: this.cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name: this.thread_name,
             _mark: PhantomData,
         })?;
 
-        let timer_driver = TimeDriver::new(driver, Clock::new());
+        let timer_driver = TimeDriver::new(driver, this.clock, this.timer_levels);
         context.time_handle = Some(timer_driver.handle.clone());
         Ok(Runtime {
             driver: timer_driver,
@@ -324,23 +787,99 @@ impl<D: time_wrap::TimeWrapable> RuntimeBuilder<D> {
     pub fn enable_timer(self) -> RuntimeBuilder<TimeDriver<D>> {
         let Self {
             entries,
+            timer_levels,
+            clock,
+            driver,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            wait_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            async_punt,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            bulk_ring,
             #[cfg(feature = "sync")]
             blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name,
             ..
         } = self;
         RuntimeBuilder {
             entries,
+            timer_levels,
+            clock,
+            driver,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            submit_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            wait_policy,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            async_punt,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            bulk_ring,
             #[cfg(feature = "sync")]
             blocking_handle,
+            #[cfg(feature = "interceptor")]
+            interceptor,
+            #[cfg(target_os = "linux")]
+            cgroup,
+            #[cfg(target_os = "linux")]
+            thread_name,
             _mark: PhantomData,
         }
     }
 }
 
+impl<D> RuntimeBuilder<TimeDriver<D>> {
+    /// Set the number of levels of the timer wheel, between 1 and 10, the
+    /// default is 6.
+    ///
+    /// Each level has 64 slots, and level `n` tracks timers up to `64^(n+1)`
+    /// milliseconds into the future. Farther timers wrap around in the top
+    /// level and are cascaded again when their slot expires, so fewer levels
+    /// save memory and scanning, and more levels avoid repeated cascades for
+    /// far timers.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `levels` is out of range.
+    #[must_use]
+    pub fn with_timer_levels(mut self, levels: usize) -> Self {
+        assert!(
+            (1..=crate::time::driver::MAX_LEVELS).contains(&levels),
+            "timer wheel levels must be between 1 and {}",
+            crate::time::driver::MAX_LEVELS
+        );
+        self.timer_levels = levels;
+        self
+    }
+
+    /// Set the clock source of the timer, e.g. a coarse clock that is cheaper
+    /// to read. See [`ClockSource`](crate::time::ClockSource) for the
+    /// requirements.
+    #[must_use]
+    pub fn with_clock<C: crate::time::ClockSource>(mut self, clock: C) -> Self {
+        self.clock = Clock::with_source(std::sync::Arc::new(clock));
+        self
+    }
+}
+
 impl<D> RuntimeBuilder<D> {
     /// Attach thread pool, this will overwrite blocking strategy.
     /// All `spawn_blocking` will be executed on given thread pool.
@@ -339,6 +339,8 @@ impl LegacyInner {
             // useless for legacy
             index: 0,
             data: Some(data),
+            #[cfg(feature = "interceptor")]
+            intercepted: None,
         })
     }
 
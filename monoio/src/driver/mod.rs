@@ -32,9 +32,11 @@ pub use self::legacy::LegacyDriver;
 use self::legacy::LegacyInner;
 use self::op::{CompletionMeta, Op, OpAble};
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-pub use self::uring::IoUringDriver;
+pub(crate) use self::uring::BufRing;
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use self::uring::UringInner;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use self::uring::{IoUringDriver, RingMessenger, SubmitPolicy, WaitPolicy};
 
 /// Unpark a runtime of another thread.
 pub(crate) mod unpark {
@@ -135,6 +137,28 @@ impl Inner {
         }
     }
 
+    fn poll_multishot<T: OpAble>(
+        &self,
+        _data: &mut T,
+        _index: usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<CompletionMeta> {
+        match self {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Inner::Uring(this) => UringInner::poll_multishot(this, _index, cx),
+            // Every completion of the legacy driver is the last one.
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(this) => LegacyInner::poll_op::<T>(this, _data, cx),
+            #[cfg(all(
+                not(feature = "legacy"),
+                not(all(target_os = "linux", feature = "iouring"))
+            ))]
+            _ => {
+                util::feature_panic();
+            }
+        }
+    }
+
     #[cfg(feature = "poll-io")]
     fn poll_legacy_op<T: OpAble>(
         &self,
@@ -211,6 +235,113 @@ impl Inner {
     }
 }
 
+/// Set whether the ops submitted to current driver are punted to io-wq with
+/// `IOSQE_ASYNC`, returns the previous setting. The legacy driver ignores it.
+pub(crate) fn replace_async_punt(enabled: bool) -> bool {
+    if !CURRENT.is_set() {
+        return false;
+    }
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::replace_async_punt(this, enabled),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => {
+            let _ = enabled;
+            false
+        }
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Set the qos tag of the ops submitted to current driver, returns the
+/// previous one. The legacy driver ignores it.
+pub(crate) fn replace_qos(qos: crate::io::Qos) -> crate::io::Qos {
+    if !CURRENT.is_set() {
+        return crate::io::Qos::default();
+    }
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::replace_qos(this, qos),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => {
+            let _ = qos;
+            crate::io::Qos::default()
+        }
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Allocate ahead what current driver would allocate on first use.
+pub(crate) fn warmup() {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::warmup(this),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => {}
+        #[cfg(all(
+            not(feature = "legacy"),
+            not(all(target_os = "linux", feature = "iouring"))
+        ))]
+        _ => {
+            util::feature_panic();
+        }
+    })
+}
+
+/// Number of in-flight ops of current driver.
+#[cfg(feature = "metrics")]
+pub(crate) fn inflight_ops() -> usize {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::inflight_ops(this),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => 0,
+    })
+}
+
+/// Number of in-flight ops of current driver and the memory they hold.
+#[cfg(feature = "metrics")]
+pub(crate) fn op_memory() -> (usize, usize) {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::op_memory(this),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => (0, 0),
+    })
+}
+
+/// Memory of the buffer rings registered on current thread.
+#[cfg(feature = "metrics")]
+pub(crate) fn buffer_ring_bytes() -> usize {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    return uring::buffer_ring_bytes();
+    #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+    0
+}
+
+/// Number of CQ overflows and dropped completions of current driver.
+#[cfg(feature = "metrics")]
+pub(crate) fn cq_overflows() -> (u64, u64) {
+    CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        Inner::Uring(this) => UringInner::cq_overflows(this),
+        #[cfg(feature = "legacy")]
+        Inner::Legacy(_) => (0, 0),
+    })
+}
+
 /// The unified UnparkHandle.
 #[cfg(feature = "sync")]
 #[derive(Clone)]
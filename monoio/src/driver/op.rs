@@ -11,16 +11,60 @@ pub(crate) mod close;
 
 mod accept;
 mod connect;
+mod deadline;
 mod fsync;
 mod open;
 mod poll;
+#[cfg(unix)]
+pub(crate) use poll::PollAdd;
 mod read;
 mod recv;
+#[cfg(target_os = "linux")]
+pub(crate) use recv::Recv;
 mod send;
 mod write;
 
+#[cfg(target_os = "linux")]
+mod advise;
+#[cfg(target_os = "linux")]
+mod fallocate;
+#[cfg(target_os = "linux")]
+mod futex;
+#[cfg(target_os = "linux")]
+mod mkdir;
+#[cfg(target_os = "linux")]
+mod mmsg;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod msg_ring;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod recv_bundle;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use recv_bundle::RecvBundle;
+#[cfg(target_os = "linux")]
+mod recv_msg_multi;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use recv_msg_multi::RecvMsgMulti;
+#[cfg(target_os = "linux")]
+pub(crate) use recv_msg_multi::{RecvMsgInto, RecvMsgOut, RECV_MSG_OUT_LEN};
+#[cfg(target_os = "linux")]
+mod socket;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use socket::claim_dropped;
+#[cfg(target_os = "linux")]
+pub(crate) use socket::{bind, listen, socket};
 #[cfg(all(target_os = "linux", feature = "splice"))]
 mod splice;
+#[cfg(target_os = "linux")]
+mod statx;
+#[cfg(target_os = "linux")]
+mod sync_file_range;
+#[cfg(target_os = "linux")]
+mod unlink;
+#[cfg(target_os = "linux")]
+mod waitid;
+#[cfg(target_os = "linux")]
+#[allow(unused)]
+pub(crate) use waitid::wait_child;
 
 /// In-flight operation
 pub(crate) struct Op<T: 'static> {
@@ -32,6 +76,10 @@ pub(crate) struct Op<T: 'static> {
 
     // Per-operation data
     pub(super) data: Option<T>,
+
+    // Kind and tag of the op given to the interceptor
+    #[cfg(feature = "interceptor")]
+    pub(super) intercepted: Option<(crate::interceptor::OpKind, u64)>,
 }
 
 /// Operation completion. Returns stored state with the result of the operation.
@@ -53,6 +101,21 @@ pub(crate) trait OpAble {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry;
 
+    /// Timespec of an `IORING_OP_LINK_TIMEOUT` to be linked after the op.
+    /// The pointer must be valid until the op is submitted.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    fn uring_link_timeout(&self) -> Option<*const io_uring::types::Timespec> {
+        None
+    }
+
+    /// Description of the op given to the interceptor.
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other)
+    }
+
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_interest(&self) -> Option<(super::ready::Direction, usize)>;
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -108,6 +171,14 @@ impl<T> Op<T> {
     where
         T: OpAble,
     {
+        #[cfg(feature = "interceptor")]
+        {
+            let intercepted = crate::interceptor::submit(&data.op_info())?;
+            let mut op = driver::CURRENT.with(|this| this.submit_with(data))?;
+            op.intercepted = intercepted;
+            Ok(op)
+        }
+        #[cfg(not(feature = "interceptor"))]
         driver::CURRENT.with(|this| this.submit_with(data))
     }
 
@@ -150,6 +221,35 @@ impl<T> Op<T> {
     }
 }
 
+impl<T: OpAble> Op<T> {
+    /// Poll the next completion of a multishot operation.
+    ///
+    /// Once a completion without [`CompletionMeta::more`] is returned, the
+    /// operation is finished and must not be polled again.
+    #[allow(unused)]
+    pub(crate) fn poll_multishot(&mut self, cx: &mut Context<'_>) -> Poll<CompletionMeta> {
+        let data_mut = self.data.as_mut().expect("unexpected operation state");
+        let meta = ready!(self.driver.poll_multishot::<T>(data_mut, self.index, cx));
+        #[cfg(feature = "interceptor")]
+        crate::interceptor::complete(self.intercepted, &meta.result);
+        if !meta.more() {
+            self.index = usize::MAX;
+        }
+        Poll::Ready(meta)
+    }
+}
+
+impl CompletionMeta {
+    /// Whether more completions of the multishot operation are coming.
+    #[inline]
+    pub(crate) fn more(&self) -> bool {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        return io_uring::cqueue::more(self.flags);
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        false
+    }
+}
+
 impl<T> Future for Op<T>
 where
     T: Unpin + OpAble + 'static,
@@ -160,6 +260,8 @@ where
         let me = &mut *self;
         let data_mut = me.data.as_mut().expect("unexpected operation state");
         let meta = ready!(me.driver.poll_op::<T>(data_mut, me.index, cx));
+        #[cfg(feature = "interceptor")]
+        crate::interceptor::complete(me.intercepted, &meta.result);
 
         me.index = usize::MAX;
         let data = me.data.take().expect("unexpected operation state");
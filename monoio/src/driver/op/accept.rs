@@ -54,6 +54,13 @@ impl Op<Accept> {
 }
 
 impl OpAble for Accept {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Accept)
+            .with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Accept::new(
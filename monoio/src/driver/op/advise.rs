@@ -0,0 +1,134 @@
+//! This module works only on linux.
+
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+use crate::buf::IoBuf;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+pub(crate) struct Fadvise {
+    fd: SharedFd,
+    offset: u64,
+    len: u32,
+    advice: i32,
+}
+
+impl Op<Fadvise> {
+    pub(crate) fn fadvise(
+        fd: &SharedFd,
+        offset: u64,
+        len: u32,
+        advice: i32,
+    ) -> io::Result<Op<Fadvise>> {
+        Op::submit_with(Fadvise {
+            fd: fd.clone(),
+            offset,
+            len,
+            advice,
+        })
+    }
+}
+
+impl OpAble for Fadvise {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Fadvise::new(
+            types::Fd(self.fd.raw_fd()),
+            self.len as libc::off_t,
+            self.advice,
+        )
+        .offset(self.offset)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        // posix_fadvise returns the error number instead of setting errno.
+        match unsafe {
+            libc::posix_fadvise(
+                self.fd.raw_fd(),
+                self.offset as libc::off_t,
+                self.len as libc::off_t,
+                self.advice,
+            )
+        } {
+            0 => Ok(0),
+            errno => Err(io::Error::from_raw_os_error(errno)),
+        }
+    }
+}
+
+pub(crate) struct Madvise<T> {
+    /// Holds the buffer so the advised memory outlives the operation.
+    pub(crate) buf: T,
+    addr: usize,
+    len: usize,
+    advice: i32,
+}
+
+impl<T: IoBuf> Op<Madvise<T>> {
+    /// Advise the whole pages inside the initialized part of `buf`.
+    pub(crate) fn madvise(buf: T, advice: i32) -> io::Result<Op<Madvise<T>>> {
+        let page = page_size();
+        let start = buf.read_ptr() as usize;
+        let end = start + buf.bytes_init();
+        let addr = (start + page - 1) & !(page - 1);
+        let len = (end & !(page - 1)).saturating_sub(addr);
+        Op::submit_with(Madvise {
+            buf,
+            addr,
+            len,
+            advice,
+        })
+    }
+}
+
+impl<T: IoBuf> OpAble for Madvise<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Madvise::new(
+            self.addr as *const libc::c_void,
+            self.len as libc::off_t,
+            self.advice,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(madvise(
+            self.addr as *mut libc::c_void,
+            self.len,
+            self.advice
+        ))
+    }
+}
+
+fn page_size() -> usize {
+    thread_local! {
+        static PAGE_SIZE: usize = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    }
+    PAGE_SIZE.with(|p| *p)
+}
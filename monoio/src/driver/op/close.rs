@@ -33,6 +33,12 @@ impl Op<Close> {
 }
 
 impl OpAble for Close {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Close).with_fd(self.fd)
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Close::new(types::Fd(self.fd)).build()
@@ -41,7 +41,37 @@ impl Op<Connect> {
     }
 }
 
+impl Op<super::deadline::Deadline<Connect>> {
+    /// Submit a request to connect, canceled if not completed before
+    /// `deadline`.
+    pub(crate) fn connect_with_deadline(
+        socket: SharedFd,
+        addr: SocketAddr,
+        _tfo: bool,
+        deadline: std::time::Instant,
+    ) -> io::Result<Self> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        Op::submit_with_deadline(
+            Connect {
+                fd: socket,
+                socket_addr: Box::new(raw_addr),
+                socket_addr_len: raw_addr_length,
+                #[cfg(any(target_os = "ios", target_os = "macos"))]
+                tfo: _tfo,
+            },
+            deadline,
+        )
+    }
+}
+
 impl OpAble for Connect {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Connect)
+            .with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Connect::new(
@@ -116,14 +146,19 @@ impl OpAble for Connect {
     }
 }
 
+/// Connect to an address of a family without a Rust representation, e.g.
+/// `sockaddr_un` or `sockaddr_vm`.
 #[cfg(unix)]
-pub(crate) struct ConnectUnix {
+pub(crate) struct ConnectRaw<A> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
     pub(crate) fd: SharedFd,
-    socket_addr: Box<(libc::sockaddr_un, libc::socklen_t)>,
+    socket_addr: Box<(A, libc::socklen_t)>,
 }
 
+#[cfg(unix)]
+pub(crate) type ConnectUnix = ConnectRaw<libc::sockaddr_un>;
+
 #[cfg(unix)]
 impl Op<ConnectUnix> {
     /// Submit a request to connect.
@@ -132,7 +167,22 @@ impl Op<ConnectUnix> {
         socket_addr: libc::sockaddr_un,
         socket_len: libc::socklen_t,
     ) -> io::Result<Op<ConnectUnix>> {
-        Op::submit_with(ConnectUnix {
+        Op::submit_with(ConnectRaw {
+            fd: socket,
+            socket_addr: Box::new((socket_addr, socket_len)),
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Op<ConnectRaw<libc::sockaddr_vm>> {
+    /// Submit a request to connect a vsock socket.
+    pub(crate) fn connect_vsock(
+        socket: SharedFd,
+        socket_addr: libc::sockaddr_vm,
+    ) -> io::Result<Self> {
+        let socket_len = std::mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t;
+        Op::submit_with(ConnectRaw {
             fd: socket,
             socket_addr: Box::new((socket_addr, socket_len)),
         })
@@ -140,7 +190,14 @@ impl Op<ConnectUnix> {
 }
 
 #[cfg(unix)]
-impl OpAble for ConnectUnix {
+impl<A: 'static> OpAble for ConnectRaw<A> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Connect)
+            .with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Connect::new(
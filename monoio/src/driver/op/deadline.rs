@@ -0,0 +1,117 @@
+use std::{io, time::Instant};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::types::Timespec;
+
+use super::{Completion, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+/// Wraps an op with a deadline.
+///
+/// With uring driver, an `IORING_OP_LINK_TIMEOUT` is linked after the op, so
+/// the kernel cancels the op on expiry. With legacy driver, the op is raced
+/// against a timer and canceled in userspace, which requires the time driver.
+pub(crate) struct Deadline<T> {
+    pub(crate) inner: T,
+
+    // Boxed to keep the address stable until the SQE is submitted.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    timespec: Box<Timespec>,
+    deadline: Instant,
+}
+
+impl<T: OpAble + Unpin + 'static> Op<Deadline<T>> {
+    /// Submit an op which will be canceled if not completed before deadline.
+    pub(crate) fn submit_with_deadline(inner: T, deadline: Instant) -> io::Result<Self> {
+        Op::submit_with(Deadline {
+            inner,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            timespec: Box::new(crate::driver::util::timespec(
+                deadline.saturating_duration_since(Instant::now()),
+            )),
+            deadline,
+        })
+    }
+
+    /// Wait for the op. If the deadline is exceeded, the result will be an
+    /// error of kind `TimedOut`.
+    pub(crate) async fn result(self) -> Completion<T> {
+        #[cfg(feature = "legacy")]
+        let mut op = self;
+        #[cfg(not(feature = "legacy"))]
+        let op = self;
+
+        #[cfg(feature = "legacy")]
+        if super::is_legacy() {
+            let deadline = op.data.as_ref().unwrap().deadline;
+            let sleep = crate::time::sleep(deadline.saturating_duration_since(Instant::now()));
+            let mut sleep = std::pin::pin!(sleep);
+            let done = std::future::poll_fn(|cx| {
+                use std::{future::Future, task::Poll};
+                if let Poll::Ready(c) = std::pin::Pin::new(&mut op).poll(cx) {
+                    return Poll::Ready(Some(c));
+                }
+                sleep.as_mut().poll(cx).map(|_| None)
+            })
+            .await;
+            if let Some(completion) = done {
+                return map_timeout(completion);
+            }
+            // Safety: the op is still alive.
+            unsafe { op.op_canceller().cancel() };
+        }
+
+        map_timeout(op.await)
+    }
+}
+
+fn map_timeout<T>(completion: Completion<Deadline<T>>) -> Completion<T> {
+    let Completion { data, mut meta } = completion;
+    if let Err(e) = &meta.result {
+        // Canceled by the linked timeout or the legacy timer. The op may also
+        // have been canceled by its owner before the deadline.
+        if crate::io::is_canceled(e) && Instant::now() >= data.deadline {
+            meta.result = Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "operation deadline exceeded",
+            ));
+        }
+    }
+    Completion {
+        data: data.inner,
+        meta,
+    }
+}
+
+impl<T: OpAble> OpAble for Deadline<T> {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        self.inner.op_info()
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        self.inner.uring_op()
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    fn uring_link_timeout(&self) -> Option<*const Timespec> {
+        Some(&*self.timespec as *const Timespec)
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.inner.legacy_interest()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        self.inner.legacy_call()
+    }
+}
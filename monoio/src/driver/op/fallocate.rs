@@ -0,0 +1,65 @@
+//! This module works only on linux.
+
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+pub(crate) struct Fallocate {
+    fd: SharedFd,
+    offset: u64,
+    len: u64,
+    mode: i32,
+}
+
+impl Op<Fallocate> {
+    pub(crate) fn fallocate(
+        fd: &SharedFd,
+        offset: u64,
+        len: u64,
+        mode: i32,
+    ) -> io::Result<Op<Fallocate>> {
+        Op::submit_with(Fallocate {
+            fd: fd.clone(),
+            offset,
+            len,
+            mode,
+        })
+    }
+}
+
+impl OpAble for Fallocate {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Fallocate::new(types::Fd(self.fd.raw_fd()), self.len)
+            .offset(self.offset)
+            .mode(self.mode)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(fallocate(
+            self.fd.raw_fd(),
+            self.mode,
+            self.offset as libc::off_t,
+            self.len as libc::off_t
+        ))
+    }
+}
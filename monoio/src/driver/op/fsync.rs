@@ -40,6 +40,12 @@ impl Op<Fsync> {
 }
 
 impl OpAble for Fsync {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Fsync).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let mut opc = opcode::Fsync::new(types::Fd(self.fd.raw_fd()));
@@ -0,0 +1,109 @@
+//! This module works only on linux.
+
+use std::{
+    io,
+    sync::{atomic::AtomicU32, Arc},
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+use super::{Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+// futex2 flags
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+const FUTEX2_SIZE_U32: u32 = 0x02;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+const FUTEX2_PRIVATE: u32 = 128;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+const FUTEX_BITSET_MATCH_ANY: u64 = u32::MAX as u64;
+
+pub(crate) struct FutexWait {
+    // Keeps the futex word alive until the op completes.
+    futex: Arc<AtomicU32>,
+    #[allow(unused)]
+    expected: u32,
+}
+
+pub(crate) struct FutexWake {
+    futex: Arc<AtomicU32>,
+    count: u32,
+}
+
+impl Op<FutexWait> {
+    pub(crate) fn futex_wait(futex: Arc<AtomicU32>, expected: u32) -> io::Result<Self> {
+        Op::submit_with(FutexWait { futex, expected })
+    }
+}
+
+impl Op<FutexWake> {
+    pub(crate) fn futex_wake(futex: Arc<AtomicU32>, count: u32) -> io::Result<Self> {
+        Op::submit_with(FutexWake { futex, count })
+    }
+}
+
+impl OpAble for FutexWait {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::FutexWait::new(
+            self.futex.as_ptr(),
+            self.expected as u64,
+            FUTEX_BITSET_MATCH_ANY,
+            FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    // Waiting on a futex blocks the thread, there is nothing to poll.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "futex wait requires the io_uring driver",
+        ))
+    }
+}
+
+impl OpAble for FutexWake {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::FutexWake::new(
+            self.futex.as_ptr(),
+            self.count as u64,
+            FUTEX_BITSET_MATCH_ANY,
+            FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                self.futex.as_ptr(),
+                libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+                self.count.min(i32::MAX as u32),
+            )
+        };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as u32)
+        }
+    }
+}
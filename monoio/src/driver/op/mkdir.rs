@@ -0,0 +1,52 @@
+//! This module works only on linux.
+
+use std::{ffi::CString, io, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Create a directory.
+pub(crate) struct MkDir {
+    path: CString,
+    mode: libc::mode_t,
+}
+
+impl Op<MkDir> {
+    /// Submit a request to create the directory `path` with permissions
+    /// `mode`, before the umask is applied.
+    pub(crate) fn mkdir<P: AsRef<Path>>(path: P, mode: libc::mode_t) -> io::Result<Op<MkDir>> {
+        let path = cstr(path.as_ref())?;
+        Op::submit_with(MkDir { path, mode })
+    }
+}
+
+impl OpAble for MkDir {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other).with_path(&self.path)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::MkDirAt::new(types::Fd(libc::AT_FDCWD), self.path.as_ptr())
+            .mode(self.mode)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(mkdirat(libc::AT_FDCWD, self.path.as_ptr(), self.mode))
+    }
+}
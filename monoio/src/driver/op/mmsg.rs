@@ -0,0 +1,256 @@
+//! This module works only on linux.
+//!
+//! On the legacy driver the whole batch is transferred with one
+//! sendmmsg/recvmmsg call. io_uring has no such opcode, so the first
+//! datagram goes through a sendmsg/recvmsg SQE, and the rest of the batch is
+//! transferred with a non-blocking sendmmsg/recvmmsg once it completed.
+
+use std::{io, mem::MaybeUninit, net::SocketAddr};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+use socket2::SockAddr;
+
+use super::{super::shared_fd::SharedFd, is_legacy, Op, OpAble};
+use crate::{
+    buf::{IoBuf, IoBufMut},
+    BufResult,
+};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+pub(crate) struct SendMmsg<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    pub(crate) datagrams: Vec<(T, SocketAddr)>,
+    // The headers point into these, their heap storage must not move.
+    #[allow(unused)]
+    addrs: Vec<SockAddr>,
+    #[allow(unused)]
+    iovecs: Vec<libc::iovec>,
+    msgs: Vec<libc::mmsghdr>,
+}
+
+impl<T: IoBuf> Op<SendMmsg<T>> {
+    pub(crate) fn send_mmsg(fd: SharedFd, datagrams: Vec<(T, SocketAddr)>) -> io::Result<Self> {
+        let addrs: Vec<SockAddr> = datagrams.iter().map(|(_, addr)| (*addr).into()).collect();
+        let mut iovecs: Vec<libc::iovec> = datagrams
+            .iter()
+            .map(|(buf, _)| libc::iovec {
+                iov_base: buf.read_ptr() as *mut libc::c_void,
+                iov_len: buf.bytes_init(),
+            })
+            .collect();
+        let msgs = addrs
+            .iter()
+            .zip(iovecs.iter_mut())
+            .map(|(addr, iov)| {
+                let mut msg: libc::mmsghdr = unsafe { std::mem::zeroed() };
+                msg.msg_hdr.msg_name = addr.as_ptr() as *mut libc::c_void;
+                msg.msg_hdr.msg_namelen = addr.len();
+                msg.msg_hdr.msg_iov = iov;
+                msg.msg_hdr.msg_iovlen = 1;
+                msg
+            })
+            .collect();
+
+        Op::submit_with(SendMmsg {
+            fd,
+            datagrams,
+            addrs,
+            iovecs,
+            msgs,
+        })
+    }
+
+    pub(crate) async fn wait(self) -> BufResult<usize, Vec<(T, SocketAddr)>> {
+        let complete = self.await;
+        let mut data = complete.data;
+        let res = complete.meta.result.map(|n| {
+            if is_legacy() {
+                return n as usize;
+            }
+            // The SQE sent the first datagram, send what fits of the rest.
+            let rest = &mut data.msgs[1..];
+            if rest.is_empty() {
+                return 1;
+            }
+            #[allow(deprecated)]
+            let flags = libc::MSG_DONTWAIT | libc::MSG_NOSIGNAL;
+            let sent = unsafe {
+                libc::sendmmsg(data.fd.raw_fd(), rest.as_mut_ptr(), rest.len() as _, flags)
+            };
+            1 + sent.max(0) as usize
+        });
+        (res, data.datagrams)
+    }
+}
+
+impl<T: IoBuf> OpAble for SendMmsg<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Send).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        #[allow(deprecated)]
+        const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
+        opcode::SendMsg::new(types::Fd(self.fd.raw_fd()), &self.msgs[0].msg_hdr)
+            .flags(FLAGS)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        #[allow(deprecated)]
+        const FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+        syscall_u32!(sendmmsg(
+            self.fd.raw_fd(),
+            self.msgs.as_mut_ptr(),
+            self.msgs.len() as _,
+            FLAGS
+        ))
+    }
+}
+
+pub(crate) struct RecvMmsg<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    pub(crate) bufs: Vec<T>,
+    // The headers point into these, their heap storage must not move.
+    addrs: Vec<MaybeUninit<libc::sockaddr_storage>>,
+    #[allow(unused)]
+    iovecs: Vec<libc::iovec>,
+    msgs: Vec<libc::mmsghdr>,
+}
+
+impl<T: IoBufMut> Op<RecvMmsg<T>> {
+    pub(crate) fn recv_mmsg(fd: SharedFd, mut bufs: Vec<T>) -> io::Result<Self> {
+        let mut addrs = vec![MaybeUninit::<libc::sockaddr_storage>::uninit(); bufs.len()];
+        let mut iovecs: Vec<libc::iovec> = bufs
+            .iter_mut()
+            .map(|buf| libc::iovec {
+                iov_base: buf.write_ptr() as *mut libc::c_void,
+                iov_len: buf.bytes_total(),
+            })
+            .collect();
+        let msgs = addrs
+            .iter_mut()
+            .zip(iovecs.iter_mut())
+            .map(|(addr, iov)| {
+                let mut msg: libc::mmsghdr = unsafe { std::mem::zeroed() };
+                msg.msg_hdr.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+                msg.msg_hdr.msg_namelen =
+                    std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+                msg.msg_hdr.msg_iov = iov;
+                msg.msg_hdr.msg_iovlen = 1;
+                msg
+            })
+            .collect();
+
+        Op::submit_with(RecvMmsg {
+            fd,
+            bufs,
+            addrs,
+            iovecs,
+            msgs,
+        })
+    }
+
+    pub(crate) async fn wait(self) -> BufResult<Vec<(usize, SocketAddr)>, Vec<T>> {
+        let complete = self.await;
+        let mut data = complete.data;
+        let count = match complete.meta.result {
+            Ok(n) if is_legacy() => n as usize,
+            Ok(n) => {
+                // The SQE received the first datagram, take what is already
+                // queued for the rest.
+                data.msgs[0].msg_len = n;
+                let rest = &mut data.msgs[1..];
+                let received = if rest.is_empty() {
+                    0
+                } else {
+                    unsafe {
+                        libc::recvmmsg(
+                            data.fd.raw_fd(),
+                            rest.as_mut_ptr(),
+                            rest.len() as _,
+                            libc::MSG_DONTWAIT,
+                            std::ptr::null_mut(),
+                        )
+                    }
+                };
+                1 + received.max(0) as usize
+            }
+            Err(e) => return (Err(e), data.bufs),
+        };
+
+        let mut received = Vec::with_capacity(count);
+        for i in 0..count {
+            let len = data.msgs[i].msg_len as usize;
+            // Safety: the kernel wrote the address and its length.
+            let addr = unsafe {
+                let storage = data.addrs[i].assume_init();
+                SockAddr::new(storage, data.msgs[i].msg_hdr.msg_namelen)
+            };
+            let addr = match addr.as_socket() {
+                Some(addr) => addr,
+                None => {
+                    return (
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "unexpected address family",
+                        )),
+                        data.bufs,
+                    )
+                }
+            };
+            // Safety: the kernel wrote `len` bytes to the buffer.
+            unsafe { data.bufs[i].set_init(len) };
+            received.push((len, addr));
+        }
+        (Ok(received), data.bufs)
+    }
+}
+
+impl<T: IoBufMut> OpAble for RecvMmsg<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.msgs[0].msg_hdr).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(recvmmsg(
+            self.fd.raw_fd(),
+            self.msgs.as_mut_ptr(),
+            self.msgs.len() as _,
+            0,
+            std::ptr::null_mut()
+        ))
+    }
+}
@@ -0,0 +1,56 @@
+use std::{
+    io,
+    os::unix::io::{AsRawFd, OwnedFd},
+    sync::Arc,
+};
+
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+/// Post a completion into another ring.
+pub(crate) struct MsgRing {
+    // Held until the op completes, so the fd is not reused for another file
+    // before the kernel reads the SQE.
+    ring_fd: Arc<OwnedFd>,
+    data: u32,
+    user_data: u64,
+}
+
+impl Op<MsgRing> {
+    pub(crate) fn msg_ring(ring_fd: Arc<OwnedFd>, data: u32, user_data: u64) -> io::Result<Self> {
+        Op::submit_with(MsgRing {
+            ring_fd,
+            data,
+            user_data,
+        })
+    }
+}
+
+impl OpAble for MsgRing {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::MsgRingData::new(
+            types::Fd(self.ring_fd.as_raw_fd()),
+            self.data as i32,
+            self.user_data,
+            None,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "msg_ring is only supported with uring driver",
+        ))
+    }
+}
@@ -23,6 +23,8 @@ pub(crate) struct PollAdd {
     fd: SharedFd,
     // true: read; false: write
     is_read: bool,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    multi: bool,
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     relaxed: bool,
 }
@@ -32,6 +34,8 @@ impl Op<PollAdd> {
         Op::submit_with(PollAdd {
             fd: fd.clone(),
             is_read: true,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            multi: false,
             #[cfg(any(feature = "legacy", feature = "poll-io"))]
             relaxed: _relaxed,
         })
@@ -41,11 +45,27 @@ impl Op<PollAdd> {
         Op::submit_with(PollAdd {
             fd: fd.clone(),
             is_read: false,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            multi: false,
             #[cfg(any(feature = "legacy", feature = "poll-io"))]
             relaxed: _relaxed,
         })
     }
 
+    /// Poll readiness repeatedly with a multishot poll. The legacy driver
+    /// completes it once, like a strict `poll_read`/`poll_write`.
+    #[cfg(unix)]
+    pub(crate) fn poll_multi(fd: &SharedFd, is_read: bool) -> io::Result<Op<PollAdd>> {
+        Op::submit_with(PollAdd {
+            fd: fd.clone(),
+            is_read,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            multi: true,
+            #[cfg(any(feature = "legacy", feature = "poll-io"))]
+            relaxed: false,
+        })
+    }
+
     pub(crate) async fn wait(self) -> io::Result<()> {
         let complete = self.await;
         complete.meta.result.map(|_| ())
@@ -53,6 +73,12 @@ impl Op<PollAdd> {
 }
 
 impl OpAble for PollAdd {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Poll).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::PollAdd::new(
@@ -63,6 +89,7 @@ impl OpAble for PollAdd {
                 libc::POLLOUT as _
             },
         )
+        .multi(self.multi)
         .build()
     }
 
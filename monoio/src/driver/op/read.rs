@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, time::Instant};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use io_uring::{opcode, types};
@@ -14,7 +14,7 @@ use {
     },
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, deadline::Deadline, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{
@@ -63,6 +63,12 @@ impl<T: IoBufMut> Op<Read<T>> {
 }
 
 impl<T: IoBufMut> OpAble for Read<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Read).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Read::new(
@@ -161,7 +167,35 @@ impl<T: IoVecBufMut> Op<ReadVec<T>> {
     }
 }
 
+impl<T: IoVecBufMut> Op<Deadline<ReadVec<T>>> {
+    pub(crate) fn readv_with_deadline(
+        fd: SharedFd,
+        buf_vec: T,
+        deadline: Instant,
+    ) -> io::Result<Self> {
+        Op::submit_with_deadline(ReadVec { fd, buf_vec }, deadline)
+    }
+
+    pub(crate) async fn read(self) -> BufResult<usize, T> {
+        let complete = self.result().await;
+        let res = complete.meta.result.map(|v| v as _);
+        let mut buf_vec = complete.data.buf_vec;
+
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe { buf_vec.set_init(n) };
+        }
+        (res, buf_vec)
+    }
+}
+
 impl<T: IoVecBufMut> OpAble for ReadVec<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Read).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let ptr = self.buf_vec.write_iovec_ptr() as _;
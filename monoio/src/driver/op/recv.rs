@@ -2,6 +2,7 @@ use std::{
     io,
     mem::{transmute, MaybeUninit},
     net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    time::Instant,
 };
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
@@ -33,7 +34,7 @@ use {
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use {crate::syscall_u32, std::os::unix::prelude::AsRawFd};
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, deadline::Deadline, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{
@@ -49,11 +50,24 @@ pub(crate) struct Recv<T> {
 
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
+
+    /// Flags passed to recv, like `MSG_PEEK`.
+    flags: i32,
 }
 
 impl<T: IoBufMut> Op<Recv<T>> {
     pub(crate) fn recv(fd: SharedFd, buf: T) -> io::Result<Self> {
-        Op::submit_with(Recv { fd, buf })
+        Op::submit_with(Recv { fd, buf, flags: 0 })
+    }
+
+    /// Receive without removing the data from the socket queue.
+    #[cfg(unix)]
+    pub(crate) fn peek(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Op::submit_with(Recv {
+            fd,
+            buf,
+            flags: libc::MSG_PEEK,
+        })
     }
 
     #[allow(unused)]
@@ -61,6 +75,7 @@ impl<T: IoBufMut> Op<Recv<T>> {
         Recv {
             fd: fd.clone(),
             buf,
+            flags: 0,
         }
     }
 
@@ -79,7 +94,43 @@ impl<T: IoBufMut> Op<Recv<T>> {
     }
 }
 
+impl<T: IoBufMut> Op<Deadline<Recv<T>>> {
+    pub(crate) fn recv_with_deadline(fd: SharedFd, buf: T, deadline: Instant) -> io::Result<Self> {
+        Op::submit_with_deadline(Recv { fd, buf, flags: 0 }, deadline)
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn peek_with_deadline(fd: SharedFd, buf: T, deadline: Instant) -> io::Result<Self> {
+        let recv = Recv {
+            fd,
+            buf,
+            flags: libc::MSG_PEEK,
+        };
+        Op::submit_with_deadline(recv, deadline)
+    }
+
+    pub(crate) async fn read(self) -> BufResult<usize, T> {
+        let complete = self.result().await;
+        let res = complete.meta.result.map(|v| v as _);
+        let mut buf = complete.data.buf;
+
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                buf.set_init(n);
+            }
+        }
+        (res, buf)
+    }
+}
+
 impl<T: IoBufMut> OpAble for Recv<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Recv::new(
@@ -87,6 +138,7 @@ impl<T: IoBufMut> OpAble for Recv<T> {
             self.buf.write_ptr(),
             self.buf.bytes_total() as _,
         )
+        .flags(self.flags)
         .build()
     }
 
@@ -103,7 +155,7 @@ impl<T: IoBufMut> OpAble for Recv<T> {
             fd,
             self.buf.write_ptr() as _,
             self.buf.bytes_total().min(u32::MAX as usize),
-            0
+            self.flags
         ))
     }
 
@@ -115,7 +167,7 @@ impl<T: IoBufMut> OpAble for Recv<T> {
                 fd as _,
                 self.buf.write_ptr(),
                 self.buf.bytes_total().min(i32::MAX as usize) as _,
-                0
+                self.flags as _
             ),
             PartialOrd::lt,
             0
@@ -224,6 +276,12 @@ static WSA_RECV_MSG: std::sync::OnceLock<
 > = std::sync::OnceLock::new();
 
 impl<T: IoBufMut> OpAble for RecvMsg<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut *self.info.2).build()
@@ -297,11 +355,24 @@ pub(crate) struct RecvMsgUnix<T> {
     pub(crate) buf: T,
     /// For multiple message recv in the future
     pub(crate) info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)>,
+    /// Ancillary data, u64 aligned as required by cmsghdr.
+    control: Vec<u64>,
+    flags: libc::c_int,
 }
 
 #[cfg(unix)]
 impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
-    pub(crate) fn recv_msg_unix(fd: SharedFd, mut buf: T) -> io::Result<Self> {
+    pub(crate) fn recv_msg_unix(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_unix_with_fds(fd, buf, 0)
+    }
+
+    /// Receive up to `max_fds` file descriptors sent as SCM_RIGHTS messages
+    /// along with the data.
+    pub(crate) fn recv_msg_unix_with_fds(
+        fd: SharedFd,
+        mut buf: T,
+        max_fds: usize,
+    ) -> io::Result<Self> {
         let mut info: Box<(MaybeUninit<sockaddr_storage>, IoVecMeta, libc::msghdr)> =
             Box::new((MaybeUninit::uninit(), IoVecMeta::from(&mut buf), unsafe {
                 std::mem::zeroed()
@@ -312,7 +383,75 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
         info.2.msg_name = &mut info.0 as *mut _ as *mut libc::c_void;
         info.2.msg_namelen = std::mem::size_of::<sockaddr_storage>() as socklen_t;
 
-        Op::submit_with(RecvMsgUnix { fd, buf, info })
+        let mut control = Vec::new();
+        let mut flags = 0;
+        if max_fds > 0 {
+            let payload = (max_fds * std::mem::size_of::<std::os::unix::io::RawFd>()) as u32;
+            let space = unsafe { libc::CMSG_SPACE(payload) } as usize;
+            control.resize(space.div_ceil(8), 0);
+            info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            info.2.msg_controllen = space as _;
+            #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+            {
+                flags = libc::MSG_CMSG_CLOEXEC;
+            }
+        }
+
+        Op::submit_with(RecvMsgUnix {
+            fd,
+            buf,
+            info,
+            control,
+            flags,
+        })
+    }
+
+    /// Like `wait`, also returning the received file descriptors and whether
+    /// the kernel dropped some which did not fit in the control buffer.
+    pub(crate) async fn wait_with_fds(
+        self,
+    ) -> BufResult<(usize, Vec<std::os::unix::io::OwnedFd>, bool), T> {
+        use std::os::unix::io::{FromRawFd, OwnedFd, RawFd};
+
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as usize);
+        let mut buf = complete.data.buf;
+        let msg = &complete.data.info.2;
+
+        // The fds are only collected on success, the control buffer is not
+        // written otherwise.
+        let mut fds = Vec::new();
+        if res.is_ok() && !complete.data.control.is_empty() {
+            // Safety: the kernel wrote valid cmsghdrs in the control buffer
+            // and updated its length.
+            unsafe {
+                let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+                while !cmsg.is_null() && (*cmsg).cmsg_len as usize >= libc::CMSG_LEN(0) as usize {
+                    if (*cmsg).cmsg_level == libc::SOL_SOCKET
+                        && (*cmsg).cmsg_type == libc::SCM_RIGHTS
+                    {
+                        let data = libc::CMSG_DATA(cmsg) as *const RawFd;
+                        let n = ((*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize)
+                            / std::mem::size_of::<RawFd>();
+                        for i in 0..n {
+                            fds.push(OwnedFd::from_raw_fd(data.add(i).read_unaligned()));
+                        }
+                    }
+                    cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+                }
+            }
+        }
+
+        // The kernel closed the fds which did not fit in the control buffer.
+        let truncated = msg.msg_flags & libc::MSG_CTRUNC != 0;
+        let res = res.map(|n| {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                buf.set_init(n);
+            }
+            (n, fds, truncated)
+        });
+        (res, buf)
     }
 
     pub(crate) async fn wait(self) -> BufResult<(usize, UnixSocketAddr), T> {
@@ -342,9 +481,17 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
 
 #[cfg(unix)]
 impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _).build()
+        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _)
+            .flags(self.flags as u32)
+            .build()
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -356,6 +503,6 @@ impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_call(&mut self) -> io::Result<u32> {
         let fd = self.fd.as_raw_fd();
-        syscall_u32!(recvmsg(fd, &mut self.info.2 as *mut _, 0))
+        syscall_u32!(recvmsg(fd, &mut self.info.2 as *mut _, self.flags))
     }
 }
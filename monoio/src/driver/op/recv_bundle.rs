@@ -0,0 +1,92 @@
+//! This module works only on linux.
+//!
+//! Bundled recv: one completion fills several buffers picked in order from a
+//! provided buffer ring, requires kernel 6.10+.
+
+use std::io;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+const IOSQE_BUFFER_SELECT: u8 = 1 << 5;
+const IORING_RECVSEND_BUNDLE: u16 = 1 << 4;
+
+/// Recv into as many buffers of the group `bgid` as the received data needs.
+pub(crate) struct RecvBundle {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    bgid: u16,
+}
+
+impl Op<RecvBundle> {
+    pub(crate) fn recv_bundle(fd: &SharedFd, bgid: u16) -> io::Result<Self> {
+        Op::submit_with(RecvBundle {
+            fd: fd.clone(),
+            bgid,
+        })
+    }
+}
+
+impl OpAble for RecvBundle {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        // The io_uring crate can not set the bundle flag yet, fill the SQE by
+        // hand.
+        #[repr(C)]
+        struct Sqe {
+            opcode: u8,
+            flags: u8,
+            ioprio: u16,
+            fd: i32,
+            off: u64,
+            addr: u64,
+            len: u32,
+            msg_flags: u32,
+            user_data: u64,
+            buf_group: u16,
+            personality: u16,
+            file_index: u32,
+            addr3: u64,
+            pad: u64,
+        }
+
+        let sqe = Sqe {
+            opcode: io_uring::opcode::Recv::CODE,
+            flags: IOSQE_BUFFER_SELECT,
+            ioprio: IORING_RECVSEND_BUNDLE,
+            fd: self.fd.raw_fd(),
+            off: 0,
+            addr: 0,
+            // No limit on the length, it is bounded by the buffers.
+            len: 0,
+            msg_flags: 0,
+            user_data: 0,
+            buf_group: self.bgid,
+            personality: 0,
+            file_index: 0,
+            addr3: 0,
+            pad: 0,
+        };
+        // Safety: the entry is a repr(C) wrapper of the 64 bytes SQE.
+        unsafe { std::mem::transmute::<Sqe, io_uring::squeue::Entry>(sqe) }
+    }
+
+    // Only submitted to the uring driver.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
@@ -0,0 +1,181 @@
+//! This module works only on linux.
+//!
+//! Multishot recvmsg fills buffers picked from a provided buffer ring with
+//! a `struct io_uring_recvmsg_out` header, followed by the name, the control
+//! messages and the payload. Without it, a oneshot recvmsg fills a buffer of
+//! the same layout.
+
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Header of a received message, `struct io_uring_recvmsg_out`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RecvMsgOut {
+    /// Length of the name, it is truncated if longer than the space for it.
+    pub(crate) namelen: u32,
+    /// Length of the control messages.
+    pub(crate) controllen: u32,
+    /// Length of the datagram, it is truncated if longer than the space for
+    /// it.
+    pub(crate) payloadlen: u32,
+    pub(crate) flags: u32,
+}
+
+pub(crate) const RECV_MSG_OUT_LEN: usize = std::mem::size_of::<RecvMsgOut>();
+
+/// Multishot recvmsg, each message is received into a buffer of the group
+/// `bgid`.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct RecvMsgMulti {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    // Only the lengths of the name and the control messages are used.
+    msghdr: Box<libc::msghdr>,
+    bgid: u16,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<RecvMsgMulti> {
+    pub(crate) fn recv_msg_multi(
+        fd: &SharedFd,
+        name_len: u32,
+        control_len: usize,
+        bgid: u16,
+    ) -> io::Result<Self> {
+        let mut msghdr: Box<libc::msghdr> = Box::new(unsafe { std::mem::zeroed() });
+        msghdr.msg_namelen = name_len;
+        msghdr.msg_controllen = control_len;
+        Op::submit_with(RecvMsgMulti {
+            fd: fd.clone(),
+            msghdr,
+            bgid,
+        })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for RecvMsgMulti {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::RecvMsgMulti::new(types::Fd(self.fd.raw_fd()), &*self.msghdr, self.bgid).build()
+    }
+
+    // Only submitted to the uring driver.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Oneshot recvmsg into a buffer of the same layout as [`RecvMsgMulti`].
+pub(crate) struct RecvMsgInto {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    fd: SharedFd,
+    pub(crate) buf: Vec<u8>,
+    // The header points into the buffer, its heap storage must not move.
+    hdr: Box<(libc::msghdr, libc::iovec)>,
+    name_len: u32,
+    control_len: usize,
+}
+
+impl Op<RecvMsgInto> {
+    /// `buf` must be longer than the header, the name and the control
+    /// messages.
+    pub(crate) fn recv_msg_into(
+        fd: &SharedFd,
+        mut buf: Vec<u8>,
+        name_len: u32,
+        control_len: usize,
+    ) -> io::Result<Self> {
+        let offset = RECV_MSG_OUT_LEN + name_len as usize + control_len;
+        assert!(buf.len() > offset, "buffer too small for the message");
+        let base = buf.as_mut_ptr();
+        let mut hdr: Box<(libc::msghdr, libc::iovec)> = Box::new(unsafe { std::mem::zeroed() });
+        unsafe {
+            hdr.1.iov_base = base.add(offset).cast();
+            hdr.1.iov_len = buf.len() - offset;
+            hdr.0.msg_name = base.add(RECV_MSG_OUT_LEN).cast();
+            hdr.0.msg_control = base.add(RECV_MSG_OUT_LEN + name_len as usize).cast();
+        }
+        hdr.0.msg_iov = &mut hdr.1;
+        hdr.0.msg_iovlen = 1;
+        hdr.0.msg_namelen = name_len;
+        hdr.0.msg_controllen = control_len;
+        Op::submit_with(RecvMsgInto {
+            fd: fd.clone(),
+            buf,
+            hdr,
+            name_len,
+            control_len,
+        })
+    }
+}
+
+impl RecvMsgInto {
+    /// Write the header of a received message, returns how many bytes of the
+    /// buffer are used like multishot recvmsg.
+    pub(crate) fn complete(mut self, res: io::Result<u32>) -> (io::Result<usize>, Vec<u8>) {
+        let n = match res {
+            Ok(n) => n as usize,
+            Err(e) => return (Err(e), self.buf),
+        };
+        let header = RecvMsgOut {
+            namelen: self.hdr.0.msg_namelen,
+            controllen: self.hdr.0.msg_controllen as u32,
+            payloadlen: n as u32,
+            flags: self.hdr.0.msg_flags as u32,
+        };
+        unsafe {
+            self.buf
+                .as_mut_ptr()
+                .cast::<RecvMsgOut>()
+                .write_unaligned(header)
+        };
+        let used = RECV_MSG_OUT_LEN + self.name_len as usize + self.control_len + n;
+        (Ok(used), self.buf)
+    }
+}
+
+impl OpAble for RecvMsgInto {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Recv).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.hdr.0).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(recvmsg(self.fd.raw_fd(), &mut self.hdr.0, 0))
+    }
+}
@@ -1,4 +1,4 @@
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, time::Instant};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use io_uring::{opcode, types};
@@ -12,7 +12,7 @@ use {
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use {crate::syscall_u32, std::os::unix::prelude::AsRawFd};
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, deadline::Deadline, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 #[cfg(unix)]
@@ -50,7 +50,24 @@ impl<T: IoBuf> Op<Send<T>> {
     }
 }
 
+impl<T: IoBuf> Op<Deadline<Send<T>>> {
+    pub(crate) fn send_with_deadline(fd: SharedFd, buf: T, deadline: Instant) -> io::Result<Self> {
+        Op::submit_with_deadline(Send { fd, buf }, deadline)
+    }
+
+    pub(crate) async fn write(self) -> BufResult<usize, T> {
+        let complete = self.result().await;
+        (complete.meta.result.map(|v| v as _), complete.data.buf)
+    }
+}
+
 impl<T: IoBuf> OpAble for Send<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Send).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         #[allow(deprecated)]
@@ -187,6 +204,12 @@ impl<T: IoBuf> Op<SendMsg<T>> {
 }
 
 impl<T: IoBuf> OpAble for SendMsg<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Send).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         #[allow(deprecated)]
@@ -248,6 +271,8 @@ pub(crate) struct SendMsgUnix<T> {
     pub(crate) buf: T,
     /// For multiple message send in the future
     pub(crate) info: Box<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)>,
+    /// Ancillary data, u64 aligned as required by cmsghdr.
+    control: Vec<u64>,
 }
 
 #[cfg(unix)]
@@ -256,6 +281,16 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
         fd: SharedFd,
         buf: T,
         socket_addr: Option<UnixSocketAddr>,
+    ) -> io::Result<Self> {
+        Self::send_msg_unix_with_fds(fd, buf, socket_addr, &[])
+    }
+
+    /// Send `fds` along with the data as a SCM_RIGHTS message.
+    pub(crate) fn send_msg_unix_with_fds(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<UnixSocketAddr>,
+        fds: &[std::os::unix::io::RawFd],
     ) -> io::Result<Self> {
         let mut info: Box<(Option<UnixSocketAddr>, IoVecMeta, libc::msghdr)> = Box::new((
             socket_addr.map(Into::into),
@@ -277,7 +312,34 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
             }
         }
 
-        Op::submit_with(SendMsgUnix { fd, buf, info })
+        let mut control = Vec::new();
+        if !fds.is_empty() {
+            let payload = std::mem::size_of_val(fds) as u32;
+            let space = unsafe { libc::CMSG_SPACE(payload) } as usize;
+            control.resize(space.div_ceil(8), 0);
+            info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            info.2.msg_controllen = space as _;
+            // Safety: the control buffer is large enough for one cmsghdr
+            // carrying `fds`.
+            unsafe {
+                let cmsg = libc::CMSG_FIRSTHDR(&info.2);
+                (*cmsg).cmsg_level = libc::SOL_SOCKET;
+                (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(payload) as _;
+                std::ptr::copy_nonoverlapping(
+                    fds.as_ptr(),
+                    libc::CMSG_DATA(cmsg) as *mut std::os::unix::io::RawFd,
+                    fds.len(),
+                );
+            }
+        }
+
+        Op::submit_with(SendMsgUnix {
+            fd,
+            buf,
+            info,
+            control,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<usize, T> {
@@ -290,6 +352,12 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
 
 #[cfg(unix)]
 impl<T: IoBuf> OpAble for SendMsgUnix<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Send).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         #[allow(deprecated)]
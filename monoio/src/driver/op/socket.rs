@@ -0,0 +1,235 @@
+//! This module works only on linux.
+//!
+//! Creating, binding and listening on sockets. `IORING_OP_SOCKET` requires
+//! Linux 5.19+, `IORING_OP_BIND` and `IORING_OP_LISTEN` require Linux 6.11+.
+//! On older kernels the syscalls are made directly.
+
+use std::{io, net::SocketAddr, os::unix::prelude::RawFd};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, squeue::Entry};
+
+use super::{
+    super::shared_fd::SharedFd,
+    connect::{socket_addr, SocketAddrCRepr},
+    Op, OpAble,
+};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+const IORING_OP_SOCKET: u8 = 45;
+const IORING_OP_BIND: u8 = 56;
+const IORING_OP_LISTEN: u8 = 57;
+
+pub(crate) struct Socket {
+    domain: libc::c_int,
+    socket_type: libc::c_int,
+    // The fd created by an op whose future was dropped, closed with the op.
+    unclaimed: Option<RawFd>,
+}
+
+pub(crate) struct Bind {
+    fd: SharedFd,
+    socket_addr: Box<SocketAddrCRepr>,
+    socket_addr_len: libc::socklen_t,
+}
+
+pub(crate) struct Listen {
+    fd: SharedFd,
+    backlog: libc::c_int,
+}
+
+impl Op<Socket> {
+    pub(crate) fn socket(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<Self> {
+        Op::submit_with(Socket {
+            domain,
+            socket_type,
+            unclaimed: None,
+        })
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        if let Some(fd) = self.unclaimed.take() {
+            unsafe { libc::close(fd) };
+        }
+    }
+}
+
+/// Give the result of an op dropped before it was polled to completion back
+/// to its data, so a socket created by a dropped `Op<Socket>` is closed
+/// instead of leaked.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn claim_dropped(data: &mut dyn std::any::Any, result: &io::Result<u32>) {
+    if let (Some(socket), Ok(fd)) = (data.downcast_mut::<Socket>(), result) {
+        socket.unclaimed = Some(*fd as RawFd);
+    }
+}
+
+impl Op<Bind> {
+    pub(crate) fn bind(fd: SharedFd, addr: SocketAddr) -> io::Result<Self> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        Op::submit_with(Bind {
+            fd,
+            socket_addr: Box::new(raw_addr),
+            socket_addr_len: raw_addr_length,
+        })
+    }
+}
+
+impl Op<Listen> {
+    pub(crate) fn listen(fd: SharedFd, backlog: libc::c_int) -> io::Result<Self> {
+        Op::submit_with(Listen { fd, backlog })
+    }
+}
+
+// Not supported by the io_uring crate yet, fill the SQE by hand.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[repr(C)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    addr2: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    file_index: u32,
+    addr3: u64,
+    pad: u64,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Sqe {
+    fn new(opcode: u8, fd: RawFd) -> Self {
+        Sqe {
+            opcode,
+            flags: 0,
+            ioprio: 0,
+            fd,
+            addr2: 0,
+            addr: 0,
+            len: 0,
+            op_flags: 0,
+            user_data: 0,
+            buf_index: 0,
+            personality: 0,
+            file_index: 0,
+            addr3: 0,
+            pad: 0,
+        }
+    }
+
+    fn build(self) -> Entry {
+        // Safety: the entry is a repr(C) wrapper of the 64 bytes SQE.
+        unsafe { std::mem::transmute::<Sqe, Entry>(self) }
+    }
+}
+
+impl OpAble for Socket {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> Entry {
+        opcode::Socket::new(self.domain, self.socket_type | libc::SOCK_CLOEXEC, 0).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        crate::net::new_socket(self.domain, self.socket_type).map(|fd| fd as u32)
+    }
+}
+
+impl OpAble for Bind {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> Entry {
+        let mut sqe = Sqe::new(IORING_OP_BIND, self.fd.raw_fd());
+        sqe.addr = self.socket_addr.as_ptr() as u64;
+        sqe.addr2 = self.socket_addr_len as u64;
+        sqe.build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        crate::syscall_u32!(bind(
+            self.fd.raw_fd(),
+            self.socket_addr.as_ptr(),
+            self.socket_addr_len
+        ))
+    }
+}
+
+impl OpAble for Listen {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> Entry {
+        let mut sqe = Sqe::new(IORING_OP_LISTEN, self.fd.raw_fd());
+        sqe.len = self.backlog as u32;
+        sqe.build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        crate::syscall_u32!(listen(self.fd.raw_fd(), self.backlog))
+    }
+}
+
+// If the op can be submitted: always on the legacy driver, which makes the
+// syscall, and on io_uring if the kernel knows the opcode.
+fn submittable(_opcode: u8) -> bool {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if !super::is_legacy() {
+        return crate::utils::uring_features().is_supported(_opcode);
+    }
+    true
+}
+
+/// Create a close-on-exec socket, non-blocking on the legacy driver.
+pub(crate) async fn socket(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<RawFd> {
+    if !submittable(IORING_OP_SOCKET) {
+        return crate::net::new_socket(domain, socket_type);
+    }
+    let completion = Op::socket(domain, socket_type)?.await;
+    completion.meta.result.map(|fd| fd as RawFd)
+}
+
+/// Bind the socket to `addr`.
+pub(crate) async fn bind(fd: &SharedFd, addr: SocketAddr) -> io::Result<()> {
+    if !submittable(IORING_OP_BIND) {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        return crate::syscall!(bind(fd.raw_fd(), raw_addr.as_ptr(), raw_addr_length)).map(|_| ());
+    }
+    Op::bind(fd.clone(), addr)?.await.meta.result.map(|_| ())
+}
+
+/// Mark the socket as listening for connections.
+pub(crate) async fn listen(fd: &SharedFd, backlog: libc::c_int) -> io::Result<()> {
+    if !submittable(IORING_OP_LISTEN) {
+        return crate::syscall!(listen(fd.raw_fd(), backlog)).map(|_| ());
+    }
+    Op::listen(fd.clone(), backlog)?
+        .await
+        .meta
+        .result
+        .map(|_| ())
+}
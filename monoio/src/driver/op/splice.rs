@@ -12,11 +12,14 @@ use {
 
 use super::{super::shared_fd::SharedFd, Op, OpAble};
 
-// Currently our Splice does not support setting offset.
+// Only the input offset can be set, for splicing from a file.
 pub(crate) struct Splice {
     fd_in: SharedFd,
+    // -1 means the current position of `fd_in`
+    off_in: i64,
     fd_out: SharedFd,
     len: u32,
+    flags: u32,
     direction: SpliceDirection,
 }
 enum SpliceDirection {
@@ -29,11 +32,32 @@ impl Op<Splice> {
         fd_in: &SharedFd,
         fd_out: &SharedFd,
         len: u32,
+        flags: u32,
     ) -> io::Result<Op<Splice>> {
         Op::submit_with(Splice {
             fd_in: fd_in.clone(),
+            off_in: -1,
             fd_out: fd_out.clone(),
             len,
+            flags,
+            direction: SpliceDirection::ToPipe,
+        })
+    }
+
+    /// Splice from a file at the given offset to pipe.
+    pub(crate) fn splice_file_to_pipe(
+        fd_in: &SharedFd,
+        off_in: u64,
+        fd_out: &SharedFd,
+        len: u32,
+        flags: u32,
+    ) -> io::Result<Op<Splice>> {
+        Op::submit_with(Splice {
+            fd_in: fd_in.clone(),
+            off_in: off_in as i64,
+            fd_out: fd_out.clone(),
+            len,
+            flags,
             direction: SpliceDirection::ToPipe,
         })
     }
@@ -42,11 +66,14 @@ impl Op<Splice> {
         fd_in: &SharedFd,
         fd_out: &SharedFd,
         len: u32,
+        flags: u32,
     ) -> io::Result<Op<Splice>> {
         Op::submit_with(Splice {
             fd_in: fd_in.clone(),
+            off_in: -1,
             fd_out: fd_out.clone(),
             len,
+            flags,
             direction: SpliceDirection::FromPipe,
         })
     }
@@ -60,15 +87,14 @@ impl Op<Splice> {
 impl OpAble for Splice {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        const FLAG: u32 = libc::SPLICE_F_MOVE;
         opcode::Splice::new(
             types::Fd(self.fd_in.raw_fd()),
-            -1,
+            self.off_in,
             types::Fd(self.fd_out.raw_fd()),
             -1,
             self.len,
         )
-        .flags(FLAG)
+        .flags(self.flags)
         .build()
     }
 
@@ -89,10 +115,15 @@ impl OpAble for Splice {
 
     #[cfg(all(unix, feature = "legacy"))]
     fn legacy_call(&mut self) -> io::Result<u32> {
-        const FLAG: u32 = libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK;
+        let flags = self.flags | libc::SPLICE_F_NONBLOCK;
         let fd_in = self.fd_in.as_raw_fd();
         let fd_out = self.fd_out.as_raw_fd();
-        let off_in = std::ptr::null_mut::<libc::loff_t>();
+        let mut offset = self.off_in as libc::loff_t;
+        let off_in = if self.off_in < 0 {
+            std::ptr::null_mut::<libc::loff_t>()
+        } else {
+            &mut offset
+        };
         let off_out = std::ptr::null_mut::<libc::loff_t>();
         syscall_u32!(splice(
             fd_in,
@@ -100,7 +131,67 @@ impl OpAble for Splice {
             fd_out,
             off_out,
             self.len as usize,
-            FLAG
+            flags
+        ))
+    }
+}
+
+/// Duplicate data between two pipes without consuming it.
+pub(crate) struct Tee {
+    fd_in: SharedFd,
+    fd_out: SharedFd,
+    len: u32,
+    flags: u32,
+}
+
+impl Op<Tee> {
+    pub(crate) fn tee(
+        fd_in: &SharedFd,
+        fd_out: &SharedFd,
+        len: u32,
+        flags: u32,
+    ) -> io::Result<Op<Tee>> {
+        Op::submit_with(Tee {
+            fd_in: fd_in.clone(),
+            fd_out: fd_out.clone(),
+            len,
+            flags,
+        })
+    }
+
+    pub(crate) async fn tee_result(self) -> io::Result<u32> {
+        let complete = self.await;
+        complete.meta.result
+    }
+}
+
+impl OpAble for Tee {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Tee::new(
+            types::Fd(self.fd_in.raw_fd()),
+            types::Fd(self.fd_out.raw_fd()),
+            self.len,
+        )
+        .flags(self.flags)
+        .build()
+    }
+
+    // Pipes are not registered to the poller, so the syscall is done at once.
+    #[cfg(all(unix, feature = "legacy"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(all(unix, feature = "legacy"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let flags = self.flags | libc::SPLICE_F_NONBLOCK;
+        syscall_u32!(tee(
+            self.fd_in.as_raw_fd(),
+            self.fd_out.as_raw_fd(),
+            self.len as usize,
+            flags
         ))
     }
 }
@@ -0,0 +1,113 @@
+//! This module works only on linux.
+
+use std::{ffi::CString, io, mem::MaybeUninit, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+use crate::driver::util::cstr;
+
+/// Get file status by path or by fd.
+pub(crate) struct Statx {
+    // keeps the fd alive when stating an open file
+    #[allow(unused)]
+    fd: Option<SharedFd>,
+    path: CString,
+    flags: i32,
+    mask: u32,
+    // boxed so the address given to the kernel is stable
+    buf: Box<MaybeUninit<libc::statx>>,
+}
+
+impl Op<Statx> {
+    pub(crate) fn statx_path<P: AsRef<Path>>(path: P, follow: bool) -> io::Result<Op<Statx>> {
+        let flags = if follow { 0 } else { libc::AT_SYMLINK_NOFOLLOW };
+        Op::submit_with(Statx {
+            fd: None,
+            path: cstr(path.as_ref())?,
+            flags,
+            mask: libc::STATX_ALL | libc::STATX_MNT_ID,
+            buf: Box::new(MaybeUninit::zeroed()),
+        })
+    }
+
+    pub(crate) fn statx_fd(fd: &SharedFd) -> io::Result<Op<Statx>> {
+        Op::submit_with(Statx {
+            fd: Some(fd.clone()),
+            path: CString::default(),
+            flags: libc::AT_EMPTY_PATH,
+            mask: libc::STATX_ALL | libc::STATX_MNT_ID,
+            buf: Box::new(MaybeUninit::zeroed()),
+        })
+    }
+
+    pub(crate) async fn statx(self) -> io::Result<libc::statx> {
+        let complete = self.await;
+        complete.meta.result?;
+        // The kernel filled the buffer, and it is zeroed anyway.
+        Ok(unsafe { complete.data.buf.assume_init_read() })
+    }
+}
+
+impl Statx {
+    fn dirfd(&self) -> i32 {
+        match &self.fd {
+            Some(fd) => fd.raw_fd(),
+            None => libc::AT_FDCWD,
+        }
+    }
+}
+
+impl OpAble for Statx {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        let info =
+            crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Stat).with_path(&self.path);
+        match &self.fd {
+            Some(fd) => info.with_fd(fd.raw_fd()),
+            None => info,
+        }
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Statx::new(
+            types::Fd(self.dirfd()),
+            self.path.as_ptr(),
+            self.buf.as_mut_ptr() as *mut types::statx,
+        )
+        .flags(self.flags)
+        .mask(self.mask)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        // libc only exposes the statx wrapper for gnu
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_statx,
+                self.dirfd(),
+                self.path.as_ptr(),
+                self.flags,
+                self.mask,
+                self.buf.as_mut_ptr(),
+            )
+        };
+        if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as u32)
+        }
+    }
+}
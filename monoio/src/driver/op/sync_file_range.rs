@@ -0,0 +1,65 @@
+//! This module works only on linux.
+
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+pub(crate) struct SyncFileRange {
+    fd: SharedFd,
+    offset: u64,
+    len: u32,
+    flags: u32,
+}
+
+impl Op<SyncFileRange> {
+    pub(crate) fn sync_file_range(
+        fd: &SharedFd,
+        offset: u64,
+        len: u32,
+        flags: u32,
+    ) -> io::Result<Op<SyncFileRange>> {
+        Op::submit_with(SyncFileRange {
+            fd: fd.clone(),
+            offset,
+            len,
+            flags,
+        })
+    }
+}
+
+impl OpAble for SyncFileRange {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other).with_fd(self.fd.raw_fd())
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::SyncFileRange::new(types::Fd(self.fd.raw_fd()), self.len)
+            .offset(self.offset)
+            .flags(self.flags)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(sync_file_range(
+            self.fd.raw_fd(),
+            self.offset as libc::off64_t,
+            self.len as libc::off64_t,
+            self.flags
+        ))
+    }
+}
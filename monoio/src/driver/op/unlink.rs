@@ -0,0 +1,60 @@
+//! This module works only on linux.
+
+use std::{ffi::CString, io, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Remove a file or an empty directory.
+pub(crate) struct Unlink {
+    path: CString,
+    flags: i32,
+}
+
+impl Op<Unlink> {
+    /// Submit a request to remove the file at `path`.
+    pub(crate) fn unlink<P: AsRef<Path>>(path: P) -> io::Result<Op<Unlink>> {
+        Self::unlink_with(path.as_ref(), 0)
+    }
+
+    /// Submit a request to remove the empty directory at `path`.
+    pub(crate) fn rmdir<P: AsRef<Path>>(path: P) -> io::Result<Op<Unlink>> {
+        Self::unlink_with(path.as_ref(), libc::AT_REMOVEDIR)
+    }
+
+    fn unlink_with(path: &Path, flags: i32) -> io::Result<Op<Unlink>> {
+        let path = cstr(path)?;
+        Op::submit_with(Unlink { path, flags })
+    }
+}
+
+impl OpAble for Unlink {
+    #[cfg(feature = "interceptor")]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Other).with_path(&self.path)
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::UnlinkAt::new(types::Fd(libc::AT_FDCWD), self.path.as_ptr())
+            .flags(self.flags)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(unlinkat(libc::AT_FDCWD, self.path.as_ptr(), self.flags))
+    }
+}
@@ -0,0 +1,196 @@
+//! This module works only on linux.
+//!
+//! Waiting for a child process to exit. `IORING_OP_WAITID` requires Linux
+//! 6.7+, older kernels and the legacy driver wait for the pidfd of the
+//! child to become readable instead, which requires Linux 5.3+.
+
+use std::{
+    io,
+    mem::MaybeUninit,
+    os::{
+        fd::{FromRawFd, OwnedFd},
+        unix::process::ExitStatusExt,
+    },
+    process::ExitStatus,
+};
+
+use super::{Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+const P_PID: u32 = 1;
+const P_PIDFD: u32 = 3;
+
+pub(crate) struct WaitId {
+    pid: libc::pid_t,
+    options: libc::c_int,
+    // Written by the kernel, boxed to keep the address stable.
+    info: Box<MaybeUninit<libc::siginfo_t>>,
+}
+
+impl Op<WaitId> {
+    /// Wait for the state of a child to change, like `waitid(P_PID, pid)`.
+    pub(crate) fn waitid(pid: libc::pid_t, options: libc::c_int) -> io::Result<Self> {
+        Op::submit_with(WaitId {
+            pid,
+            options,
+            info: Box::new(MaybeUninit::zeroed()),
+        })
+    }
+}
+
+impl OpAble for WaitId {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        // Not supported by the io_uring crate yet, fill the SQE by hand.
+        #[repr(C)]
+        struct Sqe {
+            opcode: u8,
+            flags: u8,
+            ioprio: u16,
+            fd: i32,
+            addr2: u64,
+            addr: u64,
+            len: u32,
+            waitid_flags: u32,
+            user_data: u64,
+            buf_index: u16,
+            personality: u16,
+            file_index: u32,
+            addr3: u64,
+            pad: u64,
+        }
+        const IORING_OP_WAITID: u8 = 50;
+
+        let sqe = Sqe {
+            opcode: IORING_OP_WAITID,
+            flags: 0,
+            ioprio: 0,
+            fd: self.pid,
+            addr2: self.info.as_mut_ptr() as u64,
+            addr: 0,
+            len: P_PID,
+            waitid_flags: 0,
+            user_data: 0,
+            buf_index: 0,
+            personality: 0,
+            file_index: self.options as u32,
+            addr3: 0,
+            pad: 0,
+        };
+        // Safety: the entry is a repr(C) wrapper of the 64 bytes SQE.
+        unsafe { std::mem::transmute::<Sqe, io_uring::squeue::Entry>(sqe) }
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    // A pid cannot be polled, only check the state without waiting.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        crate::syscall_u32!(waitid(
+            libc::P_PID,
+            self.pid as libc::id_t,
+            self.info.as_mut_ptr(),
+            self.options | libc::WNOHANG
+        ))
+    }
+}
+
+/// Wait for the child `pid` to exit, and reap it.
+#[allow(unused)]
+pub(crate) async fn wait_child(pid: libc::pid_t) -> io::Result<ExitStatus> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if !super::is_legacy() {
+        let completion = Op::waitid(pid, libc::WEXITED)?.await;
+        match completion.meta.result {
+            // Unknown opcode before Linux 6.7.
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {}
+            res => {
+                res?;
+                return Ok(exit_status(&completion.data.info));
+            }
+        }
+    }
+    wait_pidfd(pid).await
+}
+
+async fn wait_pidfd(pid: libc::pid_t) -> io::Result<ExitStatus> {
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let pidfd = unsafe { OwnedFd::from_raw_fd(fd as _) };
+    let pidfd = crate::io::AsyncFd::new(pidfd)?;
+    let mut info = MaybeUninit::<libc::siginfo_t>::zeroed();
+    loop {
+        // The pidfd is readable once the child has exited.
+        pidfd.readable().await?;
+        crate::syscall!(waitid(
+            P_PIDFD as _,
+            std::os::fd::AsRawFd::as_raw_fd(&pidfd) as libc::id_t,
+            info.as_mut_ptr(),
+            libc::WEXITED | libc::WNOHANG
+        ))?;
+        // Zero pid if still running.
+        if unsafe { info.assume_init_ref().si_pid() } != 0 {
+            return Ok(exit_status(&info));
+        }
+    }
+}
+
+// Convert to the status returned by waitpid.
+fn exit_status(info: &MaybeUninit<libc::siginfo_t>) -> ExitStatus {
+    let info = unsafe { info.assume_init_ref() };
+    let status = unsafe { info.si_status() };
+    let raw = match info.si_code {
+        libc::CLD_EXITED => (status & 0xff) << 8,
+        libc::CLD_DUMPED => status | 0x80,
+        _ => status,
+    };
+    ExitStatus::from_raw(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::wait_child;
+
+    // The child is reaped by wait_child.
+    #[allow(clippy::zombie_processes)]
+    async fn exit_code(cmd: &str) -> Option<i32> {
+        let child = Command::new("sh").arg("-c").arg(cmd).spawn().unwrap();
+        let status = wait_child(child.id() as _).await.unwrap();
+        status.code()
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[test]
+    fn wait_child_uring() {
+        let mut rt = crate::RuntimeBuilder::<crate::IoUringDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            assert_eq!(exit_code("exit 3").await, Some(3));
+            assert_eq!(exit_code("sleep 0.05").await, Some(0));
+            assert_eq!(exit_code("kill -9 $$").await, None);
+        });
+    }
+
+    #[cfg(feature = "legacy")]
+    #[test]
+    fn wait_child_legacy() {
+        let mut rt = crate::RuntimeBuilder::<crate::LegacyDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            assert_eq!(exit_code("exit 3").await, Some(3));
+            assert_eq!(exit_code("sleep 0.05").await, Some(0));
+            assert_eq!(exit_code("kill -9 $$").await, None);
+        });
+    }
+}
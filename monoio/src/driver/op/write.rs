@@ -1,6 +1,6 @@
-use std::io;
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use std::os::unix::prelude::AsRawFd;
+use std::{io, time::Instant};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use io_uring::{opcode, types};
@@ -11,7 +11,7 @@ use windows_sys::Win32::{
     Storage::FileSystem::{SetFilePointer, WriteFile, FILE_CURRENT, INVALID_SET_FILE_POINTER},
 };
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, deadline::Deadline, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{
@@ -45,6 +45,12 @@ impl<T: IoBuf> Op<Write<T>> {
 }
 
 impl<T: IoBuf> OpAble for Write<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Write).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         opcode::Write::new(
@@ -148,7 +154,34 @@ impl<T: IoVecBuf> Op<WriteVec<T>> {
     }
 }
 
+impl<T: IoVecBuf> Op<Deadline<WriteVec<T>>> {
+    pub(crate) fn writev_with_deadline(
+        fd: &SharedFd,
+        buf_vec: T,
+        deadline: Instant,
+    ) -> io::Result<Self> {
+        Op::submit_with_deadline(
+            WriteVec {
+                fd: fd.clone(),
+                buf_vec,
+            },
+            deadline,
+        )
+    }
+
+    pub(crate) async fn write(self) -> BufResult<usize, T> {
+        let complete = self.result().await;
+        (complete.meta.result.map(|v| v as _), complete.data.buf_vec)
+    }
+}
+
 impl<T: IoVecBuf> OpAble for WriteVec<T> {
+    #[cfg(all(unix, feature = "interceptor"))]
+    #[inline]
+    fn op_info(&self) -> crate::interceptor::OpInfo<'_> {
+        crate::interceptor::OpInfo::new(crate::interceptor::OpKind::Write).with_fd(self.fd.raw_fd())
+    }
+
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         let ptr = self.buf_vec.read_iovec_ptr() as *const _;
@@ -24,6 +24,10 @@ struct Inner {
 
     // Waker to notify when the close operation completes.
     state: UnsafeCell<State>,
+
+    // Id of the runtime the fd was created on
+    #[cfg(all(debug_assertions, feature = "debug"))]
+    runtime: Option<usize>,
 }
 
 enum State {
@@ -237,6 +241,8 @@ impl SharedFd {
             inner: Rc::new(Inner {
                 fd,
                 state: UnsafeCell::new(state),
+                #[cfg(all(debug_assertions, feature = "debug"))]
+                runtime: crate::utils::thread_id::try_get_current_thread_id(),
             }),
         })
     }
@@ -262,6 +268,8 @@ impl SharedFd {
             inner: Rc::new(Inner {
                 fd,
                 state: UnsafeCell::new(state),
+                #[cfg(all(debug_assertions, feature = "debug"))]
+                runtime: crate::utils::thread_id::try_get_current_thread_id(),
             }),
         })
     }
@@ -287,6 +295,8 @@ impl SharedFd {
             inner: Rc::new(Inner {
                 fd,
                 state: UnsafeCell::new(state),
+                #[cfg(all(debug_assertions, feature = "debug"))]
+                runtime: crate::utils::thread_id::try_get_current_thread_id(),
             }),
         }
     }
@@ -302,6 +312,8 @@ impl SharedFd {
             inner: Rc::new(Inner {
                 fd: RawFd::new(fd),
                 state: UnsafeCell::new(state),
+                #[cfg(all(debug_assertions, feature = "debug"))]
+                runtime: crate::utils::thread_id::try_get_current_thread_id(),
             }),
         }
     }
@@ -309,12 +321,14 @@ impl SharedFd {
     #[cfg(unix)]
     /// Returns the RawFd
     pub(crate) fn raw_fd(&self) -> RawFd {
+        self.inner.check_runtime();
         self.inner.fd
     }
 
     #[cfg(windows)]
     /// Returns the RawSocket
     pub(crate) fn raw_socket(&self) -> RawSocket {
+        self.inner.check_runtime();
         self.inner.fd.socket
     }
 
@@ -407,6 +421,7 @@ impl SharedFd {
 
     #[allow(unused)]
     pub(crate) fn registered_index(&self) -> Option<usize> {
+        self.inner.check_runtime();
         let state = unsafe { &*self.inner.state.get() };
         match state {
             #[cfg(all(target_os = "linux", feature = "iouring", feature = "poll-io"))]
@@ -474,6 +489,29 @@ impl SharedFd {
     }
 }
 
+impl Inner {
+    /// Panics if used from another runtime than the one it was created on.
+    ///
+    /// An fd is registered to the driver of that runtime, so using it on
+    /// another one corrupts the state of both drivers. This is only checked
+    /// in debug builds with the `debug` feature.
+    #[inline]
+    fn check_runtime(&self) {
+        #[cfg(all(debug_assertions, feature = "debug"))]
+        if let (Some(owner), Some(current)) = (
+            self.runtime,
+            crate::utils::thread_id::try_get_current_thread_id(),
+        ) {
+            assert!(
+                owner == current,
+                "fd {:?} of runtime {owner} is used on runtime {current}, io objects must stay on \
+                 the runtime they are created on",
+                self.fd,
+            );
+        }
+    }
+}
+
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 impl Inner {
     /// Completes when the FD has been closed.
@@ -524,6 +562,10 @@ impl Inner {
 #[cfg(unix)]
 impl Drop for Inner {
     fn drop(&mut self) {
+        // Don't panic again when dropped while unwinding.
+        if !std::thread::panicking() {
+            self.check_runtime();
+        }
         let fd = self.fd;
         let state = unsafe { &mut *self.state.get() };
         #[allow(unreachable_patterns)]
@@ -0,0 +1,362 @@
+//! Provided buffer rings.
+//!
+//! A buffer ring is registered to the ring with a group id, ops submitted
+//! with `IOSQE_BUFFER_SELECT` pick a buffer of the group when they have data
+//! instead of owning one while waiting (requires kernel 5.19+).
+//!
+//! With incremental consumption (requires kernel 6.12+), a completion only
+//! uses the part of a buffer it filled, and the next one continues in the
+//! same buffer until it is full.
+
+use std::{
+    cell::{Cell, UnsafeCell},
+    io,
+    os::unix::prelude::AsRawFd,
+    rc::{Rc, Weak},
+    sync::atomic::{AtomicU16, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use io_uring::types::BufRingEntry;
+
+use super::UringInner;
+use crate::driver::{Inner, CURRENT};
+
+const IORING_REGISTER_PBUF_RING: u32 = 22;
+const IOU_PBUF_RING_INC: u16 = 2;
+
+#[repr(C)]
+struct BufReg {
+    ring_addr: u64,
+    ring_entries: u32,
+    bgid: u16,
+    flags: u16,
+    resv: [u64; 3],
+}
+
+thread_local! {
+    static NEXT_BGID: Cell<u16> = const { Cell::new(0) };
+    // Memory of the rings alive on this thread.
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns the memory of the rings alive on current thread.
+#[cfg(feature = "metrics")]
+pub(crate) fn allocated_bytes() -> usize {
+    ALLOCATED.with(Cell::get)
+}
+
+pub(crate) struct BufRing {
+    driver: Weak<UnsafeCell<UringInner>>,
+    bgid: u16,
+    // Page aligned ring of buffer descriptors shared with the kernel.
+    ring: *mut BufRingEntry,
+    ring_len: usize,
+    mask: u16,
+    // Position of the next entry the kernel picks.
+    head: Cell<u16>,
+    tail: Cell<u16>,
+    // The buffers, `count * size` bytes.
+    bufs: *mut u8,
+    count: u16,
+    size: usize,
+    // Buffers in the ring, which the kernel may pick.
+    available: Cell<u16>,
+    waker: Cell<Option<Waker>>,
+    // For incremental consumption, the bytes of each buffer the kernel
+    // filled, and the number of parts of it not given back yet.
+    incremental: bool,
+    consumed: Box<[Cell<u32>]>,
+    parts: Box<[Cell<u16>]>,
+}
+
+impl BufRing {
+    /// Register a ring of `count` buffers of `size` bytes to current driver.
+    /// Returns `Unsupported` error with legacy driver.
+    pub(crate) fn new(count: u16, size: usize) -> io::Result<Rc<Self>> {
+        Self::new_inner(count, size, false)
+    }
+
+    /// Register a ring of `count` buffers of `size` bytes consumed
+    /// incrementally. Returns `InvalidInput` error if the kernel does not
+    /// support it.
+    pub(crate) fn new_incremental(count: u16, size: usize) -> io::Result<Rc<Self>> {
+        Self::new_inner(count, size, true)
+    }
+
+    fn new_inner(count: u16, size: usize, incremental: bool) -> io::Result<Rc<Self>> {
+        if count == 0 || count > 1 << 15 || size == 0 || size > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid buffer ring size",
+            ));
+        }
+        let driver: io::Result<_> = CURRENT.with(|inner| match inner {
+            Inner::Uring(this) => Ok(Rc::downgrade(this)),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Err(io::Error::from(io::ErrorKind::Unsupported)),
+        });
+        let driver = driver?;
+
+        let entries = count.next_power_of_two();
+        let ring_len = entries as usize * std::mem::size_of::<BufRingEntry>();
+        let ring = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                ring_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if ring == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let flags = if incremental { IOU_PBUF_RING_INC } else { 0 };
+        let bgid = match register(&driver, ring.cast(), entries, flags) {
+            Ok(bgid) => bgid,
+            Err(e) => {
+                unsafe { libc::munmap(ring, ring_len) };
+                return Err(e);
+            }
+        };
+        let bufs = vec![0; count as usize * size].into_boxed_slice();
+        let this = Self {
+            driver,
+            bgid,
+            ring: ring.cast(),
+            ring_len,
+            mask: entries - 1,
+            head: Cell::new(0),
+            tail: Cell::new(0),
+            bufs: Box::into_raw(bufs).cast(),
+            count,
+            size,
+            available: Cell::new(0),
+            waker: Cell::new(None),
+            incremental,
+            consumed: (0..if incremental { count } else { 0 })
+                .map(|_| Cell::new(0))
+                .collect(),
+            parts: (0..if incremental { count } else { 0 })
+                .map(|_| Cell::new(0))
+                .collect(),
+        };
+        for bid in 0..count {
+            this.recycle(bid);
+        }
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + this.allocated()));
+        Ok(Rc::new(this))
+    }
+
+    #[inline]
+    fn allocated(&self) -> usize {
+        self.count as usize * self.size + self.ring_len
+    }
+
+    #[inline]
+    pub(crate) fn bgid(&self) -> u16 {
+        self.bgid
+    }
+
+    /// Returns the first `len` bytes of a buffer picked by the kernel.
+    ///
+    /// # Safety
+    ///
+    /// The buffer must have been picked by the kernel, and not given back
+    /// with `recycle` yet.
+    pub(crate) unsafe fn buf(&self, bid: u16, len: usize) -> &[u8] {
+        self.buf_part(bid, 0, len)
+    }
+
+    /// Returns `len` bytes at `offset` of a buffer picked by the kernel.
+    ///
+    /// # Safety
+    ///
+    /// The part must have been filled by the kernel, and not given back yet.
+    pub(crate) unsafe fn buf_part(&self, bid: u16, offset: usize, len: usize) -> &[u8] {
+        debug_assert!(bid < self.count && offset + len <= self.size);
+        std::slice::from_raw_parts(self.bufs.add(bid as usize * self.size + offset), len)
+    }
+
+    /// If the buffers are consumed incrementally.
+    #[inline]
+    pub(crate) fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
+    /// Mark the `len` bytes the kernel filled for a completion of an
+    /// incremental ring as taken, starting in buffer `bid`. `f` is called with
+    /// the id, offset and length of each filled part in order, which must be
+    /// given back with `release`.
+    pub(crate) fn take_incremental(
+        &self,
+        bid: u16,
+        mut len: usize,
+        mut f: impl FnMut(u16, usize, usize),
+    ) {
+        debug_assert!(self.incremental && self.bid_at(self.head.get()) == bid);
+        let mut head = self.head.get();
+        while len > 0 {
+            let bid = self.bid_at(head) as usize;
+            let offset = self.consumed[bid].get() as usize;
+            let part = len.min(self.size - offset);
+            f(bid as u16, offset, part);
+            self.consumed[bid].set((offset + part) as u32);
+            self.parts[bid].set(self.parts[bid].get() + 1);
+            len -= part;
+            // The kernel moves to the next buffer once one is full.
+            if offset + part == self.size {
+                head = head.wrapping_add(1);
+                self.available.set(self.available.get() - 1);
+            }
+        }
+        self.head.set(head);
+    }
+
+    /// Give back a part taken with `take_incremental`. The buffer is recycled
+    /// once it is full and all its parts are given back.
+    pub(crate) fn release(&self, bid: u16) {
+        let parts = &self.parts[bid as usize];
+        parts.set(parts.get() - 1);
+        let consumed = &self.consumed[bid as usize];
+        if parts.get() == 0 && consumed.get() as usize == self.size {
+            consumed.set(0);
+            self.recycle(bid);
+        }
+    }
+
+    /// Mark the `nbufs` buffers the kernel picked for a completion as taken,
+    /// the first one being `bid`. `f` is called with their ids in the order
+    /// they were filled.
+    pub(crate) fn take(&self, bid: u16, nbufs: u16, mut f: impl FnMut(u16)) {
+        let tail = self.tail.get();
+        let mut head = self.head.get();
+        // Entries before `bid` were picked without data, e.g. by an empty
+        // completion of some kernels, they are given back.
+        let mut skipped = 0;
+        while head != tail && self.bid_at(head) != bid {
+            head = head.wrapping_add(1);
+            skipped += 1;
+        }
+        if head == tail {
+            head = self.head.get();
+            skipped = 0;
+        }
+        let first = head.wrapping_sub(skipped);
+        for _ in 0..nbufs {
+            f(self.bid_at(head));
+            head = head.wrapping_add(1);
+        }
+        self.head.set(head);
+        self.available
+            .set(self.available.get().saturating_sub(skipped + nbufs));
+        for pos in 0..skipped {
+            self.recycle(self.bid_at(first.wrapping_add(pos)));
+        }
+    }
+
+    // Id of the buffer at position `pos` of the ring.
+    fn bid_at(&self, pos: u16) -> u16 {
+        // Safety: the ring has `mask + 1` entries.
+        unsafe { &*self.ring.add((pos & self.mask) as usize) }.bid()
+    }
+
+    /// Size of the buffers.
+    #[inline]
+    pub(crate) fn buf_size(&self) -> usize {
+        self.size
+    }
+
+    /// Give a buffer back to the kernel.
+    pub(crate) fn recycle(&self, bid: u16) {
+        let tail = self.tail.get();
+        // Safety: the ring has `mask + 1` entries.
+        let entry = unsafe { &mut *self.ring.add((tail & self.mask) as usize) };
+        entry.set_addr(unsafe { self.bufs.add(bid as usize * self.size) } as u64);
+        entry.set_len(self.size as u32);
+        entry.set_bid(bid);
+        let tail = tail.wrapping_add(1);
+        self.tail.set(tail);
+        // Publish the entry to the kernel.
+        unsafe {
+            let shared_tail = BufRingEntry::tail(self.ring) as *const AtomicU16;
+            (*shared_tail).store(tail, Ordering::Release);
+        }
+        self.available.set(self.available.get() + 1);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Wait until the ring has a buffer the kernel can pick.
+    pub(crate) fn poll_available(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.available.get() > 0 {
+            return Poll::Ready(());
+        }
+        self.waker.set(Some(cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+impl Drop for BufRing {
+    fn drop(&mut self) {
+        if let Some(driver) = self.driver.upgrade() {
+            let inner = unsafe { &*driver.get() };
+            // Ops may still pick buffers until the ring is unregistered, which
+            // is serialized with them in the kernel. If it fails, the memory
+            // must stay valid.
+            if inner
+                .uring
+                .submitter()
+                .unregister_buf_ring(self.bgid)
+                .is_err()
+            {
+                return;
+            }
+        }
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() - self.allocated()));
+        unsafe {
+            let len = self.count as usize * self.size;
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                self.bufs, len,
+            )));
+            libc::munmap(self.ring.cast(), self.ring_len);
+        }
+    }
+}
+
+// Register a ring with a free group id.
+fn register(
+    driver: &Weak<UnsafeCell<UringInner>>,
+    ring: *const BufRingEntry,
+    entries: u16,
+    flags: u16,
+) -> io::Result<u16> {
+    let driver = driver.upgrade().expect("driver is alive");
+    let inner = unsafe { &*driver.get() };
+    for _ in 0..=u16::MAX {
+        let bgid = NEXT_BGID.with(|next| next.replace(next.get().wrapping_add(1)));
+        // The io_uring crate can not pass flags, make the syscall by ourselves.
+        let reg = BufReg {
+            ring_addr: ring as u64,
+            ring_entries: entries as u32,
+            bgid,
+            flags,
+            resv: [0; 3],
+        };
+        let res = crate::syscall!(syscall(
+            libc::SYS_io_uring_register,
+            inner.uring.as_raw_fd(),
+            IORING_REGISTER_PBUF_RING,
+            &reg as *const BufReg,
+            1
+        ));
+        match res {
+            Err(e) if e.raw_os_error() == Some(libc::EEXIST) => continue,
+            res => return res.map(|_| bgid),
+        }
+    }
+    Err(io::Error::from_raw_os_error(libc::EEXIST))
+}
@@ -0,0 +1,94 @@
+//! Second ring for bulk ops.
+//!
+//! Ops tagged [`Qos::Bulk`](crate::io::Qos::Bulk) are pushed to a ring of
+//! their own, so a long queue of background io does not delay the
+//! completions of latency critical ops. The bulk ring signals an eventfd on
+//! completions, which is polled by the main ring to wake up the driver.
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    mem::ManuallyDrop,
+    os::unix::io::{AsRawFd, FromRawFd},
+};
+
+use io_uring::{cqueue, squeue, IoUring};
+
+pub(crate) struct BulkRing {
+    pub(super) uring: ManuallyDrop<IoUring>,
+    // Signaled by the bulk ring on completions
+    eventfd: File,
+    // Mark if the poll of the eventfd is in the main ring
+    pub(super) poll_installed: bool,
+}
+
+impl BulkRing {
+    pub(crate) fn new(urb: &io_uring::Builder, entries: u32) -> io::Result<Self> {
+        let uring = ManuallyDrop::new(urb.build(entries)?);
+        let fd = crate::syscall!(eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK))?;
+        let eventfd = unsafe { File::from_raw_fd(fd) };
+        uring.submitter().register_eventfd(fd)?;
+        Ok(Self {
+            uring,
+            eventfd,
+            poll_installed: false,
+        })
+    }
+
+    /// Entry polling the eventfd, to be pushed to the main ring.
+    pub(super) fn poll_entry(&self) -> squeue::Entry {
+        io_uring::opcode::PollAdd::new(
+            io_uring::types::Fd(self.eventfd.as_raw_fd()),
+            libc::POLLIN as _,
+        )
+        .build()
+    }
+
+    /// Reset the eventfd. It must be done before reaping the bulk ring, or
+    /// a completion posted in between could be missed.
+    pub(super) fn drain_eventfd(&mut self) {
+        let _ = self.eventfd.read(&mut [0; 8]);
+    }
+
+    /// Flush the SQ to the kernel. It fails with `EBUSY` or `EAGAIN` when
+    /// the kernel is short of resources, the SQEs are left in the SQ for
+    /// the next submission then.
+    pub(super) fn submit(&mut self) -> io::Result<()> {
+        if self.uring.submission().is_empty() {
+            return Ok(());
+        }
+        match self.uring.submit() {
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EAGAIN) | Some(libc::EBUSY)) => Ok(()),
+            r => r.map(|_| ()),
+        }
+    }
+
+    /// Push an entry, making room first if the SQ is full. It fails with
+    /// `EBUSY` if the kernel left the SQ full.
+    pub(super) fn push(&mut self, entries: &[squeue::Entry]) -> io::Result<()> {
+        {
+            let sq = self.uring.submission();
+            if sq.len() + entries.len() > sq.capacity() {
+                drop(sq);
+                self.uring.submit()?;
+            }
+        }
+        if unsafe { self.uring.submission().push_multiple(entries).is_err() } {
+            return Err(io::Error::from_raw_os_error(libc::EBUSY));
+        }
+        Ok(())
+    }
+
+    pub(super) fn completions(&mut self) -> cqueue::CompletionQueue<'_> {
+        self.uring.completion()
+    }
+}
+
+impl Drop for BulkRing {
+    fn drop(&mut self) {
+        let _ = self.uring.submitter().submit();
+        unsafe {
+            ManuallyDrop::drop(&mut self.uring);
+        }
+    }
+}
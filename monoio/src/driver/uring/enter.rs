@@ -0,0 +1,138 @@
+//! Raw io_uring_enter and ring fd registration.
+//!
+//! The io_uring crate always enters with the real ring fd, so we do the
+//! syscalls by ourselves to make use of a registered ring fd.
+
+use std::{io, os::unix::prelude::RawFd};
+
+use io_uring::types::Timespec;
+
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+const IORING_ENTER_SQ_WAKEUP: u32 = 1 << 1;
+const IORING_ENTER_EXT_ARG: u32 = 1 << 3;
+const IORING_ENTER_REGISTERED_RING: u32 = 1 << 4;
+
+const IORING_REGISTER_RING_FDS: u32 = 20;
+pub(super) const IORING_UNREGISTER_RING_FDS: u32 = 21;
+
+#[repr(C)]
+struct RsrcUpdate {
+    offset: u32,
+    resv: u32,
+    data: u64,
+}
+
+#[repr(C)]
+struct GeteventsArg {
+    sigmask: u64,
+    sigmask_sz: u32,
+    pad: u32,
+    ts: u64,
+}
+
+/// The fd used to enter the ring.
+#[derive(Clone, Copy)]
+pub(crate) enum RingFd {
+    Raw(RawFd),
+    Registered(u32),
+}
+
+/// Register the ring fd to the current thread, returns the registered index.
+/// Requires Linux 5.18+.
+pub(crate) fn register_ring_fd(fd: RawFd) -> io::Result<u32> {
+    let mut update = RsrcUpdate {
+        // let the kernel pick a free slot
+        offset: u32::MAX,
+        resv: 0,
+        data: fd as u64,
+    };
+    register(fd, IORING_REGISTER_RING_FDS, &mut update)?;
+    Ok(update.offset)
+}
+
+/// Unregister the ring fd registered by [`register_ring_fd`]. It must be
+/// called on the registering thread, or the ring is kept alive until the
+/// thread exits.
+pub(crate) fn unregister_ring_fd(fd: RawFd, index: u32) -> io::Result<()> {
+    let mut update = RsrcUpdate {
+        offset: index,
+        resv: 0,
+        data: 0,
+    };
+    register(fd, IORING_UNREGISTER_RING_FDS, &mut update)
+}
+
+fn register(fd: RawFd, opcode: u32, update: &mut RsrcUpdate) -> io::Result<()> {
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_register,
+            fd,
+            opcode,
+            update as *mut RsrcUpdate,
+            1,
+        )
+    };
+    match ret {
+        1 => Ok(()),
+        0 => Err(io::ErrorKind::Other.into()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// Parameters of one io_uring_enter call.
+pub(crate) struct Enter<'a> {
+    pub(crate) to_submit: u32,
+    pub(crate) want: u32,
+    pub(crate) getevents: bool,
+    pub(crate) sq_wakeup: bool,
+    pub(crate) timeout: Option<&'a Timespec>,
+}
+
+pub(crate) fn enter(fd: RingFd, args: Enter<'_>) -> io::Result<usize> {
+    let mut flags = 0;
+    if args.getevents {
+        flags |= IORING_ENTER_GETEVENTS;
+    }
+    if args.sq_wakeup {
+        flags |= IORING_ENTER_SQ_WAKEUP;
+    }
+    let fd = match fd {
+        RingFd::Raw(fd) => fd,
+        RingFd::Registered(index) => {
+            flags |= IORING_ENTER_REGISTERED_RING;
+            index as RawFd
+        }
+    };
+    let ext_arg = args.timeout.map(|ts| GeteventsArg {
+        sigmask: 0,
+        sigmask_sz: 0,
+        pad: 0,
+        ts: ts as *const Timespec as u64,
+    });
+    let (arg, size) = match &ext_arg {
+        Some(arg) => {
+            flags |= IORING_ENTER_EXT_ARG;
+            (
+                arg as *const GeteventsArg as *const libc::c_void,
+                std::mem::size_of::<GeteventsArg>(),
+            )
+        }
+        None => (std::ptr::null(), 0),
+    };
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_io_uring_enter,
+            fd,
+            args.to_submit,
+            args.want,
+            flags,
+            arg,
+            size,
+        )
+    };
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret as usize)
+    }
+}
@@ -2,6 +2,7 @@
 //! Partly borrow from tokio-uring.
 
 use std::{
+    collections::VecDeque,
     io,
     task::{Context, Poll, Waker},
 };
@@ -22,26 +23,39 @@ pub(crate) enum Lifecycle {
 
     /// The operation has completed.
     Completed(io::Result<u32>, u32),
+
+    /// The multishot operation produced completions not consumed yet, more
+    /// are coming until one without `IORING_CQE_F_MORE` is queued.
+    Multishot(VecDeque<(io::Result<u32>, u32)>, Option<Waker>),
 }
 
 impl<'a> Ref<'a, Lifecycle> {
     pub(crate) fn complete(mut self, result: io::Result<u32>, flags: u32) {
+        // A multishot op has more completions coming.
+        let more = io_uring::cqueue::more(flags);
         let ref_mut = &mut *self;
         match ref_mut {
-            Lifecycle::Submitted => {
-                *ref_mut = Lifecycle::Completed(result, flags);
+            Lifecycle::Submitted | Lifecycle::Waiting(_) => {
+                let completed = if more {
+                    Lifecycle::Multishot(VecDeque::from([(result, flags)]), None)
+                } else {
+                    Lifecycle::Completed(result, flags)
+                };
+                if let Lifecycle::Waiting(waker) = std::mem::replace(ref_mut, completed) {
+                    waker.wake();
+                }
             }
-            Lifecycle::Waiting(_) => {
-                let old = std::mem::replace(ref_mut, Lifecycle::Completed(result, flags));
-                match old {
-                    Lifecycle::Waiting(waker) => {
-                        waker.wake();
-                    }
-                    _ => unsafe { std::hint::unreachable_unchecked() },
+            Lifecycle::Multishot(queue, waker) => {
+                queue.push_back((result, flags));
+                if let Some(waker) = waker.take() {
+                    waker.wake();
                 }
             }
-            Lifecycle::Ignored(..) => {
-                self.remove();
+            Lifecycle::Ignored(data) => {
+                crate::driver::op::claim_dropped(&mut **data, &result);
+                if !more {
+                    self.remove();
+                }
             }
             Lifecycle::Completed(..) => unsafe { std::hint::unreachable_unchecked() },
         }
@@ -61,6 +75,7 @@ impl<'a> Ref<'a, Lifecycle> {
                 }
                 return Poll::Pending;
             }
+            Lifecycle::Multishot(..) => panic!("multishot operation polled as oneshot"),
             _ => {}
         }
 
@@ -70,6 +85,37 @@ impl<'a> Ref<'a, Lifecycle> {
         }
     }
 
+    /// Poll the next completion of a multishot op. The slot is removed with
+    /// the last one, which has no `IORING_CQE_F_MORE` flag.
+    pub(crate) fn poll_multishot(mut self, cx: &mut Context<'_>) -> Poll<CompletionMeta> {
+        let ref_mut = &mut *self;
+        match ref_mut {
+            Lifecycle::Submitted | Lifecycle::Waiting(_) => {
+                *ref_mut = Lifecycle::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+            Lifecycle::Multishot(queue, waker) => match queue.pop_front() {
+                Some((result, flags)) => {
+                    if !io_uring::cqueue::more(flags) {
+                        self.remove();
+                    }
+                    Poll::Ready(CompletionMeta { result, flags })
+                }
+                None => {
+                    *waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            },
+            Lifecycle::Completed(..) => match self.remove() {
+                Lifecycle::Completed(result, flags) => {
+                    Poll::Ready(CompletionMeta { result, flags })
+                }
+                _ => unsafe { std::hint::unreachable_unchecked() },
+            },
+            Lifecycle::Ignored(..) => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
     // return if the op must has been finished
     pub(crate) fn drop_op<T: 'static>(mut self, data: &mut Option<T>) -> bool {
         let ref_mut = &mut *self;
@@ -83,7 +129,25 @@ impl<'a> Ref<'a, Lifecycle> {
                 };
                 return false;
             }
-            Lifecycle::Completed(..) => {
+            Lifecycle::Multishot(queue, _)
+                if queue
+                    .back()
+                    .is_some_and(|(_, flags)| !io_uring::cqueue::more(*flags)) =>
+            {
+                self.remove();
+            }
+            Lifecycle::Multishot(..) => {
+                let data: Box<dyn std::any::Any> = match data.take() {
+                    Some(data) => Box::new(data),
+                    None => Box::new(()),
+                };
+                *ref_mut = Lifecycle::Ignored(data);
+                return false;
+            }
+            Lifecycle::Completed(result, _) => {
+                if let Some(data) = data.as_mut() {
+                    crate::driver::op::claim_dropped(data, result);
+                }
                 self.remove();
             }
             Lifecycle::Ignored(..) => unsafe { std::hint::unreachable_unchecked() },
@@ -0,0 +1,118 @@
+//! Cross-ring messaging with `IORING_OP_MSG_RING`.
+
+use std::{
+    io,
+    os::unix::io::{FromRawFd, OwnedFd, RawFd},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use super::{UringInner, MSG_RING_USERDATA, MSG_RING_WAKE_USERDATA};
+use crate::driver::{op::Op, Inner, CURRENT};
+
+/// RingMessenger posts completions directly into the ring of another
+/// runtime thread, without going through eventfd.
+///
+/// Get it with [`RingMessenger::current`] on the target thread and send it to
+/// other threads. Both sides must run on uring driver (requires kernel
+/// 5.18+). Once the target runtime is dropped, sending fails with an error of
+/// kind `NotConnected`.
+#[derive(Debug, Clone)]
+pub struct RingMessenger {
+    ring: Arc<MessengerRing>,
+}
+
+/// The ring of a runtime shared by the driver with its messengers.
+///
+/// It holds a duplicate of the ring fd, so the fd a message is posted to is
+/// never reused for another file, even if the driver is dropped in between.
+#[derive(Debug)]
+pub(crate) struct MessengerRing {
+    fd: Arc<OwnedFd>,
+    // Set when the driver is dropped
+    closed: AtomicBool,
+}
+
+impl MessengerRing {
+    pub(crate) fn new(ring_fd: RawFd) -> io::Result<Self> {
+        let fd = crate::syscall!(fcntl(ring_fd, libc::F_DUPFD_CLOEXEC, 0))?;
+        Ok(Self {
+            fd: Arc::new(unsafe { OwnedFd::from_raw_fd(fd) }),
+            closed: AtomicBool::new(false),
+        })
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+}
+
+impl PartialEq for RingMessenger {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.ring, &other.ring)
+    }
+}
+
+impl Eq for RingMessenger {}
+
+impl RingMessenger {
+    /// Get the messenger of current runtime's ring.
+    /// Returns error if current runtime is not on uring driver.
+    pub fn current() -> io::Result<Self> {
+        CURRENT.with(|inner| match inner {
+            Inner::Uring(this) => Ok(Self {
+                ring: UringInner::messenger_ring(this)?,
+            }),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Err(unsupported()),
+        })
+    }
+
+    /// Post `data` into the target ring. It can be received by
+    /// [`RingMessenger::recv`] on the target thread.
+    pub async fn send(&self, data: u32) -> io::Result<()> {
+        self.post(data, MSG_RING_USERDATA).await
+    }
+
+    /// Wake the target ring if it is parked, without posting a message.
+    pub async fn wake(&self) -> io::Result<()> {
+        self.post(0, MSG_RING_WAKE_USERDATA).await
+    }
+
+    async fn post(&self, data: u32, user_data: u64) -> io::Result<()> {
+        if self.ring.closed.load(Ordering::Acquire) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "the runtime of the target ring is dropped",
+            ));
+        }
+        let op = Op::msg_ring(self.ring.fd.clone(), data, user_data)?;
+        op.await.meta.result.map(|_| ())
+    }
+
+    /// Receive the next message posted to current runtime's ring.
+    /// Returns error if current runtime is not on uring driver.
+    ///
+    /// Note: messages are queued in the ring they are posted to, only one task
+    /// should receive them at a time.
+    pub async fn recv() -> io::Result<u32> {
+        std::future::poll_fn(|cx| {
+            CURRENT.with(|inner| match inner {
+                Inner::Uring(this) => UringInner::poll_ring_message(this, cx).map(Ok),
+                #[cfg(feature = "legacy")]
+                Inner::Legacy(_) => std::task::Poll::Ready(Err(unsupported())),
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "legacy")]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "ring messenger is only supported with uring driver",
+    )
+}
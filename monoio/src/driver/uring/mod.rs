@@ -2,12 +2,13 @@
 
 use std::{
     cell::UnsafeCell,
+    collections::VecDeque,
     io,
     mem::ManuallyDrop,
     os::unix::prelude::{AsRawFd, RawFd},
     rc::Rc,
-    task::{Context, Poll},
-    time::Duration,
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
 };
 
 use io_uring::{cqueue, opcode, types::Timespec, IoUring};
@@ -24,12 +25,23 @@ use super::{
 };
 use crate::utils::slab::Slab;
 
+mod buf_ring;
+mod bulk;
+mod enter;
 mod lifecycle;
+mod messenger;
+mod submit_policy;
+mod wait_policy;
 #[cfg(feature = "sync")]
 mod waker;
 #[cfg(feature = "sync")]
 pub(crate) use waker::UnparkHandle;
 
+#[cfg(feature = "metrics")]
+pub(crate) use self::buf_ring::allocated_bytes as buffer_ring_bytes;
+pub(crate) use self::buf_ring::BufRing;
+pub use self::{messenger::RingMessenger, submit_policy::SubmitPolicy, wait_policy::WaitPolicy};
+
 #[allow(unused)]
 pub(crate) const CANCEL_USERDATA: u64 = u64::MAX;
 pub(crate) const TIMEOUT_USERDATA: u64 = u64::MAX - 1;
@@ -37,8 +49,12 @@ pub(crate) const TIMEOUT_USERDATA: u64 = u64::MAX - 1;
 pub(crate) const EVENTFD_USERDATA: u64 = u64::MAX - 2;
 #[cfg(feature = "poll-io")]
 pub(crate) const POLLER_USERDATA: u64 = u64::MAX - 3;
+pub(crate) const LINK_TIMEOUT_USERDATA: u64 = u64::MAX - 4;
+pub(crate) const MSG_RING_USERDATA: u64 = u64::MAX - 5;
+pub(crate) const MSG_RING_WAKE_USERDATA: u64 = u64::MAX - 6;
+pub(crate) const BULK_USERDATA: u64 = u64::MAX - 7;
 
-pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 3;
+pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 7;
 
 /// Driver with uring.
 pub struct IoUringDriver {
@@ -82,6 +98,36 @@ pub(crate) struct UringInner {
 
     // Uring support ext_arg
     ext_arg: bool,
+
+    // Submit with IORING_ENTER_GETEVENTS, required to reap completions when
+    // the ring is set up with DEFER_TASKRUN.
+    submit_getevents: bool,
+
+    // Registered ring fd index if registered, or the raw ring fd.
+    enter_fd: enter::RingFd,
+
+    // Messages posted by other rings
+    ring_messages: VecDeque<u32>,
+    ring_message_waker: Option<Waker>,
+    // Ring shared with the messengers, created by the first one
+    messenger_ring: Option<std::sync::Arc<messenger::MessengerRing>>,
+
+    // Number of times the CQ was found overflowed
+    cq_overflows: u64,
+
+    submit_policy: SubmitPolicy,
+    // How long to wait for completions on park
+    wait_policy: WaitPolicy,
+    // When the oldest SQE not submitted yet was queued, tracked for
+    // `SubmitPolicy::Batch`
+    queued_since: Option<Instant>,
+
+    // Submit ops with IOSQE_ASYNC
+    async_punt: bool,
+
+    // Ring for ops tagged `Qos::Bulk`, and the tag of the ops submitted now
+    bulk: Option<bulk::BulkRing>,
+    qos: crate::io::Qos,
 }
 
 // When dropping the driver, all in-flight operations must have completed. This
@@ -111,6 +157,20 @@ impl IoUringDriver {
             poller_installed: false,
             ops: Ops::new(),
             ext_arg: uring.params().is_feature_ext_arg(),
+            // DEFER_TASKRUN requires SINGLE_ISSUER, and entering with GETEVENTS
+            // is cheap for other single issuer rings.
+            submit_getevents: uring.params().is_setup_single_issuer(),
+            enter_fd: enter::RingFd::Raw(uring.as_raw_fd()),
+            ring_messages: VecDeque::new(),
+            messenger_ring: None,
+            ring_message_waker: None,
+            cq_overflows: 0,
+            submit_policy: SubmitPolicy::default(),
+            wait_policy: WaitPolicy::default(),
+            queued_since: None,
+            async_punt: false,
+            bulk: None,
+            qos: crate::io::Qos::default(),
             uring,
         }));
 
@@ -145,6 +205,20 @@ impl IoUringDriver {
             poll: super::poll::Poll::with_capacity(entries as usize)?,
             ops: Ops::new(),
             ext_arg: uring.params().is_feature_ext_arg(),
+            // DEFER_TASKRUN requires SINGLE_ISSUER, and entering with GETEVENTS
+            // is cheap for other single issuer rings.
+            submit_getevents: uring.params().is_setup_single_issuer(),
+            enter_fd: enter::RingFd::Raw(uring.as_raw_fd()),
+            ring_messages: VecDeque::new(),
+            messenger_ring: None,
+            ring_message_waker: None,
+            cq_overflows: 0,
+            submit_policy: SubmitPolicy::default(),
+            wait_policy: WaitPolicy::default(),
+            queued_since: None,
+            async_punt: false,
+            bulk: None,
+            qos: crate::io::Qos::default(),
             uring,
             shared_waker: std::sync::Arc::new(waker::EventWaker::new(waker)),
             eventfd_installed: false,
@@ -165,6 +239,81 @@ impl IoUringDriver {
         Ok(driver)
     }
 
+    /// Register the ring fd so entering the ring skips the fd lookup. It is
+    /// a no-op if not supported(requires Linux 5.18+).
+    pub(crate) fn register_ring_fd(&self) {
+        let inner = unsafe { &mut *self.inner.get() };
+        if let enter::RingFd::Raw(fd) = inner.enter_fd {
+            if let Ok(index) = enter::register_ring_fd(fd) {
+                inner.enter_fd = enter::RingFd::Registered(index);
+            }
+        }
+    }
+
+    /// Restrict the ring to `opcodes` and the ones used by the driver
+    /// itself, then enable it. The ring must be built disabled.
+    pub(crate) fn restrict(&self, opcodes: &[u8]) -> io::Result<()> {
+        use io_uring::{register::Restriction, squeue::Flags};
+
+        let inner = unsafe { &mut *self.inner.get() };
+        let mut internal = vec![
+            opcode::AsyncCancel::CODE,
+            opcode::LinkTimeout::CODE,
+            opcode::Timeout::CODE,
+            // eventfd and poller installed to the ring
+            #[cfg(feature = "sync")]
+            opcode::Read::CODE,
+            #[cfg(any(feature = "sync", feature = "poll-io"))]
+            opcode::PollAdd::CODE,
+        ];
+        // poll of the bulk ring eventfd
+        if inner.bulk.is_some() {
+            internal.push(opcode::PollAdd::CODE);
+        }
+        let restrictions = || {
+            let mut restrictions: Vec<_> = opcodes
+                .iter()
+                .chain(internal.iter())
+                .map(|&op| Restriction::sqe_op(op))
+                .collect();
+            // Unregistering the ring fd on drop.
+            restrictions.push(Restriction::register_op(
+                enter::IORING_UNREGISTER_RING_FDS as u8,
+            ));
+            restrictions.push(Restriction::sqe_flags_allowed(Flags::all().bits()));
+            restrictions
+        };
+
+        for uring in inner.bulk.iter().map(|b| &b.uring).chain([&inner.uring]) {
+            let submitter = uring.submitter();
+            submitter.register_restrictions(&mut restrictions())?;
+            submitter.register_enable_rings()?;
+        }
+        Ok(())
+    }
+
+    /// Create the ring for ops tagged `Qos::Bulk`.
+    pub(crate) fn enable_bulk_ring(&self, urb: &io_uring::Builder, entries: u32) -> io::Result<()> {
+        let inner = unsafe { &mut *self.inner.get() };
+        inner.bulk = Some(bulk::BulkRing::new(urb, entries)?);
+        Ok(())
+    }
+
+    pub(crate) fn set_submit_policy(&self, policy: SubmitPolicy) {
+        let inner = unsafe { &mut *self.inner.get() };
+        inner.submit_policy = policy;
+    }
+
+    pub(crate) fn set_wait_policy(&self, policy: WaitPolicy) {
+        let inner = unsafe { &mut *self.inner.get() };
+        inner.wait_policy = policy;
+    }
+
+    pub(crate) fn set_async_punt(&self, enabled: bool) {
+        let inner = unsafe { &mut *self.inner.get() };
+        inner.async_punt = enabled;
+    }
+
     #[allow(unused)]
     fn num_operations(&self) -> usize {
         let inner = self.inner.get();
@@ -247,6 +396,19 @@ impl IoUringDriver {
         }
 
         if need_wait {
+            // Bound the wait by the policy, and wait for more completions if
+            // the kernel can time out the wait. Without a maximum wait, the
+            // in-flight ops may never complete enough to wake up.
+            let policy = inner.wait_policy;
+            let timeout = match (timeout, policy.max_wait) {
+                (Some(timeout), Some(max_wait)) => Some(timeout.min(max_wait)),
+                (timeout, max_wait) => timeout.or(max_wait),
+            };
+            let want = match inner.ext_arg && policy.max_wait.is_some() {
+                true => policy.min_complete.min(inner.ops.slab.len().max(1) as u32),
+                false => 1,
+            };
+
             // Install timeout and eventfd for unpark if sync is enabled
 
             // 1. alloc spaces
@@ -259,6 +421,9 @@ impl IoUringDriver {
             if !inner.poller_installed {
                 space += 1;
             }
+            if inner.bulk.as_ref().is_some_and(|b| !b.poll_installed) {
+                space += 1;
+            }
             if timeout.is_some() {
                 space += 1;
             }
@@ -272,27 +437,33 @@ impl IoUringDriver {
                 self.install_poller(inner, inner.poll.as_raw_fd());
             }
 
-            // 2.2 install eventfd and timeout
+            // 2.2 install the poll of the bulk ring eventfd
+            if let Some(bulk) = inner.bulk.as_mut().filter(|b| !b.poll_installed) {
+                let entry = bulk.poll_entry().user_data(BULK_USERDATA);
+                let _ = unsafe { inner.uring.submission().push(&entry) };
+                bulk.poll_installed = true;
+            }
+
+            // 2.3 install eventfd and timeout
             #[cfg(feature = "sync")]
             if !inner.eventfd_installed {
                 self.install_eventfd(inner, inner.shared_waker.as_raw_fd());
             }
 
-            // 2.3 install timeout and submit_and_wait with timeout
+            // 2.4 install timeout and submit_and_wait with timeout
             if let Some(duration) = timeout {
                 match inner.ext_arg {
                     // Submit and Wait with timeout in an TimeoutOp way.
                     // Better compatibility(5.4+).
                     false => {
                         self.install_timeout(inner, duration);
-                        inner.uring.submit_and_wait(1)?;
+                        inner.enter(1, None)?;
                     }
                     // Submit and Wait with enter args.
                     // Better performance(5.11+).
                     true => {
                         let timespec = timespec(duration);
-                        let args = io_uring::types::SubmitArgs::new().timespec(&timespec);
-                        if let Err(e) = inner.uring.submitter().submit_with_args(1, &args) {
+                        if let Err(e) = inner.enter(want, Some(&timespec)) {
                             if e.raw_os_error() != Some(libc::ETIME) {
                                 return Err(e);
                             }
@@ -301,11 +472,11 @@ impl IoUringDriver {
                 }
             } else {
                 // Submit and Wait without timeout
-                inner.uring.submit_and_wait(1)?;
+                inner.enter(want, None)?;
             }
         } else {
             // Submit only
-            inner.uring.submit()?;
+            inner.enter(0, None)?;
         }
 
         // Set status as awake
@@ -354,7 +525,15 @@ impl Driver for IoUringDriver {
 
     fn submit(&self) -> io::Result<()> {
         let inner = unsafe { &mut *self.inner.get() };
-        inner.submit()?;
+        let submit = match inner.submit_policy {
+            SubmitPolicy::Round => true,
+            SubmitPolicy::Eager => !inner.uring.submission().is_empty(),
+            SubmitPolicy::Batch { .. } => inner.batch_due(),
+            SubmitPolicy::OnPark => false,
+        };
+        if submit {
+            inner.submit()?;
+        }
         inner.tick()?;
         Ok(())
     }
@@ -378,6 +557,36 @@ impl Driver for IoUringDriver {
 
 impl UringInner {
     fn tick(&mut self) -> io::Result<()> {
+        loop {
+            self.reap()?;
+
+            // When the CQ is full, the kernel keeps completions in an
+            // overflow list and flags the SQ. They are only moved to the CQ
+            // when entering with GETEVENTS, so flush them now that there is
+            // room instead of letting their ops stall until the next park.
+            if !self.uring.submission().cq_overflow() {
+                return Ok(());
+            }
+            self.cq_overflows += 1;
+            warn!(
+                "MONOIO DEBUG[IoUringDriver]: completion queue overflowed {} times, consider a \
+                 larger cq size",
+                self.cq_overflows
+            );
+            enter::enter(
+                self.enter_fd,
+                enter::Enter {
+                    to_submit: 0,
+                    want: 0,
+                    getevents: true,
+                    sq_wakeup: false,
+                    timeout: None,
+                },
+            )?;
+        }
+    }
+
+    fn reap(&mut self) -> io::Result<()> {
         let cq = self.uring.completion();
 
         for cqe in cq {
@@ -390,16 +599,89 @@ impl UringInner {
                     self.poller_installed = false;
                     self.poll.tick(Some(Duration::ZERO))?;
                 }
+                MSG_RING_USERDATA => {
+                    self.ring_messages.push_back(cqe.result() as u32);
+                    if let Some(waker) = self.ring_message_waker.take() {
+                        waker.wake();
+                    }
+                }
+                BULK_USERDATA => {
+                    if let Some(bulk) = self.bulk.as_mut() {
+                        bulk.poll_installed = false;
+                        bulk.drain_eventfd();
+                    }
+                }
                 _ if index >= MIN_REVERSED_USERDATA => (),
                 _ => self.ops.complete(index as _, resultify(&cqe), cqe.flags()),
             }
         }
+
+        if let Some(bulk) = self.bulk.as_mut() {
+            for cqe in bulk.completions() {
+                let index = cqe.user_data();
+                if index < MIN_REVERSED_USERDATA {
+                    self.ops.complete(index as _, resultify(&cqe), cqe.flags());
+                }
+            }
+        }
         Ok(())
     }
 
+    // Whether the queued SQEs must be submitted under
+    // `SubmitPolicy::Batch`.
+    fn batch_due(&mut self) -> bool {
+        let SubmitPolicy::Batch { max_ops, max_delay } = self.submit_policy else {
+            return true;
+        };
+        self.uring.submission().len() >= max_ops as usize
+            || self
+                .queued_since
+                .is_some_and(|since| since.elapsed() >= max_delay)
+    }
+
+    // Submit and wait for `want` completions, the wait is bounded by
+    // `timeout` which requires the ext_arg feature.
+    fn enter(&mut self, want: u32, timeout: Option<&Timespec>) -> io::Result<usize> {
+        if let Some(bulk) = self.bulk.as_mut() {
+            bulk.submit()?;
+        }
+        let (to_submit, need_wakeup, cq_overflow) = {
+            let sq = self.uring.submission();
+            (sq.len() as u32, sq.need_wakeup(), sq.cq_overflow())
+        };
+        let params = self.uring.params();
+        // With DEFER_TASKRUN completions are only posted when entering with
+        // GETEVENTS, so always pass it to let the kernel run the deferred
+        // task work.
+        let getevents =
+            want > 0 || self.submit_getevents || params.is_setup_iopoll() || cq_overflow;
+        let mut sq_wakeup = false;
+        if params.is_setup_sqpoll() {
+            if need_wakeup {
+                sq_wakeup = true;
+            } else if want == 0 {
+                // The kernel thread is polling, no need to enter.
+                self.queued_since = None;
+                return Ok(to_submit as usize);
+            }
+        }
+        let submitted = enter::enter(
+            self.enter_fd,
+            enter::Enter {
+                to_submit,
+                want,
+                getevents,
+                sq_wakeup,
+                timeout,
+            },
+        )?;
+        self.queued_since = None;
+        Ok(submitted)
+    }
+
     fn submit(&mut self) -> io::Result<()> {
         loop {
-            match self.uring.submit() {
+            match self.enter(0, None) {
                 #[cfg(feature = "unstable")]
                 Err(ref e)
                     if matches!(e.kind(), io::ErrorKind::Other | io::ErrorKind::ResourceBusy) =>
@@ -426,6 +708,8 @@ impl UringInner {
             driver,
             index: inner.ops.insert(),
             data: Some(data),
+            #[cfg(feature = "interceptor")]
+            intercepted: None,
         }
     }
 
@@ -437,36 +721,91 @@ impl UringInner {
         T: OpAble,
     {
         let inner = unsafe { &mut *this.get() };
-        // If the submission queue is full, flush it to the kernel
-        if inner.uring.submission().is_full() {
-            inner.submit()?;
+        if inner.qos == crate::io::Qos::Bulk && inner.bulk.is_some() {
+            return Self::submit_bulk(this, data);
         }
+        // If the submission queue has no space for the op(and its linked
+        // timeout), flush it to the kernel. The linked entries must be
+        // pushed in the same batch.
+        let link_timeout = OpAble::uring_link_timeout(&data);
+        let need = if link_timeout.is_some() { 2 } else { 1 };
+        IoUringDriver::flush_space(inner, need)?;
 
         // Create the operation
         let mut op = Self::new_op(data, inner, Inner::Uring(this.clone()));
 
         // Configure the SQE
         let data_mut = unsafe { op.data.as_mut().unwrap_unchecked() };
-        let sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        let mut sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        if inner.async_punt {
+            sqe = sqe.flags(io_uring::squeue::Flags::ASYNC);
+        }
 
         {
             let mut sq = inner.uring.submission();
 
-            // Push the new operation
-            if unsafe { sq.push(&sqe).is_err() } {
-                unimplemented!("when is this hit?");
+            if let Some(timespec) = link_timeout {
+                sqe = sqe.flags(io_uring::squeue::Flags::IO_LINK);
+                let timeout = opcode::LinkTimeout::new(timespec)
+                    .build()
+                    .user_data(LINK_TIMEOUT_USERDATA);
+                // Push the new operation with linked timeout
+                if unsafe { sq.push_multiple(&[sqe, timeout]).is_err() } {
+                    unimplemented!("when is this hit?");
+                }
+            } else {
+                // Push the new operation
+                if unsafe { sq.push(&sqe).is_err() } {
+                    unimplemented!("when is this hit?");
+                }
             }
         }
 
-        // Submit the new operation. At this point, the operation has been
-        // pushed onto the queue and the tail pointer has been updated, so
-        // the submission entry is visible to the kernel. If there is an
-        // error here (probably EAGAIN), we still return the operation. A
-        // future `io_uring_enter` will fully submit the event.
+        // Submit the new operation if the policy says so. At this point,
+        // the operation has been pushed onto the queue and the tail pointer
+        // has been updated, so the submission entry is visible to the
+        // kernel. If there is an error here (probably EAGAIN), we still
+        // return the operation. A future `io_uring_enter` will fully submit
+        // the event.
+
+        // CHIHAI: By default we are not going to do syscall now. If we are
+        // waiting for IO, we will submit on `park`.
+        match inner.submit_policy {
+            SubmitPolicy::Eager => {
+                let _ = inner.submit();
+            }
+            SubmitPolicy::Batch { .. } => {
+                inner.queued_since.get_or_insert_with(Instant::now);
+                if inner.batch_due() {
+                    let _ = inner.submit();
+                }
+            }
+            SubmitPolicy::Round | SubmitPolicy::OnPark => {}
+        }
+        Ok(op)
+    }
 
-        // CHIHAI: We are not going to do syscall now. If we are waiting
-        // for IO, we will submit on `park`.
-        // let _ = inner.submit();
+    // Push an op to the bulk ring. It is submitted with the main ring, the
+    // submit policy is not applied.
+    fn submit_bulk<T: OpAble>(this: &Rc<UnsafeCell<UringInner>>, data: T) -> io::Result<Op<T>> {
+        let inner = unsafe { &mut *this.get() };
+        let mut op = Self::new_op(data, inner, Inner::Uring(this.clone()));
+        let data_mut = unsafe { op.data.as_mut().unwrap_unchecked() };
+        let mut sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        if inner.async_punt {
+            sqe = sqe.flags(io_uring::squeue::Flags::ASYNC);
+        }
+        let bulk = unsafe { inner.bulk.as_mut().unwrap_unchecked() };
+        match OpAble::uring_link_timeout(data_mut) {
+            Some(timespec) => {
+                sqe = sqe.flags(io_uring::squeue::Flags::IO_LINK);
+                let timeout = opcode::LinkTimeout::new(timespec)
+                    .build()
+                    .user_data(LINK_TIMEOUT_USERDATA);
+                bulk.push(&[sqe, timeout])?;
+            }
+            None => bulk.push(&[sqe])?,
+        }
         Ok(op)
     }
 
@@ -480,6 +819,16 @@ impl UringInner {
         lifecycle.poll_op(cx)
     }
 
+    pub(crate) fn poll_multishot(
+        this: &Rc<UnsafeCell<UringInner>>,
+        index: usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<CompletionMeta> {
+        let inner = unsafe { &mut *this.get() };
+        let lifecycle = unsafe { inner.ops.slab.get(index).unwrap_unchecked() };
+        lifecycle.poll_multishot(cx)
+    }
+
     #[cfg(feature = "poll-io")]
     pub(crate) fn poll_legacy_op<T: OpAble>(
         this: &Rc<UnsafeCell<Self>>,
@@ -519,29 +868,158 @@ impl UringInner {
             let _must_finished = lifecycle.drop_op(data);
             #[cfg(feature = "async-cancel")]
             if !_must_finished {
-                unsafe {
-                    let cancel = opcode::AsyncCancel::new(index as u64)
-                        .build()
-                        .user_data(u64::MAX);
-
-                    // Try push cancel, if failed, will submit and re-push.
-                    if inner.uring.submission().push(&cancel).is_err() {
-                        let _ = inner.submit();
-                        let _ = inner.uring.submission().push(&cancel);
-                    }
-                }
+                inner.push_cancel(index);
             }
         }
     }
 
     pub(crate) unsafe fn cancel_op(this: &Rc<UnsafeCell<UringInner>>, index: usize) {
         let inner = &mut *this.get();
+        inner.push_cancel(index);
+    }
+
+    // Ops whose futures were dropped, possibly by a panic unwinding through
+    // them, are parked in the slab with their buffers, which the kernel may
+    // still write to. Cancel them and wait for their last completions before
+    // the buffers are freed. Ops not completed in time are leaked instead.
+    fn drain_ops(&mut self) {
+        const DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+        const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+
+        if self.ops.slab.len() == 0 {
+            return;
+        }
+        // IORING_ASYNC_CANCEL_ANY came with IORING_OP_SOCKET in 5.19, older
+        // kernels reject it with EINVAL: cancel the ops one by one there.
+        let canceled = if crate::utils::uring_features().is_supported(opcode::Socket::CODE) {
+            let cancel = opcode::AsyncCancel2::new(io_uring::types::CancelBuilder::any())
+                .build()
+                .user_data(u64::MAX);
+            self.push_cancel_entry(&cancel)
+        } else {
+            let mut indexes = Vec::with_capacity(self.ops.slab.len());
+            self.ops.slab.for_each_key(|index| indexes.push(index));
+            indexes.into_iter().all(|index| self.push_cancel(index))
+        };
+
+        // Waiting with a timeout requires the ext_arg feature, and the ops
+        // may never complete if they could not be canceled.
+        if canceled && self.uring.params().is_feature_ext_arg() {
+            let deadline = Instant::now() + DRAIN_TIMEOUT;
+            let interval = timespec(WAIT_INTERVAL);
+            while self.ops.slab.len() != 0 && Instant::now() < deadline {
+                let _ = self.enter(1, Some(&interval));
+                let _ = self.tick();
+            }
+        }
+        if self.ops.slab.len() != 0 {
+            warn!(
+                "MONOIO DEBUG[IoUringDriver]: {} ops still in flight on drop, leaking their \
+                 buffers",
+                self.ops.slab.len()
+            );
+            std::mem::forget(std::mem::replace(&mut self.ops, Ops::new()));
+        }
+    }
+
+    // Returns false if the cancel could not be pushed.
+    fn push_cancel(&mut self, index: usize) -> bool {
         let cancel = opcode::AsyncCancel::new(index as u64)
             .build()
             .user_data(u64::MAX);
-        if inner.uring.submission().push(&cancel).is_err() {
-            let _ = inner.submit();
-            let _ = inner.uring.submission().push(&cancel);
+        self.push_cancel_entry(&cancel)
+    }
+
+    fn push_cancel_entry(&mut self, cancel: &io_uring::squeue::Entry) -> bool {
+        // The op may be in either ring, an index is unique across both.
+        if let Some(bulk) = self.bulk.as_mut() {
+            let _ = bulk.push(std::slice::from_ref(cancel));
+        }
+        // Try push cancel, if failed, will submit and re-push.
+        if unsafe { self.uring.submission().push(cancel).is_err() } {
+            let _ = self.submit();
+            return unsafe { self.uring.submission().push(cancel).is_ok() };
+        }
+        true
+    }
+
+    /// Set the qos tag of the ops submitted now, returns the previous one.
+    pub(crate) fn replace_qos(
+        this: &Rc<UnsafeCell<UringInner>>,
+        qos: crate::io::Qos,
+    ) -> crate::io::Qos {
+        let inner = unsafe { &mut *this.get() };
+        std::mem::replace(&mut inner.qos, qos)
+    }
+
+    /// Set whether ops are submitted with `IOSQE_ASYNC`, returns the previous
+    /// setting.
+    pub(crate) fn replace_async_punt(this: &Rc<UnsafeCell<UringInner>>, enabled: bool) -> bool {
+        let inner = unsafe { &mut *this.get() };
+        std::mem::replace(&mut inner.async_punt, enabled)
+    }
+
+    /// Probe the opcodes, which sets up a ring on first use, and allocate
+    /// slots for as many in-flight ops as the completion queue holds.
+    pub(crate) fn warmup(this: &Rc<UnsafeCell<UringInner>>) {
+        let _ = crate::utils::uring_features();
+        let inner = unsafe { &mut *this.get() };
+        let entries = inner.uring.params().cq_entries() as usize;
+        inner.ops.slab.reserve(entries);
+    }
+
+    /// Returns how many times the CQ overflowed, and how many completions
+    /// the kernel dropped because it could not keep them(without
+    /// `IORING_FEAT_NODROP`, or when out of memory).
+    #[cfg(feature = "metrics")]
+    pub(crate) fn cq_overflows(this: &Rc<UnsafeCell<UringInner>>) -> (u64, u64) {
+        let inner = unsafe { &mut *this.get() };
+        let dropped = inner.uring.completion().overflow();
+        (inner.cq_overflows, dropped as u64)
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(crate) fn inflight_ops(this: &Rc<UnsafeCell<UringInner>>) -> usize {
+        let inner = unsafe { &*this.get() };
+        inner.ops.slab.len()
+    }
+
+    /// Returns the number of in-flight ops, and the memory of their slots and
+    /// of the data of the ignored ones.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn op_memory(this: &Rc<UnsafeCell<UringInner>>) -> (usize, usize) {
+        let inner = unsafe { &*this.get() };
+        let mut bytes = inner.ops.slab.allocated_bytes();
+        inner.ops.slab.for_each(|lifecycle| {
+            if let Lifecycle::Ignored(data) = lifecycle {
+                bytes += std::mem::size_of_val(&**data);
+            }
+        });
+        (inner.ops.slab.len(), bytes)
+    }
+
+    pub(crate) fn messenger_ring(
+        this: &Rc<UnsafeCell<UringInner>>,
+    ) -> io::Result<std::sync::Arc<messenger::MessengerRing>> {
+        let inner = unsafe { &mut *this.get() };
+        if let Some(ring) = inner.messenger_ring.as_ref() {
+            return Ok(ring.clone());
+        }
+        let ring = std::sync::Arc::new(messenger::MessengerRing::new(inner.uring.as_raw_fd())?);
+        Ok(inner.messenger_ring.insert(ring).clone())
+    }
+
+    pub(crate) fn poll_ring_message(
+        this: &Rc<UnsafeCell<UringInner>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<u32> {
+        let inner = unsafe { &mut *this.get() };
+        match inner.ring_messages.pop_front() {
+            Some(data) => Poll::Ready(data),
+            None => {
+                inner.ring_message_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
         }
     }
 
@@ -583,8 +1061,16 @@ impl Drop for IoUringDriver {
 
 impl Drop for UringInner {
     fn drop(&mut self) {
+        if let Some(ring) = self.messenger_ring.take() {
+            ring.close();
+        }
+        self.drain_ops();
         // no need to wait for completion, as the kernel will clean up the ring asynchronically.
         let _ = self.uring.submitter().submit();
+        // the registered ring fd holds a reference to the ring
+        if let enter::RingFd::Registered(index) = self.enter_fd {
+            let _ = enter::unregister_ring_fd(self.uring.as_raw_fd(), index);
+        }
         unsafe {
             ManuallyDrop::drop(&mut self.uring);
         }
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+/// When the io_uring driver submits queued SQEs to the kernel.
+///
+/// Submitting costs an `io_uring_enter` syscall, so submitting less often
+/// trades latency of the first ops for throughput.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SubmitPolicy {
+    /// Submit after each round of tasks if more tasks are ready, and when
+    /// the runtime parks.
+    #[default]
+    Round,
+    /// Submit every op as soon as it is created.
+    Eager,
+    /// Submit once `max_ops` SQEs are queued, or once the oldest queued SQE
+    /// waited for `max_delay`, and when the runtime parks.
+    ///
+    /// The delay is checked when ops are created and after each round of
+    /// tasks, so a task running for long delays the submission further.
+    Batch {
+        /// Number of queued SQEs triggering a submission.
+        max_ops: u32,
+        /// Maximum time an SQE stays queued.
+        max_delay: Duration,
+    },
+    /// Submit only when the runtime parks, or when the submission queue is
+    /// full.
+    OnPark,
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+/// How long the io_uring driver waits for completions when the runtime
+/// parks.
+///
+/// By default the runtime wakes up on the first completion. Waiting for more
+/// completions, bounded by a maximum wait, trades latency for fewer wakeups,
+/// which suits batch oriented workloads like storage.
+///
+/// The minimum number of completions only applies with a maximum wait.
+/// Unparks from other threads and readiness of polled fds complete one CQE
+/// each, so with a minimum above 1 they may be delayed up to `max_wait`.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let policy = monoio::WaitPolicy::new()
+///     .min_complete(32)
+///     .max_wait(Duration::from_micros(200));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitPolicy {
+    pub(crate) min_complete: u32,
+    pub(crate) max_wait: Option<Duration>,
+}
+
+impl Default for WaitPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitPolicy {
+    /// Wake up on the first completion, without a maximum wait.
+    pub const fn new() -> Self {
+        Self {
+            min_complete: 1,
+            max_wait: None,
+        }
+    }
+
+    /// Set the number of completions to wait for, 1 by default.
+    ///
+    /// It is capped by the number of in-flight ops, and only applies with a
+    /// [`max_wait`](Self::max_wait) on kernels supporting
+    /// `IORING_FEAT_EXT_ARG`(5.11+), the driver waits for one completion
+    /// otherwise.
+    #[must_use]
+    pub const fn min_complete(mut self, min_complete: u32) -> Self {
+        self.min_complete = if min_complete == 0 { 1 } else { min_complete };
+        self
+    }
+
+    /// Set the maximum time to wait, even if fewer completions arrived. The
+    /// next timer deadline bounds the wait too.
+    #[must_use]
+    pub const fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+}
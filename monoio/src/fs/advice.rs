@@ -0,0 +1,43 @@
+/// Access pattern hints for [`File::advise`](super::File::advise) and
+/// [`buf::advise`](crate::buf::advise).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Advice {
+    /// No special treatment, the default.
+    Normal,
+    /// The data will be accessed sequentially, so read ahead more.
+    Sequential,
+    /// The data will be accessed randomly, so do not read ahead.
+    Random,
+    /// The data will be accessed soon, so start loading it.
+    WillNeed,
+    /// The data will not be accessed soon, so it can be dropped from the
+    /// page cache. For anonymous memory the contents are discarded and read
+    /// back as zeros.
+    DontNeed,
+    /// The data will be accessed only once. Only supported for files.
+    NoReuse,
+}
+
+impl Advice {
+    pub(crate) fn fadvise(self) -> i32 {
+        match self {
+            Advice::Normal => libc::POSIX_FADV_NORMAL,
+            Advice::Sequential => libc::POSIX_FADV_SEQUENTIAL,
+            Advice::Random => libc::POSIX_FADV_RANDOM,
+            Advice::WillNeed => libc::POSIX_FADV_WILLNEED,
+            Advice::DontNeed => libc::POSIX_FADV_DONTNEED,
+            Advice::NoReuse => libc::POSIX_FADV_NOREUSE,
+        }
+    }
+
+    pub(crate) fn madvise(self) -> Option<i32> {
+        match self {
+            Advice::Normal => Some(libc::MADV_NORMAL),
+            Advice::Sequential => Some(libc::MADV_SEQUENTIAL),
+            Advice::Random => Some(libc::MADV_RANDOM),
+            Advice::WillNeed => Some(libc::MADV_WILLNEED),
+            Advice::DontNeed => Some(libc::MADV_DONTNEED),
+            Advice::NoReuse => None,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, IntoRawFd},
+    path::Path,
+};
+
+use crate::fs::File;
+
+const BUF_SIZE: usize = 64 * 1024;
+// max bytes copied by one copy_file_range call, the other tasks run between
+// two calls
+#[cfg(target_os = "linux")]
+const RANGE_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Copy the contents of one file to another, returning the number of bytes
+/// copied. The destination is created or truncated, and its permissions are
+/// set to the ones of the source, like `std::fs::copy`.
+///
+/// On Linux `copy_file_range` is used, so the copy is done inside the kernel
+/// and may be a reflink on filesystems that support it. io_uring has no op for
+/// it, so the syscall is issued in bounded chunks on the current thread,
+/// yielding to the other tasks between two chunks. If it is not supported,
+/// e.g. across filesystems on older kernels, the data is copied with a
+/// read/write loop.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::copy("foo.txt", "bar.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    let src = File::open(from).await?;
+    let std_src = unsafe { std::fs::File::from_raw_fd(src.as_raw_fd()) };
+    let metadata = std_src.metadata();
+    let _ = std_src.into_raw_fd();
+    let metadata = metadata?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the source path is not a file",
+        ));
+    }
+
+    let dst = File::create(to).await?;
+    let std_dst = unsafe { std::fs::File::from_raw_fd(dst.as_raw_fd()) };
+    let res = std_dst.set_permissions(metadata.permissions());
+    let _ = std_dst.into_raw_fd();
+    res?;
+
+    let len = metadata.len();
+    #[cfg(target_os = "linux")]
+    let copied = match copy_file_range(&src, &dst, len).await? {
+        Some(copied) => copied,
+        None => copy_with_buf(&src, &dst).await?,
+    };
+    #[cfg(not(target_os = "linux"))]
+    let copied = copy_with_buf(&src, &dst).await?;
+
+    src.close().await?;
+    dst.close().await?;
+    Ok(copied)
+}
+
+/// Returns `None` if copy_file_range is not usable for the files and nothing
+/// is copied.
+#[cfg(target_os = "linux")]
+async fn copy_file_range(src: &File, dst: &File, len: u64) -> io::Result<Option<u64>> {
+    let mut copied = 0;
+    loop {
+        if copied > 0 {
+            yield_now().await;
+        }
+        let chunk = (len.saturating_sub(copied) as usize).clamp(1, RANGE_CHUNK);
+        let res = unsafe {
+            libc::copy_file_range(
+                src.as_raw_fd(),
+                std::ptr::null_mut(),
+                dst.as_raw_fd(),
+                std::ptr::null_mut(),
+                chunk,
+                0,
+            )
+        };
+        match res {
+            // EOF, the file may have grown or shrunk since stat
+            0 => return Ok(Some(copied)),
+            n if n > 0 => copied += n as u64,
+            _ => {
+                let err = io::Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(
+                        libc::ENOSYS | libc::EXDEV | libc::EINVAL | libc::EOPNOTSUPP | libc::EPERM,
+                    ) if copied == 0 => Ok(None),
+                    _ => Err(err),
+                };
+            }
+        }
+    }
+}
+
+// Let the other tasks run before continuing. A task waking itself while it
+// is polled is run again first, so it is woken by a task queued behind the
+// others instead.
+#[cfg(target_os = "linux")]
+async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+        if std::mem::replace(&mut yielded, true) {
+            std::task::Poll::Ready(())
+        } else {
+            let waker = cx.waker().clone();
+            drop(crate::spawn(async move { waker.wake() }));
+            std::task::Poll::Pending
+        }
+    })
+    .await
+}
+
+async fn copy_with_buf(src: &File, dst: &File) -> io::Result<u64> {
+    let mut buf = Vec::with_capacity(BUF_SIZE);
+    let mut pos = 0;
+    loop {
+        let (res, b) = src.read_at(buf, pos).await;
+        let n = res?;
+        if n == 0 {
+            return Ok(pos);
+        }
+        let (res, b) = dst.write_all_at(b, pos).await;
+        res?;
+        buf = b;
+        buf.clear();
+        pos += n as u64;
+    }
+}
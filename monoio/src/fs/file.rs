@@ -470,6 +470,222 @@ impl File {
         Ok(())
     }
 
+    /// Initiate or wait for the write-back of the dirty pages in the range
+    /// `offset..offset+len`, like `sync_file_range(2)`. A `len` of 0 covers
+    /// the rest of the file.
+    ///
+    /// `flags` is built from the `libc::SYNC_FILE_RANGE_*` flags. Unlike
+    /// [`sync_data`](Self::sync_data), it neither flushes the metadata nor
+    /// the disk write cache, so it gives no durability guarantee.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.wal").await?;
+    ///     let (res, _) = f.write_all_at(vec![0; 4096], 0).await;
+    ///     res?;
+    ///     // start writing back the page without waiting for it
+    ///     f.sync_range(0, 4096, libc::SYNC_FILE_RANGE_WRITE).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn sync_range(&self, mut offset: u64, len: u64, flags: u32) -> io::Result<()> {
+        // io_uring takes a 32 bits length, larger ranges are synced in page
+        // aligned chunks.
+        const MAX_CHUNK: u64 = u32::MAX as u64 & !0xfff;
+
+        if len == 0 {
+            Op::sync_file_range(&self.fd, offset, 0, flags)?
+                .await
+                .meta
+                .result?;
+            return Ok(());
+        }
+        let end = offset.saturating_add(len);
+        while offset < end {
+            let chunk = (end - offset).min(MAX_CHUNK);
+            Op::sync_file_range(&self.fd, offset, chunk as u32, flags)?
+                .await
+                .meta
+                .result?;
+            offset += chunk;
+        }
+        Ok(())
+    }
+
+    /// Query the metadata of the open file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::open("foo.txt").await?;
+    ///     println!("{} bytes", f.metadata().await?.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn metadata(&self) -> io::Result<super::Metadata> {
+        let stat = Op::statx_fd(&self.fd)?.statx().await?;
+        Ok(super::Metadata::from_statx(stat))
+    }
+
+    /// Manipulate the allocated disk space of the range `offset..offset+len`,
+    /// like `fallocate(2)`.
+    ///
+    /// With `mode` 0 the space is preallocated, and the file is extended if
+    /// needed. Other modes are built from the `libc::FALLOC_FL_*` flags, e.g.
+    /// `FALLOC_FL_PUNCH_HOLE | FALLOC_FL_KEEP_SIZE` deallocates the range.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.log").await?;
+    ///     // reserve 64MiB up front
+    ///     f.allocate(0, 64 * 1024 * 1024, 0).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn allocate(&self, offset: u64, len: u64, mode: i32) -> io::Result<()> {
+        let op = Op::fallocate(&self.fd, offset, len, mode)?;
+        op.await.meta.result?;
+        Ok(())
+    }
+
+    /// Announce how `range` of the file will be accessed, like
+    /// `posix_fadvise(2)`, e.g. to drop written data from the page cache
+    /// with [`Advice::DontNeed`](super::Advice::DontNeed). An unbounded end
+    /// covers the rest of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::{Advice, File};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::open("foo.db").await?;
+    ///     f.advise(.., Advice::Sequential).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn advise(
+        &self,
+        range: impl std::ops::RangeBounds<u64>,
+        advice: super::Advice,
+    ) -> io::Result<()> {
+        use std::ops::Bound;
+
+        // io_uring takes a 32 bits length, larger ranges are advised in
+        // page aligned chunks.
+        const MAX_CHUNK: u64 = u32::MAX as u64 & !0xfff;
+
+        let mut offset = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => Some(n + 1),
+            Bound::Excluded(&n) => Some(n),
+            Bound::Unbounded => None,
+        };
+        let advice = advice.fadvise();
+        loop {
+            let len = match end {
+                Some(end) if end.saturating_sub(offset) <= MAX_CHUNK => end - offset,
+                Some(_) => MAX_CHUNK,
+                // 0 means until the end of file
+                None => 0,
+            };
+            if len == 0 && end.is_some() {
+                return Ok(());
+            }
+            Op::fadvise(&self.fd, offset, len as u32, advice)?
+                .await
+                .meta
+                .result?;
+            if end.is_none() {
+                return Ok(());
+            }
+            offset += len;
+        }
+    }
+
+    /// Send `len` bytes of the file starting at `offset` to `socket`,
+    /// without copying them through user space. Returns the number of bytes
+    /// sent, which is less than `len` only if the end of file is reached.
+    ///
+    /// The data is spliced through an internal pipe created for each call.
+    /// The file position is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{fs::File, net::TcpStream};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::open("index.html").await?;
+    ///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///     f.send_to(&mut stream, 0, u64::MAX).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn send_to<T: crate::io::as_fd::AsWriteFd>(
+        &self,
+        socket: &mut T,
+        offset: u64,
+        len: u64,
+    ) -> io::Result<u64> {
+        use crate::io::splice::SpliceDestination;
+
+        // default pipe capacity
+        const CHUNK: u64 = 64 * 1024;
+        let (mut pr, pw) = crate::net::unix::new_pipe()?;
+        let mut sent = 0;
+        while sent < len {
+            let chunk = (len - sent).min(CHUNK) as u32;
+            let n = Op::splice_file_to_pipe(
+                &self.fd,
+                offset + sent,
+                &pw.fd,
+                chunk,
+                libc::SPLICE_F_MOVE,
+            )?
+            .splice()
+            .await?;
+            if n == 0 {
+                break;
+            }
+            let mut left = n;
+            while left > 0 {
+                let written = socket.splice_from_pipe(&mut pr, left).await?;
+                if written == 0 {
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                left -= written;
+            }
+            sent += n as u64;
+        }
+        Ok(sent)
+    }
+
     /// Closes the file.
     ///
     /// The method completes once the close operation has completed,
@@ -0,0 +1,51 @@
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use crate::buf::IoBufMut;
+
+/// Issue `ioctl(fd, request, buf)` on the blocking thread pool, returning
+/// the value of the call and the argument buffer.
+///
+/// io_uring has no generic ioctl opcode, so the call is run with
+/// [`spawn_blocking`](crate::spawn_blocking) to keep slow device requests
+/// (v4l2, GPIO, network drivers) off the reactor thread. A thread pool must
+/// be attached to the runtime, or the blocking strategy set to
+/// `ExecuteLocal`.
+///
+/// The pointer to `buf` is passed as the argument, so it must hold the
+/// argument struct the request expects. Its initialized length is left
+/// untouched: initialize it to the size of the struct before the call.
+/// `fd` is duplicated for the call, it may be closed while the call runs.
+///
+/// # Safety
+///
+/// `buf` must be a valid argument for `request`: the kernel reads and writes
+/// as many bytes as `request` defines through the pointer, whatever the size
+/// of the buffer. Its capacity must be at least the size of the argument
+/// struct `request` expects, and hold a valid value of it for the requests
+/// reading their argument.
+///
+/// ```no_run
+/// # async fn f(file: monoio::fs::File) -> std::io::Result<()> {
+/// // FIONREAD writes an int.
+/// let (_, buf) = unsafe { monoio::fs::ioctl(&file, libc::FIONREAD as _, vec![0; 4]) }.await?;
+/// let available = i32::from_ne_bytes(buf[..4].try_into().unwrap());
+/// # Ok(())
+/// # }
+/// ```
+pub async unsafe fn ioctl<F, B>(fd: &F, request: libc::c_ulong, mut buf: B) -> io::Result<(i32, B)>
+where
+    F: AsRawFd,
+    B: IoBufMut + Send + 'static,
+{
+    let fd = crate::syscall!(fcntl(fd.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    crate::spawn_blocking(move || {
+        let res = crate::syscall!(ioctl(fd.as_raw_fd(), request as _, buf.write_ptr()));
+        res.map(|ret| (ret, buf))
+    })
+    .await
+    .map_err(|_| io::Error::other("ioctl canceled by the thread pool"))?
+}
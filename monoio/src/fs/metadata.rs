@@ -0,0 +1,180 @@
+use std::{
+    fs::Permissions,
+    io,
+    os::unix::fs::PermissionsExt,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::driver::op::Op;
+
+/// Metadata information about a file, obtained with `statx(2)`.
+///
+/// Besides the fields of `std::fs::Metadata`, it exposes the statx
+/// extensions, like the birth time and the mount id.
+#[derive(Clone, Copy)]
+pub struct Metadata {
+    stat: libc::statx,
+}
+
+/// Given a path, query the file system to get information about a file,
+/// directory, etc. Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let metadata = monoio::fs::metadata("/some/file/path.txt").await?;
+///     println!("{} bytes", metadata.len());
+///     Ok(())
+/// }
+/// ```
+pub async fn metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    let stat = Op::statx_path(path, true)?.statx().await?;
+    Ok(Metadata { stat })
+}
+
+/// Query the metadata about a file without following symlinks.
+pub async fn symlink_metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    let stat = Op::statx_path(path, false)?.statx().await?;
+    Ok(Metadata { stat })
+}
+
+impl Metadata {
+    pub(crate) fn from_statx(stat: libc::statx) -> Self {
+        Self { stat }
+    }
+
+    /// Returns true if it is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.stat.stx_mode as u32 & libc::S_IFMT == libc::S_IFDIR
+    }
+
+    /// Returns true if it is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.stat.stx_mode as u32 & libc::S_IFMT == libc::S_IFREG
+    }
+
+    /// Returns true if it is a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.stat.stx_mode as u32 & libc::S_IFMT == libc::S_IFLNK
+    }
+
+    /// Size of the file in bytes.
+    pub fn len(&self) -> u64 {
+        self.stat.stx_size
+    }
+
+    /// Returns true if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.stat.stx_size == 0
+    }
+
+    /// Permissions of the file.
+    pub fn permissions(&self) -> Permissions {
+        Permissions::from_mode(self.mode())
+    }
+
+    /// Raw mode, including the file type bits.
+    pub fn mode(&self) -> u32 {
+        self.stat.stx_mode as u32
+    }
+
+    /// Last modification time.
+    pub fn modified(&self) -> io::Result<SystemTime> {
+        self.time(0, self.stat.stx_mtime)
+    }
+
+    /// Last access time.
+    pub fn accessed(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_ATIME, self.stat.stx_atime)
+    }
+
+    /// Last status change time.
+    pub fn changed(&self) -> io::Result<SystemTime> {
+        self.time(0, self.stat.stx_ctime)
+    }
+
+    /// Creation time, returns an error if the filesystem does not record it.
+    pub fn created(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_BTIME, self.stat.stx_btime)
+    }
+
+    /// Id of the mount containing the file, returns `None` if not supported
+    /// by the kernel (5.8+ is required).
+    pub fn mount_id(&self) -> Option<u64> {
+        (self.stat.stx_mask & libc::STATX_MNT_ID != 0).then_some(self.stat.stx_mnt_id)
+    }
+
+    /// Inode number.
+    pub fn ino(&self) -> u64 {
+        self.stat.stx_ino
+    }
+
+    /// Id of the device containing the file.
+    pub fn dev(&self) -> u64 {
+        libc::makedev(self.stat.stx_dev_major, self.stat.stx_dev_minor)
+    }
+
+    /// Device id, if the file is a device.
+    pub fn rdev(&self) -> u64 {
+        libc::makedev(self.stat.stx_rdev_major, self.stat.stx_rdev_minor)
+    }
+
+    /// Number of hard links.
+    pub fn nlink(&self) -> u64 {
+        self.stat.stx_nlink as u64
+    }
+
+    /// User id of the owner.
+    pub fn uid(&self) -> u32 {
+        self.stat.stx_uid
+    }
+
+    /// Group id of the owner.
+    pub fn gid(&self) -> u32 {
+        self.stat.stx_gid
+    }
+
+    /// Preferred block size for io.
+    pub fn blksize(&self) -> u64 {
+        self.stat.stx_blksize as u64
+    }
+
+    /// Number of 512B blocks allocated.
+    pub fn blocks(&self) -> u64 {
+        self.stat.stx_blocks
+    }
+
+    /// File attributes, the `STATX_ATTR_*` flags.
+    pub fn attributes(&self) -> u64 {
+        self.stat.stx_attributes & self.stat.stx_attributes_mask
+    }
+
+    fn time(&self, mask: u32, ts: libc::statx_timestamp) -> io::Result<SystemTime> {
+        if mask != 0 && self.stat.stx_mask & mask == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the time is not available on this filesystem",
+            ));
+        }
+        let nanos = Duration::new(0, ts.tv_nsec);
+        Ok(if ts.tv_sec >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(ts.tv_sec as u64) + nanos
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(ts.tv_sec.unsigned_abs()) + nanos
+        })
+    }
+}
+
+impl std::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metadata")
+            .field("mode", &format_args!("{:#o}", self.mode()))
+            .field("len", &self.len())
+            .field("ino", &self.ino())
+            .field("modified", &self.modified())
+            .finish_non_exhaustive()
+    }
+}
@@ -3,11 +3,45 @@
 mod file;
 use std::{io, path::Path};
 
+#[cfg(target_os = "linux")]
+mod advice;
+#[cfg(target_os = "linux")]
+pub use advice::Advice;
+
+#[cfg(unix)]
+mod copy;
+#[cfg(unix)]
+pub use copy::copy;
+
+#[cfg(all(unix, feature = "sync"))]
+mod ioctl;
+#[cfg(all(unix, feature = "sync"))]
+pub use ioctl::ioctl;
+
+#[cfg(target_os = "linux")]
+mod metadata;
 pub use file::File;
+#[cfg(target_os = "linux")]
+pub use metadata::{metadata, symlink_metadata, Metadata};
 
 mod open_options;
 pub use open_options::OpenOptions;
 
+#[cfg(target_os = "linux")]
+mod scrub;
+#[cfg(target_os = "linux")]
+pub use scrub::{scrub, Chunk};
+
+#[cfg(all(unix, feature = "sync"))]
+mod statfs;
+#[cfg(all(unix, feature = "sync"))]
+pub use statfs::{statfs, FsStats};
+
+#[cfg(target_os = "linux")]
+mod temp;
+#[cfg(target_os = "linux")]
+pub use temp::{tempdir, tempdir_in, tempfile, tempfile_in, TempDir};
+
 use crate::buf::IoBuf;
 
 /// Read the entire contents of a file into a bytes vector.
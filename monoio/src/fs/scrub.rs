@@ -0,0 +1,157 @@
+use std::{collections::VecDeque, io, path::Path, rc::Rc};
+
+use super::File;
+use crate::{buf::IoBufMut, task::JoinHandle, BufResult};
+
+/// A chunk of a file read by [`scrub`].
+#[derive(Debug)]
+pub struct Chunk<'a> {
+    /// Path of the file.
+    pub path: &'a Path,
+    /// Offset of the chunk in the file.
+    pub offset: u64,
+    /// Content of the chunk.
+    pub data: &'a [u8],
+    /// Whether it is the last chunk of the file. An empty file has a single
+    /// empty chunk.
+    pub last: bool,
+}
+
+struct Pending {
+    path: Rc<Path>,
+    offset: u64,
+    last: bool,
+    read: JoinHandle<BufResult<(), Vec<u8>>>,
+}
+
+/// Read whole files in chunks, feeding them to `f`, and return the number of
+/// bytes read.
+///
+/// Up to `concurrency` reads of `chunk_size` bytes are kept in flight, which
+/// is the queue depth seen by the device: the reads are spread over the
+/// current file and the next ones, and reuse `concurrency` buffers
+/// allocated upfront. The chunks of a file are given to `f` in order, so it
+/// can compute a checksum, and files are processed in the order of `paths`.
+///
+/// The first error, from opening or reading a file or returned by `f`,
+/// aborts the scrub. Errors of files are prefixed with their path.
+///
+/// # Panics
+///
+/// This function panics if `concurrency` or `chunk_size` is 0.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::collections::HashMap;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut sums = HashMap::new();
+///     let paths = std::env::args().skip(1);
+///     monoio::fs::scrub(paths, 32, 128 * 1024, |chunk| {
+///         let sum = sums.entry(chunk.path.to_owned()).or_insert(0u64);
+///         *sum = chunk
+///             .data
+///             .iter()
+///             .fold(*sum, |s, b| s.wrapping_add(*b as u64));
+///         Ok(())
+///     })
+///     .await?;
+///     println!("{sums:?}");
+///     Ok(())
+/// }
+/// ```
+pub async fn scrub<I, P, F>(
+    paths: I,
+    concurrency: usize,
+    chunk_size: usize,
+    mut f: F,
+) -> io::Result<u64>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+    F: FnMut(Chunk<'_>) -> io::Result<()>,
+{
+    assert!(concurrency > 0, "scrub concurrency must be positive");
+    assert!(chunk_size > 0, "scrub chunk size must be positive");
+
+    let mut paths = paths.into_iter();
+    let mut buffers: Vec<Vec<u8>> = (0..concurrency)
+        .map(|_| Vec::with_capacity(chunk_size))
+        .collect();
+    // Reads in submission order, which is the order of the chunks of each
+    // file.
+    let mut pending = VecDeque::with_capacity(concurrency);
+    // The file being read, with the offset of the next read and its length.
+    let mut current: Option<(Rc<File>, Rc<Path>, u64, u64)> = None;
+    let mut total = 0;
+
+    loop {
+        while pending.len() < concurrency {
+            let (file, path, offset, len) = match &mut current {
+                Some(current) => current,
+                None => match paths.next() {
+                    Some(path) => {
+                        let path: Rc<Path> = path.as_ref().into();
+                        let (file, len) = open(&path).await.map_err(|e| with_path(&path, e))?;
+                        current.insert((Rc::new(file), path, 0, len))
+                    }
+                    None => break,
+                },
+            };
+            let size = (*len - *offset).min(chunk_size as u64) as usize;
+            let last = *offset + size as u64 == *len;
+            let buf = buffers.pop().expect("a buffer is free for each read");
+            let read = {
+                let file = file.clone();
+                let offset = *offset;
+                crate::spawn(async move {
+                    let (res, buf) = file.read_exact_at(buf.slice_mut(0..size), offset).await;
+                    (res, buf.into_inner())
+                })
+            };
+            pending.push_back(Pending {
+                path: path.clone(),
+                offset: *offset,
+                last,
+                read,
+            });
+            *offset += size as u64;
+            if last {
+                current = None;
+            }
+        }
+
+        let Some(Pending {
+            path,
+            offset,
+            last,
+            read,
+        }) = pending.pop_front()
+        else {
+            return Ok(total);
+        };
+        let (res, mut buf) = read.await;
+        res.map_err(|e| with_path(&path, e))?;
+        f(Chunk {
+            path: &path,
+            offset,
+            data: &buf,
+            last,
+        })?;
+        total += buf.len() as u64;
+        buf.clear();
+        buffers.push(buf);
+    }
+}
+
+async fn open(path: &Path) -> io::Result<(File, u64)> {
+    let file = File::open(path).await?;
+    let len = file.metadata().await?.len();
+    Ok((file, len))
+}
+
+fn with_path(path: &Path, e: io::Error) -> io::Error {
+    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+}
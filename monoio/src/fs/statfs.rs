@@ -0,0 +1,96 @@
+use std::{ffi::CString, io, mem::MaybeUninit, os::unix::ffi::OsStrExt, path::Path};
+
+/// Capacity and usage of a filesystem, obtained with `statvfs(3)`.
+#[derive(Clone, Copy)]
+pub struct FsStats {
+    stat: libc::statvfs,
+}
+
+/// Query the capacity and usage of the filesystem containing `path`.
+///
+/// There is no statfs op in io_uring, so the call is run with
+/// [`spawn_blocking`](crate::spawn_blocking). A thread pool must be attached
+/// to the runtime, or the blocking strategy set to `ExecuteLocal`.
+///
+/// ```no_run
+/// # async fn f() -> std::io::Result<()> {
+/// let stats = monoio::fs::statfs("/var/lib/data").await?;
+/// if stats.available_space() < 1 << 30 {
+///     // Stop accepting writes.
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn statfs<P: AsRef<Path>>(path: P) -> io::Result<FsStats> {
+    let path = CString::new(path.as_ref().as_os_str().as_bytes())?;
+    crate::spawn_blocking(move || {
+        let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+        crate::syscall!(statvfs(path.as_ptr(), stat.as_mut_ptr()))?;
+        Ok(FsStats {
+            stat: unsafe { stat.assume_init() },
+        })
+    })
+    .await
+    .map_err(|_| io::Error::other("statfs canceled by the thread pool"))?
+}
+
+// The field types of `statvfs` differ across platforms.
+#[allow(clippy::unnecessary_cast)]
+impl FsStats {
+    /// Size of a block in bytes, the unit of the block counts.
+    pub fn block_size(&self) -> u64 {
+        self.stat.f_frsize as u64
+    }
+
+    /// Total size of the filesystem in bytes.
+    pub fn total_space(&self) -> u64 {
+        self.stat.f_blocks as u64 * self.block_size()
+    }
+
+    /// Free space in bytes, including the blocks reserved for root.
+    pub fn free_space(&self) -> u64 {
+        self.stat.f_bfree as u64 * self.block_size()
+    }
+
+    /// Free space in bytes available to unprivileged users.
+    pub fn available_space(&self) -> u64 {
+        self.stat.f_bavail as u64 * self.block_size()
+    }
+
+    /// Total number of inodes.
+    pub fn total_inodes(&self) -> u64 {
+        self.stat.f_files as u64
+    }
+
+    /// Number of free inodes.
+    pub fn free_inodes(&self) -> u64 {
+        self.stat.f_ffree as u64
+    }
+
+    /// Number of free inodes available to unprivileged users.
+    pub fn available_inodes(&self) -> u64 {
+        self.stat.f_favail as u64
+    }
+
+    /// Maximum length of a file name.
+    pub fn max_name_len(&self) -> u64 {
+        self.stat.f_namemax as u64
+    }
+
+    /// Returns true if the filesystem is mounted read only.
+    pub fn is_read_only(&self) -> bool {
+        self.stat.f_flag as u64 & libc::ST_RDONLY as u64 != 0
+    }
+}
+
+impl std::fmt::Debug for FsStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FsStats")
+            .field("block_size", &self.block_size())
+            .field("total_space", &self.total_space())
+            .field("available_space", &self.available_space())
+            .field("free_inodes", &self.free_inodes())
+            .field("is_read_only", &self.is_read_only())
+            .finish()
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    future::Future,
+    io,
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+use super::{File, OpenOptions};
+use crate::driver::op::Op;
+
+// Attempts at picking a name that does not exist yet.
+const NAME_ATTEMPTS: usize = 64;
+
+/// Create an anonymous file in [`std::env::temp_dir`], see [`tempfile_in`].
+pub async fn tempfile() -> io::Result<File> {
+    tempfile_in(std::env::temp_dir()).await
+}
+
+/// Create an anonymous file in `dir`, opened for reading and writing.
+///
+/// The file is created with `O_TMPFILE`, so it has no name and its storage
+/// is released when it is closed, even if the process crashes. On
+/// filesystems without `O_TMPFILE` it is created under a random name and
+/// unlinked right away.
+pub async fn tempfile_in<P: AsRef<Path>>(dir: P) -> io::Result<File> {
+    let dir = dir.as_ref();
+    let res = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .mode(0o600)
+        .custom_flags(libc::O_TMPFILE)
+        .open(dir)
+        .await;
+    match res {
+        Err(e) if tmpfile_unsupported(&e) => {}
+        res => return res,
+    }
+
+    for _ in 0..NAME_ATTEMPTS {
+        let path = dir.join(random_name());
+        let res = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .await;
+        match res {
+            Ok(file) => {
+                Op::unlink(&path)?.await.meta.result?;
+                return Ok(file);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(exhausted())
+}
+
+/// Create a directory in [`std::env::temp_dir`], see [`tempdir_in`].
+pub async fn tempdir() -> io::Result<TempDir> {
+    tempdir_in(std::env::temp_dir()).await
+}
+
+/// Create a directory with a random name in `dir`, only accessible by the
+/// current user. It is removed with its content when the returned
+/// [`TempDir`] is closed or dropped.
+pub async fn tempdir_in<P: AsRef<Path>>(dir: P) -> io::Result<TempDir> {
+    for _ in 0..NAME_ATTEMPTS {
+        let path = dir.as_ref().join(random_name());
+        match Op::mkdir(&path, 0o700)?.await.meta.result {
+            Ok(_) => return Ok(TempDir { path: Some(path) }),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(exhausted())
+}
+
+/// A temporary directory, removed with its content on drop.
+///
+/// On drop inside a runtime, the removal is spawned as a task and may not
+/// complete if the runtime stops first. Outside a runtime the directory is
+/// removed synchronously. Use [`TempDir::close`] to wait for the removal and
+/// get its error.
+#[derive(Debug)]
+pub struct TempDir {
+    path: Option<PathBuf>,
+}
+
+impl TempDir {
+    /// Returns the path of the directory.
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().expect("path is taken on close")
+    }
+
+    /// Keep the directory and return its path.
+    pub fn into_path(mut self) -> PathBuf {
+        self.path.take().expect("path is taken on close")
+    }
+
+    /// Remove the directory with its content.
+    ///
+    /// The directories are listed synchronously, io_uring has no op for it.
+    pub async fn close(mut self) -> io::Result<()> {
+        let path = self.path.take().expect("path is taken on close");
+        remove_dir_all(path).await
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let Some(path) = self.path.take() else {
+            return;
+        };
+        if crate::runtime::CURRENT.is_set() {
+            drop(crate::spawn(remove_dir_all(path)));
+        } else {
+            let _ = std::fs::remove_dir_all(path);
+        }
+    }
+}
+
+// Boxed to recurse into the subdirectories.
+fn remove_dir_all(path: PathBuf) -> Pin<Box<dyn Future<Output = io::Result<()>>>> {
+    Box::pin(async move {
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                remove_dir_all(entry.path()).await?;
+            } else {
+                Op::unlink(entry.path())?.await.meta.result?;
+            }
+        }
+        Op::rmdir(&path)?.await.meta.result?;
+        Ok(())
+    })
+}
+
+// `O_TMPFILE` is rejected with EISDIR by kernels that do not know it, and
+// with EOPNOTSUPP by filesystems that do not implement it.
+fn tmpfile_unsupported(e: &io::Error) -> bool {
+    matches!(
+        e.raw_os_error(),
+        Some(libc::EISDIR | libc::EOPNOTSUPP | libc::EINVAL)
+    )
+}
+
+fn random_name() -> String {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let mut name = String::from(".tmp");
+    for _ in 0..10 {
+        let i = crate::utils::thread_rng_n(CHARS.len() as u32) as usize;
+        name.push(CHARS[i] as char);
+    }
+    name
+}
+
+fn exhausted() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "too many temporary files exist",
+    )
+}
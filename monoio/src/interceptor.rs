@@ -0,0 +1,179 @@
+//! Op interceptors.
+//!
+//! An [`Interceptor`] registered with
+//! [`RuntimeBuilder::with_interceptor`](crate::RuntimeBuilder::with_interceptor)
+//! sees every op before it is submitted to the driver, and can reject it. It
+//! is also told when the op completes, which makes it a natural place for
+//! cross-cutting policies like sandboxing file access or per-tenant io
+//! accounting.
+//!
+//! ```
+//! use std::{io, path::Path, sync::Arc};
+//!
+//! use monoio::interceptor::{Interceptor, OpInfo};
+//!
+//! struct Jail(&'static Path);
+//!
+//! impl Interceptor for Jail {
+//!     fn on_submit(&self, op: &OpInfo<'_>) -> io::Result<u64> {
+//!         match op.path() {
+//!             Some(path) if !path.starts_with(self.0) => {
+//!                 Err(io::ErrorKind::PermissionDenied.into())
+//!             }
+//!             _ => Ok(0),
+//!         }
+//!     }
+//! }
+//!
+//! let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+//!     .with_interceptor(Arc::new(Jail(Path::new("/tmp"))))
+//!     .build()
+//!     .unwrap();
+//! rt.block_on(async {
+//!     assert!(monoio::fs::File::open("/etc/hostname").await.is_err());
+//! });
+//! ```
+
+#[cfg(unix)]
+use std::os::fd::RawFd;
+#[cfg(windows)]
+use std::os::windows::io::RawSocket as RawFd;
+use std::{ffi::CStr, io, path::Path, sync::Arc};
+
+/// Hooks run by the runtime around every op.
+///
+/// The same interceptor can be shared by the runtimes of several threads,
+/// so its state must be thread safe.
+pub trait Interceptor: Send + Sync {
+    /// Called before an op is submitted.
+    ///
+    /// Returning an error rejects the op, which fails with that error
+    /// without reaching the driver. Otherwise the returned tag, for example
+    /// the id of the tenant which submitted the op, is passed back to
+    /// [`on_complete`](Self::on_complete).
+    #[inline]
+    fn on_submit(&self, op: &OpInfo<'_>) -> io::Result<u64> {
+        let _ = op;
+        Ok(0)
+    }
+
+    /// Called with the result of a completed op. Ops dropped before
+    /// completion are not reported.
+    #[inline]
+    fn on_complete(&self, kind: OpKind, tag: u64, result: &io::Result<u32>) {
+        let _ = (kind, tag, result);
+    }
+}
+
+/// Kind of an op.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OpKind {
+    /// Open a file.
+    Open,
+    /// Get the metadata of a file.
+    Stat,
+    /// Read from a fd.
+    Read,
+    /// Write to a fd.
+    Write,
+    /// Receive from a socket.
+    Recv,
+    /// Send to a socket.
+    Send,
+    /// Accept a connection.
+    Accept,
+    /// Connect a socket.
+    Connect,
+    /// Close a fd.
+    Close,
+    /// Sync a file to disk.
+    Fsync,
+    /// Wait for the readiness of a fd.
+    Poll,
+    /// Any other op.
+    Other,
+}
+
+/// Description of an op about to be submitted.
+#[derive(Debug, Clone, Copy)]
+pub struct OpInfo<'a> {
+    kind: OpKind,
+    fd: Option<RawFd>,
+    path: Option<&'a Path>,
+}
+
+impl<'a> OpInfo<'a> {
+    #[inline]
+    pub(crate) const fn new(kind: OpKind) -> Self {
+        Self {
+            kind,
+            fd: None,
+            path: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn with_fd(mut self, fd: RawFd) -> Self {
+        self.fd = Some(fd);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn with_path(mut self, path: &'a CStr) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            self.path = Some(Path::new(std::ffi::OsStr::from_bytes(path.to_bytes())));
+        }
+        #[cfg(windows)]
+        {
+            self.path = path.to_str().ok().map(Path::new);
+        }
+        self
+    }
+
+    /// Returns the kind of the op.
+    #[inline]
+    pub fn kind(&self) -> OpKind {
+        self.kind
+    }
+
+    /// Returns the fd the op works on, if any.
+    #[inline]
+    pub fn fd(&self) -> Option<RawFd> {
+        self.fd
+    }
+
+    /// Returns the path the op works on, if any. Relative paths are
+    /// relative to the current directory, or to the opened file for a stat
+    /// with a fd.
+    #[inline]
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+}
+
+fn current() -> Option<Arc<dyn Interceptor>> {
+    if !crate::runtime::CURRENT.is_set() {
+        return None;
+    }
+    crate::runtime::CURRENT.with(|ctx| ctx.interceptor.clone())
+}
+
+/// Run the interceptor of the current runtime before submitting an op,
+/// returns what is needed to report its completion.
+pub(crate) fn submit(op: &OpInfo<'_>) -> io::Result<Option<(OpKind, u64)>> {
+    match current() {
+        Some(interceptor) => interceptor.on_submit(op).map(|tag| Some((op.kind, tag))),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn complete(intercepted: Option<(OpKind, u64)>, result: &io::Result<u32>) {
+    if let Some((kind, tag)) = intercepted {
+        if let Some(interceptor) = current() {
+            interceptor.on_complete(kind, tag, result);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    future::poll_fn,
+    io,
+    ops::{BitOr, BitOrAssign},
+    os::fd::{AsRawFd, IntoRawFd, RawFd},
+    task::{Context, Poll},
+};
+
+use super::stream::Stream;
+use crate::driver::{
+    op::{is_legacy, Op, PollAdd},
+    shared_fd::SharedFd,
+};
+
+/// Readiness an [`AsyncFd`] is interested in, or which became ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interest(u8);
+
+impl Interest {
+    /// Interest in the fd becoming readable.
+    pub const READABLE: Interest = Interest(0b01);
+    /// Interest in the fd becoming writable.
+    pub const WRITABLE: Interest = Interest(0b10);
+
+    /// Returns true if the value includes readable.
+    #[inline]
+    pub const fn is_readable(self) -> bool {
+        self.0 & Self::READABLE.0 != 0
+    }
+
+    /// Returns true if the value includes writable.
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        self.0 & Self::WRITABLE.0 != 0
+    }
+}
+
+impl BitOr for Interest {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Interest(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Interest {
+    #[inline]
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// A raw fd whose readiness is driven by the runtime.
+///
+/// This is meant for readiness-oriented libraries which do their io with
+/// nonblocking syscalls themselves, and only need to know when to retry. The
+/// fd should be in nonblocking mode, and it is closed when the `AsyncFd` and
+/// all its [`ReadyStream`]s are dropped.
+///
+/// ```no_run
+/// use std::os::fd::AsRawFd;
+///
+/// use monoio::io::{stream::Stream, AsyncFd, Interest};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let socket = std::net::UdpSocket::bind("127.0.0.1:0")?;
+///     socket.set_nonblocking(true)?;
+///     let fd = AsyncFd::new(socket.try_clone()?)?;
+///     let mut ready = fd.ready_multi(Interest::READABLE);
+///     while let Some(ready) = ready.next().await {
+///         ready?;
+///         let mut buf = [0; 1500];
+///         while let Ok(n) = socket.recv(&mut buf) {
+///             println!("got {n} bytes on fd {}", fd.as_raw_fd());
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct AsyncFd {
+    fd: SharedFd,
+}
+
+impl AsyncFd {
+    /// Take ownership of the fd and register it to the current runtime.
+    pub fn new(fd: impl IntoRawFd) -> io::Result<Self> {
+        let fd = fd.into_raw_fd();
+        match SharedFd::new::<false>(fd) {
+            Ok(fd) => Ok(Self { fd }),
+            Err(e) => {
+                unsafe { libc::close(fd) };
+                Err(e)
+            }
+        }
+    }
+
+    /// Wait for the fd to become readable.
+    pub async fn readable(&self) -> io::Result<()> {
+        Op::poll_read(&self.fd, false)?.wait().await
+    }
+
+    /// Wait for the fd to become writable.
+    pub async fn writable(&self) -> io::Result<()> {
+        Op::poll_write(&self.fd, false)?.wait().await
+    }
+
+    /// Returns a stream yielding the readiness of the fd each time it
+    /// changes.
+    ///
+    /// With io_uring it is backed by a multishot `IORING_OP_POLL_ADD`, so
+    /// the poll is armed once instead of being submitted for every wait. The
+    /// legacy driver waits on the poller each time instead. Readiness may be
+    /// coalesced or spurious: after each item the fd should be used until
+    /// its syscalls return `WouldBlock`.
+    pub fn ready_multi(&self, interest: Interest) -> ReadyStream {
+        ReadyStream {
+            fd: self.fd.clone(),
+            interest,
+            read: None,
+            write: None,
+        }
+    }
+}
+
+impl AsRawFd for AsyncFd {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
+impl std::fmt::Debug for AsyncFd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncFd").field("fd", &self.fd).finish()
+    }
+}
+
+/// Stream of readiness returned by [`AsyncFd::ready_multi`].
+///
+/// It never ends, an error does not stop it.
+pub struct ReadyStream {
+    fd: SharedFd,
+    interest: Interest,
+    // In-flight polls, re-armed when the kernel ended them.
+    read: Option<Op<PollAdd>>,
+    write: Option<Op<PollAdd>>,
+}
+
+impl ReadyStream {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Interest>> {
+        let mut ready = None;
+        let polls = [
+            (&mut self.read, Interest::READABLE),
+            (&mut self.write, Interest::WRITABLE),
+        ];
+        for (slot, interest) in polls {
+            if self.interest.0 & interest.0 == 0 {
+                continue;
+            }
+            let op = match slot {
+                Some(op) => op,
+                None => slot.insert(Op::poll_multi(&self.fd, interest.is_readable())?),
+            };
+            if let Poll::Ready(meta) = op.poll_multishot(cx) {
+                if !meta.more() {
+                    *slot = None;
+                }
+                meta.result?;
+                *ready.get_or_insert(interest) |= interest;
+            }
+        }
+        match ready {
+            Some(ready) => Poll::Ready(Ok(ready)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Stream for ReadyStream {
+    type Item = io::Result<Interest>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        Some(poll_fn(|cx| self.poll_ready(cx)).await)
+    }
+}
+
+impl Drop for ReadyStream {
+    fn drop(&mut self) {
+        // A multishot poll only ends when it is canceled.
+        if !is_legacy() {
+            for op in [&self.read, &self.write].into_iter().flatten() {
+                unsafe { op.op_canceller().cancel() };
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ReadyStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadyStream")
+            .field("fd", &self.fd)
+            .field("interest", &self.interest)
+            .finish()
+    }
+}
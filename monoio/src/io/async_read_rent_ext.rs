@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{fmt, future::Future, io};
 
 use super::AsyncReadRent;
 use crate::{
@@ -6,6 +6,55 @@ use crate::{
     BufResult,
 };
 
+/// Error of [`read_exact_partial`](AsyncReadRentExt::read_exact_partial),
+/// with the number of bytes read before it.
+#[derive(Debug)]
+pub struct ReadExactError {
+    read: usize,
+    error: io::Error,
+}
+
+impl ReadExactError {
+    /// Number of bytes read before the error, the read can be resumed from
+    /// there.
+    #[inline]
+    pub fn read(&self) -> usize {
+        self.read
+    }
+
+    /// Returns the io error, of kind `UnexpectedEof` if the reader reached
+    /// EOF.
+    #[inline]
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Returns the io error, dropping the count.
+    #[inline]
+    pub fn into_error(self) -> io::Error {
+        self.error
+    }
+}
+
+impl fmt::Display for ReadExactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} after reading {} bytes", self.error, self.read)
+    }
+}
+
+impl std::error::Error for ReadExactError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<ReadExactError> for io::Error {
+    #[inline]
+    fn from(e: ReadExactError) -> Self {
+        e.error
+    }
+}
+
 macro_rules! reader_trait {
     ($future: ident, $n_ty: ty, $f: ident) => {
         /// Read number in async way
@@ -53,6 +102,20 @@ pub trait AsyncReadRentExt {
         buf: T,
     ) -> impl Future<Output = BufResult<usize, T>>;
 
+    /// Read until buf capacity is fulfilled, on failure the error tells how
+    /// many bytes were read before it, so the read can be resumed.
+    fn read_exact_partial<T: IoBufMut + 'static>(
+        &mut self,
+        buf: T,
+    ) -> impl Future<Output = (Result<usize, ReadExactError>, T)>;
+
+    /// Readv until buf capacity is fulfilled, on failure the error tells how
+    /// many bytes were read before it, so the read can be resumed.
+    fn read_vectored_exact_partial<T: IoVecBufMut + 'static>(
+        &mut self,
+        buf: T,
+    ) -> impl Future<Output = (Result<usize, ReadExactError>, T)>;
+
     reader_trait!(ReadU8Future, u8, read_u8);
     reader_trait!(ReadU16Future, u16, read_u16);
     reader_trait!(ReadU32Future, u32, read_u32);
@@ -84,7 +147,23 @@ impl<A> AsyncReadRentExt for A
 where
     A: AsyncReadRent + ?Sized,
 {
-    async fn read_exact<T: IoBufMut + 'static>(&mut self, mut buf: T) -> BufResult<usize, T> {
+    async fn read_exact<T: IoBufMut + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.read_exact_partial(buf).await;
+        (res.map_err(Into::into), buf)
+    }
+
+    async fn read_vectored_exact<T: IoVecBufMut + 'static>(
+        &mut self,
+        buf: T,
+    ) -> BufResult<usize, T> {
+        let (res, buf) = self.read_vectored_exact_partial(buf).await;
+        (res.map_err(Into::into), buf)
+    }
+
+    async fn read_exact_partial<T: IoBufMut + 'static>(
+        &mut self,
+        mut buf: T,
+    ) -> (Result<usize, ReadExactError>, T) {
         let len = buf.bytes_total();
         let mut read = 0;
         while read < len {
@@ -92,30 +171,22 @@ where
             let (result, buf_slice) = self.read(buf_slice).await;
             buf = buf_slice.into_inner();
             match result {
-                Ok(0) => {
-                    return (
-                        Err(std::io::Error::new(
-                            std::io::ErrorKind::UnexpectedEof,
-                            "failed to fill whole buffer",
-                        )),
-                        buf,
-                    )
-                }
+                Ok(0) => return (Err(unexpected_eof(read)), buf),
                 Ok(n) => {
                     read += n;
                     unsafe { buf.set_init(read) };
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return (Err(ReadExactError { read, error }), buf),
             }
         }
         (Ok(read), buf)
     }
 
-    async fn read_vectored_exact<T: IoVecBufMut + 'static>(
+    async fn read_vectored_exact_partial<T: IoVecBufMut + 'static>(
         &mut self,
         mut buf: T,
-    ) -> BufResult<usize, T> {
+    ) -> (Result<usize, ReadExactError>, T) {
         let mut meta = crate::buf::write_vec_meta(&mut buf);
         let len = meta.len();
         let mut read = 0;
@@ -124,18 +195,10 @@ where
             let (res, meta_) = self.readv(meta).await;
             meta = meta_;
             match res {
-                Ok(0) => {
-                    return (
-                        Err(std::io::Error::new(
-                            std::io::ErrorKind::UnexpectedEof,
-                            "failed to fill whole buffer",
-                        )),
-                        buf,
-                    )
-                }
+                Ok(0) => return (Err(unexpected_eof(read)), buf),
                 Ok(n) => read += n,
-                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return (Err(ReadExactError { read, error }), buf),
             }
         }
         (Ok(read), buf)
@@ -167,3 +230,10 @@ where
     reader_be_impl!(ReadF32LEFuture, f32, read_f32_le);
     reader_be_impl!(ReadF64LEFuture, f64, read_f64_le);
 }
+
+fn unexpected_eof(read: usize) -> ReadExactError {
+    ReadExactError {
+        read,
+        error: io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill whole buffer"),
+    }
+}
@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{fmt, future::Future, io};
 
 use crate::{
     buf::{IoBuf, IoVecBuf, Slice},
@@ -6,6 +6,54 @@ use crate::{
     BufResult,
 };
 
+/// Error of [`write_all_partial`](AsyncWriteRentExt::write_all_partial), with
+/// the number of bytes written before it.
+#[derive(Debug)]
+pub struct WriteAllError {
+    written: usize,
+    error: io::Error,
+}
+
+impl WriteAllError {
+    /// Number of bytes written before the error, the write can be resumed
+    /// from there.
+    #[inline]
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Returns the io error.
+    #[inline]
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Returns the io error, dropping the count.
+    #[inline]
+    pub fn into_error(self) -> io::Error {
+        self.error
+    }
+}
+
+impl fmt::Display for WriteAllError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} after writing {} bytes", self.error, self.written)
+    }
+}
+
+impl std::error::Error for WriteAllError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<WriteAllError> for io::Error {
+    #[inline]
+    fn from(e: WriteAllError) -> Self {
+        e.error
+    }
+}
+
 /// AsyncWriteRentExt
 pub trait AsyncWriteRentExt {
     /// Write all
@@ -19,13 +67,40 @@ pub trait AsyncWriteRentExt {
         &mut self,
         buf: T,
     ) -> impl Future<Output = BufResult<usize, T>>;
+
+    /// Write all, on failure the error tells how many bytes were written
+    /// before it, so the write can be resumed.
+    fn write_all_partial<T: IoBuf + 'static>(
+        &mut self,
+        buf: T,
+    ) -> impl Future<Output = (Result<usize, WriteAllError>, T)>;
+
+    /// Write vectored all, on failure the error tells how many bytes were
+    /// written before it, so the write can be resumed.
+    fn write_vectored_all_partial<T: IoVecBuf + 'static>(
+        &mut self,
+        buf: T,
+    ) -> impl Future<Output = (Result<usize, WriteAllError>, T)>;
 }
 
 impl<A> AsyncWriteRentExt for A
 where
     A: AsyncWriteRent + ?Sized,
 {
-    async fn write_all<T: IoBuf + 'static>(&mut self, mut buf: T) -> BufResult<usize, T> {
+    async fn write_all<T: IoBuf + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.write_all_partial(buf).await;
+        (res.map_err(Into::into), buf)
+    }
+
+    async fn write_vectored_all<T: IoVecBuf + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.write_vectored_all_partial(buf).await;
+        (res.map_err(Into::into), buf)
+    }
+
+    async fn write_all_partial<T: IoBuf + 'static>(
+        &mut self,
+        mut buf: T,
+    ) -> (Result<usize, WriteAllError>, T) {
         let len = buf.bytes_init();
         let mut written = 0;
         while written < len {
@@ -33,24 +108,19 @@ where
             let (result, buf_slice) = self.write(buf_slice).await;
             buf = buf_slice.into_inner();
             match result {
-                Ok(0) => {
-                    return (
-                        Err(std::io::Error::new(
-                            std::io::ErrorKind::WriteZero,
-                            "failed to write whole buffer",
-                        )),
-                        buf,
-                    )
-                }
+                Ok(0) => return (Err(write_zero(written)), buf),
                 Ok(n) => written += n,
-                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return (Err(WriteAllError { written, error }), buf),
             }
         }
         (Ok(written), buf)
     }
 
-    async fn write_vectored_all<T: IoVecBuf + 'static>(&mut self, buf: T) -> BufResult<usize, T> {
+    async fn write_vectored_all_partial<T: IoVecBuf + 'static>(
+        &mut self,
+        buf: T,
+    ) -> (Result<usize, WriteAllError>, T) {
         let mut meta = crate::buf::read_vec_meta(&buf);
         let len = meta.len();
         let mut written = 0;
@@ -59,23 +129,22 @@ where
             let (res, meta_) = self.writev(meta).await;
             meta = meta_;
             match res {
-                Ok(0) => {
-                    return (
-                        Err(std::io::Error::new(
-                            std::io::ErrorKind::WriteZero,
-                            "failed to write whole buffer",
-                        )),
-                        buf,
-                    )
-                }
+                Ok(0) => return (Err(write_zero(written)), buf),
                 Ok(n) => {
                     written += n;
                     meta.consume(n);
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                Err(e) => return (Err(e), buf),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return (Err(WriteAllError { written, error }), buf),
             }
         }
         (Ok(written), buf)
     }
 }
+
+fn write_zero(written: usize) -> WriteAllError {
+    WriteAllError {
+        written,
+        error: io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer"),
+    }
+}
@@ -9,6 +9,9 @@ mod async_rent_cancelable_ext;
 mod async_write_rent;
 mod async_write_rent_ext;
 
+#[cfg(unix)]
+mod async_fd;
+
 pub mod sink;
 pub mod stream;
 
@@ -19,22 +22,28 @@ pub mod splice;
 pub use async_buf_read::AsyncBufRead;
 pub use async_buf_read_ext::AsyncBufReadExt;
 pub use async_read_rent::{AsyncReadRent, AsyncReadRentAt};
-pub use async_read_rent_ext::AsyncReadRentExt;
+pub use async_read_rent_ext::{AsyncReadRentExt, ReadExactError};
 pub use async_rent_cancelable::{CancelableAsyncReadRent, CancelableAsyncWriteRent};
 pub use async_rent_cancelable_ext::{CancelableAsyncReadRentExt, CancelableAsyncWriteRentExt};
 pub use async_write_rent::{AsyncWriteRent, AsyncWriteRentAt};
-pub use async_write_rent_ext::AsyncWriteRentExt;
+pub use async_write_rent_ext::{AsyncWriteRentExt, WriteAllError};
 
 mod util;
 
+#[cfg(unix)]
+pub use async_fd::{AsyncFd, Interest, ReadyStream};
+#[cfg(all(target_os = "linux", feature = "splice"))]
+pub use splice::SpliceFlags;
 #[cfg(feature = "poll-io")]
 pub use tokio::io as poll_io;
 pub(crate) use util::operation_canceled;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub use util::zero_copy;
 pub use util::{
-    copy, BufReader, BufWriter, CancelHandle, Canceller, OwnedReadHalf, OwnedWriteHalf,
-    PrefixedReadIo, Split, Splitable,
+    async_punt, copy, forward, is_canceled, sink_from_writer, with_qos, AsyncPunt, BufReader,
+    BufWriter, CancelHandle, Canceller, ChunkCipher, ChunkedCipherStream, CountedStream,
+    IoCounters, OwnedReadHalf, OwnedWriteHalf, PrefixedReadIo, Qos, Split, Splitable, WithQos,
+    WriterSink,
 };
 #[cfg(feature = "poll-io")]
 /// Convert a completion-based io to a poll-based io.
@@ -1,10 +1,51 @@
 //! Splice related trait and default impl.
 
-use std::future::Future;
+use std::{future::Future, ops::BitOr};
 
 use super::as_fd::{AsReadFd, AsWriteFd};
 use crate::{driver::op::Op, net::Pipe};
 
+/// Flags of a splice operation, see `splice(2)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpliceFlags(u32);
+
+impl SpliceFlags {
+    /// No flags.
+    pub const NONE: SpliceFlags = SpliceFlags(0);
+    /// Attempt to move pages instead of copying. Used by default.
+    pub const MOVE: SpliceFlags = SpliceFlags(libc::SPLICE_F_MOVE);
+    /// More data will be coming in a subsequent splice, like `MSG_MORE`.
+    pub const MORE: SpliceFlags = SpliceFlags(libc::SPLICE_F_MORE);
+
+    /// Returns the raw flag bits.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Returns true if all flags in `other` are set.
+    #[inline]
+    pub const fn contains(self, other: SpliceFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for SpliceFlags {
+    #[inline]
+    fn default() -> Self {
+        Self::MOVE
+    }
+}
+
+impl BitOr for SpliceFlags {
+    type Output = SpliceFlags;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        SpliceFlags(self.0 | rhs.0)
+    }
+}
+
 /// Splice data from self to pipe.
 pub trait SpliceSource {
     /// Splice data from self to pipe.
@@ -12,6 +53,16 @@ pub trait SpliceSource {
         &'a mut self,
         pipe: &'a mut Pipe,
         len: u32,
+    ) -> impl Future<Output = std::io::Result<u32>> {
+        self.splice_to_pipe_with_flags(pipe, len, SpliceFlags::default())
+    }
+
+    /// Splice data from self to pipe with the given flags.
+    fn splice_to_pipe_with_flags<'a>(
+        &'a mut self,
+        pipe: &'a mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
     ) -> impl Future<Output = std::io::Result<u32>>;
 }
 
@@ -22,17 +73,28 @@ pub trait SpliceDestination {
         &'a mut self,
         pipe: &'a mut Pipe,
         len: u32,
+    ) -> impl Future<Output = std::io::Result<u32>> {
+        self.splice_from_pipe_with_flags(pipe, len, SpliceFlags::default())
+    }
+
+    /// Splice data from self from pipe with the given flags.
+    fn splice_from_pipe_with_flags<'a>(
+        &'a mut self,
+        pipe: &'a mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
     ) -> impl Future<Output = std::io::Result<u32>>;
 }
 
 impl<T: AsReadFd> SpliceSource for T {
     #[inline]
-    async fn splice_to_pipe<'a>(
+    async fn splice_to_pipe_with_flags<'a>(
         &'a mut self,
         pipe: &'a mut Pipe,
         len: u32,
+        flags: SpliceFlags,
     ) -> std::io::Result<u32> {
-        Op::splice_to_pipe(self.as_reader_fd().as_ref(), &pipe.fd, len)?
+        Op::splice_to_pipe(self.as_reader_fd().as_ref(), &pipe.fd, len, flags.bits())?
             .splice()
             .await
     }
@@ -40,12 +102,13 @@ impl<T: AsReadFd> SpliceSource for T {
 
 impl<T: AsWriteFd> SpliceDestination for T {
     #[inline]
-    async fn splice_from_pipe<'a>(
+    async fn splice_from_pipe_with_flags<'a>(
         &'a mut self,
         pipe: &'a mut Pipe,
         len: u32,
+        flags: SpliceFlags,
     ) -> std::io::Result<u32> {
-        Op::splice_from_pipe(&pipe.fd, self.as_writer_fd().as_ref(), len)?
+        Op::splice_from_pipe(&pipe.fd, self.as_writer_fd().as_ref(), len, flags.bits())?
             .splice()
             .await
     }
@@ -97,7 +97,7 @@ impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
         let owned_len = owned_buf.len();
         let amt = buf.bytes_init();
 
-        if self.pos + amt > owned_len {
+        if self.cap + amt > owned_len {
             // Buf can not be copied directly into OwnedBuf,
             // we must flush OwnedBuf first.
             match self.flush_buf().await {
@@ -109,7 +109,7 @@ impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
         }
 
         // Now there are two situations here:
-        // 1. OwnedBuf has data, and self.pos + amt <= owned_len,
+        // 1. OwnedBuf has data, and self.cap + amt <= owned_len,
         // which means the data can be copied into OwnedBuf.
         // 2. OwnedBuf is empty. If we can copy buf into OwnedBuf,
         // we will copy it, otherwise we will send it directly(in
@@ -65,7 +65,9 @@ impl Canceller {
 }
 
 impl CancelHandle {
-    pub(crate) fn canceled(&self) -> bool {
+    /// Returns true if the related [`Canceller`] has been canceled.
+    #[inline]
+    pub fn canceled(&self) -> bool {
         self.shared.borrow().canceled
     }
 
@@ -91,3 +93,14 @@ impl Drop for AssociateGuard {
 pub(crate) fn operation_canceled() -> std::io::Error {
     std::io::Error::from_raw_os_error(125)
 }
+
+/// Returns true if the error means the operation was canceled before it took
+/// effect.
+///
+/// A canceled operation either completes normally, if it finished before the
+/// cancellation reached the driver, or fails with this error, so the result
+/// always tells definitively whether the operation happened.
+#[inline]
+pub fn is_canceled(e: &std::io::Error) -> bool {
+    e.raw_os_error() == Some(125)
+}
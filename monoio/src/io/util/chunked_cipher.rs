@@ -0,0 +1,264 @@
+use std::io;
+
+use crate::{
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapper, IoVecWrapperMut},
+    io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRent, AsyncWriteRentExt},
+    BufResult,
+};
+
+/// Cipher used by [`ChunkedCipherStream`] to seal and open chunks.
+///
+/// Each call handles exactly one chunk, so implementations can keep
+/// per-chunk state like sequence numbers or nonces.
+pub trait ChunkCipher {
+    /// Encrypt a plaintext chunk and append the sealed bytes to `out`.
+    fn seal(&mut self, plain: &[u8], out: &mut Vec<u8>) -> io::Result<()>;
+
+    /// Decrypt a sealed chunk and append the plaintext to `out`.
+    fn open(&mut self, sealed: &[u8], out: &mut Vec<u8>) -> io::Result<()>;
+}
+
+/// ChunkedCipherStream is a packetized encrypted transport over an io.
+///
+/// Every write is split into chunks of at most `max_chunk` bytes. Each chunk
+/// is sealed by the [`ChunkCipher`] and sent with a 4 bytes big endian length
+/// prefix. Reads do the opposite and buffer the plaintext of one chunk.
+pub struct ChunkedCipherStream<IO, C> {
+    io: IO,
+    cipher: C,
+    max_chunk: usize,
+    max_frame: usize,
+
+    // opened plaintext of the current chunk
+    plain: Vec<u8>,
+    pos: usize,
+    // buffer for sealed frames
+    frame: Option<Vec<u8>>,
+}
+
+const DEFAULT_MAX_CHUNK: usize = 16 * 1024;
+const DEFAULT_MAX_FRAME: usize = 1 << 24;
+const HEADER_LEN: usize = 4;
+
+impl<IO, C> ChunkedCipherStream<IO, C> {
+    /// Create ChunkedCipherStream with default chunk size.
+    #[inline]
+    pub fn new(io: IO, cipher: C) -> Self {
+        Self {
+            io,
+            cipher,
+            max_chunk: DEFAULT_MAX_CHUNK,
+            max_frame: DEFAULT_MAX_FRAME,
+            plain: Vec::new(),
+            pos: 0,
+            frame: Some(Vec::new()),
+        }
+    }
+
+    /// Set max plaintext size of a chunk on writing.
+    #[must_use]
+    #[inline]
+    pub fn with_max_chunk(mut self, max_chunk: usize) -> Self {
+        assert!(max_chunk > 0, "chunk size must be positive");
+        self.max_chunk = max_chunk;
+        self
+    }
+
+    /// Set max sealed frame size accepted on reading. Larger frames are
+    /// rejected with `InvalidData`.
+    #[must_use]
+    #[inline]
+    pub fn with_max_frame(mut self, max_frame: usize) -> Self {
+        self.max_frame = max_frame;
+        self
+    }
+
+    /// Gets a reference to the underlying io.
+    #[inline]
+    pub fn get_ref(&self) -> &IO {
+        &self.io
+    }
+
+    /// Gets a mutable reference to the underlying io.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut IO {
+        &mut self.io
+    }
+
+    /// Gets a mutable reference to the cipher, e.g. for rekeying.
+    #[inline]
+    pub fn cipher_mut(&mut self) -> &mut C {
+        &mut self.cipher
+    }
+
+    /// Consumes this `ChunkedCipherStream`, returning the io and cipher.
+    ///
+    /// Note that any buffered plaintext is lost.
+    #[inline]
+    pub fn into_inner(self) -> (IO, C) {
+        (self.io, self.cipher)
+    }
+}
+
+impl<IO: AsyncReadRent, C: ChunkCipher> ChunkedCipherStream<IO, C> {
+    // Read and open the next chunk. Returns false on eof.
+    async fn fill_chunk(&mut self) -> io::Result<bool> {
+        let mut frame = self
+            .frame
+            .take()
+            .expect("no buffer available, generated future must be awaited");
+        let res = self.read_frame(&mut frame).await;
+        let res = match res {
+            Ok(true) => {
+                self.plain.clear();
+                self.pos = 0;
+                self.cipher.open(&frame, &mut self.plain).map(|_| true)
+            }
+            other => other,
+        };
+        self.frame = Some(frame);
+        res
+    }
+
+    async fn read_frame(&mut self, frame: &mut Vec<u8>) -> io::Result<bool> {
+        // read header, eof is only allowed at chunk boundary
+        let mut header = std::mem::take(frame);
+        header.clear();
+        header.reserve(HEADER_LEN);
+        while header.len() < HEADER_LEN {
+            let filled = header.len();
+            let slice = header.slice_mut(filled..HEADER_LEN);
+            let (res, slice) = self.io.read(slice).await;
+            header = slice.into_inner();
+            match res {
+                Ok(0) if header.is_empty() => {
+                    *frame = header;
+                    return Ok(false);
+                }
+                Ok(0) => {
+                    *frame = header;
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    *frame = header;
+                    return Err(e);
+                }
+            }
+        }
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        if len > self.max_frame {
+            *frame = header;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "chunk frame too large",
+            ));
+        }
+
+        header.clear();
+        header.reserve(len);
+        let (res, slice) = self.io.read_exact(header.slice_mut(0..len)).await;
+        *frame = slice.into_inner();
+        res.map(|_| true)
+    }
+}
+
+impl<IO: AsyncReadRent, C: ChunkCipher> AsyncReadRent for ChunkedCipherStream<IO, C> {
+    async fn read<T: IoBufMut>(&mut self, mut buf: T) -> BufResult<usize, T> {
+        if buf.bytes_total() == 0 {
+            return (Ok(0), buf);
+        }
+        // empty chunks are allowed, so loop until we get some data or eof
+        while self.pos == self.plain.len() {
+            match self.fill_chunk().await {
+                Ok(true) => {}
+                Ok(false) => return (Ok(0), buf),
+                Err(e) => return (Err(e), buf),
+            }
+        }
+
+        let amt = (self.plain.len() - self.pos).min(buf.bytes_total());
+        unsafe {
+            buf.write_ptr()
+                .copy_from_nonoverlapping(self.plain.as_ptr().add(self.pos), amt);
+            buf.set_init(amt);
+        }
+        self.pos += amt;
+        (Ok(amt), buf)
+    }
+
+    async fn readv<T: IoVecBufMut>(&mut self, mut buf: T) -> BufResult<usize, T> {
+        let slice = match IoVecWrapperMut::new(buf) {
+            Ok(slice) => slice,
+            Err(buf) => return (Ok(0), buf),
+        };
+
+        let (result, slice) = self.read(slice).await;
+        buf = slice.into_inner();
+        if let Ok(n) = result {
+            unsafe { buf.set_init(n) };
+        }
+        (result, buf)
+    }
+}
+
+impl<IO: AsyncWriteRent, C: ChunkCipher> AsyncWriteRent for ChunkedCipherStream<IO, C> {
+    async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let amt = buf.bytes_init().min(self.max_chunk);
+        if amt == 0 {
+            return (Ok(0), buf);
+        }
+        let mut frame = self
+            .frame
+            .take()
+            .expect("no buffer available, generated future must be awaited");
+        frame.clear();
+        frame.extend_from_slice(&[0; HEADER_LEN]);
+        let plain = unsafe { std::slice::from_raw_parts(buf.read_ptr(), amt) };
+        if let Err(e) = self.cipher.seal(plain, &mut frame) {
+            self.frame = Some(frame);
+            return (Err(e), buf);
+        }
+        let len = match u32::try_from(frame.len() - HEADER_LEN) {
+            Ok(len) => len,
+            Err(_) => {
+                self.frame = Some(frame);
+                return (
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "sealed chunk too large",
+                    )),
+                    buf,
+                );
+            }
+        };
+        frame[..HEADER_LEN].copy_from_slice(&len.to_be_bytes());
+
+        let (res, frame) = self.io.write_all(frame).await;
+        self.frame = Some(frame);
+        match res {
+            Ok(_) => (Ok(amt), buf),
+            Err(e) => (Err(e), buf),
+        }
+    }
+
+    async fn writev<T: IoVecBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let slice = match IoVecWrapper::new(buf) {
+            Ok(slice) => slice,
+            Err(buf) => return (Ok(0), buf),
+        };
+
+        let (result, slice) = self.write(slice).await;
+        (result, slice.into_inner())
+    }
+
+    #[inline]
+    async fn flush(&mut self) -> io::Result<()> {
+        self.io.flush().await
+    }
+
+    #[inline]
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.io.shutdown().await
+    }
+}
@@ -0,0 +1,216 @@
+use std::{cell::Cell, future::Future, rc::Rc};
+
+use super::{split::Split, CancelHandle};
+use crate::{
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
+    io::{AsyncReadRent, AsyncWriteRent, CancelableAsyncReadRent, CancelableAsyncWriteRent},
+    BufResult,
+};
+
+/// Byte counters shared by one or more [`CountedStream`]s.
+///
+/// Clones share the same counters, so a single `IoCounters` can account for
+/// all the connections of a tenant.
+#[derive(Debug, Clone, Default)]
+pub struct IoCounters {
+    inner: Rc<Counts>,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    read: Cell<u64>,
+    written: Cell<u64>,
+}
+
+impl IoCounters {
+    /// Create zeroed counters.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of bytes read.
+    #[inline]
+    pub fn read_bytes(&self) -> u64 {
+        self.inner.read.get()
+    }
+
+    /// Total number of bytes written.
+    #[inline]
+    pub fn written_bytes(&self) -> u64 {
+        self.inner.written.get()
+    }
+
+    /// Reset both counters to zero, returning the read and written bytes
+    /// until now.
+    #[inline]
+    pub fn take(&self) -> (u64, u64) {
+        (self.inner.read.take(), self.inner.written.take())
+    }
+
+    #[inline]
+    fn add_read(&self, res: &std::io::Result<usize>) {
+        if let Ok(n) = res {
+            self.inner.read.set(self.inner.read.get() + *n as u64);
+        }
+    }
+
+    #[inline]
+    fn add_written(&self, res: &std::io::Result<usize>) {
+        if let Ok(n) = res {
+            self.inner.written.set(self.inner.written.get() + *n as u64);
+        }
+    }
+}
+
+/// CountedStream transparently wraps an IO and accounts the bytes read and
+/// written through it in [`IoCounters`].
+///
+/// ```
+/// # use monoio::io::{AsyncWriteRent, CountedStream, IoCounters};
+///
+/// async fn demo<T: AsyncWriteRent>(stream: T, tenant: &IoCounters) {
+///     let mut stream = CountedStream::with_counters(stream, tenant.clone());
+///     let _ = stream.write(b"hello").await;
+///     println!("tenant sent {} bytes", tenant.written_bytes());
+/// }
+/// ```
+pub struct CountedStream<I> {
+    io: I,
+    counters: IoCounters,
+}
+
+impl<I> CountedStream<I> {
+    /// Wrap the io with its own counters.
+    #[inline]
+    pub fn new(io: I) -> Self {
+        Self::with_counters(io, IoCounters::new())
+    }
+
+    /// Wrap the io, accounting in the given counters.
+    #[inline]
+    pub fn with_counters(io: I, counters: IoCounters) -> Self {
+        Self { io, counters }
+    }
+
+    /// Returns the counters.
+    #[inline]
+    pub fn counters(&self) -> &IoCounters {
+        &self.counters
+    }
+
+    /// Gets a reference to the underlying io.
+    #[inline]
+    pub fn get_ref(&self) -> &I {
+        &self.io
+    }
+
+    /// Gets a mutable reference to the underlying io.
+    ///
+    /// Bytes transferred through it directly are not accounted.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.io
+    }
+
+    /// Into inner
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.io
+    }
+}
+
+/// CountedStream is safe to split if the inner io is, the halves share the
+/// counters.
+unsafe impl<I: Split> Split for CountedStream<I> {}
+
+impl<I: AsyncReadRent> AsyncReadRent for CountedStream<I> {
+    async fn read<T: IoBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.io.read(buf).await;
+        self.counters.add_read(&res);
+        (res, buf)
+    }
+
+    async fn readv<T: IoVecBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.io.readv(buf).await;
+        self.counters.add_read(&res);
+        (res, buf)
+    }
+}
+
+impl<I: CancelableAsyncReadRent> CancelableAsyncReadRent for CountedStream<I> {
+    async fn cancelable_read<T: IoBufMut>(
+        &mut self,
+        buf: T,
+        c: CancelHandle,
+    ) -> BufResult<usize, T> {
+        let (res, buf) = self.io.cancelable_read(buf, c).await;
+        self.counters.add_read(&res);
+        (res, buf)
+    }
+
+    async fn cancelable_readv<T: IoVecBufMut>(
+        &mut self,
+        buf: T,
+        c: CancelHandle,
+    ) -> BufResult<usize, T> {
+        let (res, buf) = self.io.cancelable_readv(buf, c).await;
+        self.counters.add_read(&res);
+        (res, buf)
+    }
+}
+
+impl<I: AsyncWriteRent> AsyncWriteRent for CountedStream<I> {
+    async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.io.write(buf).await;
+        self.counters.add_written(&res);
+        (res, buf)
+    }
+
+    async fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> BufResult<usize, T> {
+        let (res, buf_vec) = self.io.writev(buf_vec).await;
+        self.counters.add_written(&res);
+        (res, buf_vec)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> impl Future<Output = std::io::Result<()>> {
+        self.io.flush()
+    }
+
+    #[inline]
+    fn shutdown(&mut self) -> impl Future<Output = std::io::Result<()>> {
+        self.io.shutdown()
+    }
+}
+
+impl<I: CancelableAsyncWriteRent> CancelableAsyncWriteRent for CountedStream<I> {
+    async fn cancelable_write<T: IoBuf>(&mut self, buf: T, c: CancelHandle) -> BufResult<usize, T> {
+        let (res, buf) = self.io.cancelable_write(buf, c).await;
+        self.counters.add_written(&res);
+        (res, buf)
+    }
+
+    async fn cancelable_writev<T: IoVecBuf>(
+        &mut self,
+        buf_vec: T,
+        c: CancelHandle,
+    ) -> BufResult<usize, T> {
+        let (res, buf_vec) = self.io.cancelable_writev(buf_vec, c).await;
+        self.counters.add_written(&res);
+        (res, buf_vec)
+    }
+
+    #[inline]
+    fn cancelable_flush(&mut self, c: CancelHandle) -> impl Future<Output = std::io::Result<()>> {
+        self.io.cancelable_flush(c)
+    }
+
+    #[inline]
+    fn cancelable_shutdown(
+        &mut self,
+        c: CancelHandle,
+    ) -> impl Future<Output = std::io::Result<()>> {
+        self.io.cancelable_shutdown(c)
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    future::{poll_fn, Future},
+    io,
+    task::Poll,
+};
+
+use super::BufWriter;
+use crate::{
+    buf::IoBuf,
+    io::{sink::Sink, stream::Stream, AsyncWriteRent, AsyncWriteRentExt},
+};
+
+/// Wrap a writer into a [`Sink`] of buffers.
+///
+/// Small buffers are copied into an internal buffer and written in batches,
+/// large ones are written directly. `send` waits for the internal buffer to
+/// be written when it is full, which gives backpressure. Call
+/// [`flush`](Sink::flush) to write out the buffered data.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{sink::Sink, sink_from_writer},
+///     net::TcpStream,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let mut sink = sink_from_writer(stream);
+///     sink.send("hello ").await?;
+///     sink.send("world").await?;
+///     Sink::<&str>::flush(&mut sink).await
+/// }
+/// ```
+pub fn sink_from_writer<W: AsyncWriteRent>(writer: W) -> WriterSink<W> {
+    WriterSink {
+        inner: BufWriter::new(writer),
+    }
+}
+
+/// A [`Sink`] of buffers writing to an [`AsyncWriteRent`], created by
+/// [`sink_from_writer`].
+pub struct WriterSink<W> {
+    inner: BufWriter<W>,
+}
+
+impl<W> WriterSink<W> {
+    /// Create a `WriterSink` batching up to `capacity` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize, writer: W) -> Self {
+        Self {
+            inner: BufWriter::with_capacity(capacity, writer),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.get_mut()
+    }
+
+    /// Consumes this `WriterSink`, returning the underlying writer.
+    ///
+    /// Note that any data not flushed is lost.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: IoBuf + 'static, W: AsyncWriteRent> Sink<T> for WriterSink<W> {
+    type Error = io::Error;
+
+    async fn send(&mut self, item: T) -> Result<(), Self::Error> {
+        self.inner.write_all(item).await.0.map(|_| ())
+    }
+
+    fn flush(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        self.inner.flush()
+    }
+
+    fn close(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
+        self.inner.shutdown()
+    }
+}
+
+/// Send all the items of `stream` into `sink`, and return how many were
+/// sent.
+///
+/// The sink is flushed whenever the stream has no item ready, so items are
+/// sent in batches while the stream is busy and without delay when it
+/// stalls. It is flushed once more at the end, but not closed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     io::{forward, sink_from_writer, stream::iter},
+///     net::TcpStream,
+/// };
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let lines = iter(["a\n", "b\n", "c\n"]);
+///     forward(lines, sink_from_writer(stream)).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn forward<S, K>(mut stream: S, mut sink: K) -> Result<u64, K::Error>
+where
+    S: Stream,
+    K: Sink<S::Item>,
+{
+    let mut sent = 0;
+    loop {
+        let mut next = std::pin::pin!(stream.next());
+        let item = match poll_fn(|cx| Poll::Ready(next.as_mut().poll(cx))).await {
+            Poll::Ready(item) => item,
+            Poll::Pending => {
+                sink.flush().await?;
+                next.await
+            }
+        };
+        match item {
+            Some(item) => {
+                sink.send(item).await?;
+                sent += 1;
+            }
+            None => break,
+        }
+    }
+    sink.flush().await?;
+    Ok(sent)
+}
@@ -3,16 +3,26 @@
 mod buf_reader;
 mod buf_writer;
 mod cancel;
+mod chunked_cipher;
 mod copy;
+mod counted;
+mod forward;
 mod prefixed_io;
+mod punt;
+mod qos;
 mod split;
 
 pub use buf_reader::BufReader;
 pub use buf_writer::BufWriter;
 pub(crate) use cancel::operation_canceled;
-pub use cancel::{CancelHandle, Canceller};
+pub use cancel::{is_canceled, CancelHandle, Canceller};
+pub use chunked_cipher::{ChunkCipher, ChunkedCipherStream};
 pub use copy::copy;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub use copy::zero_copy;
+pub use counted::{CountedStream, IoCounters};
+pub use forward::{forward, sink_from_writer, WriterSink};
 pub use prefixed_io::PrefixedReadIo;
+pub use punt::{async_punt, AsyncPunt};
+pub use qos::{with_qos, Qos, WithQos};
 pub use split::{OwnedReadHalf, OwnedWriteHalf, Split, Splitable};
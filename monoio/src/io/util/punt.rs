@@ -0,0 +1,66 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// Run `future` with the ops it submits punted to io-wq or not.
+///
+/// With io_uring driver, an op submitted with `IOSQE_ASYNC` is handed to the
+/// kernel io-wq workers at once, instead of being tried nonblocking first
+/// and punted when it would block. That saves the wasted attempt for ops
+/// which almost always block, like buffered reads of cold data, at the cost
+/// of a worker hop for the others.
+///
+/// `enabled` overrides the runtime default set with
+/// [`RuntimeBuilder::with_async_punt`](crate::RuntimeBuilder::with_async_punt)
+/// for the ops submitted while polling `future`, which does not include the
+/// ops of tasks it spawns. The legacy driver ignores it.
+///
+/// ```no_run
+/// use monoio::{fs::File, io::async_punt};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let file = File::open("archive.tar").await?;
+///     let buf = vec![0; 1 << 20];
+///     let (res, _buf) = async_punt(true, file.read_at(buf, 0)).await;
+///     res?;
+///     Ok(())
+/// }
+/// ```
+pub fn async_punt<F: Future>(enabled: bool, future: F) -> AsyncPunt<F> {
+    AsyncPunt { future, enabled }
+}
+
+pin_project! {
+    /// Future returned by [`async_punt`].
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AsyncPunt<F> {
+        #[pin]
+        future: F,
+        enabled: bool,
+    }
+}
+
+impl<F: Future> Future for AsyncPunt<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let previous = crate::driver::replace_async_punt(*this.enabled);
+        // Restore the setting even if the future panics, other tasks may go
+        // on running on this thread.
+        struct Restore(bool);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                crate::driver::replace_async_punt(self.0);
+            }
+        }
+        let _restore = Restore(previous);
+        this.future.poll(cx)
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// Quality of service of ops, selecting the ring they are pushed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Qos {
+    /// Ops on the request path, pushed to the main ring.
+    #[default]
+    Latency,
+    /// Background ops like compaction or scrubbing, pushed to the bulk
+    /// ring if the runtime has one, see
+    /// [`RuntimeBuilder::with_bulk_ring`](crate::RuntimeBuilder::with_bulk_ring).
+    Bulk,
+}
+
+/// Run `future` with the ops it submits tagged with `qos`.
+///
+/// With io_uring driver and a bulk ring, [`Qos::Bulk`] ops are pushed to the
+/// bulk ring, so their completions never delay the ones of the main ring.
+/// It applies to the ops submitted while polling `future`, which does not
+/// include the ops of tasks it spawns. Without a bulk ring, and with the
+/// legacy driver, the tag is ignored.
+///
+/// ```no_run
+/// use monoio::{
+///     fs::File,
+///     io::{with_qos, Qos},
+/// };
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let file = File::open("segment.log").await?;
+///     let buf = vec![0; 1 << 20];
+///     let (res, _buf) = with_qos(Qos::Bulk, file.read_at(buf, 0)).await;
+///     res?;
+///     Ok(())
+/// }
+/// ```
+pub fn with_qos<F: Future>(qos: Qos, future: F) -> WithQos<F> {
+    WithQos { future, qos }
+}
+
+pin_project! {
+    /// Future returned by [`with_qos`].
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct WithQos<F> {
+        #[pin]
+        future: F,
+        qos: Qos,
+    }
+}
+
+impl<F: Future> Future for WithQos<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let previous = crate::driver::replace_qos(*this.qos);
+        // Restore the tag even if the future panics.
+        struct Restore(Qos);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                crate::driver::replace_qos(self.0);
+            }
+        }
+        let _restore = Restore(previous);
+        this.future.poll(cx)
+    }
+}
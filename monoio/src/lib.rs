@@ -27,8 +27,13 @@ pub mod blocking;
 
 pub mod buf;
 pub mod fs;
+#[cfg(feature = "interceptor")]
+pub mod interceptor;
 pub mod io;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod net;
+pub mod sync;
 pub mod task;
 pub mod utils;
 
@@ -38,13 +43,14 @@ use std::future::Future;
 pub use blocking::spawn_blocking;
 pub use builder::{Buildable, RuntimeBuilder};
 pub use driver::Driver;
-#[cfg(all(target_os = "linux", feature = "iouring"))]
-pub use driver::IoUringDriver;
 #[cfg(feature = "legacy")]
 pub use driver::LegacyDriver;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use driver::{IoUringDriver, RingMessenger, SubmitPolicy, WaitPolicy};
 #[cfg(feature = "macros")]
 pub use monoio_macros::{main, test, test_all};
 pub use runtime::{spawn, Runtime};
+pub use utils::runtime_features;
 #[cfg(any(all(target_os = "linux", feature = "iouring"), feature = "legacy"))]
 pub use {builder::FusionDriver, runtime::FusionRuntime};
 
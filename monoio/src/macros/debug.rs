@@ -19,3 +19,15 @@ macro_rules! info {
 macro_rules! info {
     ($( $args:expr ),*) => {};
 }
+
+#[allow(unused_macros)]
+#[cfg(all(debug_assertions, feature = "debug"))]
+macro_rules! warn {
+    ($( $args:expr ),*) => { tracing::warn!( $( $args ),* ); }
+}
+
+#[allow(unused_macros)]
+#[cfg(not(all(debug_assertions, feature = "debug")))]
+macro_rules! warn {
+    ($( $args:expr ),*) => {};
+}
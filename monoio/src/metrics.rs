@@ -0,0 +1,406 @@
+//! Runtime metrics.
+//!
+//! Metrics are collected per runtime thread. Take a snapshot of the current
+//! runtime with [`RuntimeMetrics::current`], or serve them in Prometheus text
+//! format with [`serve_prometheus`].
+
+use std::{
+    cell::Cell,
+    fmt::Write,
+    io,
+    net::ToSocketAddrs,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    buf::IoBufMut,
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Counters owned by the runtime context.
+#[derive(Default)]
+pub(crate) struct Counters {
+    pub(crate) tasks_polled: Cell<u64>,
+    pub(crate) parks: Cell<u64>,
+    pub(crate) busy: Cell<Duration>,
+    pub(crate) parked: Cell<Duration>,
+    // start of current busy period, None if the runtime is not running
+    pub(crate) busy_since: Cell<Option<Instant>>,
+}
+
+impl Counters {
+    #[inline]
+    pub(crate) fn incr_tasks_polled(&self) {
+        self.tasks_polled.set(self.tasks_polled.get() + 1);
+    }
+
+    /// Called when the runtime starts to run.
+    #[inline]
+    pub(crate) fn start(&self) {
+        self.busy_since.set(Some(Instant::now()));
+    }
+
+    /// Called when the runtime stops running.
+    #[inline]
+    pub(crate) fn stop(&self) {
+        if let Some(since) = self.busy_since.take() {
+            self.busy.set(self.busy.get() + since.elapsed());
+        }
+    }
+
+    /// Called before park, returns the park start time.
+    #[inline]
+    pub(crate) fn park_begin(&self) -> Instant {
+        let now = Instant::now();
+        self.parks.set(self.parks.get() + 1);
+        if let Some(since) = self.busy_since.take() {
+            self.busy
+                .set(self.busy.get() + now.saturating_duration_since(since));
+        }
+        now
+    }
+
+    /// Called after park.
+    #[inline]
+    pub(crate) fn park_end(&self, begin: Instant) {
+        let now = Instant::now();
+        self.parked
+            .set(self.parked.get() + now.saturating_duration_since(begin));
+        self.busy_since.set(Some(now));
+    }
+
+    fn busy_now(&self) -> Duration {
+        match self.busy_since.get() {
+            Some(since) => self.busy.get() + since.elapsed(),
+            None => self.busy.get(),
+        }
+    }
+}
+
+/// A snapshot of the metrics of a runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RuntimeMetrics {
+    /// Runtime thread id(not the kernel thread id but a generated unique
+    /// number).
+    pub thread_id: usize,
+    /// Total number of task polls.
+    pub tasks_polled: u64,
+    /// Total number of times the runtime parked on the driver.
+    pub parks: u64,
+    /// Number of tasks in the local run queue.
+    pub task_queue_depth: usize,
+    /// Number of in-flight ops. Always 0 on legacy driver.
+    pub inflight_ops: usize,
+    /// Number of registered timers. Always 0 if the timer is not enabled.
+    pub timers: usize,
+    /// Total number of times the io_uring completion queue overflowed.
+    /// Always 0 on legacy driver.
+    pub cq_overflows: u64,
+    /// Total number of completions dropped by the kernel on overflow, their
+    /// ops never complete. Always 0 on legacy driver.
+    pub cq_dropped: u64,
+    /// Total time the runtime spent on running tasks and processing events.
+    pub busy_duration: Duration,
+    /// Total time the runtime spent parked on the driver.
+    pub park_duration: Duration,
+}
+
+impl RuntimeMetrics {
+    /// Take a snapshot of the metrics of current runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn current() -> Self {
+        let (cq_overflows, cq_dropped) = crate::driver::cq_overflows();
+        crate::runtime::CURRENT.with(|cx| Self {
+            thread_id: cx.thread_id,
+            tasks_polled: cx.metrics.tasks_polled.get(),
+            parks: cx.metrics.parks.get(),
+            task_queue_depth: cx.tasks.len(),
+            inflight_ops: crate::driver::inflight_ops(),
+            timers: cx
+                .time_handle
+                .as_ref()
+                .map(|handle| handle.timer_count())
+                .unwrap_or(0),
+            cq_overflows,
+            cq_dropped,
+            busy_duration: cx.metrics.busy_now(),
+            park_duration: cx.metrics.parked.get(),
+        })
+    }
+
+    /// Ratio of busy time to total running time since the runtime started,
+    /// between 0 and 1.
+    pub fn utilization(&self) -> f64 {
+        ratio(self.busy_duration, self.park_duration)
+    }
+
+    /// Ratio of busy time to total running time between an earlier snapshot
+    /// and this one, between 0 and 1.
+    ///
+    /// Compare snapshots periodically to get a gauge of current core
+    /// saturation, which is useful to drive autoscaling or load shedding.
+    pub fn utilization_since(&self, earlier: &RuntimeMetrics) -> f64 {
+        ratio(
+            self.busy_duration.saturating_sub(earlier.busy_duration),
+            self.park_duration.saturating_sub(earlier.park_duration),
+        )
+    }
+
+    /// Encode the metrics in Prometheus text format.
+    pub fn encode_prometheus(&self, out: &mut String) {
+        macro_rules! metric {
+            ($name: literal, $typ: literal, $help: literal, $value: expr) => {
+                let _ = write!(
+                    out,
+                    concat!(
+                        "# HELP monoio_",
+                        $name,
+                        " ",
+                        $help,
+                        "\n# TYPE monoio_",
+                        $name,
+                        " ",
+                        $typ,
+                        "\nmonoio_",
+                        $name,
+                        "{{thread=\"{}\"}} {}\n"
+                    ),
+                    self.thread_id, $value
+                );
+            };
+        }
+        metric!(
+            "tasks_polled_total",
+            "counter",
+            "Total number of task polls.",
+            self.tasks_polled
+        );
+        metric!(
+            "parks_total",
+            "counter",
+            "Total number of times the runtime parked on the driver.",
+            self.parks
+        );
+        metric!(
+            "task_queue_depth",
+            "gauge",
+            "Number of tasks in the local run queue.",
+            self.task_queue_depth
+        );
+        metric!(
+            "inflight_ops",
+            "gauge",
+            "Number of in-flight ops.",
+            self.inflight_ops
+        );
+        metric!(
+            "timers",
+            "gauge",
+            "Number of registered timers.",
+            self.timers
+        );
+        metric!(
+            "cq_overflows_total",
+            "counter",
+            "Total number of io_uring completion queue overflows.",
+            self.cq_overflows
+        );
+        metric!(
+            "cq_dropped_total",
+            "counter",
+            "Total number of io_uring completions dropped on overflow.",
+            self.cq_dropped
+        );
+        metric!(
+            "busy_seconds_total",
+            "counter",
+            "Total time spent on running tasks and processing events.",
+            self.busy_duration.as_secs_f64()
+        );
+        metric!(
+            "park_seconds_total",
+            "counter",
+            "Total time spent parked on the driver.",
+            self.park_duration.as_secs_f64()
+        );
+        metric!(
+            "utilization",
+            "gauge",
+            "Ratio of busy time to total running time since the runtime started.",
+            self.utilization()
+        );
+    }
+}
+
+thread_local! {
+    // Number and size in bytes of the tasks allocated on this thread.
+    static TASKS: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+#[inline]
+pub(crate) fn task_alloc(size: usize) {
+    TASKS.with(|tasks| {
+        let (count, bytes) = tasks.get();
+        tasks.set((count + 1, bytes + size));
+    });
+}
+
+#[inline]
+pub(crate) fn task_dealloc(size: usize) {
+    // Tasks freed on another thread are not subtracted there.
+    let _ = TASKS.try_with(|tasks| {
+        let (count, bytes) = tasks.get();
+        tasks.set((count.saturating_sub(1), bytes.saturating_sub(size)));
+    });
+}
+
+/// Approximate memory held by a runtime, in bytes.
+///
+/// Only the memory the runtime allocates itself is accounted: task
+/// allocations hold the spawned futures, but memory they allocate on their
+/// own is not included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MemoryReport {
+    /// Number of tasks alive, spawned on this thread.
+    pub tasks: usize,
+    /// Memory of the tasks alive, including their futures and outputs.
+    pub task_bytes: usize,
+    /// Memory of the local run queue.
+    pub task_queue_bytes: usize,
+    /// Memory of the provided buffer rings registered to the driver. Always 0
+    /// on legacy driver.
+    pub buffer_ring_bytes: usize,
+    /// Number of registered timers.
+    pub timers: usize,
+    /// Memory of the timer wheel and the registered timers. Timers are held by
+    /// their futures, so the ones of spawned tasks are in `task_bytes` too.
+    pub timer_bytes: usize,
+    /// Number of in-flight ops. Always 0 on legacy driver.
+    pub inflight_ops: usize,
+    /// Memory of the in-flight op slots, and of the resources of ops whose
+    /// future was dropped, held until the kernel completes them. Always 0 on
+    /// legacy driver.
+    pub op_bytes: usize,
+}
+
+impl MemoryReport {
+    /// Take a memory report of current runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn current() -> Self {
+        let (tasks, task_bytes) = TASKS.with(Cell::get);
+        let (inflight_ops, op_bytes) = crate::driver::op_memory();
+        crate::runtime::CURRENT.with(|cx| {
+            let (timers, timer_bytes) = cx
+                .time_handle
+                .as_ref()
+                .map(|handle| (handle.timer_count(), handle.timer_bytes()))
+                .unwrap_or((0, 0));
+            Self {
+                tasks,
+                task_bytes,
+                task_queue_bytes: cx.tasks.allocated_bytes(),
+                buffer_ring_bytes: crate::driver::buffer_ring_bytes(),
+                timers,
+                timer_bytes,
+                inflight_ops,
+                op_bytes,
+            }
+        })
+    }
+
+    /// Total memory accounted in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.task_bytes
+            + self.task_queue_bytes
+            + self.buffer_ring_bytes
+            + self.timer_bytes
+            + self.op_bytes
+    }
+}
+
+fn ratio(busy: Duration, parked: Duration) -> f64 {
+    let total = busy + parked;
+    if total.is_zero() {
+        return 0.0;
+    }
+    busy.as_secs_f64() / total.as_secs_f64()
+}
+
+/// Serve metrics of current runtime in Prometheus text format over HTTP.
+///
+/// It runs a minimal HTTP endpoint on the local runtime: any request is
+/// answered with the metrics, and the connection is closed afterwards.
+/// Connections not sending a request within 10 seconds are closed too, which
+/// requires the timer with the legacy driver. The future only returns on bind
+/// or accept error.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() {
+///     monoio::spawn(monoio::metrics::serve_prometheus("127.0.0.1:9090"));
+///     // run the service here
+/// }
+/// ```
+pub async fn serve_prometheus<A: ToSocketAddrs>(addr: A) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    loop {
+        let (stream, _) = listener.accept().await?;
+        crate::spawn(serve_conn(stream));
+    }
+}
+
+async fn serve_conn(mut stream: TcpStream) {
+    const MAX_REQUEST_SIZE: usize = 8 * 1024;
+    const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+    // Reads time out with a linked timeout on io_uring, and a timer with the
+    // legacy driver.
+    let timer = crate::runtime::CURRENT.with(|ctx| ctx.time_handle.is_some());
+    if (timer || !crate::driver::op::is_legacy())
+        && stream.set_read_timeout(Some(READ_TIMEOUT)).is_err()
+    {
+        return;
+    }
+
+    // Read until the end of request header, the content is ignored.
+    let mut buf = Vec::with_capacity(1024);
+    loop {
+        if buf.len() == buf.capacity() {
+            if buf.len() >= MAX_REQUEST_SIZE {
+                return;
+            }
+            buf.reserve(buf.len());
+        }
+        let filled = buf.len();
+        let (res, slice) = stream.read(buf.slice_mut(filled..)).await;
+        buf = slice.into_inner();
+        match res {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        if memchr::memmem::find(&buf, b"\r\n\r\n").is_some() {
+            break;
+        }
+    }
+
+    let mut body = String::new();
+    RuntimeMetrics::current().encode_prometheus(&mut body);
+    let mut resp = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: \
+         {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    resp.push_str(&body);
+    let _ = stream.write_all(resp.into_bytes()).await;
+}
@@ -0,0 +1,68 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::io::stream::Stream;
+
+type Accept<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + 'a>>;
+
+/// A stream of the connections accepted by a listener, returned by
+/// [`TcpListener::incoming`](crate::net::TcpListener::incoming) and
+/// [`UnixListener::incoming`](crate::net::UnixListener::incoming).
+///
+/// It implements both the monoio [`Stream`] and `futures_core::Stream`, so
+/// the accept loop composes with stream combinators and `select!`. The stream
+/// never ends, accept errors are yielded as items.
+pub struct Incoming<'a, T> {
+    accept: Box<dyn FnMut() -> Accept<'a, T> + 'a>,
+    pending: Option<Accept<'a, T>>,
+}
+
+impl<'a, T> Incoming<'a, T> {
+    pub(crate) fn new<F: Future<Output = io::Result<T>> + 'a>(
+        mut accept: impl FnMut() -> F + 'a,
+    ) -> Self {
+        Self {
+            accept: Box::new(move || Box::pin(accept())),
+            pending: None,
+        }
+    }
+
+    /// Poll for the next connection.
+    pub fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<T>> {
+        let pending = self.pending.get_or_insert_with(&mut self.accept);
+        let res = std::task::ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<T> Stream for Incoming<'_, T> {
+    type Item = io::Result<T>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        Some(poll_fn(|cx| self.poll_accept(cx)).await)
+    }
+}
+
+impl<T> futures_core::Stream for Incoming<'_, T> {
+    type Item = io::Result<T>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_accept(cx).map(Some)
+    }
+}
+
+impl<T> fmt::Debug for Incoming<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Incoming")
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Custom listener options
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
@@ -14,6 +16,16 @@ pub struct ListenerOpts {
     pub recv_buf_size: Option<usize>,
     /// TCP fast open.
     pub tcp_fast_open: bool,
+    /// Length of the queue of TCP fast open connections not accepted yet, or
+    /// None to use the backlog.
+    pub tcp_fast_open_queue: Option<i32>,
+    /// `SO_INCOMING_CPU` or None to not set it.
+    pub incoming_cpu: Option<usize>,
+    /// Number of listeners in the `SO_REUSEPORT` group to steer connections
+    /// between by CPU, or None to use the kernel's hashing.
+    pub cpu_steering: Option<u32>,
+    /// `TCP_DEFER_ACCEPT` or None to not set it.
+    pub defer_accept: Option<Duration>,
 }
 
 impl Default for ListenerOpts {
@@ -34,6 +46,10 @@ impl ListenerOpts {
             send_buf_size: None,
             recv_buf_size: None,
             tcp_fast_open: false,
+            tcp_fast_open_queue: None,
+            incoming_cpu: None,
+            cpu_steering: None,
+            defer_accept: None,
         }
     }
 
@@ -88,4 +104,67 @@ impl ListenerOpts {
         self.tcp_fast_open = fast_open;
         self
     }
+
+    /// Enable FastOpen with a queue of `qlen` connections whose handshake
+    /// completed with data but which are not accepted yet. Past it, clients
+    /// fall back to a regular handshake.
+    /// Note: the queue length is only used on linux, see
+    /// [`tcp_fast_open`](Self::tcp_fast_open).
+    #[must_use]
+    #[inline]
+    pub fn tcp_fastopen(mut self, qlen: i32) -> Self {
+        self.tcp_fast_open = true;
+        self.tcp_fast_open_queue = Some(qlen);
+        self
+    }
+
+    /// Specify SO_INCOMING_CPU, the listener is preferred in its
+    /// `SO_REUSEPORT` group for connections processed on `cpu`.
+    /// Note: it only works on linux.
+    #[must_use]
+    #[inline]
+    pub fn incoming_cpu(mut self, cpu: usize) -> Self {
+        self.incoming_cpu = Some(cpu);
+        self
+    }
+
+    /// Steer connections to the listener at index `cpu % listeners` of the
+    /// `SO_REUSEPORT` group, with a BPF program attached to it.
+    /// Listeners are indexed in the order they are bound, so the listener
+    /// of the thread pinned to CPU `i` must be the `i`th to bind.
+    /// Note: it only works on linux.
+    #[must_use]
+    #[inline]
+    pub fn cpu_steering(mut self, listeners: u32) -> Self {
+        self.cpu_steering = Some(listeners);
+        self
+    }
+
+    /// Specify TCP_DEFER_ACCEPT, connections are only accepted once the peer
+    /// has sent data or `timeout` has elapsed. It saves a wakeup per
+    /// connection for protocols where the client speaks first, like HTTP.
+    /// The kernel rounds `timeout` up to a number of SYN-ACK retransmits.
+    /// Note: it only works on linux.
+    #[must_use]
+    #[inline]
+    pub fn defer_accept(mut self, timeout: Duration) -> Self {
+        self.defer_accept = Some(timeout);
+        self
+    }
+
+    /// Align the listener with the CPU current thread is pinned to: enable
+    /// SO_REUSEPORT, set SO_INCOMING_CPU to the pinned CPU and steer
+    /// connections between `listeners` listeners by CPU.
+    /// Note: it only works on linux, SO_INCOMING_CPU is not set if current
+    /// thread is not pinned to a single CPU.
+    #[must_use]
+    pub fn steer_to_current_cpu(mut self, listeners: u32) -> Self {
+        self.reuse_port = true;
+        #[cfg(target_os = "linux")]
+        {
+            self.incoming_cpu = crate::utils::pinned_cpu();
+        }
+        self.cpu_steering = Some(listeners);
+        self
+    }
 }
@@ -1,18 +1,42 @@
 //! Network related
 //! Currently, TCP/UnixStream/UnixDatagram are implemented.
 
+mod incoming;
 mod listener_config;
+#[cfg(target_os = "linux")]
+mod recv_bundle;
+#[cfg(target_os = "linux")]
+mod recv_msg;
+mod serve;
+#[cfg(unix)]
+pub(crate) mod sockopt;
+#[cfg(target_os = "linux")]
+pub(crate) mod steering;
 pub mod tcp;
+mod timeout;
 pub mod udp;
 #[cfg(unix)]
 pub mod unix;
+#[cfg(target_os = "linux")]
+pub mod vsock;
 
+pub use incoming::Incoming;
 pub use listener_config::ListenerOpts;
 #[deprecated(since = "0.2.0", note = "use ListenerOpts")]
 pub use listener_config::ListenerOpts as ListenerConfig;
-pub use tcp::{TcpConnectOpts, TcpListener, TcpStream};
+#[cfg(target_os = "linux")]
+pub use recv_bundle::{RecvBundle, RecvBundleStream};
+#[cfg(target_os = "linux")]
+pub use recv_msg::{ControlMessage, ControlMessages, RecvMsg, RecvMsgAddr, RecvMsgStream};
+pub use serve::{ServeEvent, ServeLoop};
+#[cfg(unix)]
+pub use sockopt::{GetOptionValue, Level, Name, SetOptionValue};
+pub use tcp::{KeepAlive, TcpConnectOpts, TcpListener, TcpSocket, TcpStream};
+pub(crate) use timeout::Timeouts;
 #[cfg(unix)]
 pub use unix::{Pipe, UnixDatagram, UnixListener, UnixStream};
+#[cfg(target_os = "linux")]
+pub use vsock::{VsockAddr, VsockListener, VsockStream};
 #[cfg(windows)]
 use {
     std::os::windows::prelude::RawSocket,
@@ -88,6 +112,76 @@ pub(crate) fn new_socket(
     socket
 }
 
+/// Borrow `socket` as a `socket2::Socket` to get or set its options.
+#[cfg(unix)]
+pub(crate) fn with_socket<S: std::os::unix::prelude::AsRawFd, R>(
+    socket: &S,
+    f: impl FnOnce(&socket2::Socket) -> R,
+) -> R {
+    use std::os::unix::prelude::FromRawFd;
+    // The fd is owned by `socket`, it must not be closed.
+    let socket =
+        std::mem::ManuallyDrop::new(unsafe { socket2::Socket::from_raw_fd(socket.as_raw_fd()) });
+    f(&socket)
+}
+
+/// Borrow `socket` as a `socket2::Socket` to get or set its options.
+#[cfg(windows)]
+pub(crate) fn with_socket<S: std::os::windows::prelude::AsRawSocket, R>(
+    socket: &S,
+    f: impl FnOnce(&socket2::Socket) -> R,
+) -> R {
+    use std::os::windows::prelude::FromRawSocket;
+    // The socket is owned by `socket`, it must not be closed.
+    let socket = std::mem::ManuallyDrop::new(unsafe {
+        socket2::Socket::from_raw_socket(socket.as_raw_socket())
+    });
+    f(&socket)
+}
+
+/// Bind `socket` to the network interface named `interface`, or unbind it
+/// with `None`: `SO_BINDTODEVICE` on linux, `IP_BOUND_IF` on apple systems.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "ios",
+    target_os = "macos"
+))]
+pub(crate) fn bind_device(
+    socket: &socket2::Socket,
+    interface: Option<&str>,
+) -> std::io::Result<()> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    return socket.bind_device(interface.map(str::as_bytes));
+
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    {
+        let index = match interface {
+            Some(interface) => {
+                let name = std::ffi::CString::new(interface)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+                // if_nametoindex returns 0 if the interface does not exist.
+                let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+                Some(std::num::NonZeroU32::new(index).ok_or_else(std::io::Error::last_os_error)?)
+            }
+            None => None,
+        };
+        if socket.local_addr()?.is_ipv6() {
+            socket.bind_device_by_index_v6(index)
+        } else {
+            socket.bind_device_by_index_v4(index)
+        }
+    }
+}
+
+/// Returns the name of the network interface `socket` is bound to.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn device(socket: &socket2::Socket) -> std::io::Result<Option<String>> {
+    Ok(socket
+        .device()?
+        .map(|name| String::from_utf8_lossy(&name).into_owned()))
+}
+
 #[allow(non_snake_case, missing_docs)]
 #[cfg(windows)]
 #[inline]
@@ -0,0 +1,320 @@
+//! Stream of data received with bundled recv.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::{op::RecvBundle as RecvBundleOp, BufRing};
+use crate::{
+    driver::{
+        op::{Op, Recv},
+        shared_fd::SharedFd,
+    },
+    io::stream::Stream,
+};
+
+type Pool = Rc<RefCell<Vec<Vec<u8>>>>;
+
+enum Bufs {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    Ring {
+        ring: Rc<BufRing>,
+        // Id, offset and length of the filled part of each buffer.
+        parts: Vec<(u16, usize, usize)>,
+        len: usize,
+    },
+    Owned {
+        buf: Vec<u8>,
+        pool: Pool,
+        max: usize,
+    },
+}
+
+/// Data received by [`RecvBundleStream`] with one completion, spread over
+/// one or more buffers of the stream.
+///
+/// The buffers are given back when it is dropped.
+pub struct RecvBundle {
+    bufs: Bufs,
+}
+
+impl RecvBundle {
+    /// Returns the number of bytes received.
+    pub fn len(&self) -> usize {
+        match &self.bufs {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Bufs::Ring { len, .. } => *len,
+            Bufs::Owned { buf, .. } => buf.len(),
+        }
+    }
+
+    /// Returns true if no bytes were received.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the received bytes, one slice per buffer.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let chunks: Box<dyn Iterator<Item = &[u8]>> = match &self.bufs {
+            // Safety: the parts are filled by the kernel and owned by self.
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Bufs::Ring { ring, parts, .. } => Box::new(
+                parts
+                    .iter()
+                    .map(|&(bid, offset, len)| unsafe { ring.buf_part(bid, offset, len) }),
+            ),
+            Bufs::Owned { buf, .. } => Box::new(std::iter::once(&buf[..])),
+        };
+        chunks
+    }
+
+    /// Copy the received bytes to `dst`.
+    pub fn copy_to(&self, dst: &mut Vec<u8>) {
+        dst.reserve(self.len());
+        for chunk in self.chunks() {
+            dst.extend_from_slice(chunk);
+        }
+    }
+}
+
+impl Drop for RecvBundle {
+    fn drop(&mut self) {
+        match &mut self.bufs {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Bufs::Ring { ring, parts, .. } => {
+                for &(bid, ..) in parts.iter() {
+                    if ring.is_incremental() {
+                        ring.release(bid);
+                    } else {
+                        ring.recycle(bid);
+                    }
+                }
+            }
+            Bufs::Owned { buf, pool, max } => {
+                let mut pool = pool.borrow_mut();
+                if pool.len() < *max {
+                    pool.push(std::mem::take(buf));
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RecvBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvBundle")
+            .field("len", &self.len())
+            .field("chunks", &self.chunks().count())
+            .finish()
+    }
+}
+
+enum State {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    Ring {
+        ring: Rc<BufRing>,
+        op: Option<Op<RecvBundleOp>>,
+        received: bool,
+    },
+    Oneshot {
+        pool: Pool,
+        op: Option<Op<Recv<Vec<u8>>>>,
+    },
+}
+
+/// Stream of data returned by `recv_bundle` of
+/// [`TcpStream`](crate::net::TcpStream) and
+/// [`UnixStream`](crate::net::UnixStream).
+///
+/// It ends when the peer shuts down writing.
+pub struct RecvBundleStream {
+    fd: SharedFd,
+    count: u16,
+    size: usize,
+    state: State,
+}
+
+impl RecvBundleStream {
+    pub(crate) fn new(fd: &SharedFd, count: u16, size: usize) -> io::Result<Self> {
+        if count == 0 || size == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buffers must not be empty",
+            ));
+        }
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let state = match BufRing::new(count, size) {
+            Ok(ring) => State::Ring {
+                ring,
+                op: None,
+                received: false,
+            },
+            Err(_) => Self::oneshot(),
+        };
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let state = Self::oneshot();
+        Ok(Self {
+            fd: fd.clone(),
+            count,
+            size,
+            state,
+        })
+    }
+
+    /// Consume the buffers incrementally: each receive only uses the part of
+    /// a buffer it filled, and the next one continues in the same buffer. A
+    /// buffer is given back once it is full and all the data received in it
+    /// is dropped.
+    ///
+    /// It lets few large buffers hold many small receives. It requires the
+    /// io_uring driver on kernel 6.12+, the stream is left unchanged
+    /// otherwise, see [`is_incremental`](Self::is_incremental). It must be
+    /// called before the first receive.
+    #[must_use]
+    #[cfg_attr(not(all(target_os = "linux", feature = "iouring")), allow(unused_mut))]
+    pub fn incremental(mut self) -> Self {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let State::Ring {
+            ring,
+            op: None,
+            received: false,
+        } = &mut self.state
+        {
+            if let Ok(incremental) = BufRing::new_incremental(self.count, self.size) {
+                *ring = incremental;
+            }
+        }
+        self
+    }
+
+    /// If the buffers are consumed incrementally.
+    pub fn is_incremental(&self) -> bool {
+        match &self.state {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            State::Ring { ring, .. } => ring.is_incremental(),
+            State::Oneshot { .. } => false,
+        }
+    }
+
+    fn oneshot() -> State {
+        State::Oneshot {
+            pool: Default::default(),
+            op: None,
+        }
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<io::Result<RecvBundle>>> {
+        let bufs = match &mut self.state {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            State::Ring { ring, op, received } => loop {
+                let slot = match op {
+                    Some(op) => op,
+                    None => {
+                        ready!(ring.poll_available(cx));
+                        op.insert(Op::recv_bundle(&self.fd, ring.bgid())?)
+                    }
+                };
+                let meta = ready!(Pin::new(slot).poll(cx)).meta;
+                *op = None;
+                match meta.result {
+                    Ok(0) => {
+                        if let Some(bid) = io_uring::cqueue::buffer_select(meta.flags) {
+                            ring.take(bid, 0, |_| {});
+                        }
+                        return Poll::Ready(None);
+                    }
+                    Ok(len) => {
+                        *received = true;
+                        let bid = io_uring::cqueue::buffer_select(meta.flags)
+                            .expect("recv with buffer select completes with a buffer");
+                        let len = len as usize;
+                        let size = ring.buf_size();
+                        let mut parts = Vec::with_capacity(len.div_ceil(size));
+                        if ring.is_incremental() {
+                            ring.take_incremental(bid, len, |bid, offset, part| {
+                                parts.push((bid, offset, part))
+                            });
+                        } else {
+                            let mut remaining = len;
+                            ring.take(bid, parts.capacity() as u16, |bid| {
+                                let part = remaining.min(size);
+                                parts.push((bid, 0, part));
+                                remaining -= part;
+                            });
+                        }
+                        break Bufs::Ring {
+                            ring: ring.clone(),
+                            parts,
+                            len,
+                        };
+                    }
+                    // The ring ran out of buffers, wait for some.
+                    Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => continue,
+                    // Bundled recv requires kernel 6.10+.
+                    Err(e) if e.raw_os_error() == Some(libc::EINVAL) && !*received => {
+                        self.state = Self::oneshot();
+                        return self.poll_recv(cx);
+                    }
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                }
+            },
+            State::Oneshot { pool, op } => {
+                let slot = match op {
+                    Some(op) => op,
+                    None => {
+                        let buf = pool.borrow_mut().pop();
+                        let buf = buf.unwrap_or_else(|| Vec::with_capacity(self.size));
+                        op.insert(Op::recv(self.fd.clone(), buf)?)
+                    }
+                };
+                let completion = ready!(Pin::new(slot).poll(cx));
+                *op = None;
+                let mut buf = completion.data.buf;
+                match completion.meta.result {
+                    Ok(0) => {
+                        pool.borrow_mut().push(buf);
+                        return Poll::Ready(None);
+                    }
+                    Ok(len) => {
+                        unsafe { buf.set_len(len as usize) };
+                        Bufs::Owned {
+                            buf,
+                            pool: pool.clone(),
+                            max: self.count as usize,
+                        }
+                    }
+                    Err(e) => {
+                        pool.borrow_mut().push(buf);
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+            }
+        };
+        Poll::Ready(Some(Ok(RecvBundle { bufs })))
+    }
+}
+
+impl Stream for RecvBundleStream {
+    type Item = io::Result<RecvBundle>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+}
+
+impl fmt::Debug for RecvBundleStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvBundleStream")
+            .field("fd", &self.fd)
+            .field("count", &self.count)
+            .field("size", &self.size)
+            .finish()
+    }
+}
@@ -0,0 +1,425 @@
+//! Stream of datagrams received with multishot recvmsg.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    marker::PhantomData,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::{op::RecvMsgMulti, BufRing};
+use crate::{
+    driver::{
+        op::{Op, RecvMsgInto, RecvMsgOut, RECV_MSG_OUT_LEN},
+        shared_fd::SharedFd,
+    },
+    io::stream::Stream,
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Address of the sender of a datagram received by [`RecvMsgStream`].
+///
+/// It is implemented for [`std::net::SocketAddr`] and
+/// [`unix::SocketAddr`](crate::net::unix::SocketAddr).
+pub trait RecvMsgAddr: sealed::Sealed + Sized {
+    #[doc(hidden)]
+    const NAME_LEN: u32;
+
+    #[doc(hidden)]
+    fn from_name(name: &[u8]) -> Option<Self>;
+}
+
+impl sealed::Sealed for std::net::SocketAddr {}
+
+impl RecvMsgAddr for std::net::SocketAddr {
+    const NAME_LEN: u32 = align(std::mem::size_of::<libc::sockaddr_in6>()) as u32;
+
+    fn from_name(name: &[u8]) -> Option<Self> {
+        let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+        if name.len() > std::mem::size_of_val(&storage) {
+            return None;
+        }
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                name.as_ptr(),
+                (&mut storage as *mut libc::sockaddr_storage).cast(),
+                name.len(),
+            );
+            socket2::SockAddr::new(storage, name.len() as _).as_socket()
+        }
+    }
+}
+
+impl sealed::Sealed for super::unix::SocketAddr {}
+
+impl RecvMsgAddr for super::unix::SocketAddr {
+    const NAME_LEN: u32 = align(std::mem::size_of::<libc::sockaddr_un>()) as u32;
+
+    fn from_name(name: &[u8]) -> Option<Self> {
+        let mut sockaddr: libc::sockaddr_un = unsafe { std::mem::zeroed() };
+        if name.len() > std::mem::size_of_val(&sockaddr) {
+            return None;
+        }
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                name.as_ptr(),
+                (&mut sockaddr as *mut libc::sockaddr_un).cast(),
+                name.len(),
+            )
+        };
+        if !name.is_empty() && sockaddr.sun_family != libc::AF_UNIX as libc::sa_family_t {
+            return None;
+        }
+        Some(Self::from_parts(sockaddr, name.len() as _))
+    }
+}
+
+// Keep the control messages following the name aligned.
+const fn align(len: usize) -> usize {
+    (len + 7) & !7
+}
+
+type Pool = Rc<RefCell<Vec<Vec<u8>>>>;
+
+enum MsgBuf {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    Ring {
+        ring: Rc<BufRing>,
+        bid: u16,
+        len: usize,
+    },
+    Owned {
+        buf: Vec<u8>,
+        len: usize,
+        pool: Pool,
+        max: usize,
+    },
+}
+
+/// A datagram received by [`RecvMsgStream`], with its control messages and
+/// the address of its sender.
+///
+/// It holds one of the buffers of the stream, which is given back when it is
+/// dropped.
+pub struct RecvMsg<A> {
+    buf: MsgBuf,
+    name_len: usize,
+    control_len: usize,
+    _addr: PhantomData<A>,
+}
+
+impl<A: RecvMsgAddr> RecvMsg<A> {
+    fn bytes(&self) -> &[u8] {
+        match &self.buf {
+            // Safety: the buffer is picked by the kernel and owned by self.
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            MsgBuf::Ring { ring, bid, len } => unsafe { ring.buf(*bid, *len) },
+            MsgBuf::Owned { buf, len, .. } => &buf[..*len],
+        }
+    }
+
+    fn header(&self) -> RecvMsgOut {
+        unsafe { self.bytes().as_ptr().cast::<RecvMsgOut>().read_unaligned() }
+    }
+
+    /// Returns the payload of the datagram.
+    pub fn data(&self) -> &[u8] {
+        let offset = RECV_MSG_OUT_LEN + self.name_len + self.control_len;
+        let data = &self.bytes()[offset..];
+        &data[..data.len().min(self.header().payloadlen as usize)]
+    }
+
+    /// Returns true if the datagram was longer than the buffer, and
+    /// [`data`](Self::data) holds only its beginning.
+    pub fn is_truncated(&self) -> bool {
+        let header = self.header();
+        header.flags & libc::MSG_TRUNC as u32 != 0 || header.payloadlen as usize > self.data().len()
+    }
+
+    /// Returns the address of the sender, or `None` if the socket did not
+    /// report it.
+    pub fn addr(&self) -> Option<A> {
+        let len = self.header().namelen as usize;
+        if len > self.name_len {
+            return None;
+        }
+        A::from_name(&self.bytes()[RECV_MSG_OUT_LEN..][..len])
+    }
+
+    /// Returns the raw control messages.
+    pub fn control(&self) -> &[u8] {
+        let len = (self.header().controllen as usize).min(self.control_len);
+        &self.bytes()[RECV_MSG_OUT_LEN + self.name_len..][..len]
+    }
+
+    /// Returns true if some control messages did not fit in the space
+    /// reserved for them.
+    pub fn is_control_truncated(&self) -> bool {
+        self.header().flags & libc::MSG_CTRUNC as u32 != 0
+    }
+
+    /// Returns an iterator over the control messages.
+    pub fn control_messages(&self) -> ControlMessages<'_> {
+        ControlMessages {
+            data: self.control(),
+        }
+    }
+}
+
+impl<A> Drop for RecvMsg<A> {
+    fn drop(&mut self) {
+        match &mut self.buf {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            MsgBuf::Ring { ring, bid, .. } => ring.recycle(*bid),
+            MsgBuf::Owned { buf, pool, max, .. } => {
+                let mut pool = pool.borrow_mut();
+                if pool.len() < *max {
+                    pool.push(std::mem::take(buf));
+                }
+            }
+        }
+    }
+}
+
+impl<A: RecvMsgAddr + fmt::Debug> fmt::Debug for RecvMsg<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvMsg")
+            .field("len", &self.data().len())
+            .field("addr", &self.addr())
+            .field("control_len", &self.control().len())
+            .finish()
+    }
+}
+
+/// A control message, `struct cmsghdr` and its data.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlMessage<'a> {
+    /// Originating protocol, like `libc::SOL_SOCKET`.
+    pub level: i32,
+    /// Protocol-specific type, like `libc::SCM_RIGHTS`.
+    pub ty: i32,
+    /// Data of the message.
+    pub data: &'a [u8],
+}
+
+/// Iterator over control messages returned by
+/// [`RecvMsg::control_messages`].
+#[derive(Debug, Clone)]
+pub struct ControlMessages<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for ControlMessages<'a> {
+    type Item = ControlMessage<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < std::mem::size_of::<libc::cmsghdr>() {
+            return None;
+        }
+        let header = unsafe { self.data.as_ptr().cast::<libc::cmsghdr>().read_unaligned() };
+        let header_len = unsafe { libc::CMSG_LEN(0) } as usize;
+        // `cmsg_len` is not `usize` on every libc.
+        #[allow(clippy::unnecessary_cast)]
+        let len = header.cmsg_len as usize;
+        if len < header_len || len > self.data.len() {
+            self.data = &[];
+            return None;
+        }
+        let message = ControlMessage {
+            level: header.cmsg_level,
+            ty: header.cmsg_type,
+            data: &self.data[header_len..len],
+        };
+        let space = unsafe { libc::CMSG_SPACE((len - header_len) as _) } as usize;
+        self.data = &self.data[space.min(self.data.len())..];
+        Some(message)
+    }
+}
+
+enum State {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    Ring {
+        ring: Rc<BufRing>,
+        op: Option<Op<RecvMsgMulti>>,
+        received: bool,
+    },
+    Oneshot {
+        pool: Pool,
+        op: Option<Op<RecvMsgInto>>,
+    },
+}
+
+/// Stream of datagrams returned by `recv_msg_multi` of
+/// [`UdpSocket`](crate::net::udp::UdpSocket) and
+/// [`UnixDatagram`](crate::net::UnixDatagram).
+///
+/// It never ends, an error does not stop it.
+pub struct RecvMsgStream<A> {
+    fd: SharedFd,
+    count: u16,
+    size: usize,
+    control_len: usize,
+    state: State,
+    _addr: PhantomData<A>,
+}
+
+impl<A: RecvMsgAddr> RecvMsgStream<A> {
+    pub(crate) fn new(
+        fd: &SharedFd,
+        count: u16,
+        size: usize,
+        control_len: usize,
+    ) -> io::Result<Self> {
+        let control_len = align(control_len);
+        if count == 0 || size <= RECV_MSG_OUT_LEN + A::NAME_LEN as usize + control_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buffers are too small for the messages",
+            ));
+        }
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let state = match BufRing::new(count, size) {
+            Ok(ring) => State::Ring {
+                ring,
+                op: None,
+                received: false,
+            },
+            Err(_) => Self::oneshot(),
+        };
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let state = Self::oneshot();
+        Ok(Self {
+            fd: fd.clone(),
+            count,
+            size,
+            control_len,
+            state,
+            _addr: PhantomData,
+        })
+    }
+
+    fn oneshot() -> State {
+        State::Oneshot {
+            pool: Default::default(),
+            op: None,
+        }
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<RecvMsg<A>>> {
+        let buf = match &mut self.state {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            State::Ring { ring, op, received } => loop {
+                let slot = match op {
+                    Some(op) => op,
+                    None => {
+                        ready!(ring.poll_available(cx));
+                        op.insert(Op::recv_msg_multi(
+                            &self.fd,
+                            A::NAME_LEN,
+                            self.control_len,
+                            ring.bgid(),
+                        )?)
+                    }
+                };
+                let meta = ready!(slot.poll_multishot(cx));
+                if !meta.more() {
+                    *op = None;
+                }
+                match meta.result {
+                    Ok(len) => {
+                        *received = true;
+                        let bid = io_uring::cqueue::buffer_select(meta.flags)
+                            .expect("multishot recvmsg completes with a buffer");
+                        ring.take(bid, 1, |_| {});
+                        break MsgBuf::Ring {
+                            ring: ring.clone(),
+                            bid,
+                            len: len as usize,
+                        };
+                    }
+                    // The ring ran out of buffers, wait for some.
+                    Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => continue,
+                    // Multishot recvmsg requires kernel 6.0+.
+                    Err(e) if e.raw_os_error() == Some(libc::EINVAL) && !*received => {
+                        self.state = Self::oneshot();
+                        return self.poll_recv(cx);
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
+                }
+            },
+            State::Oneshot { pool, op } => {
+                let slot = match op {
+                    Some(op) => op,
+                    None => {
+                        let buf = pool.borrow_mut().pop();
+                        let buf = buf.unwrap_or_else(|| vec![0; self.size]);
+                        op.insert(Op::recv_msg_into(
+                            &self.fd,
+                            buf,
+                            A::NAME_LEN,
+                            self.control_len,
+                        )?)
+                    }
+                };
+                let completion = ready!(Pin::new(slot).poll(cx));
+                *op = None;
+                let (res, buf) = completion.data.complete(completion.meta.result);
+                match res {
+                    Ok(len) => MsgBuf::Owned {
+                        buf,
+                        len,
+                        pool: pool.clone(),
+                        max: self.count as usize,
+                    },
+                    Err(e) => {
+                        pool.borrow_mut().push(buf);
+                        return Poll::Ready(Err(e));
+                    }
+                }
+            }
+        };
+        Poll::Ready(Ok(RecvMsg {
+            buf,
+            name_len: A::NAME_LEN as usize,
+            control_len: self.control_len,
+            _addr: PhantomData,
+        }))
+    }
+}
+
+impl<A: RecvMsgAddr> Stream for RecvMsgStream<A> {
+    type Item = io::Result<RecvMsg<A>>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        Some(poll_fn(|cx| self.poll_recv(cx)).await)
+    }
+}
+
+impl<A> Drop for RecvMsgStream<A> {
+    fn drop(&mut self) {
+        // A multishot recvmsg only ends when it is canceled, or out of
+        // buffers.
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let State::Ring { op: Some(op), .. } = &self.state {
+            unsafe { op.op_canceller().cancel() };
+        }
+    }
+}
+
+impl<A> fmt::Debug for RecvMsgStream<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvMsgStream")
+            .field("fd", &self.fd)
+            .field("count", &self.count)
+            .field("size", &self.size)
+            .finish()
+    }
+}
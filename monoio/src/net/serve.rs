@@ -0,0 +1,173 @@
+use std::{
+    fmt,
+    future::{poll_fn, Future},
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    io::{stream::Stream, CancelHandle, Canceller},
+    time::{interval_at, Duration, Instant, Interval, MissedTickBehavior},
+};
+
+type Accept<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + 'a>>;
+
+/// An event of a [`ServeLoop`].
+#[derive(Debug)]
+pub enum ServeEvent<T> {
+    /// A connection was accepted, or accepting one failed.
+    Accept(io::Result<T>),
+    /// The tick period elapsed.
+    Tick(Instant),
+}
+
+/// The select loop of a server over the accepted connections, a shutdown
+/// signal and a periodic tick, returned by
+/// [`TcpListener::serve_loop`](crate::net::TcpListener::serve_loop) and
+/// [`UnixListener::serve_loop`](crate::net::UnixListener::serve_loop).
+///
+/// The accept in flight is kept across ticks instead of being dropped and
+/// restarted. On shutdown it is canceled and waited for: a connection accepted
+/// while the cancellation reached the driver is closed rather than leaked in
+/// the completion queue. The shutdown signal is polled first, so no event is
+/// returned once it fired.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{
+///     net::{ServeEvent, TcpListener},
+///     time::Duration,
+/// };
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let listener = TcpListener::bind("127.0.0.1:8080").unwrap();
+///     let mut serve = listener
+///         .serve_loop()
+///         // Or a `monoio::utils::CtrlC` with the `signal` feature.
+///         .shutdown(monoio::time::sleep(Duration::from_secs(3600)))
+///         .tick(Duration::from_secs(10));
+///     while let Some(event) = serve.next_event().await {
+///         match event {
+///             ServeEvent::Accept(Ok((stream, _addr))) => drop(monoio::spawn(async move {
+///                 // handle the connection ...
+///                 drop(stream);
+///             })),
+///             ServeEvent::Accept(Err(e)) => eprintln!("accept failed: {e}"),
+///             ServeEvent::Tick(_) => { /* report stats ... */ }
+///         }
+///     }
+/// }
+/// ```
+pub struct ServeLoop<'a, T> {
+    accept: Box<dyn FnMut(CancelHandle) -> Accept<'a, T> + 'a>,
+    pending: Option<Accept<'a, T>>,
+    canceller: Canceller,
+    shutdown: Option<Pin<Box<dyn Future<Output = ()> + 'a>>>,
+    tick: Option<Interval>,
+    done: bool,
+}
+
+impl<'a, T> ServeLoop<'a, T> {
+    pub(crate) fn new<F: Future<Output = io::Result<T>> + 'a>(
+        mut accept: impl FnMut(CancelHandle) -> F + 'a,
+    ) -> Self {
+        Self {
+            accept: Box::new(move |c| Box::pin(accept(c))),
+            pending: None,
+            canceller: Canceller::new(),
+            shutdown: None,
+            tick: None,
+            done: false,
+        }
+    }
+
+    /// Stop the loop when `signal` completes, like a Ctrl+C future or a
+    /// channel receive.
+    #[must_use]
+    pub fn shutdown(mut self, signal: impl Future<Output = ()> + 'a) -> Self {
+        self.shutdown = Some(Box::pin(signal));
+        self
+    }
+
+    /// Return a [`ServeEvent::Tick`] every `period`, starting one `period`
+    /// from now. Ticks missed while the loop was not polled are skipped.
+    /// The timer must be enabled on the runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    #[must_use]
+    pub fn tick(mut self, period: Duration) -> Self {
+        let mut interval = interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        self.tick = Some(interval);
+        self
+    }
+
+    /// Returns true if the shutdown signal fired.
+    pub fn is_shutdown(&self) -> bool {
+        self.done
+    }
+
+    /// Wait for the next event, or returns `None` once the shutdown signal
+    /// fired and the accept in flight was canceled.
+    pub async fn next_event(&mut self) -> Option<ServeEvent<T>> {
+        if self.done {
+            return None;
+        }
+        let event = poll_fn(|cx| self.poll_event(cx)).await;
+        if event.is_none() {
+            self.done = true;
+            self.canceller = std::mem::take(&mut self.canceller).cancel();
+            if let Some(pending) = self.pending.take() {
+                // The accepted connection, if any, is closed on drop.
+                let _ = pending.await;
+            }
+        }
+        event
+    }
+
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<ServeEvent<T>>> {
+        if let Some(shutdown) = self.shutdown.as_mut() {
+            if shutdown.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+        }
+        let pending = match self.pending.as_mut() {
+            Some(pending) => pending,
+            None => self.pending.insert((self.accept)(self.canceller.handle())),
+        };
+        if let Poll::Ready(res) = pending.as_mut().poll(cx) {
+            self.pending = None;
+            return Poll::Ready(Some(ServeEvent::Accept(res)));
+        }
+        if let Some(tick) = self.tick.as_mut() {
+            if let Poll::Ready(at) = tick.poll_tick(cx) {
+                return Poll::Ready(Some(ServeEvent::Tick(at)));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Stream for ServeLoop<'_, T> {
+    type Item = ServeEvent<T>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().await
+    }
+}
+
+impl<T> fmt::Debug for ServeLoop<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServeLoop")
+            .field("pending", &self.pending.is_some())
+            .field("tick", &self.tick.as_ref().map(Interval::period))
+            .field("done", &self.done)
+            .finish()
+    }
+}
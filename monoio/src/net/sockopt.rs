@@ -0,0 +1,258 @@
+//! Raw socket options.
+//!
+//! Options the crate does not wrap can be set and read with `set_option` and
+//! `option` of the sockets. The type of the value decides how it is passed to
+//! `setsockopt`/`getsockopt`.
+
+use std::{io, os::unix::prelude::RawFd};
+
+use libc::c_int;
+
+/// Protocol level of a socket option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Level {
+    /// `SOL_SOCKET`
+    Socket,
+    /// `IPPROTO_IP`
+    Ip,
+    /// `IPPROTO_IPV6`
+    Ipv6,
+    /// `IPPROTO_TCP`
+    Tcp,
+    /// `IPPROTO_UDP`
+    Udp,
+    /// Any other level.
+    Raw(c_int),
+}
+
+impl Level {
+    /// Returns the raw level.
+    pub const fn as_raw(self) -> c_int {
+        match self {
+            Level::Socket => libc::SOL_SOCKET,
+            Level::Ip => libc::IPPROTO_IP,
+            Level::Ipv6 => libc::IPPROTO_IPV6,
+            Level::Tcp => libc::IPPROTO_TCP,
+            Level::Udp => libc::IPPROTO_UDP,
+            Level::Raw(level) => level,
+        }
+    }
+}
+
+/// Name of a socket option.
+///
+/// Common options are provided as constants, others can be made with
+/// [`Name::from_raw`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Name(c_int);
+
+impl Name {
+    /// `SO_REUSEADDR` of [`Level::Socket`].
+    pub const REUSEADDR: Name = Name(libc::SO_REUSEADDR);
+    /// `SO_REUSEPORT` of [`Level::Socket`].
+    pub const REUSEPORT: Name = Name(libc::SO_REUSEPORT);
+    /// `SO_KEEPALIVE` of [`Level::Socket`].
+    pub const KEEPALIVE: Name = Name(libc::SO_KEEPALIVE);
+    /// `SO_BROADCAST` of [`Level::Socket`].
+    pub const BROADCAST: Name = Name(libc::SO_BROADCAST);
+    /// `SO_RCVBUF` of [`Level::Socket`].
+    pub const RCVBUF: Name = Name(libc::SO_RCVBUF);
+    /// `SO_SNDBUF` of [`Level::Socket`].
+    pub const SNDBUF: Name = Name(libc::SO_SNDBUF);
+    /// `SO_ERROR` of [`Level::Socket`].
+    pub const ERROR: Name = Name(libc::SO_ERROR);
+    /// `SO_MARK` of [`Level::Socket`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const MARK: Name = Name(libc::SO_MARK);
+    /// `SO_PRIORITY` of [`Level::Socket`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const PRIORITY: Name = Name(libc::SO_PRIORITY);
+    /// `SO_INCOMING_CPU` of [`Level::Socket`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const INCOMING_CPU: Name = Name(libc::SO_INCOMING_CPU);
+    /// `SO_BINDTODEVICE` of [`Level::Socket`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const BINDTODEVICE: Name = Name(libc::SO_BINDTODEVICE);
+
+    /// `IP_TTL` of [`Level::Ip`].
+    pub const TTL: Name = Name(libc::IP_TTL);
+    /// `IP_TOS` of [`Level::Ip`].
+    pub const TOS: Name = Name(libc::IP_TOS);
+    /// `IP_TRANSPARENT` of [`Level::Ip`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const TRANSPARENT: Name = Name(libc::IP_TRANSPARENT);
+    /// `IPV6_V6ONLY` of [`Level::Ipv6`].
+    pub const V6ONLY: Name = Name(libc::IPV6_V6ONLY);
+
+    /// `TCP_NODELAY` of [`Level::Tcp`].
+    pub const NODELAY: Name = Name(libc::TCP_NODELAY);
+    /// `TCP_CONGESTION` of [`Level::Tcp`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const CONGESTION: Name = Name(libc::TCP_CONGESTION);
+    /// `TCP_QUICKACK` of [`Level::Tcp`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const QUICKACK: Name = Name(libc::TCP_QUICKACK);
+    /// `TCP_DEFER_ACCEPT` of [`Level::Tcp`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const DEFER_ACCEPT: Name = Name(libc::TCP_DEFER_ACCEPT);
+    /// `TCP_USER_TIMEOUT` of [`Level::Tcp`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const USER_TIMEOUT: Name = Name(libc::TCP_USER_TIMEOUT);
+    /// `TCP_NOTSENT_LOWAT` of [`Level::Tcp`].
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub const NOTSENT_LOWAT: Name = Name(libc::TCP_NOTSENT_LOWAT);
+
+    /// Name from the raw option name.
+    pub const fn from_raw(name: c_int) -> Self {
+        Name(name)
+    }
+
+    /// Returns the raw option name.
+    pub const fn as_raw(self) -> c_int {
+        self.0
+    }
+}
+
+/// Value which can be passed to `setsockopt`.
+///
+/// Integers and `bool` are passed as `int`, strings and bytes as they are.
+pub trait SetOptionValue {
+    /// Call `f` with the bytes of the value.
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R;
+}
+
+/// Value which can be read with `getsockopt`.
+pub trait GetOptionValue: Sized {
+    /// Size of the buffer given to `getsockopt`.
+    const MAX_LEN: usize;
+
+    /// Decode the bytes written by `getsockopt`.
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self>;
+}
+
+impl<T: SetOptionValue + ?Sized> SetOptionValue for &T {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        (**self).with_bytes(f)
+    }
+}
+
+impl SetOptionValue for c_int {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(&self.to_ne_bytes())
+    }
+}
+
+impl SetOptionValue for u32 {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(&self.to_ne_bytes())
+    }
+}
+
+impl SetOptionValue for bool {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        (*self as c_int).with_bytes(f)
+    }
+}
+
+impl SetOptionValue for str {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(self.as_bytes())
+    }
+}
+
+impl SetOptionValue for [u8] {
+    #[inline]
+    fn with_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(self)
+    }
+}
+
+impl GetOptionValue for c_int {
+    const MAX_LEN: usize = std::mem::size_of::<c_int>();
+
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        match bytes.len() {
+            // Some options of `IPPROTO_IP` are a single byte.
+            1 => Ok(bytes[0] as c_int),
+            4 => Ok(c_int::from_ne_bytes(bytes.try_into().unwrap())),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected socket option length",
+            )),
+        }
+    }
+}
+
+impl GetOptionValue for u32 {
+    const MAX_LEN: usize = std::mem::size_of::<u32>();
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        c_int::from_bytes(bytes).map(|v| v as u32)
+    }
+}
+
+impl GetOptionValue for bool {
+    const MAX_LEN: usize = std::mem::size_of::<c_int>();
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        c_int::from_bytes(bytes).map(|v| v != 0)
+    }
+}
+
+impl GetOptionValue for Vec<u8> {
+    const MAX_LEN: usize = 256;
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl GetOptionValue for String {
+    const MAX_LEN: usize = 256;
+
+    fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        // Strings are NUL terminated or padded.
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8(bytes[..len].to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+pub(crate) fn set_option<V: SetOptionValue>(
+    fd: RawFd,
+    level: Level,
+    name: Name,
+    value: V,
+) -> io::Result<()> {
+    value.with_bytes(|bytes| {
+        crate::syscall!(setsockopt(
+            fd,
+            level.as_raw(),
+            name.as_raw(),
+            bytes.as_ptr().cast(),
+            bytes.len() as libc::socklen_t
+        ))
+        .map(|_| ())
+    })
+}
+
+pub(crate) fn get_option<V: GetOptionValue>(fd: RawFd, level: Level, name: Name) -> io::Result<V> {
+    let mut buf = vec![0u8; V::MAX_LEN];
+    let mut len = buf.len() as libc::socklen_t;
+    crate::syscall!(getsockopt(
+        fd,
+        level.as_raw(),
+        name.as_raw(),
+        buf.as_mut_ptr().cast(),
+        &mut len
+    ))?;
+    V::from_bytes(&buf[..len as usize])
+}
@@ -0,0 +1,87 @@
+//! Steer connections of a `SO_REUSEPORT` group to the listener on the CPU
+//! which handled the packet.
+//!
+//! With `SO_INCOMING_CPU` and a classic BPF program picking the socket at index
+//! `cpu % listeners`, the NIC queue interrupt, the kernel processing and the
+//! runtime thread of a connection all stay on the same core.
+
+use std::{io, os::unix::prelude::RawFd};
+
+use crate::syscall;
+
+// Offset of the ancillary data loads of classic BPF, and the one of the
+// CPU the packet is processed on.
+const SKF_AD_OFF: i32 = -0x1000;
+const SKF_AD_CPU: i32 = 36;
+
+/// Set `SO_INCOMING_CPU`, the socket is preferred in its `SO_REUSEPORT`
+/// group for packets processed on `cpu`.
+pub(crate) fn set_incoming_cpu(fd: RawFd, cpu: usize) -> io::Result<()> {
+    let cpu = cpu as libc::c_int;
+    syscall!(setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_INCOMING_CPU,
+        &cpu as *const _ as *const _,
+        std::mem::size_of_val(&cpu) as libc::socklen_t
+    ))
+    .map(|_| ())
+}
+
+/// Get `SO_INCOMING_CPU`, `None` if it is not set.
+pub(crate) fn incoming_cpu(fd: RawFd) -> io::Result<Option<usize>> {
+    let cpu: libc::c_int = crate::net::sockopt::get_option(
+        fd,
+        crate::net::Level::Socket,
+        crate::net::Name::INCOMING_CPU,
+    )?;
+    Ok(usize::try_from(cpu).ok())
+}
+
+/// Attach to the `SO_REUSEPORT` group of the socket a classic BPF program which
+/// picks the socket at index `cpu % listeners`. Sockets are indexed in the
+/// order they are bound.
+pub(crate) fn attach_cpu_steering(fd: RawFd, listeners: u32) -> io::Result<()> {
+    if listeners == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "listeners must not be zero",
+        ));
+    }
+    let filter = [
+        // A = cpu
+        libc::sock_filter {
+            code: 0x20, // BPF_LD | BPF_W | BPF_ABS
+            jt: 0,
+            jf: 0,
+            k: (SKF_AD_OFF + SKF_AD_CPU) as u32,
+        },
+        // A %= listeners
+        libc::sock_filter {
+            code: 0x94, // BPF_ALU | BPF_MOD | BPF_K
+            jt: 0,
+            jf: 0,
+            k: listeners,
+        },
+        // return A
+        libc::sock_filter {
+            code: 0x16, // BPF_RET | BPF_A
+            jt: 0,
+            jf: 0,
+            k: 0,
+        },
+    ];
+    let prog = libc::sock_fprog {
+        len: filter.len() as u16,
+        filter: filter.as_ptr() as *mut _,
+    };
+    // The kernel copies the program, classic BPF needs no privilege.
+    syscall!(setsockopt(
+        fd,
+        libc::SOL_SOCKET,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        &prog as *const _ as *const _,
+        std::mem::size_of_val(&prog) as libc::socklen_t
+    ))
+    .map(|_| ())
+}
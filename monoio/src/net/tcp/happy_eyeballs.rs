@@ -0,0 +1,105 @@
+//! Connection racing over the resolved addresses of a host, RFC 8305.
+//!
+//! Addresses are interleaved by family, starting with the family of the first
+//! one. A new attempt is started whenever the previous one failed or did not
+//! complete within the attempt delay. The first established connection wins
+//! and the other attempts are canceled.
+
+use std::{
+    future::{poll_fn, Future},
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    task::Poll,
+};
+
+use super::{TcpConnectOpts, TcpStream};
+use crate::io::Canceller;
+
+type Attempt<'a> = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + 'a>>;
+
+pub(super) async fn connect(
+    addrs: Vec<SocketAddr>,
+    opts: &TcpConnectOpts,
+) -> io::Result<TcpStream> {
+    let addrs = interleave(addrs);
+    if addrs.len() == 1 {
+        return TcpStream::connect_addr_with_config(addrs[0], opts).await;
+    }
+
+    // Without the timer, attempts are only started when the previous one
+    // failed.
+    let timer = crate::runtime::CURRENT.with(|ctx| ctx.time_handle.is_some());
+    let canceller = Canceller::new();
+    let mut attempts: Vec<Attempt<'_>> = Vec::new();
+    let mut next = 0;
+    let mut delay: Option<Pin<Box<crate::time::Sleep>>> = None;
+    let mut failed = false;
+    let mut elapsed = false;
+    let mut last_err = None;
+
+    let res = poll_fn(|cx| loop {
+        let start = std::mem::take(&mut failed) || std::mem::take(&mut elapsed);
+        if (start || attempts.is_empty()) && next < addrs.len() {
+            let handle = canceller.handle();
+            attempts.push(Box::pin(TcpStream::cancelable_connect_addr(
+                addrs[next],
+                opts,
+                handle,
+            )));
+            next += 1;
+            delay = (timer && next < addrs.len())
+                .then(|| Box::pin(crate::time::sleep(opts.attempt_delay)));
+        }
+
+        let mut i = 0;
+        while i < attempts.len() {
+            match attempts[i].as_mut().poll(cx) {
+                Poll::Ready(Ok(stream)) => return Poll::Ready(Ok(stream)),
+                Poll::Ready(Err(e)) => {
+                    last_err = Some(e);
+                    failed = true;
+                    drop(attempts.swap_remove(i));
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+        if attempts.is_empty() && next == addrs.len() {
+            return Poll::Ready(Err(last_err.take().expect("an attempt failed")));
+        }
+        if let Some(sleep) = delay.as_mut() {
+            if sleep.as_mut().poll(cx).is_ready() {
+                delay = None;
+                elapsed = true;
+            }
+        }
+        if !failed && !elapsed {
+            return Poll::Pending;
+        }
+    })
+    .await;
+
+    // Cancel the ops of the losers before dropping them, so their sockets
+    // are closed without waiting for the handshakes to complete.
+    let _ = canceller.cancel();
+    drop(attempts);
+    res
+}
+
+// Alternate address families, starting with the family of the first address.
+fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else {
+        return addrs;
+    };
+    let v6 = first.is_ipv6();
+    let (preferred, other): (Vec<_>, Vec<_>) =
+        addrs.into_iter().partition(|addr| addr.is_ipv6() == v6);
+    let mut out = Vec::with_capacity(preferred.len() + other.len());
+    let mut other = other.into_iter();
+    for addr in preferred {
+        out.push(addr);
+        out.extend(other.next());
+    }
+    out.extend(other);
+    out
+}
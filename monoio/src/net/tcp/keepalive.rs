@@ -0,0 +1,112 @@
+use std::{io, time::Duration};
+
+/// TCP keepalive parameters, fields left as `None` keep the system
+/// defaults.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use monoio::net::KeepAlive;
+///
+/// let keepalive = KeepAlive::new()
+///     .time(Duration::from_secs(60))
+///     .interval(Duration::from_secs(10))
+///     .retries(5);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAlive {
+    /// Idle time before the first probe is sent, `TCP_KEEPIDLE`.
+    pub time: Option<Duration>,
+    /// Time between probes, `TCP_KEEPINTVL`.
+    pub interval: Option<Duration>,
+    /// Number of unanswered probes before the connection is dropped,
+    /// `TCP_KEEPCNT`. It is ignored on windows.
+    pub retries: Option<u32>,
+}
+
+impl KeepAlive {
+    /// Create a KeepAlive with the system defaults.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            time: None,
+            interval: None,
+            retries: None,
+        }
+    }
+
+    /// Set the idle time before the first probe is sent.
+    #[must_use]
+    #[inline]
+    pub const fn time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Set the time between probes.
+    #[must_use]
+    #[inline]
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Set the number of unanswered probes before the connection is dropped.
+    #[must_use]
+    #[inline]
+    pub const fn retries(mut self, retries: u32) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+}
+
+/// Enable keepalive on `socket` with `keepalive`, or disable it with `None`.
+pub(crate) fn set_keepalive(
+    socket: &socket2::Socket,
+    keepalive: Option<KeepAlive>,
+) -> io::Result<()> {
+    let Some(keepalive) = keepalive else {
+        return socket.set_keepalive(false);
+    };
+    let mut t = socket2::TcpKeepalive::new();
+    if let Some(time) = keepalive.time {
+        t = t.with_time(time);
+    }
+    if let Some(interval) = keepalive.interval {
+        t = t.with_interval(interval);
+    }
+    #[cfg(unix)]
+    if let Some(retries) = keepalive.retries {
+        t = t.with_retries(retries);
+    }
+    socket.set_tcp_keepalive(&t)
+}
+
+/// Returns the keepalive parameters of `socket`, or `None` if keepalive is
+/// disabled. The parameters are `None` where they can not be queried.
+pub(crate) fn keepalive(socket: &socket2::Socket) -> io::Result<Option<KeepAlive>> {
+    if !socket.keepalive()? {
+        return Ok(None);
+    }
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos",
+        target_os = "ios",
+    ))]
+    let keepalive = KeepAlive {
+        time: Some(socket.keepalive_time()?),
+        interval: Some(socket.keepalive_interval()?),
+        retries: Some(socket.keepalive_retries()?),
+    };
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos",
+        target_os = "ios",
+    )))]
+    let keepalive = KeepAlive::new();
+    Ok(Some(keepalive))
+}
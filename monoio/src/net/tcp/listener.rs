@@ -2,6 +2,7 @@ use std::{
     cell::UnsafeCell,
     io,
     net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs},
+    time::Duration,
 };
 
 #[cfg(unix)]
@@ -24,6 +25,21 @@ use crate::{
     net::ListenerOpts,
 };
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_defer_accept(fd: RawFd, timeout: Option<Duration>) -> io::Result<()> {
+    // Round up so a sub-second timeout does not disable the option.
+    let secs = timeout.map_or(0, |t| {
+        let secs = t.as_secs() + u64::from(t.subsec_nanos() > 0);
+        secs.min(libc::c_int::MAX as u64) as libc::c_int
+    });
+    crate::net::sockopt::set_option(
+        fd,
+        crate::net::Level::Tcp,
+        crate::net::Name::DEFER_ACCEPT,
+        secs,
+    )
+}
+
 /// TcpListener
 pub struct TcpListener {
     fd: SharedFd,
@@ -63,29 +79,16 @@ impl TcpListener {
         #[cfg(feature = "legacy")]
         Self::set_non_blocking(&sys_listener)?;
 
+        Self::set_bind_opts(&sys_listener, opts)?;
         let addr = socket2::SockAddr::from(addr);
-        #[cfg(unix)]
-        if opts.reuse_port {
-            sys_listener.set_reuse_port(true)?;
-        }
-        if opts.reuse_addr {
-            sys_listener.set_reuse_address(true)?;
-        }
-        if let Some(send_buf_size) = opts.send_buf_size {
-            sys_listener.set_send_buffer_size(send_buf_size)?;
-        }
-        if let Some(recv_buf_size) = opts.recv_buf_size {
-            sys_listener.set_recv_buffer_size(recv_buf_size)?;
-        }
-        if opts.tcp_fast_open {
-            #[cfg(any(target_os = "linux", target_os = "android"))]
-            super::tfo::set_tcp_fastopen(&sys_listener, opts.backlog)?;
-            #[cfg(any(target_os = "ios", target_os = "macos"))]
-            let _ = super::tfo::set_tcp_fastopen_force_enable(&sys_listener);
-        }
         sys_listener.bind(&addr)?;
         sys_listener.listen(opts.backlog)?;
 
+        #[cfg(target_os = "linux")]
+        if let Some(listeners) = opts.cpu_steering {
+            crate::net::steering::attach_cpu_steering(sys_listener.as_raw_fd(), listeners)?;
+        }
+
         #[cfg(any(target_os = "ios", target_os = "macos"))]
         if opts.tcp_fast_open {
             super::tfo::set_tcp_fastopen(&sys_listener)?;
@@ -106,7 +109,88 @@ impl TcpListener {
         Self::bind_with_config(addr, &DEFAULT_CFG)
     }
 
+    /// Bind to address with config, creating the socket, binding and
+    /// listening with io_uring ops so they can be batched with other ops.
+    /// Kernels without `IORING_OP_SOCKET` (5.19) or `IORING_OP_BIND` and
+    /// `IORING_OP_LISTEN` (6.11) and the legacy driver make the syscalls.
+    /// Note: the ops are only used on linux.
+    pub async fn bind_with_config_async<A: ToSocketAddrs>(
+        addr: A,
+        opts: &ListenerOpts,
+    ) -> io::Result<Self> {
+        #[cfg(not(target_os = "linux"))]
+        return Self::bind_with_config(addr, opts);
+
+        #[cfg(target_os = "linux")]
+        {
+            let addr = addr
+                .to_socket_addrs()?
+                .next()
+                .ok_or_else(|| io::Error::other("empty address"))?;
+            let domain = if addr.is_ipv6() { AF_INET6 } else { AF_INET };
+
+            let fd = crate::driver::op::socket(domain, libc::SOCK_STREAM).await?;
+            let sys_listener = unsafe { socket2::Socket::from_raw_fd(fd) };
+            Self::set_bind_opts(&sys_listener, opts)?;
+
+            let fd = SharedFd::new::<false>(sys_listener.into_raw_fd())?;
+            crate::driver::op::bind(&fd, addr).await?;
+            crate::driver::op::listen(&fd, opts.backlog).await?;
+            if let Some(listeners) = opts.cpu_steering {
+                crate::net::steering::attach_cpu_steering(fd.raw_fd(), listeners)?;
+            }
+            Ok(Self::from_shared_fd(fd))
+        }
+    }
+
+    /// Bind to address, see [`TcpListener::bind_with_config_async`].
+    pub async fn bind_async<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        const DEFAULT_CFG: ListenerOpts = ListenerOpts::new();
+        Self::bind_with_config_async(addr, &DEFAULT_CFG).await
+    }
+
+    // Set the options which must be set before bind.
+    fn set_bind_opts(sys_listener: &socket2::Socket, opts: &ListenerOpts) -> io::Result<()> {
+        #[cfg(unix)]
+        if opts.reuse_port {
+            sys_listener.set_reuse_port(true)?;
+        }
+        if opts.reuse_addr {
+            sys_listener.set_reuse_address(true)?;
+        }
+        if let Some(send_buf_size) = opts.send_buf_size {
+            sys_listener.set_send_buffer_size(send_buf_size)?;
+        }
+        if let Some(recv_buf_size) = opts.recv_buf_size {
+            sys_listener.set_recv_buffer_size(recv_buf_size)?;
+        }
+        if opts.tcp_fast_open {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            super::tfo::set_tcp_fastopen(
+                sys_listener,
+                opts.tcp_fast_open_queue.unwrap_or(opts.backlog),
+            )?;
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            let _ = super::tfo::set_tcp_fastopen_force_enable(sys_listener);
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(cpu) = opts.incoming_cpu {
+            crate::net::steering::set_incoming_cpu(sys_listener.as_raw_fd(), cpu)?;
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(timeout) = opts.defer_accept {
+            set_defer_accept(sys_listener.as_raw_fd(), Some(timeout))?;
+        }
+        Ok(())
+    }
+
     /// Accept
+    ///
+    /// With [`ListenerOpts::defer_accept`] or [`TcpListener::set_defer_accept`]
+    /// the accept only completes once the peer has sent its first bytes, so a
+    /// read on the returned stream can be issued right away without waiting.
+    /// Connections whose peer never sends are dropped by the kernel once the
+    /// timeout expires.
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         let op = Op::accept(&self.fd)?;
 
@@ -157,6 +241,18 @@ impl TcpListener {
         Ok((stream, addr))
     }
 
+    /// Returns a stream of the accepted connections, implementing both the
+    /// monoio `Stream` and `futures_core::Stream`.
+    pub fn incoming(&self) -> crate::net::Incoming<'_, (TcpStream, SocketAddr)> {
+        crate::net::Incoming::new(move || self.accept())
+    }
+
+    /// Returns the select loop of a server over the accepted connections, a
+    /// shutdown signal and a periodic tick, see [`ServeLoop`](crate::net::ServeLoop).
+    pub fn serve_loop(&self) -> crate::net::ServeLoop<'_, (TcpStream, SocketAddr)> {
+        crate::net::ServeLoop::new(move |c| self.cancelable_accept(c))
+    }
+
     /// Cancelable accept
     pub async fn cancelable_accept(&self, c: CancelHandle) -> io::Result<(TcpStream, SocketAddr)> {
         use crate::io::operation_canceled;
@@ -255,6 +351,174 @@ impl TcpListener {
         op.wait().await
     }
 
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
+    /// Get the value of the `TCP_NODELAY` option on this socket.
+    pub fn nodelay(&self) -> io::Result<bool> {
+        crate::net::with_socket(self, |s| s.nodelay())
+    }
+
+    /// Set the value of the `TCP_NODELAY` option on this socket, accepted
+    /// connections inherit it.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_nodelay(nodelay))
+    }
+
+    /// Get the value of the `SO_LINGER` option on this socket.
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        crate::net::with_socket(self, |s| s.linger())
+    }
+
+    /// Set the value of the `SO_LINGER` option on this socket, accepted connections inherit it.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_linger(linger))
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.ttl())
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket, accepted connections inherit it.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_ttl(ttl))
+    }
+
+    /// Get the value of the `SO_SNDBUF` option on this socket.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.send_buffer_size())
+    }
+
+    /// Set the value of the `SO_SNDBUF` option on this socket, accepted connections inherit it.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_send_buffer_size(send_buffer_size))
+    }
+
+    /// Get the value of the `SO_RCVBUF` option on this socket.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.recv_buffer_size())
+    }
+
+    /// Set the value of the `SO_RCVBUF` option on this socket, accepted connections inherit it.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_recv_buffer_size(recv_buffer_size))
+    }
+
+    /// Bind the socket to the network interface `interface`, or unbind it
+    /// with `None`, accepted connections inherit it. See
+    /// [`TcpSocket::bind_device`](crate::net::TcpSocket::bind_device).
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios",
+        target_os = "macos"
+    ))]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        crate::net::with_socket(self, |s| crate::net::bind_device(s, interface))
+    }
+
+    /// Returns the name of the network interface the socket is bound to.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn device(&self) -> io::Result<Option<String>> {
+        crate::net::with_socket(self, crate::net::device)
+    }
+
+    /// Get the value of the `IP_TOS` option on this socket.
+    pub fn tos(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tos())
+    }
+
+    /// Set the value of the `IP_TOS` option on this socket, accepted connections inherit it.
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tos(tos))
+    }
+
+    /// Get the value of the `IPV6_TCLASS` option on this socket.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tclass_v6())
+    }
+
+    /// Set the value of the `IPV6_TCLASS` option on this socket, accepted connections inherit it.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn set_tclass_v6(&self, tclass_v6: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tclass_v6(tclass_v6))
+    }
+
+    /// Get the value of the `SO_INCOMING_CPU` option on this socket, `None`
+    /// if it is not set.
+    #[cfg(target_os = "linux")]
+    pub fn incoming_cpu(&self) -> io::Result<Option<usize>> {
+        crate::net::steering::incoming_cpu(self.as_raw_fd())
+    }
+
+    /// Set the value of the `SO_INCOMING_CPU` option on this socket, it is
+    /// preferred in its `SO_REUSEPORT` group for connections processed on `cpu`.
+    #[cfg(target_os = "linux")]
+    pub fn set_incoming_cpu(&self, cpu: usize) -> io::Result<()> {
+        crate::net::steering::set_incoming_cpu(self.as_raw_fd(), cpu)
+    }
+
+    /// Set the value of the `TCP_DEFER_ACCEPT` option on this socket, `None`
+    /// disables it. Connections are only accepted once the peer has sent data
+    /// or `timeout` has elapsed.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn set_defer_accept(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_defer_accept(self.as_raw_fd(), timeout)
+    }
+
+    /// Get the value of the `TCP_DEFER_ACCEPT` option on this socket.
+    /// The kernel reports the timeout rounded up to a number of SYN-ACK
+    /// retransmits, so it may be larger than the one set.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn defer_accept(&self) -> io::Result<Option<Duration>> {
+        let secs: libc::c_int = crate::net::sockopt::get_option(
+            self.as_raw_fd(),
+            crate::net::Level::Tcp,
+            crate::net::Name::DEFER_ACCEPT,
+        )?;
+        Ok((secs > 0).then(|| Duration::from_secs(secs as u64)))
+    }
+
+    /// Steer connections to the listener at index `cpu % listeners` of the
+    /// `SO_REUSEPORT` group of this listener, with a BPF program attached to
+    /// the group. Listeners are indexed in the order they are bound.
+    #[cfg(target_os = "linux")]
+    pub fn attach_cpu_steering(&self, listeners: u32) -> io::Result<()> {
+        crate::net::steering::attach_cpu_steering(self.as_raw_fd(), listeners)
+    }
+
     /// Creates new `TcpListener` from a `std::net::TcpListener`.
     pub fn from_std(stdl: std::net::TcpListener) -> io::Result<Self> {
         #[cfg(unix)]
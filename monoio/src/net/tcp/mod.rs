@@ -1,12 +1,17 @@
 #![allow(unreachable_pub)]
 //! TCP related.
 
+mod happy_eyeballs;
+mod keepalive;
 mod listener;
+mod socket;
 mod split;
 mod stream;
 mod tfo;
 
+pub use keepalive::KeepAlive;
 pub use listener::TcpListener;
+pub use socket::TcpSocket;
 pub use split::{TcpOwnedReadHalf, TcpOwnedWriteHalf};
 pub use stream::{TcpConnectOpts, TcpStream};
 
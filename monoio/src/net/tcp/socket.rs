@@ -0,0 +1,292 @@
+#[cfg(unix)]
+use std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::prelude::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::{io, net::SocketAddr, time::Duration};
+
+use super::{listener::TcpListener, stream::TcpStream};
+use crate::driver::shared_fd::SharedFd;
+
+/// A TCP socket that has not been converted to a [`TcpStream`] or a
+/// [`TcpListener`] yet.
+///
+/// It gives full control over the socket before the connection is
+/// established: create it, set options, bind it, then connect it to get a
+/// [`TcpStream`] or listen on it to get a [`TcpListener`].
+///
+/// ```no_run
+/// use monoio::net::TcpSocket;
+///
+/// # async fn f() -> std::io::Result<()> {
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuseport(true)?;
+/// socket.bind("127.0.0.1:8080".parse().unwrap())?;
+/// let listener = socket.listen(1024)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    inner: socket2::Socket,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<Self> {
+        Self::new(socket2::Domain::IPV4)
+    }
+
+    /// Create a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<Self> {
+        Self::new(socket2::Domain::IPV6)
+    }
+
+    /// Create a new TCP socket of the family of `addr`.
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<Self> {
+        Self::new(socket2::Domain::for_address(addr))
+    }
+
+    fn new(domain: socket2::Domain) -> io::Result<Self> {
+        let inner =
+            socket2::Socket::new(domain, socket2::Type::STREAM, Some(socket2::Protocol::TCP))?;
+        Ok(Self { inner })
+    }
+
+    /// Set `SO_REUSEADDR`.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.inner.set_reuse_address(reuseaddr)
+    }
+
+    /// Get `SO_REUSEADDR`.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.inner.reuse_address()
+    }
+
+    /// Set `SO_REUSEPORT`.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuseport)
+    }
+
+    /// Get `SO_REUSEPORT`.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    /// Set `SO_INCOMING_CPU`, the socket is preferred in its `SO_REUSEPORT`
+    /// group for connections processed on `cpu`.
+    #[cfg(target_os = "linux")]
+    pub fn set_incoming_cpu(&self, cpu: usize) -> io::Result<()> {
+        crate::net::steering::set_incoming_cpu(self.as_raw_fd(), cpu)
+    }
+
+    /// Set `TCP_NODELAY`.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    /// Get `TCP_NODELAY`.
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    /// Set `SO_KEEPALIVE`.
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.inner.set_keepalive(keepalive)
+    }
+
+    /// Get `SO_KEEPALIVE`.
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.inner.keepalive()
+    }
+
+    /// Set `SO_LINGER`.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(linger)
+    }
+
+    /// Get `SO_LINGER`.
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
+    /// Set `SO_SNDBUF`.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size)
+    }
+
+    /// Get `SO_SNDBUF`.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.send_buffer_size()
+    }
+
+    /// Set `SO_RCVBUF`.
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size)
+    }
+
+    /// Get `SO_RCVBUF`.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.recv_buffer_size()
+    }
+
+    /// Bind the socket to the network interface `interface`, or unbind it
+    /// with `None`, so its traffic only goes through that interface.
+    /// It is `SO_BINDTODEVICE` on linux, which also selects the VRF the
+    /// interface belongs to, and `IP_BOUND_IF` on apple systems.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios",
+        target_os = "macos"
+    ))]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        crate::net::bind_device(&self.inner, interface)
+    }
+
+    /// Returns the name of the network interface the socket is bound to.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn device(&self) -> io::Result<Option<String>> {
+        crate::net::device(&self.inner)
+    }
+
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method, like [`Name::TRANSPARENT`](crate::net::Name).
+    #[cfg(unix)]
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
+    /// Bind the socket to `addr`.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.inner.bind(&addr.into())
+    }
+
+    /// Returns the local address the socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner
+            .local_addr()?
+            .as_socket()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not an inet address"))
+    }
+
+    /// Get and clear the pending error of the socket, `SO_ERROR`.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.inner.take_error()
+    }
+
+    /// Establish a connection to `addr`, consuming the socket.
+    pub async fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.set_driver_mode()?;
+        let fd = SharedFd::new::<false>(self.into_raw())?;
+        TcpStream::connect_fd(fd, addr, false, None, None).await
+    }
+
+    /// Listen for connections with a queue of `backlog`, consuming the
+    /// socket.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.set_driver_mode()?;
+        let backlog = backlog.min(i32::MAX as u32) as i32;
+        self.inner.listen(backlog)?;
+        Ok(TcpListener::from_shared_fd(SharedFd::new::<false>(
+            self.into_raw(),
+        )?))
+    }
+
+    // The legacy driver requires nonblocking sockets.
+    fn set_driver_mode(&self) -> io::Result<()> {
+        if crate::driver::op::is_legacy() {
+            self.inner.set_nonblocking(true)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn into_raw(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+
+    #[cfg(windows)]
+    fn into_raw(self) -> RawSocket {
+        self.inner.into_raw_socket()
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpSocket {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(unix)]
+impl IntoRawFd for TcpSocket {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+}
+
+#[cfg(unix)]
+impl FromRawFd for TcpSocket {
+    /// Converts a raw TCP socket fd to a `TcpSocket`.
+    ///
+    /// # Safety
+    ///
+    /// The fd must be an open TCP socket owned by the caller.
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self {
+            inner: socket2::Socket::from_raw_fd(fd),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpSocket {
+    #[inline]
+    fn as_raw_socket(&self) -> RawSocket {
+        self.inner.as_raw_socket()
+    }
+}
+
+#[cfg(windows)]
+impl IntoRawSocket for TcpSocket {
+    #[inline]
+    fn into_raw_socket(self) -> RawSocket {
+        self.inner.into_raw_socket()
+    }
+}
+
+#[cfg(windows)]
+impl FromRawSocket for TcpSocket {
+    /// Converts a raw TCP socket to a `TcpSocket`.
+    ///
+    /// # Safety
+    ///
+    /// The socket must be an open TCP socket owned by the caller.
+    #[inline]
+    unsafe fn from_raw_socket(socket: RawSocket) -> Self {
+        Self {
+            inner: socket2::Socket::from_raw_socket(socket),
+        }
+    }
+}
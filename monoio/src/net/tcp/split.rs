@@ -5,6 +5,8 @@ use crate::io::{
     as_fd::{AsReadFd, AsWriteFd, SharedFdWrapper},
     OwnedReadHalf, OwnedWriteHalf,
 };
+#[cfg(unix)]
+use crate::{buf::IoBufMut, BufResult};
 
 /// OwnedReadHalf.
 pub type TcpOwnedReadHalf = OwnedReadHalf<TcpStream>;
@@ -23,6 +25,14 @@ impl TcpOwnedReadHalf {
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         unsafe { &*self.0.get() }.local_addr()
     }
+
+    /// Receive data without removing it from the socket queue, see
+    /// [`TcpStream::peek`].
+    #[cfg(unix)]
+    #[inline]
+    pub async fn peek<T: IoBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        unsafe { &mut *self.0.get() }.peek(buf).await
+    }
 }
 
 impl AsReadFd for TcpOwnedReadHalf {
@@ -3,7 +3,7 @@ use std::{
     future::Future,
     io,
     net::{SocketAddr, ToSocketAddrs},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 #[cfg(unix)]
@@ -27,8 +27,11 @@ use crate::{
         operation_canceled, AsyncReadRent, AsyncWriteRent, CancelHandle, CancelableAsyncReadRent,
         CancelableAsyncWriteRent, Split,
     },
+    net::Timeouts,
     BufResult,
 };
+#[cfg(all(target_os = "linux", feature = "splice"))]
+use crate::{io::splice::SpliceFlags, net::Pipe};
 
 /// Custom tcp connect options
 #[derive(Debug, Clone, Copy)]
@@ -36,6 +39,9 @@ use crate::{
 pub struct TcpConnectOpts {
     /// TCP fast open.
     pub tcp_fast_open: bool,
+    /// Delay before racing the next address when connecting to a host with
+    /// several addresses.
+    pub attempt_delay: Duration,
 }
 
 impl Default for TcpConnectOpts {
@@ -51,6 +57,7 @@ impl TcpConnectOpts {
     pub const fn new() -> Self {
         Self {
             tcp_fast_open: false,
+            attempt_delay: Duration::from_millis(250),
         }
     }
 
@@ -61,15 +68,28 @@ impl TcpConnectOpts {
     /// established on the first call to write.
     #[must_use]
     #[inline]
-    pub fn tcp_fast_open(mut self, fast_open: bool) -> Self {
+    pub const fn tcp_fast_open(mut self, fast_open: bool) -> Self {
         self.tcp_fast_open = fast_open;
         self
     }
+
+    /// Specify the delay before racing the next address when connecting to a
+    /// host with several addresses, 250ms by default as recommended by
+    /// RFC 8305.
+    /// Note: the timer is required to be enabled, otherwise the next address
+    /// is only tried once the previous attempt failed.
+    #[must_use]
+    #[inline]
+    pub fn attempt_delay(mut self, delay: Duration) -> Self {
+        self.attempt_delay = delay;
+        self
+    }
 }
 /// TcpStream
 pub struct TcpStream {
     pub(super) fd: SharedFd,
     meta: StreamMeta,
+    timeouts: Timeouts,
 }
 
 /// TcpStream is safe to split to two parts
@@ -85,28 +105,43 @@ impl TcpStream {
         // enable SOCK_ZEROCOPY
         meta.set_zero_copy();
 
-        Self { fd, meta }
+        Self {
+            fd,
+            meta,
+            timeouts: Timeouts::default(),
+        }
     }
 
     /// Open a TCP connection to a remote host.
+    ///
+    /// If `addr` resolves to several addresses, they are raced as described
+    /// by RFC 8305 (Happy Eyeballs): interleaved by address family, with the
+    /// next one tried when the previous attempt failed or did not complete
+    /// within [`attempt_delay`](TcpConnectOpts::attempt_delay). The first
+    /// established connection is returned.
     /// Note: This function may block the current thread while resolution is
     /// performed.
     // TODO(chihai): Fix it, maybe spawn_blocking like tokio.
     pub async fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
-        // TODO(chihai): loop for all addrs
-        let addr = addr
-            .to_socket_addrs()?
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "empty address"))?;
+        Self::connect_with_config(addr, &TcpConnectOpts::new()).await
+    }
 
-        Self::connect_addr(addr).await
+    /// Open a TCP connection to a remote host with given config, see
+    /// [`connect`](Self::connect).
+    pub async fn connect_with_config<A: ToSocketAddrs>(
+        addr: A,
+        opts: &TcpConnectOpts,
+    ) -> io::Result<Self> {
+        let addrs: Vec<_> = addr.to_socket_addrs()?.collect();
+        if addrs.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::Other, "empty address"));
+        }
+        super::happy_eyeballs::connect(addrs, opts).await
     }
 
     /// Establish a connection to the specified `addr`.
     pub async fn connect_addr(addr: SocketAddr) -> io::Result<Self> {
-        const DEFAULT_OPTS: TcpConnectOpts = TcpConnectOpts {
-            tcp_fast_open: false,
-        };
+        const DEFAULT_OPTS: TcpConnectOpts = TcpConnectOpts::new();
         Self::connect_addr_with_config(addr, &DEFAULT_OPTS).await
     }
 
@@ -115,6 +150,72 @@ impl TcpStream {
         addr: SocketAddr,
         opts: &TcpConnectOpts,
     ) -> io::Result<Self> {
+        Self::connect_inner(addr, opts, None, None).await
+    }
+
+    /// Establish a connection to the specified `addr`, failing with an error
+    /// of kind `TimedOut` if it is not established within `timeout`.
+    ///
+    /// On expiry the connect is canceled and the socket is closed. In uring
+    /// impl, a linked timeout is attached to the op so the kernel will cancel
+    /// it; in epoll impl, the timer is required to be enabled.
+    pub async fn connect_timeout(addr: SocketAddr, timeout: Duration) -> io::Result<Self> {
+        if timeout == Duration::ZERO {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cannot set a 0 duration timeout",
+            ));
+        }
+        let deadline = Instant::now().checked_add(timeout);
+        Self::connect_inner(addr, &TcpConnectOpts::new(), None, deadline).await
+    }
+
+    /// Cancelable connect to the specified `addr` with given config.
+    ///
+    /// If canceled before the connection is established, an error for which
+    /// [`is_canceled`](crate::io::is_canceled) returns true is returned and
+    /// the socket is closed.
+    pub async fn cancelable_connect_addr(
+        addr: SocketAddr,
+        opts: &TcpConnectOpts,
+        c: CancelHandle,
+    ) -> io::Result<Self> {
+        Self::connect_inner(addr, opts, Some(c), None).await
+    }
+
+    /// Establish a connection to the specified `addr` with TCP fast open and
+    /// write all of `buf` on it, the stream is returned with the buffer.
+    ///
+    /// With a fast open cookie of the server cached by the kernel, the
+    /// beginning of `buf` is carried in the SYN, saving a round trip before
+    /// the server can respond. Without one, or if the server does not support
+    /// fast open, the data is sent after a regular handshake.
+    /// Note: the data may be sent twice if the SYN is retransmitted, so it
+    /// should be idempotent, like the request of an idempotent method.
+    /// It only works for linux 4.11+ and macos/ios 9.0+.
+    pub async fn connect_with_data<T: IoBuf>(addr: SocketAddr, buf: T) -> BufResult<Self, T> {
+        use crate::io::AsyncWriteRentExt;
+
+        const OPTS: TcpConnectOpts = TcpConnectOpts::new().tcp_fast_open(true);
+        let mut stream = match Self::connect_inner(addr, &OPTS, None, None).await {
+            Ok(stream) => stream,
+            Err(e) => return (Err(e), buf),
+        };
+        // The connection is established by the first write, it carries the
+        // data in the SYN.
+        let (res, buf) = stream.write_all(buf).await;
+        (res.map(|_| stream), buf)
+    }
+
+    async fn connect_inner(
+        addr: SocketAddr,
+        opts: &TcpConnectOpts,
+        c: Option<CancelHandle>,
+        deadline: Option<Instant>,
+    ) -> io::Result<Self> {
+        if c.as_ref().map(|c| c.canceled()).unwrap_or(false) {
+            return Err(operation_canceled());
+        }
         let domain = match addr {
             SocketAddr::V4(_) => AF_INET,
             SocketAddr::V6(_) => AF_INET6,
@@ -132,7 +233,29 @@ impl TcpStream {
                 tfo = false;
             }
         }
-        let completion = Op::connect(SharedFd::new::<false>(socket)?, addr, tfo)?.await;
+        Self::connect_fd(SharedFd::new::<false>(socket)?, addr, tfo, c, deadline).await
+    }
+
+    // Connect the socket to `addr`, it is closed on error.
+    pub(super) async fn connect_fd(
+        fd: SharedFd,
+        addr: SocketAddr,
+        tfo: bool,
+        c: Option<CancelHandle>,
+        deadline: Option<Instant>,
+    ) -> io::Result<Self> {
+        let completion = match deadline {
+            None => {
+                let op = Op::connect(fd, addr, tfo)?;
+                let _guard = c.clone().map(|c| c.associate_op(op.op_canceller()));
+                op.await
+            }
+            Some(deadline) => {
+                let op = Op::connect_with_deadline(fd, addr, tfo, deadline)?;
+                let _guard = c.clone().map(|c| c.associate_op(op.op_canceller()));
+                op.result().await
+            }
+        };
         completion.meta.result?;
 
         let stream = TcpStream::from_shared_fd(completion.data.fd);
@@ -140,7 +263,7 @@ impl TcpStream {
         if crate::driver::op::is_legacy() {
             #[cfg(all(any(target_os = "ios", target_os = "macos"), feature = "legacy"))]
             if !tfo {
-                stream.writable(true).await?;
+                until(deadline, stream.writable(true)).await?;
             } else {
                 // set writable as init state
                 crate::driver::CURRENT.with(|inner| match inner {
@@ -157,7 +280,7 @@ impl TcpStream {
                 })
             }
             #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-            stream.writable(true).await?;
+            until(deadline, stream.cancelable_writable(c)).await?;
 
             // getsockopt libc::SO_ERROR
             #[cfg(unix)]
@@ -201,6 +324,30 @@ impl TcpStream {
         self.meta.set_no_delay(nodelay)
     }
 
+    /// Get the value of the `TCP_QUICKACK` option on this socket.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn quickack(&self) -> io::Result<bool> {
+        crate::net::sockopt::get_option(
+            self.as_raw_fd(),
+            crate::net::Level::Tcp,
+            crate::net::Name::QUICKACK,
+        )
+    }
+
+    /// Set the value of the `TCP_QUICKACK` option on this socket, ACKs are
+    /// sent immediately instead of being delayed.
+    /// Note: the kernel may leave quickack mode on its own, latency sensitive
+    /// servers usually set it again after each read.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        crate::net::sockopt::set_option(
+            self.as_raw_fd(),
+            crate::net::Level::Tcp,
+            crate::net::Name::QUICKACK,
+            quickack,
+        )
+    }
+
     /// Set the value of the `SO_KEEPALIVE` option on this socket.
     #[inline]
     pub fn set_tcp_keepalive(
@@ -209,7 +356,163 @@ impl TcpStream {
         interval: Option<Duration>,
         retries: Option<u32>,
     ) -> io::Result<()> {
-        self.meta.set_tcp_keepalive(time, interval, retries)
+        self.set_keepalive(Some(super::KeepAlive {
+            time,
+            interval,
+            retries,
+        }))
+    }
+
+    /// Enable TCP keepalive with `keepalive` parameters on this socket, or
+    /// disable it with `None`.
+    #[inline]
+    pub fn set_keepalive(&self, keepalive: Option<super::KeepAlive>) -> io::Result<()> {
+        self.meta.set_keepalive(keepalive)
+    }
+
+    /// Get the TCP keepalive parameters of this socket, `None` if keepalive
+    /// is disabled. The parameters are `None` on platforms where they can not
+    /// be queried.
+    #[inline]
+    pub fn keepalive(&self) -> io::Result<Option<super::KeepAlive>> {
+        self.meta.keepalive()
+    }
+
+    /// Returns a stream of the data received by the socket.
+    ///
+    /// With io_uring, a bundled recv (kernel 6.10+) receives into as many of
+    /// `buffers` buffers of `buffer_size` bytes as the data needs with one
+    /// completion, they are provided to the kernel with a buffer ring. The
+    /// buffers are held by each [`RecvBundle`](crate::net::RecvBundle) until
+    /// it is dropped, the stream waits for one when all are held. Otherwise,
+    /// or on older kernels, each item is received into one buffer with a recv
+    /// call.
+    #[cfg(target_os = "linux")]
+    pub fn recv_bundle(
+        &self,
+        buffers: u16,
+        buffer_size: usize,
+    ) -> io::Result<crate::net::RecvBundleStream> {
+        crate::net::RecvBundleStream::new(&self.fd, buffers, buffer_size)
+    }
+
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    ///
+    /// ```no_run
+    /// use monoio::net::{Level, Name, TcpStream};
+    ///
+    /// # async fn f(stream: TcpStream) -> std::io::Result<()> {
+    /// stream.set_option(Level::Tcp, Name::CONGESTION, "bbr")?;
+    /// let congestion: String = stream.option(Level::Tcp, Name::CONGESTION)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
+    /// Get the value of the `SO_LINGER` option on this socket.
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        crate::net::with_socket(self, |s| s.linger())
+    }
+
+    /// Set the value of the `SO_LINGER` option on this socket.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_linger(linger))
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.ttl())
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_ttl(ttl))
+    }
+
+    /// Get the value of the `SO_SNDBUF` option on this socket.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.send_buffer_size())
+    }
+
+    /// Set the value of the `SO_SNDBUF` option on this socket.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_send_buffer_size(send_buffer_size))
+    }
+
+    /// Get the value of the `SO_RCVBUF` option on this socket.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.recv_buffer_size())
+    }
+
+    /// Set the value of the `SO_RCVBUF` option on this socket.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_recv_buffer_size(recv_buffer_size))
+    }
+
+    /// Get the value of the `IP_TOS` option on this socket.
+    pub fn tos(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tos())
+    }
+
+    /// Set the value of the `IP_TOS` option on this socket.
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tos(tos))
+    }
+
+    /// Get the value of the `IPV6_TCLASS` option on this socket.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tclass_v6())
+    }
+
+    /// Set the value of the `IPV6_TCLASS` option on this socket.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn set_tclass_v6(&self, tclass_v6: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tclass_v6(tclass_v6))
+    }
+
+    /// Get the value of the `SO_INCOMING_CPU` option on this socket, `None`
+    /// if it is not set.
+    #[cfg(target_os = "linux")]
+    pub fn incoming_cpu(&self) -> io::Result<Option<usize>> {
+        crate::net::steering::incoming_cpu(self.as_raw_fd())
+    }
+
+    /// Set the value of the `SO_INCOMING_CPU` option on this socket, it is
+    /// preferred in its `SO_REUSEPORT` group for connections processed on `cpu`.
+    #[cfg(target_os = "linux")]
+    pub fn set_incoming_cpu(&self, cpu: usize) -> io::Result<()> {
+        crate::net::steering::set_incoming_cpu(self.as_raw_fd(), cpu)
     }
 
     /// Creates new `TcpStream` from a `std::net::TcpStream`.
@@ -259,6 +562,129 @@ impl TcpStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+    async fn cancelable_writable(&self, c: Option<CancelHandle>) -> io::Result<()> {
+        let op = Op::poll_write(&self.fd, true).unwrap();
+        let _guard = c.map(|c| c.associate_op(op.op_canceller()));
+        op.wait().await
+    }
+
+    /// Read with a deadline.
+    /// If the read is not finished before the deadline, it will be canceled and
+    /// an error with kind `TimedOut` is returned along with the buffer.
+    ///
+    /// In uring impl, a linked timeout is attached to the op so the kernel will
+    /// cancel it on expiry; in epoll impl, the timer is required to be enabled.
+    pub async fn read_with_deadline<T: IoBufMut>(
+        &mut self,
+        buf: T,
+        deadline: Instant,
+    ) -> BufResult<usize, T> {
+        let op = Op::recv_with_deadline(self.fd.clone(), buf, deadline).unwrap();
+        op.read().await
+    }
+
+    /// Receive data into `buf` without removing it from the socket queue, a
+    /// later read returns the same bytes. It is useful to sniff the protocol,
+    /// like TLS or plaintext, before handing the stream over.
+    ///
+    /// Returns 0 when the peer closed the connection. The read timeout
+    /// applies.
+    #[cfg(unix)]
+    pub async fn peek<T: IoBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        let fd = self.fd.clone();
+        match self.timeouts.read_deadline() {
+            None => Op::peek(fd, buf).unwrap().read().await,
+            Some(deadline) => {
+                Op::peek_with_deadline(fd, buf, deadline)
+                    .unwrap()
+                    .read()
+                    .await
+            }
+        }
+    }
+
+    /// Write with a deadline.
+    /// If the write is not finished before the deadline, it will be canceled and
+    /// an error with kind `TimedOut` is returned along with the buffer.
+    ///
+    /// In uring impl, a linked timeout is attached to the op so the kernel will
+    /// cancel it on expiry; in epoll impl, the timer is required to be enabled.
+    pub async fn write_with_deadline<T: IoBuf>(
+        &mut self,
+        buf: T,
+        deadline: Instant,
+    ) -> BufResult<usize, T> {
+        let op = Op::send_with_deadline(self.fd.clone(), buf, deadline).unwrap();
+        op.write().await
+    }
+
+    /// Set the read timeout, `None` means reads never time out.
+    ///
+    /// Reads taking longer fail with an error of kind `TimedOut`. The timeout
+    /// is also set as `SO_RCVTIMEO` on the socket. In uring impl, a linked
+    /// timeout is attached to each read; in epoll impl, the timer is required
+    /// to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `InvalidInput` is returned for a zero duration.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeouts.set_read(&self.fd, timeout)
+    }
+
+    /// Returns the read timeout.
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(self.timeouts.read())
+    }
+
+    /// Set the write timeout, `None` means writes never time out.
+    ///
+    /// Writes taking longer fail with an error of kind `TimedOut`. The timeout
+    /// is also set as `SO_SNDTIMEO` on the socket. In uring impl, a linked
+    /// timeout is attached to each write; in epoll impl, the timer is required
+    /// to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `InvalidInput` is returned for a zero duration.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeouts.set_write(&self.fd, timeout)
+    }
+
+    /// Returns the write timeout.
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(self.timeouts.write())
+    }
+
+    /// Splice up to `len` bytes from the socket into the pipe without copying
+    /// them to user space. Returns the number of bytes moved, 0 means EOF.
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn splice_to(
+        &mut self,
+        pipe: &mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
+    ) -> io::Result<u32> {
+        Op::splice_to_pipe(&self.fd, &pipe.fd, len, flags.bits())?
+            .splice()
+            .await
+    }
+
+    /// Splice up to `len` bytes from the pipe into the socket without copying
+    /// them to user space. Returns the number of bytes moved.
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn splice_from(
+        &mut self,
+        pipe: &mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
+    ) -> io::Result<u32> {
+        Op::splice_from_pipe(&pipe.fd, &self.fd, len, flags.bits())?
+            .splice()
+            .await
+    }
 }
 
 impl AsReadFd for TcpStream {
@@ -310,6 +736,23 @@ impl AsRawSocket for TcpStream {
     }
 }
 
+// Wait for `fut`, failing with `TimedOut` error once `deadline` is exceeded.
+async fn until(
+    deadline: Option<Instant>,
+    fut: impl Future<Output = io::Result<()>>,
+) -> io::Result<()> {
+    match deadline {
+        None => fut.await,
+        Some(deadline) => {
+            crate::time::timeout(deadline.saturating_duration_since(Instant::now()), fut)
+                .await
+                .map_err(|_| {
+                    io::Error::new(io::ErrorKind::TimedOut, "operation deadline exceeded")
+                })?
+        }
+    }
+}
+
 impl std::fmt::Debug for TcpStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TcpStream").field("fd", &self.fd).finish()
@@ -319,15 +762,37 @@ impl std::fmt::Debug for TcpStream {
 impl AsyncWriteRent for TcpStream {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the write operation
-        let op = Op::send(self.fd.clone(), buf).unwrap();
-        op.write()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.write_deadline();
+        async move {
+            // Submit the write operation
+            match deadline {
+                None => Op::send(fd, buf).unwrap().write().await,
+                Some(deadline) => {
+                    Op::send_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .write()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
     fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> impl Future<Output = BufResult<usize, T>> {
-        let op = Op::writev(&self.fd, buf_vec).unwrap();
-        op.write()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.write_deadline();
+        async move {
+            match deadline {
+                None => Op::writev(&fd, buf_vec).unwrap().write().await,
+                Some(deadline) => {
+                    Op::writev_with_deadline(&fd, buf_vec, deadline)
+                        .unwrap()
+                        .write()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
@@ -364,9 +829,18 @@ impl CancelableAsyncWriteRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.write().await
+        match self.timeouts.write_deadline() {
+            None => {
+                let op = Op::send(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+            Some(deadline) => {
+                let op = Op::send_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+        }
     }
 
     #[inline]
@@ -381,9 +855,18 @@ impl CancelableAsyncWriteRent for TcpStream {
             return (Err(operation_canceled()), buf_vec);
         }
 
-        let op = Op::writev(&fd, buf_vec).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.write().await
+        match self.timeouts.write_deadline() {
+            None => {
+                let op = Op::writev(&fd, buf_vec).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+            Some(deadline) => {
+                let op = Op::writev_with_deadline(&fd, buf_vec, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+        }
     }
 
     #[inline]
@@ -410,16 +893,38 @@ impl CancelableAsyncWriteRent for TcpStream {
 impl AsyncReadRent for TcpStream {
     #[inline]
     fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::recv(self.fd.clone(), buf).unwrap();
-        op.read()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.read_deadline();
+        async move {
+            // Submit the read operation
+            match deadline {
+                None => Op::recv(fd, buf).unwrap().read().await,
+                Some(deadline) => {
+                    Op::recv_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .read()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
     fn readv<T: IoVecBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::readv(self.fd.clone(), buf).unwrap();
-        op.read()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.read_deadline();
+        async move {
+            // Submit the read operation
+            match deadline {
+                None => Op::readv(fd, buf).unwrap().read().await,
+                Some(deadline) => {
+                    Op::readv_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .read()
+                        .await
+                }
+            }
+        }
     }
 }
 
@@ -436,9 +941,18 @@ impl CancelableAsyncReadRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.read().await
+        match self.timeouts.read_deadline() {
+            None => {
+                let op = Op::recv(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+            Some(deadline) => {
+                let op = Op::recv_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+        }
     }
 
     #[inline]
@@ -453,9 +967,18 @@ impl CancelableAsyncReadRent for TcpStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::readv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.read().await
+        match self.timeouts.read_deadline() {
+            None => {
+                let op = Op::readv(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+            Some(deadline) => {
+                let op = Op::readv_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+        }
     }
 }
 
@@ -609,25 +1132,12 @@ impl StreamMeta {
         self.socket.as_ref().unwrap().set_nodelay(no_delay)
     }
 
-    #[allow(unused_variables)]
-    fn set_tcp_keepalive(
-        &self,
-        time: Option<Duration>,
-        interval: Option<Duration>,
-        retries: Option<u32>,
-    ) -> io::Result<()> {
-        let mut t = socket2::TcpKeepalive::new();
-        if let Some(time) = time {
-            t = t.with_time(time)
-        }
-        if let Some(interval) = interval {
-            t = t.with_interval(interval)
-        }
-        #[cfg(unix)]
-        if let Some(retries) = retries {
-            t = t.with_retries(retries)
-        }
-        self.socket.as_ref().unwrap().set_tcp_keepalive(&t)
+    fn set_keepalive(&self, keepalive: Option<super::KeepAlive>) -> io::Result<()> {
+        super::keepalive::set_keepalive(self.socket.as_ref().unwrap(), keepalive)
+    }
+
+    fn keepalive(&self) -> io::Result<Option<super::KeepAlive>> {
+        super::keepalive::keepalive(self.socket.as_ref().unwrap())
     }
 
     #[cfg(feature = "zero-copy")]
@@ -179,6 +179,20 @@ impl TcpStreamPoll {
     ) -> io::Result<()> {
         self.0.set_tcp_keepalive(time, interval, retries)
     }
+
+    /// Enable TCP keepalive with `keepalive` parameters on this socket, or
+    /// disable it with `None`.
+    #[inline]
+    pub fn set_keepalive(&self, keepalive: Option<crate::net::KeepAlive>) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Get the TCP keepalive parameters of this socket, `None` if keepalive
+    /// is disabled.
+    #[inline]
+    pub fn keepalive(&self) -> io::Result<Option<crate::net::KeepAlive>> {
+        self.0.keepalive()
+    }
 }
 
 #[cfg(unix)]
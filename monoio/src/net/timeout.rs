@@ -0,0 +1,79 @@
+use std::{
+    cell::Cell,
+    io,
+    mem::ManuallyDrop,
+    time::{Duration, Instant},
+};
+
+use crate::driver::shared_fd::SharedFd;
+
+/// Read and write timeouts of a stream.
+///
+/// They are set as `SO_RCVTIMEO` and `SO_SNDTIMEO`, so they stay with the
+/// socket when it is converted into a std one. Since these options have no
+/// effect on non-blocking io, each read and write also gets a deadline: a
+/// linked timeout with uring driver, a timer with legacy driver.
+#[derive(Debug, Default)]
+pub(crate) struct Timeouts {
+    read: Cell<Option<Duration>>,
+    write: Cell<Option<Duration>>,
+}
+
+impl Timeouts {
+    pub(crate) fn read(&self) -> Option<Duration> {
+        self.read.get()
+    }
+
+    pub(crate) fn write(&self) -> Option<Duration> {
+        self.write.get()
+    }
+
+    /// Deadline of a read starting now.
+    pub(crate) fn read_deadline(&self) -> Option<Instant> {
+        self.read.get().and_then(|t| Instant::now().checked_add(t))
+    }
+
+    /// Deadline of a write starting now.
+    pub(crate) fn write_deadline(&self) -> Option<Instant> {
+        self.write.get().and_then(|t| Instant::now().checked_add(t))
+    }
+
+    pub(crate) fn set_read(&self, fd: &SharedFd, timeout: Option<Duration>) -> io::Result<()> {
+        check(timeout)?;
+        socket(fd).set_read_timeout(timeout)?;
+        self.read.set(timeout);
+        Ok(())
+    }
+
+    pub(crate) fn set_write(&self, fd: &SharedFd, timeout: Option<Duration>) -> io::Result<()> {
+        check(timeout)?;
+        socket(fd).set_write_timeout(timeout)?;
+        self.write.set(timeout);
+        Ok(())
+    }
+}
+
+// Same as std, a zero timeout would mean no timeout to the kernel.
+fn check(timeout: Option<Duration>) -> io::Result<()> {
+    if timeout == Some(Duration::ZERO) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot set a 0 duration timeout",
+        ));
+    }
+    Ok(())
+}
+
+fn socket(fd: &SharedFd) -> ManuallyDrop<socket2::Socket> {
+    #[cfg(unix)]
+    let socket = unsafe {
+        use std::os::unix::prelude::FromRawFd;
+        socket2::Socket::from_raw_fd(fd.raw_fd())
+    };
+    #[cfg(windows)]
+    let socket = unsafe {
+        use std::os::windows::prelude::FromRawSocket;
+        socket2::Socket::from_raw_socket(fd.raw_socket())
+    };
+    ManuallyDrop::new(socket)
+}
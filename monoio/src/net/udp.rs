@@ -6,15 +6,20 @@ use std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd};
 use std::os::windows::prelude::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
 use std::{
     io,
-    net::{SocketAddr, ToSocketAddrs},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
 };
 
+#[cfg(target_os = "linux")]
+use crate::net::RecvMsgStream;
 use crate::{
     buf::{IoBuf, IoBufMut},
     driver::{op::Op, shared_fd::SharedFd},
     io::{operation_canceled, CancelHandle, Split},
 };
 
+mod flow;
+pub use flow::{FlowOpts, UdpFlow, UdpListener};
+
 /// A UDP socket.
 ///
 /// After creating a `UdpSocket` by [`bind`]ing it to a socket address, data can be
@@ -147,6 +152,255 @@ impl UdpSocket {
         op.read().await
     }
 
+    /// Sends a batch of datagrams, each to its address. On success, returns
+    /// the number of datagrams sent from the start of the batch, which may
+    /// be less than its length.
+    ///
+    /// On the legacy driver the batch is sent with a single `sendmmsg` call.
+    /// On io_uring the first datagram is sent by the ring, and as much of
+    /// the rest as fits in the socket buffer with a non-blocking `sendmmsg`.
+    #[cfg(target_os = "linux")]
+    pub async fn send_to_batch<T: IoBuf>(
+        &self,
+        datagrams: Vec<(T, SocketAddr)>,
+    ) -> crate::BufResult<usize, Vec<(T, SocketAddr)>> {
+        if datagrams.is_empty() {
+            return (Ok(0), datagrams);
+        }
+        let op = Op::send_mmsg(self.fd.clone(), datagrams).unwrap();
+        op.wait().await
+    }
+
+    /// Receives a batch of datagrams, one per buffer. Waits for at least
+    /// one datagram, then fills the following buffers with the datagrams
+    /// already queued. On success, returns the length and origin of each
+    /// datagram received, in the order of the buffers.
+    #[cfg(target_os = "linux")]
+    pub async fn recv_from_batch<T: IoBufMut>(
+        &self,
+        bufs: Vec<T>,
+    ) -> crate::BufResult<Vec<(usize, SocketAddr)>, Vec<T>> {
+        if bufs.is_empty() {
+            return (Ok(Vec::new()), bufs);
+        }
+        let op = Op::recv_mmsg(self.fd.clone(), bufs).unwrap();
+        op.wait().await
+    }
+
+    /// Returns a stream of the datagrams received by the socket, with their
+    /// control messages and the address of their sender.
+    ///
+    /// With io_uring, a multishot recvmsg (kernel 6.0+) keeps receiving into
+    /// `buffers` buffers of `buffer_size` bytes, provided to the kernel with
+    /// a buffer ring. A buffer is held by each received datagram until it is
+    /// dropped, the stream waits for one when all are held. Otherwise, or on
+    /// older kernels, each datagram is received with a recvmsg call.
+    ///
+    /// Each buffer holds a header, the address of the sender, up to
+    /// `control_len` bytes of control messages and the payload, longer
+    /// datagrams are truncated.
+    #[cfg(target_os = "linux")]
+    pub fn recv_msg_multi(
+        &self,
+        buffers: u16,
+        buffer_size: usize,
+        control_len: usize,
+    ) -> io::Result<RecvMsgStream<SocketAddr>> {
+        RecvMsgStream::new(&self.fd, buffers, buffer_size, control_len)
+    }
+
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    #[cfg(unix)]
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.ttl())
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_ttl(ttl))
+    }
+
+    /// Get the value of the `SO_SNDBUF` option on this socket.
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.send_buffer_size())
+    }
+
+    /// Set the value of the `SO_SNDBUF` option on this socket.
+    pub fn set_send_buffer_size(&self, send_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_send_buffer_size(send_buffer_size))
+    }
+
+    /// Get the value of the `SO_RCVBUF` option on this socket.
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        crate::net::with_socket(self, |s| s.recv_buffer_size())
+    }
+
+    /// Set the value of the `SO_RCVBUF` option on this socket.
+    pub fn set_recv_buffer_size(&self, recv_buffer_size: usize) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_recv_buffer_size(recv_buffer_size))
+    }
+
+    /// Bind the socket to the network interface `interface`, or unbind it
+    /// with `None`. See [`TcpSocket::bind_device`](crate::net::TcpSocket::bind_device).
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "ios",
+        target_os = "macos"
+    ))]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        crate::net::with_socket(self, |s| crate::net::bind_device(s, interface))
+    }
+
+    /// Returns the name of the network interface the socket is bound to.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn device(&self) -> io::Result<Option<String>> {
+        crate::net::with_socket(self, crate::net::device)
+    }
+
+    /// Get the value of the `IP_TOS` option on this socket.
+    pub fn tos(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tos())
+    }
+
+    /// Set the value of the `IP_TOS` option on this socket.
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tos(tos))
+    }
+
+    /// Get the value of the `IPV6_TCLASS` option on this socket.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn tclass_v6(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.tclass_v6())
+    }
+
+    /// Set the value of the `IPV6_TCLASS` option on this socket.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "macos"
+    ))]
+    pub fn set_tclass_v6(&self, tclass_v6: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_tclass_v6(tclass_v6))
+    }
+
+    /// Join the IPv4 multicast group `multiaddr` on the interface with the
+    /// address `interface`, `Ipv4Addr::UNSPECIFIED` lets the system choose.
+    pub fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.join_multicast_v4(multiaddr, interface))
+    }
+
+    /// Leave the IPv4 multicast group `multiaddr` on the interface with the
+    /// address `interface`.
+    pub fn leave_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.leave_multicast_v4(multiaddr, interface))
+    }
+
+    /// Join the IPv6 multicast group `multiaddr` on the interface of index
+    /// `interface`, 0 lets the system choose.
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.join_multicast_v6(multiaddr, interface))
+    }
+
+    /// Leave the IPv6 multicast group `multiaddr` on the interface of index
+    /// `interface`.
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.leave_multicast_v6(multiaddr, interface))
+    }
+
+    /// Get the value of the `IP_MULTICAST_LOOP` option on this socket.
+    pub fn multicast_loop_v4(&self) -> io::Result<bool> {
+        crate::net::with_socket(self, |s| s.multicast_loop_v4())
+    }
+
+    /// Set the value of the `IP_MULTICAST_LOOP` option on this socket, whether
+    /// multicast packets sent are looped back to the local sockets.
+    pub fn set_multicast_loop_v4(&self, multicast_loop_v4: bool) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_loop_v4(multicast_loop_v4))
+    }
+
+    /// Get the value of the `IPV6_MULTICAST_LOOP` option on this socket.
+    pub fn multicast_loop_v6(&self) -> io::Result<bool> {
+        crate::net::with_socket(self, |s| s.multicast_loop_v6())
+    }
+
+    /// Set the value of the `IPV6_MULTICAST_LOOP` option on this socket,
+    /// whether multicast packets sent are looped back to the local sockets.
+    pub fn set_multicast_loop_v6(&self, multicast_loop_v6: bool) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_loop_v6(multicast_loop_v6))
+    }
+
+    /// Get the value of the `IP_MULTICAST_TTL` option on this socket.
+    pub fn multicast_ttl_v4(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.multicast_ttl_v4())
+    }
+
+    /// Set the value of the `IP_MULTICAST_TTL` option on this socket, 1 keeps
+    /// multicast packets on the local network.
+    pub fn set_multicast_ttl_v4(&self, multicast_ttl_v4: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_ttl_v4(multicast_ttl_v4))
+    }
+
+    /// Get the value of the `IPV6_MULTICAST_HOPS` option on this socket.
+    pub fn multicast_hops_v6(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.multicast_hops_v6())
+    }
+
+    /// Set the value of the `IPV6_MULTICAST_HOPS` option on this socket.
+    pub fn set_multicast_hops_v6(&self, multicast_hops_v6: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_hops_v6(multicast_hops_v6))
+    }
+
+    /// Get the value of the `IP_MULTICAST_IF` option on this socket.
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        crate::net::with_socket(self, |s| s.multicast_if_v4())
+    }
+
+    /// Set the value of the `IP_MULTICAST_IF` option on this socket, the
+    /// address of the interface multicast packets are sent from.
+    pub fn set_multicast_if_v4(&self, interface: &Ipv4Addr) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_if_v4(interface))
+    }
+
+    /// Get the value of the `IPV6_MULTICAST_IF` option on this socket.
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        crate::net::with_socket(self, |s| s.multicast_if_v6())
+    }
+
+    /// Set the value of the `IPV6_MULTICAST_IF` option on this socket, the
+    /// index of the interface multicast packets are sent from.
+    pub fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        crate::net::with_socket(self, |s| s.set_multicast_if_v6(interface))
+    }
+
     /// Creates new `UdpSocket` from a `std::net::UdpSocket`.
     pub fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
         #[cfg(unix)]
@@ -203,6 +457,28 @@ impl UdpSocket {
         r
     }
 
+    /// Get the value of the `SO_INCOMING_CPU` option on this socket, `None`
+    /// if it is not set.
+    #[cfg(target_os = "linux")]
+    pub fn incoming_cpu(&self) -> io::Result<Option<usize>> {
+        crate::net::steering::incoming_cpu(self.as_raw_fd())
+    }
+
+    /// Set value for the `SO_INCOMING_CPU` option on this socket, it is
+    /// preferred in its `SO_REUSEPORT` group for packets processed on `cpu`.
+    #[cfg(target_os = "linux")]
+    pub fn set_incoming_cpu(&self, cpu: usize) -> io::Result<()> {
+        crate::net::steering::set_incoming_cpu(self.fd.as_raw_fd(), cpu)
+    }
+
+    /// Steer packets to the socket at index `cpu % sockets` of the
+    /// `SO_REUSEPORT` group of this socket, with a BPF program attached to
+    /// the group. Sockets are indexed in the order they are bound.
+    #[cfg(target_os = "linux")]
+    pub fn attach_cpu_steering(&self, sockets: u32) -> io::Result<()> {
+        crate::net::steering::attach_cpu_steering(self.fd.as_raw_fd(), sockets)
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
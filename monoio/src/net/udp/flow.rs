@@ -0,0 +1,420 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    future::poll_fn,
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    rc::{Rc, Weak},
+    task::{Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use super::UdpSocket;
+use crate::{
+    buf::IoBuf,
+    io::{is_canceled, CancelHandle, Canceller},
+};
+
+/// Options of a [`UdpListener`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct FlowOpts {
+    /// Max number of flows waiting to be accepted. Datagrams opening a new
+    /// flow are dropped when it is reached.
+    pub backlog: usize,
+    /// Max number of datagrams queued per flow. Datagrams are dropped when
+    /// it is reached.
+    pub queue_size: usize,
+    /// Max size of a received datagram, longer ones are truncated.
+    pub max_datagram_size: usize,
+    /// Expire flows without activity for this duration, or None to keep
+    /// them until they are dropped.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for FlowOpts {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlowOpts {
+    /// Create a default FlowOpts.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            backlog: 128,
+            queue_size: 64,
+            max_datagram_size: 65535,
+            idle_timeout: None,
+        }
+    }
+
+    /// Specify backlog
+    #[must_use]
+    #[inline]
+    pub fn backlog(mut self, backlog: usize) -> Self {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Specify the per flow queue size
+    #[must_use]
+    #[inline]
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size;
+        self
+    }
+
+    /// Specify the max datagram size
+    #[must_use]
+    #[inline]
+    pub fn max_datagram_size(mut self, max_datagram_size: usize) -> Self {
+        self.max_datagram_size = max_datagram_size;
+        self
+    }
+
+    /// Specify the idle timeout
+    #[must_use]
+    #[inline]
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+}
+
+/// A UDP server demultiplexing datagrams by peer address into flows.
+///
+/// A background task receives the datagrams of the socket. The first
+/// datagram of an unknown peer opens a new [`UdpFlow`], returned by
+/// [`accept`](Self::accept), and the following ones are queued to it. This
+/// gives a connection-like model to protocols built on UDP.
+///
+/// A flow ends when it is dropped, or when it expires after
+/// [`idle_timeout`](FlowOpts::idle_timeout), which requires the timer. A
+/// datagram from its peer then opens a new flow. Dropping the listener stops
+/// receiving, but the flows can still send.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::net::udp::UdpListener;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = UdpListener::bind("127.0.0.1:4433")?;
+///     loop {
+///         let (flow, _peer) = listener.accept().await?;
+///         monoio::spawn(async move {
+///             // echo every datagram of the flow
+///             while let Some(datagram) = flow.recv().await {
+///                 let _ = flow.send(datagram).await;
+///             }
+///         });
+///     }
+/// }
+/// ```
+pub struct UdpListener {
+    socket: Rc<UdpSocket>,
+    demux: Rc<Demux>,
+    canceller: Canceller,
+}
+
+#[derive(Debug)]
+struct Demux {
+    opts: FlowOpts,
+    flows: RefCell<HashMap<SocketAddr, Rc<Flow>>>,
+    pending: RefCell<VecDeque<Rc<Flow>>>,
+    accept_waker: RefCell<Option<Waker>>,
+    error: RefCell<Option<io::Error>>,
+    closed: Cell<bool>,
+}
+
+#[derive(Debug)]
+struct Flow {
+    peer: SocketAddr,
+    queue: RefCell<VecDeque<Vec<u8>>>,
+    waker: RefCell<Option<Waker>>,
+    closed: Cell<bool>,
+    last_active: Cell<Instant>,
+}
+
+impl UdpListener {
+    /// Bind a socket to the given address with the default options.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self::from_socket(
+            UdpSocket::bind(addr)?,
+            FlowOpts::default(),
+        ))
+    }
+
+    /// Demultiplex the datagrams received by `socket`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn from_socket(socket: UdpSocket, opts: FlowOpts) -> Self {
+        let socket = Rc::new(socket);
+        let demux = Rc::new(Demux {
+            opts,
+            flows: RefCell::new(HashMap::new()),
+            pending: RefCell::new(VecDeque::new()),
+            accept_waker: RefCell::new(None),
+            error: RefCell::new(None),
+            closed: Cell::new(false),
+        });
+        let canceller = Canceller::new();
+        crate::spawn(pump(
+            socket.clone(),
+            Rc::downgrade(&demux),
+            canceller.handle(),
+        ));
+        if let Some(timeout) = opts.idle_timeout {
+            crate::spawn(expire(Rc::downgrade(&demux), timeout));
+        }
+        Self {
+            socket,
+            demux,
+            canceller,
+        }
+    }
+
+    /// Wait for a datagram from a new peer and return its flow.
+    ///
+    /// Returns an error if receiving from the socket failed.
+    pub async fn accept(&self) -> io::Result<(UdpFlow, SocketAddr)> {
+        let demux = &self.demux;
+        poll_fn(|cx| {
+            if let Some(flow) = demux.pending.borrow_mut().pop_front() {
+                let peer = flow.peer;
+                let flow = UdpFlow {
+                    socket: self.socket.clone(),
+                    demux: Rc::downgrade(demux),
+                    flow,
+                };
+                return Poll::Ready(Ok((flow, peer)));
+            }
+            if let Some(e) = demux.error.borrow().as_ref() {
+                return Poll::Ready(Err(io::Error::new(e.kind(), e.to_string())));
+            }
+            *demux.accept_waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Returns the local address of the socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns the number of live flows, including the ones not accepted
+    /// yet.
+    pub fn flows(&self) -> usize {
+        self.demux.flows.borrow().len()
+    }
+}
+
+impl std::fmt::Debug for UdpListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UdpListener")
+            .field("socket", &self.socket)
+            .field("flows", &self.flows())
+            .finish()
+    }
+}
+
+impl Drop for UdpListener {
+    fn drop(&mut self) {
+        std::mem::take(&mut self.canceller).cancel();
+        self.demux.close();
+    }
+}
+
+impl Demux {
+    fn dispatch(&self, peer: SocketAddr, datagram: Vec<u8>) {
+        let mut flows = self.flows.borrow_mut();
+        let flow = match flows.get(&peer) {
+            Some(flow) => flow.clone(),
+            None => {
+                let mut pending = self.pending.borrow_mut();
+                if pending.len() >= self.opts.backlog {
+                    return;
+                }
+                let flow = Rc::new(Flow {
+                    peer,
+                    queue: RefCell::new(VecDeque::new()),
+                    waker: RefCell::new(None),
+                    closed: Cell::new(false),
+                    last_active: Cell::new(Instant::now()),
+                });
+                flows.insert(peer, flow.clone());
+                pending.push_back(flow.clone());
+                if let Some(waker) = self.accept_waker.borrow_mut().take() {
+                    waker.wake();
+                }
+                flow
+            }
+        };
+        drop(flows);
+        flow.last_active.set(Instant::now());
+        let mut queue = flow.queue.borrow_mut();
+        if queue.len() < self.opts.queue_size {
+            queue.push_back(datagram);
+            drop(queue);
+            flow.wake();
+        }
+    }
+
+    fn remove(&self, flow: &Rc<Flow>) {
+        let mut flows = self.flows.borrow_mut();
+        if flows.get(&flow.peer).is_some_and(|f| Rc::ptr_eq(f, flow)) {
+            flows.remove(&flow.peer);
+        }
+        drop(flows);
+        self.pending.borrow_mut().retain(|f| !Rc::ptr_eq(f, flow));
+        flow.closed.set(true);
+        flow.wake();
+    }
+
+    fn close(&self) {
+        self.closed.set(true);
+        for (_, flow) in self.flows.borrow_mut().drain() {
+            flow.closed.set(true);
+            flow.wake();
+        }
+        self.pending.borrow_mut().clear();
+        if let Some(waker) = self.accept_waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Flow {
+    fn wake(&self) {
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+async fn pump(socket: Rc<UdpSocket>, demux: Weak<Demux>, cancel: CancelHandle) {
+    let size = match demux.upgrade() {
+        Some(demux) => demux.opts.max_datagram_size,
+        None => return,
+    };
+    let mut buf = Vec::with_capacity(size);
+    loop {
+        let (res, b) = socket.cancelable_recv_from(buf, cancel.clone()).await;
+        buf = b;
+        let Some(demux) = demux.upgrade() else {
+            return;
+        };
+        match res {
+            Ok((n, peer)) => demux.dispatch(peer, buf[..n].to_vec()),
+            Err(e) if is_canceled(&e) || demux.closed.get() => return,
+            // ICMP errors of previous sends are reported on receive.
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::ConnectionRefused
+                        | io::ErrorKind::ConnectionReset
+                        | io::ErrorKind::Interrupted
+                        | io::ErrorKind::WouldBlock
+                ) => {}
+            Err(e) => {
+                *demux.error.borrow_mut() = Some(e);
+                demux.close();
+                return;
+            }
+        }
+    }
+}
+
+async fn expire(demux: Weak<Demux>, timeout: Duration) {
+    let interval = (timeout / 4).max(Duration::from_millis(1));
+    loop {
+        crate::time::sleep(interval).await;
+        let Some(demux) = demux.upgrade() else {
+            return;
+        };
+        if demux.closed.get() {
+            return;
+        }
+        let now = Instant::now();
+        let expired: Vec<_> = demux
+            .flows
+            .borrow()
+            .values()
+            .filter(|f| now.duration_since(f.last_active.get()) >= timeout)
+            .cloned()
+            .collect();
+        for flow in expired {
+            demux.remove(&flow);
+        }
+    }
+}
+
+/// A flow of datagrams exchanged with one peer, accepted by a
+/// [`UdpListener`].
+#[derive(Debug)]
+pub struct UdpFlow {
+    socket: Rc<UdpSocket>,
+    demux: Weak<Demux>,
+    flow: Rc<Flow>,
+}
+
+impl UdpFlow {
+    /// Wait for the next datagram of the peer.
+    ///
+    /// Returns `None` when the flow expired or the listener is dropped.
+    pub async fn recv(&self) -> Option<Vec<u8>> {
+        poll_fn(|cx| {
+            if let Some(datagram) = self.flow.queue.borrow_mut().pop_front() {
+                return Poll::Ready(Some(datagram));
+            }
+            if self.flow.closed.get() {
+                return Poll::Ready(None);
+            }
+            *self.flow.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Returns the next queued datagram without waiting.
+    pub fn try_recv(&self) -> Option<Vec<u8>> {
+        self.flow.queue.borrow_mut().pop_front()
+    }
+
+    /// Send a datagram to the peer.
+    pub async fn send<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
+        self.flow.last_active.set(Instant::now());
+        self.socket.send_to(buf, self.flow.peer).await
+    }
+
+    /// Returns the address of the peer.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.flow.peer
+    }
+
+    /// Returns the local address of the socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns true if the flow expired or the listener is dropped. Queued
+    /// datagrams can still be received.
+    pub fn is_closed(&self) -> bool {
+        self.flow.closed.get()
+    }
+}
+
+impl Drop for UdpFlow {
+    fn drop(&mut self) {
+        if let Some(demux) = self.demux.upgrade() {
+            demux.remove(&self.flow);
+        }
+    }
+}
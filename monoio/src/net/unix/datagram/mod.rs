@@ -4,7 +4,7 @@ use std::{
     io,
     os::unix::{
         net::UnixDatagram as StdUnixDatagram,
-        prelude::{AsRawFd, IntoRawFd, RawFd},
+        prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
     },
     path::Path,
 };
@@ -13,6 +13,8 @@ use super::{
     socket_addr::{local_addr, pair, peer_addr, socket_addr},
     SocketAddr,
 };
+#[cfg(target_os = "linux")]
+use crate::net::RecvMsgStream;
 use crate::{
     buf::{IoBuf, IoBufMut},
     driver::{op::Op, shared_fd::SharedFd},
@@ -30,8 +32,30 @@ impl UnixDatagram {
     }
 
     /// Creates a Unix datagram socket bound to the given path.
+    ///
+    /// On linux, a path starting with a null byte is bound in the abstract
+    /// namespace.
     pub fn bind<P: AsRef<Path>>(path: P) -> io::Result<Self> {
-        StdUnixDatagram::bind(path).and_then(Self::from_std)
+        let (addr, addr_len) = socket_addr(path.as_ref())?;
+        Self::bind_raw(addr, addr_len)
+    }
+
+    /// Creates a Unix datagram socket bound to `addr`, like an abstract
+    /// address created with [`SocketAddr::from_abstract_name`].
+    pub fn bind_addr(addr: SocketAddr) -> io::Result<Self> {
+        let (addr, addr_len) = addr.into_parts();
+        Self::bind_raw(addr, addr_len)
+    }
+
+    fn bind_raw(addr: libc::sockaddr_un, addr_len: libc::socklen_t) -> io::Result<Self> {
+        let socket = new_socket(libc::AF_UNIX, libc::SOCK_DGRAM)?;
+        let datagram = unsafe { StdUnixDatagram::from_raw_fd(socket) };
+        crate::syscall!(bind(
+            socket,
+            &addr as *const libc::sockaddr_un as *const libc::sockaddr,
+            addr_len
+        ))?;
+        Self::from_std(datagram)
     }
 
     /// Creates a new `UnixDatagram` which is not bound to any address.
@@ -57,6 +81,18 @@ impl UnixDatagram {
         Self::inner_connect(addr, addr_len).await
     }
 
+    /// Connects this socket to the specified path, e.g. to receive replies on a
+    /// bound socket. Only datagrams from that address are received afterwards.
+    pub fn connect_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let (addr, addr_len) = socket_addr(path.as_ref())?;
+        crate::syscall!(connect(
+            self.as_raw_fd(),
+            &addr as *const libc::sockaddr_un as *const libc::sockaddr,
+            addr_len
+        ))
+        .map(|_| ())
+    }
+
     #[inline(always)]
     async fn inner_connect(
         sockaddr: libc::sockaddr_un,
@@ -70,6 +106,27 @@ impl UnixDatagram {
         Ok(Self::from_shared_fd(completion.data.fd))
     }
 
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
     /// Creates new `UnixDatagram` from a `std::os::unix::net::UnixDatagram`.
     pub fn from_std(datagram: StdUnixDatagram) -> io::Result<Self> {
         match SharedFd::new::<false>(datagram.as_raw_fd()) {
@@ -141,6 +198,18 @@ impl UnixDatagram {
         op.wait().await
     }
 
+    /// Sends data on the socket to the given address, like the origin returned
+    /// by [`recv_from`](Self::recv_from). On success, returns the number of bytes
+    /// written.
+    pub async fn send_to_addr<T: IoBuf>(
+        &self,
+        buf: T,
+        addr: SocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::send_msg_unix(self.fd.clone(), buf, Some(addr)).unwrap();
+        op.wait().await
+    }
+
     /// Receives a single datagram message on the socket. On success, returns the number
     /// of bytes read and the origin.
     pub async fn recv_from<T: IoBufMut>(&self, buf: T) -> crate::BufResult<(usize, SocketAddr), T> {
@@ -160,6 +229,28 @@ impl UnixDatagram {
         let op = Op::recv(self.fd.clone(), buf).unwrap();
         op.read().await
     }
+
+    /// Returns a stream of the datagrams received by the socket, with their
+    /// control messages and the address of their sender.
+    ///
+    /// With io_uring, a multishot recvmsg (kernel 6.0+) keeps receiving into
+    /// `buffers` buffers of `buffer_size` bytes, provided to the kernel with
+    /// a buffer ring. A buffer is held by each received datagram until it is
+    /// dropped, the stream waits for one when all are held. Otherwise, or on
+    /// older kernels, each datagram is received with a recvmsg call.
+    ///
+    /// Each buffer holds a header, the address of the sender, up to
+    /// `control_len` bytes of control messages and the payload, longer
+    /// datagrams are truncated.
+    #[cfg(target_os = "linux")]
+    pub fn recv_msg_multi(
+        &self,
+        buffers: u16,
+        buffer_size: usize,
+        control_len: usize,
+    ) -> io::Result<RecvMsgStream<SocketAddr>> {
+        RecvMsgStream::new(&self.fd, buffers, buffer_size, control_len)
+    }
 }
 
 impl AsRawFd for UnixDatagram {
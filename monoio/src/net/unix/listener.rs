@@ -5,7 +5,10 @@ use std::{
     path::Path,
 };
 
-use super::{socket_addr::SocketAddr, UnixStream};
+use super::{
+    socket_addr::{socket_addr, SocketAddr},
+    UnixStream,
+};
 use crate::{
     driver::{op::Op, shared_fd::SharedFd},
     io::{stream::Stream, CancelHandle},
@@ -29,16 +32,39 @@ impl UnixListener {
 
     /// Creates a new `UnixListener` bound to the specified socket with custom
     /// config.
+    ///
+    /// On linux, a path starting with a null byte is bound in the abstract
+    /// namespace.
     pub fn bind_with_config<P: AsRef<Path>>(
         path: P,
         config: &ListenerOpts,
+    ) -> io::Result<UnixListener> {
+        let (addr, addr_len) = socket_addr(path.as_ref())?;
+        Self::bind_raw(addr, addr_len, config)
+    }
+
+    /// Creates a new `UnixListener` bound to `addr`, like an abstract address
+    /// created with [`SocketAddr::from_abstract_name`].
+    pub fn bind_addr(addr: SocketAddr, config: &ListenerOpts) -> io::Result<UnixListener> {
+        let (addr, addr_len) = addr.into_parts();
+        Self::bind_raw(addr, addr_len, config)
+    }
+
+    fn bind_raw(
+        addr: libc::sockaddr_un,
+        addr_len: libc::socklen_t,
+        config: &ListenerOpts,
     ) -> io::Result<UnixListener> {
         let sys_listener =
             socket2::Socket::new(socket2::Domain::UNIX, socket2::Type::STREAM, None)?;
-        let addr = socket2::SockAddr::unix(path)?;
 
         if config.reuse_port {
-            sys_listener.set_reuse_port(true)?;
+            // Unix sockets have no port to share, recent kernels reject the
+            // option.
+            match sys_listener.set_reuse_port(true) {
+                Err(e) if e.raw_os_error() == Some(libc::EOPNOTSUPP) => {}
+                res => res?,
+            }
         }
         if config.reuse_addr {
             sys_listener.set_reuse_address(true)?;
@@ -50,7 +76,11 @@ impl UnixListener {
             sys_listener.set_recv_buffer_size(recv_buf_size)?;
         }
 
-        sys_listener.bind(&addr)?;
+        crate::syscall!(bind(
+            sys_listener.as_raw_fd(),
+            &addr as *const libc::sockaddr_un as *const libc::sockaddr,
+            addr_len
+        ))?;
         sys_listener.listen(config.backlog)?;
 
         let fd = SharedFd::new::<false>(sys_listener.into_raw_fd())?;
@@ -88,6 +118,18 @@ impl UnixListener {
         Ok((stream, addr))
     }
 
+    /// Returns a stream of the accepted connections, implementing both the
+    /// monoio `Stream` and `futures_core::Stream`.
+    pub fn incoming(&self) -> crate::net::Incoming<'_, (UnixStream, SocketAddr)> {
+        crate::net::Incoming::new(move || self.accept())
+    }
+
+    /// Returns the select loop of a server over the accepted connections, a
+    /// shutdown signal and a periodic tick, see [`ServeLoop`](crate::net::ServeLoop).
+    pub fn serve_loop(&self) -> crate::net::ServeLoop<'_, (UnixStream, SocketAddr)> {
+        crate::net::ServeLoop::new(move |c| self.cancelable_accept(c))
+    }
+
     /// Cancelable accept
     pub async fn cancelable_accept(&self, c: CancelHandle) -> io::Result<(UnixStream, SocketAddr)> {
         use crate::io::operation_canceled;
@@ -133,6 +175,27 @@ impl UnixListener {
         op.wait().await
     }
 
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
     /// Creates new `UnixListener` from a `std::os::unix::net::UnixListener`.
     pub fn from_std(sys_listener: std::os::unix::net::UnixListener) -> io::Result<Self> {
         match SharedFd::new::<false>(sys_listener.as_raw_fd()) {
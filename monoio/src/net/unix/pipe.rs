@@ -1,14 +1,28 @@
-use std::{io, os::unix::prelude::RawFd};
+use std::{
+    io,
+    os::{
+        fd::OwnedFd,
+        unix::prelude::{AsRawFd, IntoRawFd, RawFd},
+    },
+};
 
 use crate::driver::shared_fd::SharedFd;
 
 /// Unix pipe.
+///
+/// Used as the intermediate buffer of zero-copy transfers, see
+/// `TcpStream::splice_to` and `TcpStream::splice_from`.
 pub struct Pipe {
-    #[allow(dead_code)]
     pub(crate) fd: SharedFd,
 }
 
 impl Pipe {
+    /// Create a new pair of pipe, returns the read end and the write end.
+    #[inline]
+    pub fn new() -> io::Result<(Pipe, Pipe)> {
+        new_pipe()
+    }
+
     pub(crate) fn from_shared_fd(fd: SharedFd) -> Self {
         Self { fd }
     }
@@ -16,6 +30,86 @@ impl Pipe {
     fn from_raw_fd(fd: RawFd) -> Self {
         Self::from_shared_fd(SharedFd::new_without_register(fd))
     }
+
+    fn from_owned_fd(fd: OwnedFd) -> io::Result<Self> {
+        // The legacy driver requires nonblocking pipes, like `new_pipe`.
+        #[cfg(target_os = "linux")]
+        if crate::driver::op::is_legacy() {
+            let flags = crate::syscall!(fcntl(fd.as_raw_fd(), libc::F_GETFL))?;
+            crate::syscall!(fcntl(
+                fd.as_raw_fd(),
+                libc::F_SETFL,
+                flags | libc::O_NONBLOCK
+            ))?;
+        }
+        Ok(Self::from_raw_fd(fd.into_raw_fd()))
+    }
+
+    /// Duplicate up to `len` bytes from this pipe, which must be a read end,
+    /// into the write end `dst`, without consuming them. The data can still
+    /// be read or spliced from this pipe afterwards, so a stream can be
+    /// mirrored to a second destination. Returns the number of bytes
+    /// duplicated, 0 means the write end of this pipe is closed and the pipe
+    /// is empty.
+    ///
+    /// With the legacy driver pipes are not polled, so an error with kind
+    /// `WouldBlock` is returned if this pipe is empty or `dst` is full.
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn tee(
+        &mut self,
+        dst: &mut Pipe,
+        len: u32,
+        flags: crate::io::SpliceFlags,
+    ) -> io::Result<u32> {
+        crate::driver::op::Op::tee(&self.fd, &dst.fd, len, flags.bits())?
+            .tee_result()
+            .await
+    }
+}
+
+macro_rules! impl_from_child_pipe {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<$ty> for Pipe {
+            type Error = io::Error;
+
+            /// Take over the pipe of a child process, so it can be spliced
+            /// from or into a socket without user space copies.
+            ///
+            /// With the legacy driver pipes are not polled, splicing returns
+            /// an error with kind `WouldBlock` when the pipe is not ready.
+            fn try_from(pipe: $ty) -> io::Result<Self> {
+                Pipe::from_owned_fd(pipe.into())
+            }
+        }
+    )*};
+}
+
+impl_from_child_pipe!(
+    std::process::ChildStdin,
+    std::process::ChildStdout,
+    std::process::ChildStderr
+);
+
+impl AsRawFd for Pipe {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
+impl IntoRawFd for Pipe {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.fd
+            .try_unwrap()
+            .expect("unexpected multiple reference to rawfd")
+    }
+}
+
+impl std::fmt::Debug for Pipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipe").field("fd", &self.fd).finish()
+    }
 }
 
 /// Create a new pair of pipe.
@@ -128,6 +128,18 @@ impl SocketAddr {
         }
     }
 
+    /// Creates an address in the abstract namespace, `name` is given without
+    /// the leading null byte.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_abstract_name<N: AsRef<[u8]>>(name: N) -> io::Result<SocketAddr> {
+        let name = name.as_ref();
+        let mut path = Vec::with_capacity(name.len() + 1);
+        path.push(0);
+        path.extend_from_slice(name);
+        let (sockaddr, socklen) = socket_addr(OsStr::from_bytes(&path).as_ref())?;
+        Ok(SocketAddr::from_parts(sockaddr, socklen))
+    }
+
     #[inline]
     pub(crate) fn as_ptr(&self) -> *const libc::sockaddr_un {
         &self.sockaddr as *const _
@@ -36,6 +36,20 @@ impl AsReadFd for UnixOwnedReadHalf {
     }
 }
 
+impl UnixOwnedWriteHalf {
+    /// Returns the remote address that this stream is connected to.
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        unsafe { &*self.0.get() }.peer_addr()
+    }
+
+    /// Returns the local address that this stream is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        unsafe { &*self.0.get() }.local_addr()
+    }
+}
+
 impl AsWriteFd for UnixOwnedWriteHalf {
     #[inline]
     fn as_writer_fd(&mut self) -> &SharedFdWrapper {
@@ -1,8 +1,9 @@
 use std::{
     future::Future,
     io::{self},
-    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
     path::Path,
+    time::{Duration, Instant},
 };
 
 use super::{
@@ -17,13 +18,16 @@ use crate::{
         operation_canceled, AsyncReadRent, AsyncWriteRent, CancelHandle, CancelableAsyncReadRent,
         CancelableAsyncWriteRent, Split,
     },
-    net::new_socket,
+    net::{new_socket, Timeouts},
     BufResult,
 };
+#[cfg(all(target_os = "linux", feature = "splice"))]
+use crate::{io::splice::SpliceFlags, net::Pipe};
 
 /// UnixStream
 pub struct UnixStream {
     pub(super) fd: SharedFd,
+    timeouts: Timeouts,
 }
 
 /// UnixStream is safe to split to two parts
@@ -31,34 +35,58 @@ unsafe impl Split for UnixStream {}
 
 impl UnixStream {
     pub(crate) fn from_shared_fd(fd: SharedFd) -> Self {
-        Self { fd }
+        Self {
+            fd,
+            timeouts: Timeouts::default(),
+        }
     }
 
     /// Connect UnixStream to a path.
+    ///
+    /// On linux, a path starting with a null byte is an abstract namespace
+    /// address.
     pub async fn connect<P: AsRef<Path>>(path: P) -> io::Result<Self> {
         let (addr, addr_len) = socket_addr(path.as_ref())?;
-        Self::inner_connect(addr, addr_len).await
+        Self::inner_connect(addr, addr_len, None).await
     }
 
     /// Connects the socket to an address.
     pub async fn connect_addr(addr: SocketAddr) -> io::Result<Self> {
         let (addr, addr_len) = addr.into_parts();
-        Self::inner_connect(addr, addr_len).await
+        Self::inner_connect(addr, addr_len, None).await
+    }
+
+    /// Cancelable connect to the socket named by path.
+    ///
+    /// If canceled before the connection is established, an error for which
+    /// [`is_canceled`](crate::io::is_canceled) returns true is returned and
+    /// the socket is closed.
+    pub async fn cancelable_connect<P: AsRef<Path>>(path: P, c: CancelHandle) -> io::Result<Self> {
+        let (addr, addr_len) = socket_addr(path.as_ref())?;
+        Self::inner_connect(addr, addr_len, Some(c)).await
     }
 
     #[inline(always)]
     async fn inner_connect(
         sockaddr: libc::sockaddr_un,
         socklen: libc::socklen_t,
+        c: Option<CancelHandle>,
     ) -> io::Result<Self> {
+        if c.as_ref().map(|c| c.canceled()).unwrap_or(false) {
+            return Err(operation_canceled());
+        }
         let socket = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
         let op = Op::connect_unix(SharedFd::new::<false>(socket)?, sockaddr, socklen)?;
+        let guard = c.clone().map(|c| c.associate_op(op.op_canceller()));
         let completion = op.await;
+        drop(guard);
         completion.meta.result?;
 
         let stream = Self::from_shared_fd(completion.data.fd);
         if crate::driver::op::is_legacy() {
-            stream.writable(true).await?;
+            let op = Op::poll_write(&stream.fd, true).unwrap();
+            let _guard = c.map(|c| c.associate_op(op.op_canceller()));
+            op.wait().await?;
         }
         // getsockopt
         let sys_socket = unsafe { std::os::unix::net::UnixStream::from_raw_fd(stream.fd.raw_fd()) };
@@ -84,6 +112,45 @@ impl UnixStream {
         super::ucred::get_peer_cred(self)
     }
 
+    /// Returns a stream of the data received by the socket.
+    ///
+    /// With io_uring, a bundled recv (kernel 6.10+) receives into as many of
+    /// `buffers` buffers of `buffer_size` bytes as the data needs with one
+    /// completion, they are provided to the kernel with a buffer ring. The
+    /// buffers are held by each [`RecvBundle`](crate::net::RecvBundle) until
+    /// it is dropped, the stream waits for one when all are held. Otherwise,
+    /// or on older kernels, each item is received into one buffer with a recv
+    /// call.
+    #[cfg(target_os = "linux")]
+    pub fn recv_bundle(
+        &self,
+        buffers: u16,
+        buffer_size: usize,
+    ) -> io::Result<crate::net::RecvBundleStream> {
+        crate::net::RecvBundleStream::new(&self.fd, buffers, buffer_size)
+    }
+
+    /// Set a socket option with `setsockopt`, for options without a
+    /// dedicated method.
+    pub fn set_option<V: crate::net::SetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+        value: V,
+    ) -> io::Result<()> {
+        crate::net::sockopt::set_option(self.as_raw_fd(), level, name, value)
+    }
+
+    /// Get a socket option with `getsockopt`, for options without a
+    /// dedicated method.
+    pub fn option<V: crate::net::GetOptionValue>(
+        &self,
+        level: crate::net::Level,
+        name: crate::net::Name,
+    ) -> io::Result<V> {
+        crate::net::sockopt::get_option(self.as_raw_fd(), level, name)
+    }
+
     /// Creates new `UnixStream` from a `std::os::unix::net::UnixStream`.
     pub fn from_std(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
         match SharedFd::new::<false>(stream.as_raw_fd()) {
@@ -134,6 +201,126 @@ impl UnixStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Read with a deadline, see
+    /// [`TcpStream::read_with_deadline`](crate::net::TcpStream::read_with_deadline).
+    pub async fn read_with_deadline<T: IoBufMut>(
+        &mut self,
+        buf: T,
+        deadline: Instant,
+    ) -> BufResult<usize, T> {
+        let op = Op::recv_with_deadline(self.fd.clone(), buf, deadline).unwrap();
+        op.read().await
+    }
+
+    /// Write with a deadline, see
+    /// [`TcpStream::write_with_deadline`](crate::net::TcpStream::write_with_deadline).
+    pub async fn write_with_deadline<T: IoBuf>(
+        &mut self,
+        buf: T,
+        deadline: Instant,
+    ) -> BufResult<usize, T> {
+        let op = Op::send_with_deadline(self.fd.clone(), buf, deadline).unwrap();
+        op.write().await
+    }
+
+    /// Set the read timeout, `None` means reads never time out.
+    ///
+    /// Reads taking longer fail with an error of kind `TimedOut`. The timeout
+    /// is also set as `SO_RCVTIMEO` on the socket. In uring impl, a linked
+    /// timeout is attached to each read; in epoll impl, the timer is required
+    /// to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `InvalidInput` is returned for a zero duration.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeouts.set_read(&self.fd, timeout)
+    }
+
+    /// Returns the read timeout.
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(self.timeouts.read())
+    }
+
+    /// Set the write timeout, `None` means writes never time out.
+    ///
+    /// Writes taking longer fail with an error of kind `TimedOut`. The timeout
+    /// is also set as `SO_SNDTIMEO` on the socket. In uring impl, a linked
+    /// timeout is attached to each write; in epoll impl, the timer is required
+    /// to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind `InvalidInput` is returned for a zero duration.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeouts.set_write(&self.fd, timeout)
+    }
+
+    /// Returns the write timeout.
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(self.timeouts.write())
+    }
+
+    /// Send data along with file descriptors, passed to the peer as a
+    /// `SCM_RIGHTS` message. The descriptors are duplicated into the peer
+    /// process and stay open here.
+    ///
+    /// The descriptors are attached to the first byte sent, so they are
+    /// received by the [`recv_with_fds`](Self::recv_with_fds) call reading
+    /// it. Returns the number of bytes sent, which may be less than the
+    /// buffer length.
+    pub async fn send_with_fds<T: IoBuf>(&mut self, buf: T, fds: &[RawFd]) -> BufResult<usize, T> {
+        let op = Op::send_msg_unix_with_fds(self.fd.clone(), buf, None, fds).unwrap();
+        op.wait().await
+    }
+
+    /// Receive data along with the file descriptors sent by
+    /// [`send_with_fds`](Self::send_with_fds). Returns the number of bytes
+    /// read, the received descriptors, owned by the caller and close-on-exec
+    /// where supported, and whether the kernel dropped some descriptors.
+    ///
+    /// Up to 253 descriptors can be received per call, the kernel limit of a
+    /// single message; the ones beyond are closed by the kernel.
+    ///
+    /// With io_uring, descriptors received by a call whose future is dropped
+    /// while the receive is in flight are not closed.
+    pub async fn recv_with_fds<T: IoBufMut>(
+        &mut self,
+        buf: T,
+    ) -> BufResult<(usize, Vec<OwnedFd>, bool), T> {
+        const SCM_MAX_FD: usize = 253;
+        let op = Op::recv_msg_unix_with_fds(self.fd.clone(), buf, SCM_MAX_FD).unwrap();
+        op.wait_with_fds().await
+    }
+
+    /// Splice up to `len` bytes from the socket into the pipe without copying
+    /// them to user space. Returns the number of bytes moved, 0 means EOF.
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn splice_to(
+        &mut self,
+        pipe: &mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
+    ) -> io::Result<u32> {
+        Op::splice_to_pipe(&self.fd, &pipe.fd, len, flags.bits())?
+            .splice()
+            .await
+    }
+
+    /// Splice up to `len` bytes from the pipe into the socket without copying
+    /// them to user space. Returns the number of bytes moved.
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn splice_from(
+        &mut self,
+        pipe: &mut Pipe,
+        len: u32,
+        flags: SpliceFlags,
+    ) -> io::Result<u32> {
+        Op::splice_from_pipe(&pipe.fd, &self.fd, len, flags.bits())?
+            .splice()
+            .await
+    }
 }
 
 impl AsReadFd for UnixStream {
@@ -175,15 +362,37 @@ impl std::fmt::Debug for UnixStream {
 impl AsyncWriteRent for UnixStream {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the write operation
-        let op = Op::send(self.fd.clone(), buf).unwrap();
-        op.write()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.write_deadline();
+        async move {
+            // Submit the write operation
+            match deadline {
+                None => Op::send(fd, buf).unwrap().write().await,
+                Some(deadline) => {
+                    Op::send_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .write()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
     fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> impl Future<Output = BufResult<usize, T>> {
-        let op = Op::writev(&self.fd, buf_vec).unwrap();
-        op.write()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.write_deadline();
+        async move {
+            match deadline {
+                None => Op::writev(&fd, buf_vec).unwrap().write().await,
+                Some(deadline) => {
+                    Op::writev_with_deadline(&fd, buf_vec, deadline)
+                        .unwrap()
+                        .write()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
@@ -218,9 +427,18 @@ impl CancelableAsyncWriteRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::send(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.write().await
+        match self.timeouts.write_deadline() {
+            None => {
+                let op = Op::send(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+            Some(deadline) => {
+                let op = Op::send_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+        }
     }
 
     #[inline]
@@ -235,9 +453,18 @@ impl CancelableAsyncWriteRent for UnixStream {
             return (Err(operation_canceled()), buf_vec);
         }
 
-        let op = Op::writev(&fd, buf_vec).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.write().await
+        match self.timeouts.write_deadline() {
+            None => {
+                let op = Op::writev(&fd, buf_vec).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+            Some(deadline) => {
+                let op = Op::writev_with_deadline(&fd, buf_vec, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.write().await
+            }
+        }
     }
 
     #[inline]
@@ -260,16 +487,38 @@ impl CancelableAsyncWriteRent for UnixStream {
 impl AsyncReadRent for UnixStream {
     #[inline]
     fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::recv(self.fd.clone(), buf).unwrap();
-        op.read()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.read_deadline();
+        async move {
+            // Submit the read operation
+            match deadline {
+                None => Op::recv(fd, buf).unwrap().read().await,
+                Some(deadline) => {
+                    Op::recv_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .read()
+                        .await
+                }
+            }
+        }
     }
 
     #[inline]
     fn readv<T: IoVecBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
-        // Submit the read operation
-        let op = Op::readv(self.fd.clone(), buf).unwrap();
-        op.read()
+        let fd = self.fd.clone();
+        let deadline = self.timeouts.read_deadline();
+        async move {
+            // Submit the read operation
+            match deadline {
+                None => Op::readv(fd, buf).unwrap().read().await,
+                Some(deadline) => {
+                    Op::readv_with_deadline(fd, buf, deadline)
+                        .unwrap()
+                        .read()
+                        .await
+                }
+            }
+        }
     }
 }
 
@@ -286,9 +535,18 @@ impl CancelableAsyncReadRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::recv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.read().await
+        match self.timeouts.read_deadline() {
+            None => {
+                let op = Op::recv(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+            Some(deadline) => {
+                let op = Op::recv_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+        }
     }
 
     #[inline]
@@ -303,9 +561,18 @@ impl CancelableAsyncReadRent for UnixStream {
             return (Err(operation_canceled()), buf);
         }
 
-        let op = Op::readv(fd, buf).unwrap();
-        let _guard = c.associate_op(op.op_canceller());
-        op.read().await
+        match self.timeouts.read_deadline() {
+            None => {
+                let op = Op::readv(fd, buf).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+            Some(deadline) => {
+                let op = Op::readv_with_deadline(fd, buf, deadline).unwrap();
+                let _guard = c.associate_op(op.op_canceller());
+                op.read().await
+            }
+        }
     }
 }
 
@@ -26,16 +26,89 @@ impl UCred {
     }
 
     /// Gets PID (process ID) of the process.
+    ///
+    /// This is `None` on FreeBSD and DragonFly, which do not report it.
     #[inline]
     pub fn pid(&self) -> Option<pid_t> {
         self.pid
     }
 }
 
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+pub(crate) use self::impl_bsd::get_peer_cred;
 #[cfg(any(target_os = "linux", target_os = "android", target_os = "openbsd"))]
 pub(crate) use self::impl_linux::get_peer_cred;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub(crate) use self::impl_macos::get_peer_cred;
+#[cfg(target_os = "netbsd")]
+pub(crate) use self::impl_netbsd::get_peer_cred;
+
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+pub(crate) mod impl_bsd {
+    use std::{io, mem::MaybeUninit, os::unix::io::AsRawFd};
+
+    use libc::getpeereid;
+
+    use crate::net::unix::UnixStream;
+
+    // `getpeereid` does not report the pid of the peer.
+    pub(crate) fn get_peer_cred(sock: &UnixStream) -> io::Result<super::UCred> {
+        unsafe {
+            let mut uid = MaybeUninit::uninit();
+            let mut gid = MaybeUninit::uninit();
+
+            let ret = getpeereid(sock.as_raw_fd(), uid.as_mut_ptr(), gid.as_mut_ptr());
+
+            if ret == 0 {
+                Ok(super::UCred {
+                    uid: uid.assume_init(),
+                    gid: gid.assume_init(),
+                    pid: None,
+                })
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "netbsd")]
+pub(crate) mod impl_netbsd {
+    use std::{io, mem, os::unix::io::AsRawFd};
+
+    use libc::{c_void, getsockopt, socklen_t, unpcbid, LOCAL_PEEREID};
+
+    use crate::net::unix::UnixStream;
+
+    pub(crate) fn get_peer_cred(sock: &UnixStream) -> io::Result<super::UCred> {
+        unsafe {
+            let mut unpcbid = unpcbid {
+                unp_pid: 0,
+                unp_euid: 0,
+                unp_egid: 0,
+            };
+            let mut unpcbid_size = mem::size_of::<unpcbid>() as socklen_t;
+
+            // `LOCAL_PEEREID` is an option of level 0, `SOL_LOCAL`.
+            let ret = getsockopt(
+                sock.as_raw_fd(),
+                0,
+                LOCAL_PEEREID,
+                &mut unpcbid as *mut unpcbid as *mut c_void,
+                &mut unpcbid_size,
+            );
+            if ret == 0 && unpcbid_size as usize == mem::size_of::<unpcbid>() {
+                Ok(super::UCred {
+                    uid: unpcbid.unp_euid,
+                    gid: unpcbid.unp_egid,
+                    pid: Some(unpcbid.unp_pid),
+                })
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        }
+    }
+}
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub(crate) mod impl_macos {
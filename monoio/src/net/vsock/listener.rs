@@ -0,0 +1,92 @@
+use std::{
+    io,
+    os::fd::{AsRawFd, RawFd},
+};
+
+use super::{local_addr, VsockAddr, VsockStream};
+use crate::{
+    driver::{op::Op, shared_fd::SharedFd},
+    io::stream::Stream,
+    net::new_socket,
+};
+
+const DEFAULT_BACKLOG: libc::c_int = 128;
+
+/// A vsock socket server, listening for connections.
+pub struct VsockListener {
+    fd: SharedFd,
+}
+
+impl VsockListener {
+    /// Creates a new `VsockListener` bound to `addr` with a custom backlog.
+    pub fn bind_with_backlog(addr: VsockAddr, backlog: libc::c_int) -> io::Result<Self> {
+        let socket = new_socket(libc::AF_VSOCK, libc::SOCK_STREAM)?;
+        // Owned right away, so the socket is closed if bind or listen fails.
+        let fd = SharedFd::new::<false>(socket)?;
+        let addr = addr.into_raw();
+        crate::syscall!(bind(
+            socket,
+            &addr as *const _ as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t
+        ))?;
+        crate::syscall!(listen(socket, backlog))?;
+        Ok(Self { fd })
+    }
+
+    /// Creates a new `VsockListener` bound to `addr` with the default
+    /// backlog(128).
+    #[inline]
+    pub fn bind(addr: VsockAddr) -> io::Result<Self> {
+        Self::bind_with_backlog(addr, DEFAULT_BACKLOG)
+    }
+
+    /// Accept a connection, returns the stream and the address of the peer.
+    pub async fn accept(&self) -> io::Result<(VsockStream, VsockAddr)> {
+        let op = Op::accept(&self.fd)?;
+
+        // Await the completion of the event
+        let completion = op.await;
+
+        // Convert fd
+        let fd = completion.meta.result?;
+
+        // Construct stream
+        let stream = VsockStream::from_shared_fd(SharedFd::new::<false>(fd as _)?);
+
+        // Construct VsockAddr
+        let storage = unsafe { std::mem::MaybeUninit::assume_init(completion.data.addr.0) };
+        let raw_addr: libc::sockaddr_vm =
+            unsafe { *(&storage as *const libc::sockaddr_storage).cast() };
+
+        Ok((stream, VsockAddr::from_raw(&raw_addr)))
+    }
+
+    /// Returns the local address that this listener is bound to.
+    pub fn local_addr(&self) -> io::Result<VsockAddr> {
+        local_addr(self.fd.raw_fd())
+    }
+}
+
+impl AsRawFd for VsockListener {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
+impl std::fmt::Debug for VsockListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VsockListener")
+            .field("fd", &self.fd)
+            .finish()
+    }
+}
+
+impl Stream for VsockListener {
+    type Item = io::Result<(VsockStream, VsockAddr)>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        Some(self.accept().await)
+    }
+}
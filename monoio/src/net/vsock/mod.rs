@@ -0,0 +1,82 @@
+//! Virtio socket (`AF_VSOCK`) related, for the communication between virtual
+//! machines and their host.
+//! Only available on linux.
+
+use std::{fmt, io, mem, os::fd::RawFd};
+
+mod listener;
+mod stream;
+
+pub use listener::VsockListener;
+pub use stream::VsockStream;
+
+/// Address of a vsock socket: the context id (CID) of the machine and a port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VsockAddr {
+    cid: u32,
+    port: u32,
+}
+
+impl VsockAddr {
+    /// Bind to any CID of the machine.
+    pub const CID_ANY: u32 = libc::VMADDR_CID_ANY;
+    /// CID of the hypervisor.
+    pub const CID_HYPERVISOR: u32 = libc::VMADDR_CID_HYPERVISOR;
+    /// CID of the local machine, for loopback communication.
+    pub const CID_LOCAL: u32 = libc::VMADDR_CID_LOCAL;
+    /// CID of the host.
+    pub const CID_HOST: u32 = libc::VMADDR_CID_HOST;
+    /// Bind to any free port.
+    pub const PORT_ANY: u32 = libc::VMADDR_PORT_ANY;
+
+    /// Creates an address from a CID and a port.
+    pub const fn new(cid: u32, port: u32) -> Self {
+        Self { cid, port }
+    }
+
+    /// Returns the context id.
+    pub const fn cid(&self) -> u32 {
+        self.cid
+    }
+
+    /// Returns the port.
+    pub const fn port(&self) -> u32 {
+        self.port
+    }
+
+    pub(crate) fn into_raw(self) -> libc::sockaddr_vm {
+        let mut addr: libc::sockaddr_vm = unsafe { mem::zeroed() };
+        addr.svm_family = libc::AF_VSOCK as libc::sa_family_t;
+        addr.svm_cid = self.cid;
+        addr.svm_port = self.port;
+        addr
+    }
+
+    pub(crate) fn from_raw(addr: &libc::sockaddr_vm) -> Self {
+        Self::new(addr.svm_cid, addr.svm_port)
+    }
+}
+
+impl fmt::Display for VsockAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "vsock:{}:{}", self.cid, self.port)
+    }
+}
+
+fn local_addr(socket: RawFd) -> io::Result<VsockAddr> {
+    sock_name(|addr, len| crate::syscall!(getsockname(socket, addr, len)))
+}
+
+fn peer_addr(socket: RawFd) -> io::Result<VsockAddr> {
+    sock_name(|addr, len| crate::syscall!(getpeername(socket, addr, len)))
+}
+
+fn sock_name<F>(f: F) -> io::Result<VsockAddr>
+where
+    F: FnOnce(*mut libc::sockaddr, *mut libc::socklen_t) -> io::Result<libc::c_int>,
+{
+    let mut addr: libc::sockaddr_vm = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t;
+    f(&mut addr as *mut _ as *mut libc::sockaddr, &mut len)?;
+    Ok(VsockAddr::from_raw(&addr))
+}
@@ -21,11 +21,17 @@ use crate::{
 thread_local! {
     pub(crate) static DEFAULT_CTX: Context = Context {
         thread_id: crate::utils::thread_id::DEFAULT_THREAD_ID,
+        #[cfg(target_os = "linux")]
+        os_thread_id: 0,
         unpark_cache: std::cell::RefCell::new(fxhash::FxHashMap::default()),
         waker_sender_cache: std::cell::RefCell::new(fxhash::FxHashMap::default()),
         tasks: Default::default(),
         time_handle: None,
         blocking_handle: crate::blocking::BlockingHandle::Empty(crate::blocking::BlockingStrategy::Panic),
+        #[cfg(feature = "metrics")]
+        metrics: Default::default(),
+        #[cfg(feature = "interceptor")]
+        interceptor: None,
     };
 }
 
@@ -38,6 +44,10 @@ pub(crate) struct Context {
     /// Thread id(not the kernel thread id but a generated unique number)
     pub(crate) thread_id: usize,
 
+    /// Kernel thread id of the thread the runtime was built on
+    #[cfg(target_os = "linux")]
+    pub(crate) os_thread_id: libc::pid_t,
+
     /// Thread unpark handles
     #[cfg(feature = "sync")]
     pub(crate) unpark_cache:
@@ -54,6 +64,14 @@ pub(crate) struct Context {
     /// Blocking Handle
     #[cfg(feature = "sync")]
     pub(crate) blocking_handle: crate::blocking::BlockingHandle,
+
+    /// Runtime metrics
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: crate::metrics::Counters,
+
+    /// Op interceptor
+    #[cfg(feature = "interceptor")]
+    pub(crate) interceptor: Option<std::sync::Arc<dyn crate::interceptor::Interceptor>>,
 }
 
 impl Context {
@@ -63,11 +81,17 @@ impl Context {
 
         Self {
             thread_id,
+            #[cfg(target_os = "linux")]
+            os_thread_id: crate::utils::thread_id::gettid(),
             unpark_cache: std::cell::RefCell::new(fxhash::FxHashMap::default()),
             waker_sender_cache: std::cell::RefCell::new(fxhash::FxHashMap::default()),
             tasks: TaskQueue::default(),
             time_handle: None,
             blocking_handle,
+            #[cfg(feature = "metrics")]
+            metrics: Default::default(),
+            #[cfg(feature = "interceptor")]
+            interceptor: None,
         }
     }
 
@@ -77,8 +101,14 @@ impl Context {
 
         Self {
             thread_id,
+            #[cfg(target_os = "linux")]
+            os_thread_id: crate::utils::thread_id::gettid(),
             tasks: TaskQueue::default(),
             time_handle: None,
+            #[cfg(feature = "metrics")]
+            metrics: Default::default(),
+            #[cfg(feature = "interceptor")]
+            interceptor: None,
         }
     }
 
@@ -150,11 +180,15 @@ impl<D> Runtime<D> {
 
                 let mut join = std::pin::pin!(join);
                 set_poll();
+                #[cfg(feature = "metrics")]
+                self.context.metrics.start();
                 loop {
                     loop {
                         // Consume all tasks(with max round to prevent io starvation)
                         let mut max_round = self.context.tasks.len() * 2;
                         while let Some(t) = self.context.tasks.pop() {
+                            #[cfg(feature = "metrics")]
+                            self.context.metrics.incr_tasks_polled();
                             t.run();
                             if max_round == 0 {
                                 // maybe there's a looping task
@@ -168,6 +202,8 @@ impl<D> Runtime<D> {
                         while should_poll() {
                             // check if ready
                             if let std::task::Poll::Ready(t) = join.as_mut().poll(cx) {
+                                #[cfg(feature = "metrics")]
+                                self.context.metrics.stop();
                                 return t;
                             }
                         }
@@ -183,6 +219,8 @@ impl<D> Runtime<D> {
                     }
 
                     // Wait and Process CQ(the error is ignored for not debug mode)
+                    #[cfg(feature = "metrics")]
+                    let park_begin = self.context.metrics.park_begin();
                     #[cfg(not(all(debug_assertions, feature = "debug")))]
                     let _ = self.driver.park();
 
@@ -190,10 +228,80 @@ impl<D> Runtime<D> {
                     if let Err(e) = self.driver.park() {
                         trace!("park error: {:?}", e);
                     }
+
+                    #[cfg(feature = "metrics")]
+                    self.context.metrics.park_end(park_begin);
                 }
             })
         })
     }
+
+    /// Prepare the runtime to serve, then run `f` on it and return its
+    /// output.
+    ///
+    /// It allocates the driver resources that are otherwise allocated on
+    /// first use, like the slots of in-flight ops, and probes the io_uring
+    /// opcodes. Do the application warm up in `f`, like filling connection
+    /// pools or caches, so the first requests served by
+    /// [`block_on`](Self::block_on) do not pay for it.
+    ///
+    /// ```
+    /// let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///     .build()
+    ///     .unwrap();
+    /// let table: Vec<u64> = rt.warmup(|| async { (0..1024).collect() });
+    /// rt.block_on(async move {
+    ///     assert_eq!(table.len(), 1024);
+    /// });
+    /// ```
+    pub fn warmup<F, Fut>(&mut self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+        D: Driver,
+    {
+        self.block_on(async move {
+            crate::driver::warmup();
+            f().await
+        })
+    }
+
+    /// Returns the id of the runtime, unique in the process. It identifies
+    /// the runtime thread in monoio, e.g. for cross thread wakeups.
+    pub fn thread_id(&self) -> usize {
+        self.context.thread_id
+    }
+
+    /// Returns the kernel thread id of the thread the runtime was built on,
+    /// as listed in `/proc/self/task`.
+    #[cfg(target_os = "linux")]
+    pub fn os_thread_id(&self) -> libc::pid_t {
+        self.context.os_thread_id
+    }
+
+    /// Returns the approximate memory held by the runtime.
+    ///
+    /// See [`MemoryReport::current`](crate::metrics::MemoryReport::current)
+    /// to take it from within the runtime.
+    #[cfg(feature = "metrics")]
+    pub fn memory_report(&self) -> crate::metrics::MemoryReport
+    where
+        D: Driver,
+    {
+        self.driver
+            .with(|| CURRENT.set(&self.context, crate::metrics::MemoryReport::current))
+    }
+
+    /// Shut down the runtime and the blocking thread pool attached to it.
+    ///
+    /// The pool stops accepting tasks, then the queued and running ones are
+    /// waited for up to `timeout`. Returns false if some were still pending
+    /// at the deadline: the queued ones are canceled and the running ones are
+    /// detached. See [`ThreadPool::shutdown`](crate::blocking::ThreadPool::shutdown).
+    #[cfg(feature = "sync")]
+    pub fn shutdown(self, timeout: std::time::Duration) -> bool {
+        self.context.blocking_handle.shutdown(timeout)
+    }
 }
 
 /// Fusion Runtime is a wrapper of io_uring driver or legacy driver based
@@ -237,6 +345,56 @@ where
             }
         }
     }
+
+    /// Prepare the runtime to serve, then run `f` on it and return its
+    /// output, see [`Runtime::warmup`].
+    pub fn warmup<F, Fut>(&mut self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.warmup(f),
+            FusionRuntime::Legacy(inner) => inner.warmup(f),
+        }
+    }
+
+    /// Returns the id of the runtime, see [`Runtime::thread_id`].
+    pub fn thread_id(&self) -> usize {
+        match self {
+            FusionRuntime::Uring(inner) => inner.thread_id(),
+            FusionRuntime::Legacy(inner) => inner.thread_id(),
+        }
+    }
+
+    /// Returns the kernel thread id of the runtime, see
+    /// [`Runtime::os_thread_id`].
+    #[cfg(target_os = "linux")]
+    pub fn os_thread_id(&self) -> libc::pid_t {
+        match self {
+            FusionRuntime::Uring(inner) => inner.os_thread_id(),
+            FusionRuntime::Legacy(inner) => inner.os_thread_id(),
+        }
+    }
+
+    /// Returns the approximate memory held by the runtime.
+    #[cfg(feature = "metrics")]
+    pub fn memory_report(&self) -> crate::metrics::MemoryReport {
+        match self {
+            FusionRuntime::Uring(inner) => inner.memory_report(),
+            FusionRuntime::Legacy(inner) => inner.memory_report(),
+        }
+    }
+
+    /// Shut down the runtime and the blocking thread pool attached to it, see
+    /// [`Runtime::shutdown`].
+    #[cfg(feature = "sync")]
+    pub fn shutdown(self, timeout: std::time::Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.shutdown(timeout),
+            FusionRuntime::Legacy(inner) => inner.shutdown(timeout),
+        }
+    }
 }
 
 #[cfg(all(feature = "legacy", not(all(target_os = "linux", feature = "iouring"))))]
@@ -253,6 +411,51 @@ where
             FusionRuntime::Legacy(inner) => inner.block_on(future),
         }
     }
+
+    /// Prepare the runtime to serve, then run `f` on it and return its
+    /// output, see [`Runtime::warmup`].
+    pub fn warmup<F, Fut>(&mut self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.warmup(f),
+        }
+    }
+
+    /// Returns the id of the runtime, see [`Runtime::thread_id`].
+    pub fn thread_id(&self) -> usize {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.thread_id(),
+        }
+    }
+
+    /// Returns the kernel thread id of the runtime, see
+    /// [`Runtime::os_thread_id`].
+    #[cfg(target_os = "linux")]
+    pub fn os_thread_id(&self) -> libc::pid_t {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.os_thread_id(),
+        }
+    }
+
+    /// Returns the approximate memory held by the runtime.
+    #[cfg(feature = "metrics")]
+    pub fn memory_report(&self) -> crate::metrics::MemoryReport {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.memory_report(),
+        }
+    }
+
+    /// Shut down the runtime and the blocking thread pool attached to it, see
+    /// [`Runtime::shutdown`].
+    #[cfg(feature = "sync")]
+    pub fn shutdown(self, timeout: std::time::Duration) -> bool {
+        match self {
+            FusionRuntime::Legacy(inner) => inner.shutdown(timeout),
+        }
+    }
 }
 
 #[cfg(all(not(feature = "legacy"), all(target_os = "linux", feature = "iouring")))]
@@ -269,6 +472,51 @@ where
             FusionRuntime::Uring(inner) => inner.block_on(future),
         }
     }
+
+    /// Prepare the runtime to serve, then run `f` on it and return its
+    /// output, see [`Runtime::warmup`].
+    pub fn warmup<F, Fut>(&mut self, f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        match self {
+            FusionRuntime::Uring(inner) => inner.warmup(f),
+        }
+    }
+
+    /// Returns the id of the runtime, see [`Runtime::thread_id`].
+    pub fn thread_id(&self) -> usize {
+        match self {
+            FusionRuntime::Uring(inner) => inner.thread_id(),
+        }
+    }
+
+    /// Returns the kernel thread id of the runtime, see
+    /// [`Runtime::os_thread_id`].
+    #[cfg(target_os = "linux")]
+    pub fn os_thread_id(&self) -> libc::pid_t {
+        match self {
+            FusionRuntime::Uring(inner) => inner.os_thread_id(),
+        }
+    }
+
+    /// Returns the approximate memory held by the runtime.
+    #[cfg(feature = "metrics")]
+    pub fn memory_report(&self) -> crate::metrics::MemoryReport {
+        match self {
+            FusionRuntime::Uring(inner) => inner.memory_report(),
+        }
+    }
+
+    /// Shut down the runtime and the blocking thread pool attached to it, see
+    /// [`Runtime::shutdown`].
+    #[cfg(feature = "sync")]
+    pub fn shutdown(self, timeout: std::time::Duration) -> bool {
+        match self {
+            FusionRuntime::Uring(inner) => inner.shutdown(timeout),
+        }
+    }
 }
 
 // L -> Fusion<L, R>
@@ -52,6 +52,11 @@ impl TaskQueue {
         unsafe { (*self.queue.get()).len() }
     }
 
+    #[cfg(feature = "metrics")]
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        unsafe { (*self.queue.get()).capacity() * std::mem::size_of::<Task<LocalScheduler>>() }
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -0,0 +1,102 @@
+//! Futexes waited on and woken through io_uring.
+//!
+//! A [`Futex`] is a 32-bit word shared between threads, like the ones
+//! `futex(2)` works on. With the io_uring driver, waiting on it is an op
+//! (`IORING_OP_FUTEX_WAIT`, requires Linux 6.7+), so a runtime can wait on
+//! a futex without blocking its thread, and be woken by another runtime
+//! or a plain thread without an eventfd round-trip.
+//!
+//! They are building blocks for cross-thread synchronization: the value
+//! means whatever the primitive built on top decides, and waiting may
+//! return spuriously, so callers must check the value again.
+//!
+//! ```no_run
+//! use std::sync::atomic::Ordering;
+//!
+//! use monoio::sync::futex::Futex;
+//!
+//! #[monoio::main(driver = "uring")]
+//! async fn main() -> std::io::Result<()> {
+//!     let ready = Futex::new(0);
+//!     let notifier = ready.clone();
+//!     std::thread::spawn(move || {
+//!         notifier.value().store(1, Ordering::Release);
+//!         notifier.wake_blocking(u32::MAX).unwrap();
+//!     });
+//!     while ready.value().load(Ordering::Acquire) == 0 {
+//!         ready.wait(0).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    io,
+    sync::{atomic::AtomicU32, Arc},
+};
+
+use crate::driver::op::Op;
+
+/// A futex word, shared between threads.
+///
+/// Clones share the same word.
+#[derive(Debug, Clone, Default)]
+pub struct Futex {
+    word: Arc<AtomicU32>,
+}
+
+impl Futex {
+    /// Create a futex with the given value.
+    pub fn new(value: u32) -> Self {
+        Self {
+            word: Arc::new(AtomicU32::new(value)),
+        }
+    }
+
+    /// Returns the futex word.
+    #[inline]
+    pub fn value(&self) -> &AtomicU32 {
+        &self.word
+    }
+
+    /// Wait until woken if the value is `expected`.
+    ///
+    /// It returns at once if the value is not `expected`, and may return
+    /// spuriously, so check the value again before waiting another time.
+    /// The legacy driver, which would have to block the thread, returns
+    /// an error of kind `Unsupported`.
+    pub async fn wait(&self, expected: u32) -> io::Result<()> {
+        let op = Op::futex_wait(self.word.clone(), expected)?;
+        match op.await.meta.result {
+            Ok(_) => Ok(()),
+            // The value has changed.
+            Err(e) if e.raw_os_error() == Some(libc::EAGAIN) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wake up at most `count` waiters, returns how many were woken.
+    pub async fn wake(&self, count: u32) -> io::Result<u32> {
+        let op = Op::futex_wake(self.word.clone(), count)?;
+        op.await.meta.result
+    }
+
+    /// Wake up at most `count` waiters with a `futex(2)` call, returns how
+    /// many were woken. Unlike [`wake`](Self::wake), it can be called from
+    /// any thread, in or outside a runtime.
+    pub fn wake_blocking(&self, count: u32) -> io::Result<u32> {
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                self.word.as_ptr(),
+                libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+                count.min(i32::MAX as u32),
+            )
+        };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as u32)
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    cell::RefCell,
+    future::poll_fn,
+    task::{Poll, Waker},
+};
+
+/// A barrier enabling a number of tasks to wait until all of them reach a
+/// point.
+///
+/// The barrier is reusable: after all tasks are released, the next `n` calls
+/// to [`wait`](Barrier::wait) form a new round.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::rc::Rc;
+///
+/// use monoio::sync::local::Barrier;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let barrier = Rc::new(Barrier::new(3));
+///     for _ in 0..2 {
+///         let barrier = barrier.clone();
+///         monoio::spawn(async move {
+///             // initialize
+///             barrier.wait().await;
+///         });
+///     }
+///     barrier.wait().await;
+///     println!("all initialized");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Barrier {
+    n: usize,
+    state: RefCell<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    arrived: usize,
+    generation: u64,
+    waiters: Vec<Waker>,
+}
+
+/// Returned by [`Barrier::wait`].
+#[derive(Debug, Clone, Copy)]
+pub struct BarrierWaitResult(bool);
+
+impl BarrierWaitResult {
+    /// Returns true for exactly one task of each round, the last one arriving.
+    pub fn is_leader(&self) -> bool {
+        self.0
+    }
+}
+
+impl Barrier {
+    /// Create a barrier releasing tasks when `n` of them are waiting.
+    ///
+    /// A barrier of 0 behaves like a barrier of 1.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n: n.max(1),
+            state: RefCell::new(State {
+                arrived: 0,
+                generation: 0,
+                waiters: Vec::new(),
+            }),
+        }
+    }
+
+    /// Wait until `n` tasks reach the barrier.
+    ///
+    /// The task counts as arrived once the returned future is first polled,
+    /// even if it is dropped before the round completes.
+    pub async fn wait(&self) -> BarrierWaitResult {
+        let generation = {
+            let mut state = self.state.borrow_mut();
+            state.arrived += 1;
+            if state.arrived == self.n {
+                state.arrived = 0;
+                state.generation += 1;
+                for waker in state.waiters.drain(..) {
+                    waker.wake();
+                }
+                return BarrierWaitResult(true);
+            }
+            state.generation
+        };
+        poll_fn(|cx| {
+            let mut state = self.state.borrow_mut();
+            if state.generation != generation {
+                return Poll::Ready(BarrierWaitResult(false));
+            }
+            if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                state.waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
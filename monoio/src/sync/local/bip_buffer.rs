@@ -0,0 +1,400 @@
+use std::{
+    cell::{Cell, UnsafeCell},
+    future::poll_fn,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use crate::buf::{IoBuf, IoBufMut};
+
+/// Create a single-producer single-consumer byte ring of `capacity` bytes.
+///
+/// Unlike a plain ring buffer, a bip buffer always lends out contiguous
+/// regions: the writer [`reserve`](BipWriter::reserve)s a [`WriteGrant`] and
+/// fills it in place, the reader takes the committed bytes as a
+/// [`ReadGrant`]. Both grants own a reference to the ring, implement
+/// [`IoBufMut`] and [`IoBuf`] respectively, so they can be passed to the io
+/// methods directly, or handed over to another task, without copying.
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::{io::AsyncReadRent, net::TcpStream, sync::local::bip_buffer};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let (mut tx, mut rx) = bip_buffer(64 * 1024);
+///     monoio::spawn(async move {
+///         while let Some(grant) = rx.read().await {
+///             // parse the frames in place
+///             let len = grant.len();
+///             grant.release(len);
+///         }
+///     });
+///     loop {
+///         let grant = tx.reserve(4096).await.unwrap();
+///         let (res, grant) = stream.read(grant).await;
+///         match res? {
+///             0 => return Ok(()),
+///             n => grant.commit(n),
+///         }
+///     }
+/// }
+/// ```
+pub fn bip_buffer(capacity: usize) -> (BipWriter, BipReader) {
+    assert!(capacity > 0, "capacity must be non-zero");
+    let shared = Rc::new(Shared {
+        buf: (0..capacity).map(|_| UnsafeCell::new(0)).collect(),
+        read: Cell::new(0),
+        write: Cell::new(0),
+        watermark: Cell::new(0),
+        inverted: Cell::new(false),
+        writing: Cell::new(false),
+        reading: Cell::new(false),
+        closed: Cell::new(false),
+        write_waker: Cell::new(None),
+        read_waker: Cell::new(None),
+    });
+    (
+        BipWriter {
+            shared: shared.clone(),
+        },
+        BipReader { shared },
+    )
+}
+
+struct Shared {
+    buf: Box<[UnsafeCell<u8>]>,
+    // Committed data is [read, write) if not inverted, otherwise
+    // [read, watermark) followed by [0, write).
+    read: Cell<usize>,
+    write: Cell<usize>,
+    watermark: Cell<usize>,
+    inverted: Cell<bool>,
+    // a grant is outstanding
+    writing: Cell<bool>,
+    reading: Cell<bool>,
+    // one of the halves is dropped
+    closed: Cell<bool>,
+    write_waker: Cell<Option<Waker>>,
+    read_waker: Cell<Option<Waker>>,
+}
+
+impl Shared {
+    #[inline]
+    fn ptr(&self, offset: usize) -> *mut u8 {
+        self.buf[offset].get()
+    }
+
+    // Find a contiguous free region of `len` bytes.
+    fn try_reserve(&self, len: usize) -> Option<usize> {
+        let (read, write) = (self.read.get(), self.write.get());
+        if self.inverted.get() {
+            return (read - write >= len).then_some(write);
+        }
+        if read == write && !self.reading.get() {
+            // empty, start over from the beginning
+            self.read.set(0);
+            self.write.set(0);
+            return (self.buf.len() >= len).then_some(0);
+        }
+        if self.buf.len() - write >= len {
+            Some(write)
+        } else if read > len {
+            // wrap around, keep `write < read` after commit
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    // The contiguous readable region.
+    fn readable(&self) -> (usize, usize) {
+        if self.inverted.get() && self.read.get() == self.watermark.get() {
+            self.read.set(0);
+            self.inverted.set(false);
+        }
+        let end = if self.inverted.get() {
+            self.watermark.get()
+        } else {
+            self.write.get()
+        };
+        (self.read.get(), end)
+    }
+
+    fn wake_writer(&self) {
+        if let Some(waker) = self.write_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_reader(&self) {
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Writing half of a [`bip_buffer`].
+pub struct BipWriter {
+    shared: Rc<Shared>,
+}
+
+impl BipWriter {
+    /// Wait until `len` contiguous bytes are free and lend them out.
+    ///
+    /// Returns `None` if the reader is dropped.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `len` is larger than the capacity, or if the
+    /// previous [`WriteGrant`] is still alive.
+    pub async fn reserve(&mut self, len: usize) -> Option<WriteGrant> {
+        let shared = &self.shared;
+        assert!(len <= shared.buf.len(), "reservation exceeds the capacity");
+        assert!(!shared.writing.get(), "a write grant is outstanding");
+        poll_fn(|cx| {
+            if shared.closed.get() {
+                return Poll::Ready(None);
+            }
+            match shared.try_reserve(len) {
+                Some(start) => {
+                    shared.writing.set(true);
+                    Poll::Ready(Some(WriteGrant {
+                        shared: shared.clone(),
+                        start,
+                        len,
+                        init: 0,
+                    }))
+                }
+                None => {
+                    shared.write_waker.set(Some(cx.waker().clone()));
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Total capacity of the ring.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.buf.len()
+    }
+}
+
+impl Drop for BipWriter {
+    fn drop(&mut self) {
+        self.shared.closed.set(true);
+        self.shared.wake_reader();
+    }
+}
+
+/// Reading half of a [`bip_buffer`].
+pub struct BipReader {
+    shared: Rc<Shared>,
+}
+
+impl BipReader {
+    /// Wait until some bytes are committed and lend out the contiguous
+    /// readable region.
+    ///
+    /// Returns `None` if the writer is dropped and all bytes are read.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the previous [`ReadGrant`] is still alive.
+    pub async fn read(&mut self) -> Option<ReadGrant> {
+        let shared = &self.shared;
+        assert!(!shared.reading.get(), "a read grant is outstanding");
+        poll_fn(|cx| {
+            let (start, end) = shared.readable();
+            if start < end {
+                shared.reading.set(true);
+                return Poll::Ready(Some(ReadGrant {
+                    shared: shared.clone(),
+                    start,
+                    len: end - start,
+                }));
+            }
+            if shared.closed.get() {
+                return Poll::Ready(None);
+            }
+            shared.read_waker.set(Some(cx.waker().clone()));
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Drop for BipReader {
+    fn drop(&mut self) {
+        self.shared.closed.set(true);
+        self.shared.wake_writer();
+    }
+}
+
+/// A writable region lent out by [`BipWriter::reserve`].
+///
+/// Nothing is published to the reader until [`commit`](WriteGrant::commit)
+/// is called. Dropping the grant commits nothing.
+pub struct WriteGrant {
+    shared: Rc<Shared>,
+    start: usize,
+    len: usize,
+    // set by io operations through `IoBufMut`
+    init: usize,
+}
+
+impl WriteGrant {
+    /// Publish the first `used` bytes of the grant to the reader.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `used` is larger than the grant.
+    pub fn commit(self, used: usize) {
+        assert!(used <= self.len, "commit exceeds the grant");
+        let shared = &self.shared;
+        if self.start == 0 && shared.write.get() != 0 && !shared.inverted.get() {
+            // the grant wrapped around
+            if used == 0 {
+                return;
+            }
+            shared.watermark.set(shared.write.get());
+            shared.inverted.set(true);
+        }
+        shared.write.set(self.start + used);
+        if used > 0 {
+            shared.wake_reader();
+        }
+    }
+
+    /// Number of bytes written by io operations, i.e. the `n` returned by a
+    /// read into this grant.
+    #[inline]
+    pub fn written(&self) -> usize {
+        self.init
+    }
+}
+
+impl Deref for WriteGrant {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.shared.ptr(self.start), self.len) }
+    }
+}
+
+impl DerefMut for WriteGrant {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.shared.ptr(self.start), self.len) }
+    }
+}
+
+unsafe impl IoBufMut for WriteGrant {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.shared.ptr(self.start)
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, pos: usize) {
+        self.init = pos;
+    }
+}
+
+impl Drop for WriteGrant {
+    fn drop(&mut self) {
+        self.shared.writing.set(false);
+    }
+}
+
+impl std::fmt::Debug for WriteGrant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteGrant")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// A readable region lent out by [`BipReader::read`].
+///
+/// The bytes stay in the ring until [`release`](ReadGrant::release)d.
+/// Dropping the grant releases nothing.
+pub struct ReadGrant {
+    shared: Rc<Shared>,
+    start: usize,
+    len: usize,
+}
+
+impl ReadGrant {
+    /// Give the first `used` bytes of the grant back to the writer.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `used` is larger than the grant.
+    pub fn release(self, used: usize) {
+        assert!(used <= self.len, "release exceeds the grant");
+        self.shared.read.set(self.start + used);
+        if used > 0 {
+            self.shared.wake_writer();
+        }
+    }
+}
+
+impl Deref for ReadGrant {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.shared.ptr(self.start), self.len) }
+    }
+}
+
+unsafe impl IoBuf for ReadGrant {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.shared.ptr(self.start)
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+}
+
+impl Drop for ReadGrant {
+    fn drop(&mut self) {
+        self.shared.reading.set(false);
+    }
+}
+
+impl std::fmt::Debug for ReadGrant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadGrant").field("len", &self.len).finish()
+    }
+}
+
+impl std::fmt::Debug for BipWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BipWriter")
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for BipReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BipReader").finish()
+    }
+}
@@ -0,0 +1,14 @@
+//! Synchronization primitives for tasks on the same thread.
+
+mod barrier;
+mod bip_buffer;
+mod single_flight;
+mod wait_group;
+mod wait_map;
+pub mod watch;
+
+pub use barrier::{Barrier, BarrierWaitResult};
+pub use bip_buffer::{bip_buffer, BipReader, BipWriter, ReadGrant, WriteGrant};
+pub use single_flight::SingleFlight;
+pub use wait_group::WaitGroup;
+pub use wait_map::{Wait, WaitMap};
@@ -0,0 +1,143 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::{poll_fn, Future},
+    hash::Hash,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+/// Deduplicate concurrent identical async operations.
+///
+/// While an operation for a key is running, other callers of
+/// [`run`](SingleFlight::run) with the same key wait for it and get a clone
+/// of its result instead of starting their own. Nothing is cached: once the
+/// operation completes, the next call runs it again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::rc::Rc;
+///
+/// use monoio::sync::local::SingleFlight;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let group = Rc::new(SingleFlight::new());
+///     for _ in 0..4 {
+///         let group = group.clone();
+///         monoio::spawn(async move {
+///             // resolved only once for the 4 tasks
+///             let addr = group
+///                 .run("example.com", || async { [93, 184, 216, 34] })
+///                 .await;
+///             println!("{addr:?}");
+///         });
+///     }
+/// }
+/// ```
+pub struct SingleFlight<K, V> {
+    calls: RefCell<HashMap<K, Rc<Call<V>>>>,
+}
+
+struct Call<V> {
+    result: RefCell<Option<V>>,
+    // completed or abandoned by the leader
+    done: Cell<bool>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> SingleFlight<K, V> {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self {
+            calls: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Run `f` for `key`, or wait for the running call of `key` and share its
+    /// result.
+    ///
+    /// If the running call is canceled, one of the waiters runs its own `f`.
+    pub async fn run<F, Fut>(&self, key: K, f: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        loop {
+            let call = self.calls.borrow().get(&key).cloned();
+            let Some(call) = call else {
+                break;
+            };
+            if let Some(v) = wait(&call).await {
+                return v;
+            }
+        }
+
+        let call = Rc::new(Call {
+            result: RefCell::new(None),
+            done: Cell::new(false),
+            waiters: RefCell::new(Vec::new()),
+        });
+        self.calls.borrow_mut().insert(key.clone(), call.clone());
+        let guard = LeaderGuard {
+            group: self,
+            key: &key,
+            call: &call,
+        };
+        let v = f().await;
+        *call.result.borrow_mut() = Some(v.clone());
+        drop(guard);
+        v
+    }
+
+    /// Returns true if a call for `key` is running.
+    pub fn is_running(&self, key: &K) -> bool {
+        self.calls.borrow().contains_key(key)
+    }
+}
+
+// Returns `None` if the leader is canceled.
+async fn wait<V: Clone>(call: &Call<V>) -> Option<V> {
+    poll_fn(|cx| {
+        if call.done.get() {
+            return Poll::Ready(call.result.borrow().clone());
+        }
+        let mut waiters = call.waiters.borrow_mut();
+        if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+struct LeaderGuard<'a, K: Hash + Eq, V> {
+    group: &'a SingleFlight<K, V>,
+    key: &'a K,
+    call: &'a Rc<Call<V>>,
+}
+
+impl<K: Hash + Eq, V> Drop for LeaderGuard<'_, K, V> {
+    fn drop(&mut self) {
+        self.group.calls.borrow_mut().remove(self.key);
+        self.call.done.set(true);
+        for waker in self.call.waiters.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::fmt::Debug for SingleFlight<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("running", &self.calls.borrow().len())
+            .finish()
+    }
+}
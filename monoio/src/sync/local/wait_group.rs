@@ -0,0 +1,110 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+/// Wait for a group of tasks to finish.
+///
+/// Each clone of a `WaitGroup` is a member of the group, and dropping it
+/// marks the member done. [`wait`](WaitGroup::wait) drops the member it is
+/// called on and waits until all other members are dropped.
+///
+/// See `monoio::sync::WaitGroup` for the variant that works across threads.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::sync::local::WaitGroup;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let wg = WaitGroup::new();
+///     for _ in 0..4 {
+///         let wg = wg.clone();
+///         monoio::spawn(async move {
+///             // do the work
+///             drop(wg);
+///         });
+///     }
+///     wg.wait().await;
+/// }
+/// ```
+pub struct WaitGroup {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    count: Cell<usize>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl WaitGroup {
+    /// Create a group with one member.
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                count: Cell::new(1),
+                waiters: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Number of members not done yet.
+    pub fn count(&self) -> usize {
+        self.inner.count.get()
+    }
+
+    /// Mark this member done and wait until all other members are done.
+    pub async fn wait(self) {
+        let inner = self.inner.clone();
+        drop(self);
+        poll_fn(|cx| {
+            if inner.count.get() == 0 {
+                return Poll::Ready(());
+            }
+            let mut waiters = inner.waiters.borrow_mut();
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for WaitGroup {
+    fn clone(&self) -> Self {
+        self.inner.count.set(self.inner.count.get() + 1);
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Drop for WaitGroup {
+    fn drop(&mut self) {
+        let count = self.inner.count.get() - 1;
+        self.inner.count.set(count);
+        if count == 0 {
+            for waker in self.inner.waiters.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// A map of values to be waited on by key.
+///
+/// A task registers interest in a key with [`wait`](WaitMap::wait), and a
+/// producer fulfills it with [`complete`](WaitMap::complete). This is the
+/// usual way to correlate responses with requests in a multiplexed protocol
+/// client: the requester waits on the request id, and the connection reader
+/// completes the id when the response arrives.
+///
+/// Dropping a [`Wait`] removes its entry, so a request that is timed out or
+/// canceled does not leak, and a late response is handed back to the
+/// producer.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::rc::Rc;
+///
+/// use monoio::sync::local::WaitMap;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let pending = Rc::new(WaitMap::new());
+///     // register before sending the request, so the response can not be missed
+///     let response = pending.wait(1_u64);
+///     let reader = pending.clone();
+///     monoio::spawn(async move {
+///         reader.complete(&1, "pong").unwrap();
+///     });
+///     assert_eq!(response.await, Some("pong"));
+/// }
+/// ```
+pub struct WaitMap<K, V> {
+    slots: RefCell<HashMap<K, Slot<V>>>,
+    next_id: Cell<u64>,
+}
+
+struct Slot<V> {
+    // identifies the `Wait` owning the slot
+    id: u64,
+    state: State<V>,
+}
+
+enum State<V> {
+    Waiting(Option<Waker>),
+    Ready(V),
+    Canceled,
+}
+
+impl<K: Hash + Eq + Clone, V> WaitMap<K, V> {
+    /// Create an empty `WaitMap`.
+    pub fn new() -> Self {
+        Self {
+            slots: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+        }
+    }
+
+    /// Register a waiter on `key` and return a future resolving to the value
+    /// passed to [`complete`](WaitMap::complete), or `None` if it is canceled.
+    ///
+    /// The waiter is registered when this method is called, not when the
+    /// future is first polled. If `key` is already waited on, the previous
+    /// waiter is canceled.
+    pub fn wait(&self, key: K) -> Wait<'_, K, V> {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        let prev = self.slots.borrow_mut().insert(
+            key.clone(),
+            Slot {
+                id,
+                state: State::Waiting(None),
+            },
+        );
+        if let Some(Slot {
+            state: State::Waiting(Some(waker)),
+            ..
+        }) = prev
+        {
+            waker.wake();
+        }
+        Wait {
+            map: self,
+            key,
+            id,
+            done: false,
+        }
+    }
+
+    /// Complete the waiter on `key` with `value`.
+    ///
+    /// Returns the value back if nobody is waiting on `key`, e.g. because the
+    /// waiter was dropped or the key was already completed.
+    pub fn complete(&self, key: &K, value: V) -> Result<(), V> {
+        let mut slots = self.slots.borrow_mut();
+        match slots.get_mut(key) {
+            Some(slot) if matches!(slot.state, State::Waiting(_)) => {
+                if let State::Waiting(Some(waker)) =
+                    std::mem::replace(&mut slot.state, State::Ready(value))
+                {
+                    waker.wake();
+                }
+                Ok(())
+            }
+            _ => Err(value),
+        }
+    }
+
+    /// Cancel the waiter on `key`, which then resolves to `None`.
+    ///
+    /// Returns true if there was a waiter to cancel.
+    pub fn cancel(&self, key: &K) -> bool {
+        let mut slots = self.slots.borrow_mut();
+        match slots.get_mut(key) {
+            Some(slot) if matches!(slot.state, State::Waiting(_)) => {
+                if let State::Waiting(Some(waker)) =
+                    std::mem::replace(&mut slot.state, State::Canceled)
+                {
+                    waker.wake();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Cancel all waiters, e.g. when the connection is closed.
+    pub fn cancel_all(&self) {
+        let mut wakers = Vec::new();
+        for slot in self.slots.borrow_mut().values_mut() {
+            if let State::Waiting(waker) = &mut slot.state {
+                wakers.extend(waker.take());
+                slot.state = State::Canceled;
+            }
+        }
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns true if `key` is waited on and not completed yet.
+    pub fn contains_key(&self, key: &K) -> bool {
+        matches!(
+            self.slots.borrow().get(key),
+            Some(Slot {
+                state: State::Waiting(_),
+                ..
+            })
+        )
+    }
+
+    /// Number of registered waiters, including the completed ones which have
+    /// not taken their value yet.
+    pub fn len(&self) -> usize {
+        self.slots.borrow().len()
+    }
+
+    /// Returns true if there is no registered waiter.
+    pub fn is_empty(&self) -> bool {
+        self.slots.borrow().is_empty()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for WaitMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::fmt::Debug for WaitMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitMap")
+            .field("len", &self.slots.borrow().len())
+            .finish()
+    }
+}
+
+/// Future returned by [`WaitMap::wait`].
+///
+/// Dropping it removes the waiter from the map.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a, K: Hash + Eq, V> {
+    map: &'a WaitMap<K, V>,
+    key: K,
+    id: u64,
+    done: bool,
+}
+
+// No field is structurally pinned.
+impl<K: Hash + Eq, V> Unpin for Wait<'_, K, V> {}
+
+impl<K: Hash + Eq, V> Future for Wait<'_, K, V> {
+    type Output = Option<V>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let mut slots = this.map.slots.borrow_mut();
+        let slot = match slots.get_mut(&this.key) {
+            Some(slot) if slot.id == this.id => slot,
+            // replaced by a later waiter
+            _ => {
+                this.done = true;
+                return Poll::Ready(None);
+            }
+        };
+        match &mut slot.state {
+            State::Waiting(waker) => {
+                match waker {
+                    Some(w) if w.will_wake(cx.waker()) => {}
+                    _ => *waker = Some(cx.waker().clone()),
+                }
+                Poll::Pending
+            }
+            _ => {
+                this.done = true;
+                match slots.remove(&this.key).map(|slot| slot.state) {
+                    Some(State::Ready(value)) => Poll::Ready(Some(value)),
+                    _ => Poll::Ready(None),
+                }
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Drop for Wait<'_, K, V> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        let mut slots = self.map.slots.borrow_mut();
+        if matches!(slots.get(&self.key), Some(slot) if slot.id == self.id) {
+            slots.remove(&self.key);
+        }
+    }
+}
+
+impl<K: Hash + Eq + std::fmt::Debug, V> std::fmt::Debug for Wait<'_, K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Wait").field("key", &self.key).finish()
+    }
+}
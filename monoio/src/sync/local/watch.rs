@@ -0,0 +1,235 @@
+//! A single-producer, multi-consumer channel that only retains the last sent
+//! value, for tasks on the same thread.
+//!
+//! Receivers are notified when a new value is sent, but only observe the
+//! latest one, which suits propagating configuration or state snapshots. See
+//! `monoio::sync::watch` for the variant that works across threads.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use monoio::sync::local::watch;
+//!
+//! #[monoio::main]
+//! async fn main() {
+//!     let (tx, mut rx) = watch::channel("v1");
+//!     monoio::spawn(async move {
+//!         while rx.changed().await.is_ok() {
+//!             println!("config changed to {}", *rx.borrow());
+//!         }
+//!     });
+//!     tx.send("v2");
+//! }
+//! ```
+
+use std::{
+    cell::{Cell, Ref, RefCell},
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+/// Error returned by [`Receiver::changed`] and [`Receiver::has_changed`]
+/// when the sender is dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError(pub(crate) ());
+
+impl std::fmt::Display for RecvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "watch sender dropped")
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// Create a watch channel with an initial value.
+///
+/// The initial value is considered seen by the returned receiver.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Rc::new(Shared {
+        value: RefCell::new(init),
+        version: Cell::new(0),
+        closed: Cell::new(false),
+        receivers: Cell::new(1),
+        waiters: RefCell::new(Vec::new()),
+    });
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared, seen: 0 },
+    )
+}
+
+struct Shared<T> {
+    value: RefCell<T>,
+    version: Cell<u64>,
+    closed: Cell<bool>,
+    receivers: Cell<usize>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl<T> Shared<T> {
+    fn notify(&self) {
+        for waker in self.waiters.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Sending half of a local watch channel.
+pub struct Sender<T> {
+    shared: Rc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Store a new value and notify all receivers.
+    ///
+    /// The value is stored even if there is no receiver, so receivers
+    /// created later with [`subscribe`](Sender::subscribe) observe it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if a [`Ref`] returned by `borrow` is alive.
+    pub fn send(&self, value: T) {
+        self.send_replace(value);
+    }
+
+    /// Store a new value, notify all receivers and return the previous value.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if a [`Ref`] returned by `borrow` is alive.
+    pub fn send_replace(&self, value: T) -> T {
+        let prev = self.shared.value.replace(value);
+        self.shared.version.set(self.shared.version.get() + 1);
+        self.shared.notify();
+        prev
+    }
+
+    /// Modify the value in place and notify all receivers.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if a [`Ref`] returned by `borrow` is alive.
+    pub fn send_modify<F: FnOnce(&mut T)>(&self, modify: F) {
+        modify(&mut self.shared.value.borrow_mut());
+        self.shared.version.set(self.shared.version.get() + 1);
+        self.shared.notify();
+    }
+
+    /// Borrow the current value.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.value.borrow()
+    }
+
+    /// Create a new receiver, which has seen the current value.
+    pub fn subscribe(&self) -> Receiver<T> {
+        self.shared.receivers.set(self.shared.receivers.get() + 1);
+        Receiver {
+            shared: self.shared.clone(),
+            seen: self.shared.version.get(),
+        }
+    }
+
+    /// Number of alive receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.receivers.get()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.closed.set(true);
+        self.shared.notify();
+    }
+}
+
+/// Receiving half of a local watch channel.
+///
+/// Cloning a receiver keeps the seen state.
+pub struct Receiver<T> {
+    shared: Rc<Shared<T>>,
+    seen: u64,
+}
+
+impl<T> Receiver<T> {
+    /// Borrow the latest value without marking it as seen.
+    ///
+    /// The returned [`Ref`] must not be held across an await point, or the
+    /// sender will panic on the next send.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.value.borrow()
+    }
+
+    /// Borrow the latest value and mark it as seen.
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        self.seen = self.shared.version.get();
+        self.shared.value.borrow()
+    }
+
+    /// Returns true if a value is sent since it was last seen.
+    ///
+    /// Returns an error if the sender is dropped.
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        if self.shared.closed.get() {
+            return Err(RecvError(()));
+        }
+        Ok(self.shared.version.get() != self.seen)
+    }
+
+    /// Wait for a value not seen yet and mark it as seen.
+    ///
+    /// Returns an error if the sender is dropped and there is no unseen
+    /// value.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        poll_fn(|cx| {
+            let version = self.shared.version.get();
+            if version != self.seen {
+                self.seen = version;
+                return Poll::Ready(Ok(()));
+            }
+            if self.shared.closed.get() {
+                return Poll::Ready(Err(RecvError(())));
+            }
+            let mut waiters = self.shared.waiters.borrow_mut();
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.set(self.shared.receivers.get() + 1);
+        Self {
+            shared: self.shared.clone(),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receivers.set(self.shared.receivers.get() - 1);
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &*self.shared.value.borrow())
+            .finish()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("value", &*self.shared.value.borrow())
+            .finish()
+    }
+}
@@ -0,0 +1,15 @@
+//! Synchronization primitives.
+//!
+//! Primitives under [`local`] are `!Send` and meant to be shared between
+//! tasks of the same runtime, so they need no atomics or locks.
+
+#[cfg(target_os = "linux")]
+pub mod futex;
+pub mod local;
+#[cfg(feature = "sync")]
+mod wait_group;
+#[cfg(feature = "sync")]
+pub mod watch;
+
+#[cfg(feature = "sync")]
+pub use wait_group::WaitGroup;
@@ -0,0 +1,118 @@
+use std::{
+    future::poll_fn,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+};
+
+/// Wait for a group of tasks to finish, across threads.
+///
+/// It works like [`local::WaitGroup`](crate::sync::local::WaitGroup), but the
+/// members can be moved to other threads, e.g. to the runtimes running on
+/// each core, to coordinate their startup or shutdown. The waiting task is
+/// woken through its driver when the last member is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::sync::WaitGroup;
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let wg = WaitGroup::new();
+///     for _ in 0..4 {
+///         let wg = wg.clone();
+///         std::thread::spawn(move || {
+///             let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+///                 .build()
+///                 .unwrap();
+///             rt.block_on(async move {
+///                 // initialize the worker, then report ready
+///                 drop(wg);
+///             });
+///         });
+///     }
+///     wg.wait().await;
+///     println!("all workers ready");
+/// }
+/// ```
+pub struct WaitGroup {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    count: AtomicUsize,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl WaitGroup {
+    /// Create a group with one member.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                count: AtomicUsize::new(1),
+                waiters: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Number of members not done yet.
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::Acquire)
+    }
+
+    /// Mark this member done and wait until all other members are done.
+    pub async fn wait(self) {
+        let inner = self.inner.clone();
+        drop(self);
+        poll_fn(|cx| {
+            // The last member takes the lock after the count reaches zero, so
+            // a waker registered under the lock is never missed.
+            let mut waiters = inner.waiters.lock().unwrap();
+            if inner.count.load(Ordering::Acquire) == 0 {
+                return Poll::Ready(());
+            }
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for WaitGroup {
+    fn clone(&self) -> Self {
+        self.inner.count.fetch_add(1, Ordering::Relaxed);
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Drop for WaitGroup {
+    fn drop(&mut self) {
+        if self.inner.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let waiters = std::mem::take(&mut *self.inner.waiters.lock().unwrap());
+            for waker in waiters {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
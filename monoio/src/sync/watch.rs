@@ -0,0 +1,239 @@
+//! A single-producer, multi-consumer channel that only retains the last sent
+//! value, shared across threads.
+//!
+//! It is typically used to propagate configuration to the runtimes running on
+//! each core: the control thread sends new snapshots, and tasks on every
+//! runtime wait for [`changed`](Receiver::changed). See
+//! [`crate::sync::local::watch`] for the cheaper variant for tasks on the same
+//! thread.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use monoio::sync::watch;
+//!
+//! let (tx, rx) = watch::channel(String::from("v1"));
+//! let handles: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let mut rx = rx.clone();
+//!         std::thread::spawn(move || {
+//!             let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+//!                 .build()
+//!                 .unwrap();
+//!             rt.block_on(async move {
+//!                 while rx.changed().await.is_ok() {
+//!                     println!("config changed to {}", *rx.borrow());
+//!                 }
+//!             })
+//!         })
+//!     })
+//!     .collect();
+//! tx.send(String::from("v2"));
+//! drop(tx);
+//! for h in handles {
+//!     h.join().unwrap();
+//! }
+//! ```
+
+use std::{
+    future::poll_fn,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock, RwLockReadGuard,
+    },
+    task::{Poll, Waker},
+};
+
+pub use crate::sync::local::watch::RecvError;
+
+/// Create a watch channel with an initial value.
+///
+/// The initial value is considered seen by the returned receiver.
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        version: AtomicU64::new(0),
+        closed: AtomicBool::new(false),
+        receivers: AtomicUsize::new(1),
+        waiters: Mutex::new(Vec::new()),
+    });
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared, seen: 0 },
+    )
+}
+
+struct Shared<T> {
+    value: RwLock<T>,
+    version: AtomicU64,
+    closed: AtomicBool,
+    receivers: AtomicUsize,
+    // Receivers register under the lock after checking the version, and the
+    // sender takes the lock after bumping it, so no wakeup is lost.
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl<T> Shared<T> {
+    fn notify(&self) {
+        let waiters = std::mem::take(&mut *self.waiters.lock().unwrap());
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// Sending half of a watch channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Store a new value and notify all receivers.
+    ///
+    /// The value is stored even if there is no receiver, so receivers
+    /// created later with [`subscribe`](Sender::subscribe) observe it.
+    pub fn send(&self, value: T) {
+        self.send_replace(value);
+    }
+
+    /// Store a new value, notify all receivers and return the previous value.
+    pub fn send_replace(&self, value: T) -> T {
+        let prev = {
+            let mut guard = self.shared.value.write().unwrap();
+            self.shared.version.fetch_add(1, Ordering::Release);
+            std::mem::replace(&mut *guard, value)
+        };
+        self.shared.notify();
+        prev
+    }
+
+    /// Modify the value in place and notify all receivers.
+    pub fn send_modify<F: FnOnce(&mut T)>(&self, modify: F) {
+        {
+            let mut guard = self.shared.value.write().unwrap();
+            modify(&mut guard);
+            self.shared.version.fetch_add(1, Ordering::Release);
+        }
+        self.shared.notify();
+    }
+
+    /// Borrow the current value.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read().unwrap()
+    }
+
+    /// Create a new receiver, which has seen the current value.
+    pub fn subscribe(&self) -> Receiver<T> {
+        self.shared.receivers.fetch_add(1, Ordering::Relaxed);
+        Receiver {
+            shared: self.shared.clone(),
+            seen: self.shared.version.load(Ordering::Acquire),
+        }
+    }
+
+    /// Number of alive receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.receivers.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+        self.shared.notify();
+    }
+}
+
+/// Receiving half of a watch channel.
+///
+/// Cloning a receiver keeps the seen state. Receivers can be sent to other
+/// threads and awaited on any runtime.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    seen: u64,
+}
+
+impl<T> Receiver<T> {
+    /// Borrow the latest value without marking it as seen.
+    ///
+    /// The returned guard blocks the sender, so it should not be held across
+    /// an await point.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read().unwrap()
+    }
+
+    /// Borrow the latest value and mark it as seen.
+    pub fn borrow_and_update(&mut self) -> RwLockReadGuard<'_, T> {
+        let value = self.shared.value.read().unwrap();
+        // the version can not change while the read lock is held
+        self.seen = self.shared.version.load(Ordering::Acquire);
+        value
+    }
+
+    /// Returns true if a value is sent since it was last seen.
+    ///
+    /// Returns an error if the sender is dropped.
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        if self.shared.closed.load(Ordering::Acquire) {
+            return Err(RecvError(()));
+        }
+        Ok(self.shared.version.load(Ordering::Acquire) != self.seen)
+    }
+
+    /// Wait for a value not seen yet and mark it as seen.
+    ///
+    /// Returns an error if the sender is dropped and there is no unseen
+    /// value.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        poll_fn(|cx| {
+            let mut waiters = self.shared.waiters.lock().unwrap();
+            let version = self.shared.version.load(Ordering::Acquire);
+            if version != self.seen {
+                self.seen = version;
+                return Poll::Ready(Ok(()));
+            }
+            if self.shared.closed.load(Ordering::Acquire) {
+                return Poll::Ready(Err(RecvError(())));
+            }
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: self.shared.clone(),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receivers.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    any::Any,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// Catch a panic of the future, which is returned as an error.
+///
+/// Tasks are polled without catching panics, so a panic unwinds through the
+/// runtime. Wrap the future of a task with it to keep the runtime running:
+/// on panic the future is dropped right away, and the ops it has in flight
+/// hand their buffers over to the driver until the kernel completes them.
+///
+/// ```
+/// # monoio::start::<monoio::LegacyDriver, _>(async {
+/// let res = monoio::task::catch_unwind(async { panic!("boom") }).await;
+/// assert!(res.is_err());
+/// # });
+/// ```
+pub fn catch_unwind<F: Future>(future: F) -> CatchUnwind<F> {
+    CatchUnwind {
+        inner: Some(future),
+    }
+}
+
+pin_project! {
+    /// Future returned by [`catch_unwind`].
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CatchUnwind<F> {
+        #[pin]
+        inner: Option<F>,
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let inner = this
+            .inner
+            .as_mut()
+            .as_pin_mut()
+            .expect("CatchUnwind polled after completion");
+        match panic::catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => {
+                this.inner.set(None);
+                Poll::Ready(Ok(output))
+            }
+            Err(payload) => {
+                // A panic while dropping is ignored, the first one is returned.
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| this.inner.set(None)));
+                Poll::Ready(Err(payload))
+            }
+        }
+    }
+}
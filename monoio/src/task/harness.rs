@@ -101,6 +101,8 @@ where
         unsafe {
             drop(Box::from_raw(self.cell.as_ptr()));
         }
+        #[cfg(feature = "metrics")]
+        crate::metrics::task_dealloc(std::mem::size_of::<Cell<T, S>>());
     }
 
     #[cfg(feature = "sync")]
@@ -15,6 +15,9 @@ mod join;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::join::JoinHandle;
 
+mod catch_unwind;
+pub use self::catch_unwind::{catch_unwind, CatchUnwind};
+
 mod raw;
 use self::raw::RawTask;
 
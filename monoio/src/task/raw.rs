@@ -53,6 +53,8 @@ impl RawTask {
         T: Future,
         S: Schedule,
     {
+        #[cfg(feature = "metrics")]
+        crate::metrics::task_alloc(std::mem::size_of::<Cell<T, S>>());
         let ptr = Box::into_raw(Cell::new(owner_id, task, scheduler));
         let ptr = unsafe { NonNull::new_unchecked(ptr as *mut Header) };
 
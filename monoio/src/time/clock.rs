@@ -1,13 +1,28 @@
 //! Source of time abstraction.
 //!
-//! By default, `std::time::Instant::now()` is used. However, when the
-//! `test-util` feature flag is enabled, the values returned for `now()` are
-//! configurable.
+//! By default, `std::time::Instant::now()` is used. A custom [`ClockSource`]
+//! can be injected with `RuntimeBuilder::with_clock`.
+
+use std::{fmt, sync::Arc};
 
 use crate::time::Instant;
 
-#[derive(Default, Debug, Clone)]
-pub(crate) struct Clock {}
+/// Source of monotonic time used by the time driver.
+///
+/// The instants returned must be monotonic and consistent with
+/// [`Instant::now`], since user deadlines are created with it. A typical
+/// implementation returns a cached or coarse-grained reading of the monotonic
+/// clock, trading precision for a cheaper read, e.g. with
+/// `CLOCK_MONOTONIC_COARSE` or a TSC calibrated against `Instant::now`.
+pub trait ClockSource: Send + Sync + 'static {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct Clock {
+    source: Option<Arc<dyn ClockSource>>,
+}
 
 pub(crate) fn now() -> Instant {
     Instant::from_std(std::time::Instant::now())
@@ -15,10 +30,27 @@ pub(crate) fn now() -> Instant {
 
 impl Clock {
     pub(crate) fn new() -> Clock {
-        Clock {}
+        Clock { source: None }
+    }
+
+    pub(crate) fn with_source(source: Arc<dyn ClockSource>) -> Clock {
+        Clock {
+            source: Some(source),
+        }
     }
 
     pub(crate) fn now(&self) -> Instant {
-        now()
+        match &self.source {
+            Some(source) => source.now(),
+            None => now(),
+        }
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("custom", &self.source.is_some())
+            .finish()
     }
 }
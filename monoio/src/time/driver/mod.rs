@@ -13,6 +13,7 @@ mod handle;
 pub(crate) use self::handle::Handle;
 
 mod wheel;
+pub(crate) use self::wheel::{DEFAULT_LEVELS, MAX_LEVELS};
 
 pub(super) mod sleep;
 
@@ -160,11 +161,12 @@ where
     /// Creates a new `Driver` instance that uses `park` to block the current
     /// thread and `time_source` to get the current time and convert to ticks.
     ///
-    /// Specifying the source of time is useful when testing.
-    pub(crate) fn new(park: D, clock: Clock) -> TimeDriver<D> {
+    /// Specifying the source of time is useful when testing. `levels` is the
+    /// number of levels of the timing wheel.
+    pub(crate) fn new(park: D, clock: Clock, levels: usize) -> TimeDriver<D> {
         let time_source = ClockTime::new(clock);
 
-        let inner = Inner::new(time_source.clone());
+        let inner = Inner::new(time_source.clone(), levels);
 
         TimeDriver {
             time_source,
@@ -233,6 +235,18 @@ impl Handle {
         self.process_at_time(now)
     }
 
+    /// Returns the number of registered timers.
+    pub(crate) fn timer_count(&self) -> usize {
+        self.get().state.borrow().wheel.len()
+    }
+
+    /// Returns the memory of the timer wheel and the registered timers.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn timer_bytes(&self) -> usize {
+        let state = self.get().state.borrow();
+        state.wheel.allocated_bytes() + state.wheel.len() * std::mem::size_of::<TimerEntry>()
+    }
+
     pub(self) fn process_at_time(&self, mut now: u64) {
         let mut state = self.get().state.borrow_mut();
 
@@ -359,13 +373,13 @@ where
 // ===== impl Inner =====
 
 impl Inner {
-    pub(self) fn new(time_source: ClockTime) -> Self {
+    pub(self) fn new(time_source: ClockTime, levels: usize) -> Self {
         Inner {
             state: RefCell::new(InnerState {
                 time_source,
                 elapsed: 0,
                 next_wake: None,
-                wheel: wheel::Wheel::new(),
+                wheel: wheel::Wheel::new(levels),
             }),
         }
     }
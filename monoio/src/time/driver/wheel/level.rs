@@ -5,6 +5,7 @@ use crate::time::driver::{EntryList, TimerHandle, TimerShared};
 /// Wheel for a single level in the timer. This wheel contains 64 slots.
 pub(crate) struct Level {
     level: usize,
+    top: bool,
     slot_range: u64,
     level_range: u64,
 
@@ -41,7 +42,7 @@ pub(crate) struct Expiration {
 const LEVEL_MULT: usize = 64;
 
 impl Level {
-    pub(crate) fn new(level: usize) -> Level {
+    pub(crate) fn new(level: usize, top: bool) -> Level {
         // A value has to be Copy in order to use syntax like:
         //     let stack = Stack::default();
         //     ...
@@ -58,6 +59,7 @@ impl Level {
 
         Level {
             level,
+            top,
             slot_range: slot_range(level),
             level_range: level_range(level),
             occupied: 0,
@@ -156,7 +158,7 @@ impl Level {
             // arrays.
             //
             // To deal with this, we first limit timers to being scheduled no
-            // more than MAX_DURATION ticks in the future; that is, they're at
+            // more than max_duration ticks in the future; that is, they're at
             // most one rotation of the top level away. Then, we force timers
             // that logically would go into the top+1 level, to instead go into
             // the top level's slots.
@@ -165,7 +167,7 @@ impl Level {
             // pseudo-ring buffer, and we rotate around them indefinitely. If we
             // compute a deadline before now, and it's the top level, it
             // therefore means we're actually looking at a slot in the future.
-            debug_assert_eq!(self.level, super::NUM_LEVELS - 1);
+            debug_assert!(self.top, "level={}", self.level);
 
             deadline += self.level_range;
         }
@@ -36,39 +36,60 @@ pub(crate) struct Wheel {
     /// * ~ 4 min slots / ~ 4 hr range
     /// * ~ 4 hr slots / ~ 12 day range
     /// * ~ 12 day slots / ~ 2 yr range
-    levels: Box<[Level; NUM_LEVELS]>,
+    ///
+    /// The number of levels is configurable, timers further than the range of
+    /// the top level wrap around in it.
+    levels: Box<[Level]>,
+
+    /// The maximum duration that can be tracked without wrapping around.
+    max_duration: u64,
 
     /// Entries queued for firing
     pending: EntryList,
+
+    /// Number of entries registered in the wheel, including pending ones.
+    len: usize,
 }
 
-/// Number of levels. Each level has 64 slots. By using 6 levels with 64 slots
-/// each, the timer is able to track time up to 2 years into the future with a
-/// precision of 1 millisecond.
-const NUM_LEVELS: usize = 6;
+/// Default number of levels. Each level has 64 slots. By using 6 levels with
+/// 64 slots each, the timer is able to track time up to 2 years into the
+/// future with a precision of 1 millisecond.
+pub(crate) const DEFAULT_LEVELS: usize = 6;
 
-/// The maximum duration of a `Sleep`
-pub(super) const MAX_DURATION: u64 = (1 << (6 * NUM_LEVELS)) - 1;
+/// Max number of levels, which tracks time up to 2^60 milliseconds.
+pub(crate) const MAX_LEVELS: usize = 10;
 
 impl Wheel {
-    /// Create a new timing wheel
-    pub(crate) fn new() -> Wheel {
-        let levels = Box::new([
-            Level::new(0),
-            Level::new(1),
-            Level::new(2),
-            Level::new(3),
-            Level::new(4),
-            Level::new(5),
-        ]);
+    /// Create a new timing wheel with the given number of levels.
+    pub(crate) fn new(num_levels: usize) -> Wheel {
+        assert!(
+            (1..=MAX_LEVELS).contains(&num_levels),
+            "timer wheel levels must be between 1 and {MAX_LEVELS}"
+        );
+        let levels = (0..num_levels)
+            .map(|level| Level::new(level, level == num_levels - 1))
+            .collect();
 
         Wheel {
             elapsed: 0,
             levels,
+            max_duration: (1 << (6 * num_levels)) - 1,
             pending: EntryList::new(),
+            len: 0,
         }
     }
 
+    /// Return the number of registered timers.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return the size in bytes of the levels.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        std::mem::size_of_val(&*self.levels)
+    }
+
     /// Return the number of milliseconds that have elapsed since the timing
     /// wheel's creation.
     pub(crate) fn elapsed(&self) -> u64 {
@@ -112,6 +133,7 @@ impl Wheel {
         unsafe {
             self.levels.get_unchecked_mut(level).add_entry(item);
         }
+        self.len += 1;
 
         debug_assert!({
             unsafe { self.levels.get_unchecked(level) }
@@ -125,6 +147,7 @@ impl Wheel {
 
     /// Remove `item` from the timing wheel.
     pub(crate) unsafe fn remove(&mut self, item: NonNull<TimerShared>) {
+        self.len -= 1;
         unsafe {
             let when = item.as_ref().cached_when();
             if when == u64::MAX {
@@ -153,6 +176,7 @@ impl Wheel {
     pub(crate) fn poll(&mut self, now: u64) -> Option<TimerHandle> {
         loop {
             if let Some(handle) = self.pending.pop_back() {
+                self.len -= 1;
                 return Some(handle);
             }
 
@@ -173,7 +197,11 @@ impl Wheel {
             }
         }
 
-        self.pending.pop_back()
+        let handle = self.pending.pop_back();
+        if handle.is_some() {
+            self.len -= 1;
+        }
+        handle
     }
 
     /// Returns the instant at which the next timeout expires.
@@ -188,7 +216,7 @@ impl Wheel {
         }
 
         // Check all levels
-        for level in 0..NUM_LEVELS {
+        for level in 0..self.levels.len() {
             if let Some(expiration) = self.levels[level].next_expiration(self.elapsed) {
                 // There cannot be any expirations at a higher level that happen
                 // before this one.
@@ -211,7 +239,7 @@ impl Wheel {
     fn no_expirations_before(&self, start_level: usize, before: u64) -> bool {
         let mut res = true;
 
-        for l2 in start_level..NUM_LEVELS {
+        for l2 in start_level..self.levels.len() {
             if let Some(e2) = self.levels[l2].next_expiration(self.elapsed) {
                 if e2.deadline < before {
                     res = false;
@@ -232,15 +260,16 @@ impl Wheel {
         // those entries might need to be reinserted into the same slot.
         //
         // This happens only on the highest level, when an entry is inserted
-        // more than MAX_DURATION into the future. When this happens, we wrap
-        // around, and process some entries a multiple of MAX_DURATION before
+        // more than max_duration into the future. When this happens, we wrap
+        // around, and process some entries a multiple of max_duration before
         // they actually need to be dropped down a level. We then reinsert them
         // back into the same position; we must make sure we don't then process
         // those entries again or we'll end up in an infinite loop.
         let mut entries = self.take_entries(expiration);
 
         while let Some(item) = entries.pop_back() {
-            if expiration.level == 0 {
+            // entries of a single level wheel may wrap around
+            if expiration.level == 0 && self.levels.len() > 1 {
                 debug_assert_eq!(unsafe { item.cached_when() }, expiration.deadline);
             }
 
@@ -252,7 +281,7 @@ impl Wheel {
                     self.pending.push_front(item);
                 }
                 Err(expiration_tick) => {
-                    let level = level_for(expiration.deadline, expiration_tick);
+                    let level = level_for(expiration.deadline, expiration_tick, self.max_duration);
                     unsafe {
                         self.levels.get_unchecked_mut(level).add_entry(item);
                     }
@@ -281,20 +310,20 @@ impl Wheel {
     }
 
     fn level_for(&self, when: u64) -> usize {
-        level_for(self.elapsed, when)
+        level_for(self.elapsed, when, self.max_duration)
     }
 }
 
-fn level_for(elapsed: u64, when: u64) -> usize {
+fn level_for(elapsed: u64, when: u64, max_duration: u64) -> usize {
     const SLOT_MASK: u64 = (1 << 6) - 1;
 
     // Mask in the trailing bits ignored by the level calculation in order to cap
     // the possible leading zeros
     let mut masked = elapsed ^ when | SLOT_MASK;
 
-    if masked >= MAX_DURATION {
+    if masked >= max_duration {
         // Fudge the timer into the top level
-        masked = MAX_DURATION - 1;
+        masked = max_duration - 1;
     }
 
     let leading_zeros = masked.leading_zeros() as usize;
@@ -307,10 +336,16 @@ fn level_for(elapsed: u64, when: u64) -> usize {
 mod test {
     use super::*;
 
+    const MAX_DURATION: u64 = (1 << (6 * DEFAULT_LEVELS)) - 1;
+
     #[test]
     fn test_level_for() {
         for pos in 0..64 {
-            assert_eq!(0, level_for(0, pos), "level_for({pos}) -- binary = {pos:b}");
+            assert_eq!(
+                0,
+                level_for(0, pos, MAX_DURATION),
+                "level_for({pos}) -- binary = {pos:b}"
+            );
         }
 
         for level in 1..5 {
@@ -318,7 +353,7 @@ mod test {
                 let a = pos * 64_usize.pow(level as u32);
                 assert_eq!(
                     level,
-                    level_for(0, a as u64),
+                    level_for(0, a as u64, MAX_DURATION),
                     "level_for({a}) -- binary = {a:b}"
                 );
 
@@ -326,7 +361,7 @@ mod test {
                     let a = a - 1;
                     assert_eq!(
                         level,
-                        level_for(0, a as u64),
+                        level_for(0, a as u64, MAX_DURATION),
                         "level_for({a}) -- binary = {a:b}"
                     );
                 }
@@ -335,7 +370,7 @@ mod test {
                     let a = a + 1;
                     assert_eq!(
                         level,
-                        level_for(0, a as u64),
+                        level_for(0, a as u64, MAX_DURATION),
                         "level_for({a}) -- binary = {a:b}"
                     );
                 }
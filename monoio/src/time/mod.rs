@@ -12,6 +12,8 @@
 //!   allowed to execute. If the future or stream does not complete in time, then it is canceled and
 //!   an error is returned.
 //!
+//! * [`sleep_until_system`] and [`at`] schedule on the wall clock instead of the monotonic clock.
+//!
 //! These types are sufficient for handling a large number of scenarios
 //! involving time.
 //!
@@ -88,6 +90,7 @@
 
 mod clock;
 pub(crate) use self::clock::Clock;
+pub use self::clock::ClockSource;
 
 pub(crate) mod driver;
 
@@ -105,10 +108,31 @@ pub use self::instant::Instant;
 mod interval;
 pub use interval::{interval, interval_at, Interval, MissedTickBehavior};
 
+mod scheduler;
+pub use scheduler::{JobBuilder, OverlapPolicy, Scheduler};
+
 mod timeout;
+
+mod wall;
 // Re-export for convenience
 #[doc(no_inline)]
 pub use std::time::Duration;
 
 #[doc(inline)]
 pub use timeout::{timeout, timeout_at, Timeout};
+pub use wall::{at, sleep_until_system, WallInterval, MAX_WALL_STEP};
+
+/// Returns the number of timers registered on the current runtime, or 0 if
+/// the timer is not enabled.
+///
+/// # Panics
+///
+/// This function panics if called outside a monoio runtime.
+pub fn timer_count() -> usize {
+    crate::runtime::CURRENT.with(|cx| {
+        cx.time_handle
+            .as_ref()
+            .map(|handle| handle.timer_count())
+            .unwrap_or(0)
+    })
+}
@@ -0,0 +1,263 @@
+//! Recurring background jobs.
+//!
+//! A [`Scheduler`] runs jobs as local tasks, on a fixed interval or at wall
+//! clock points like a cron schedule, until it is shut down.
+
+use std::{
+    future::{poll_fn, Future},
+    task::Poll,
+    time::SystemTime,
+};
+
+use crate::{
+    sync::local::{
+        watch::{self, Receiver, Sender},
+        WaitGroup,
+    },
+    time::{sleep, sleep_until, Duration, Instant, WallInterval},
+};
+
+/// What to do with the points of a schedule that elapse while a run of the
+/// job is in progress.
+///
+/// Runs of a job never overlap: the next run starts after the previous one
+/// completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Drop the missed points, the next run starts at the next point of the
+    /// schedule.
+    #[default]
+    Skip,
+    /// Start one run right after the previous one completed, for all missed
+    /// points, then follow the schedule again.
+    Queue,
+}
+
+/// Runs recurring jobs as local tasks of the current runtime.
+///
+/// Jobs stop when the scheduler is shut down with [`shutdown`], which waits
+/// for the runs in progress, or when it is dropped, which does not.
+///
+/// The timer must be enabled on the runtime.
+///
+/// [`shutdown`]: Scheduler::shutdown
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::time::{Duration, OverlapPolicy, Scheduler};
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let scheduler = Scheduler::new();
+///     scheduler
+///         .every(Duration::from_secs(60))
+///         .jitter(Duration::from_secs(5))
+///         .spawn(|| async { /* evict expired cache entries */ });
+///     // Every day at 03:00 UTC.
+///     scheduler
+///         .at(Duration::from_secs(86400), Duration::from_secs(3 * 3600))
+///         .overlap(OverlapPolicy::Queue)
+///         .spawn(|| async { /* compact the storage */ });
+///
+///     // serve ...
+///
+///     scheduler.shutdown().await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Scheduler {
+    shutdown: Sender<bool>,
+    jobs: WaitGroup,
+}
+
+impl Scheduler {
+    /// Create a scheduler without jobs.
+    pub fn new() -> Self {
+        Self {
+            shutdown: watch::channel(false).0,
+            jobs: WaitGroup::new(),
+        }
+    }
+
+    /// Configure a job run every `period`, starting one `period` from now.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    pub fn every(&self, period: Duration) -> JobBuilder<'_> {
+        assert!(!period.is_zero(), "`period` must be non-zero.");
+        self.job(Schedule::Every(period, Instant::now() + period))
+    }
+
+    /// Configure a job run at the wall clock points of
+    /// [`time::at(period, offset)`](crate::time::at), like a cron schedule.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    pub fn at(&self, period: Duration, offset: Duration) -> JobBuilder<'_> {
+        self.job(Schedule::Wall(crate::time::at(period, offset)))
+    }
+
+    fn job(&self, schedule: Schedule) -> JobBuilder<'_> {
+        JobBuilder {
+            scheduler: self,
+            schedule,
+            jitter: Duration::ZERO,
+            overlap: OverlapPolicy::default(),
+        }
+    }
+
+    /// Number of jobs not stopped yet.
+    pub fn job_count(&self) -> usize {
+        self.jobs.count() - 1
+    }
+
+    /// Stop scheduling runs, and wait for the runs in progress to complete.
+    pub async fn shutdown(self) {
+        self.shutdown.send(true);
+        self.jobs.wait().await;
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configuration of a job, returned by [`Scheduler::every`] and
+/// [`Scheduler::at`].
+#[derive(Debug)]
+#[must_use = "the job is not scheduled until `spawn` is called"]
+pub struct JobBuilder<'a> {
+    scheduler: &'a Scheduler,
+    schedule: Schedule,
+    jitter: Duration,
+    overlap: OverlapPolicy,
+}
+
+impl JobBuilder<'_> {
+    /// Delay each run by a random duration below `jitter`, so the jobs of
+    /// many instances do not run in lockstep.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set what to do with the points that elapse during a run, the default
+    /// is [`OverlapPolicy::Skip`].
+    pub fn overlap(mut self, overlap: OverlapPolicy) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Spawn the job, `f` is called to create the future of each run.
+    pub fn spawn<F, Fut>(self, f: F)
+    where
+        F: FnMut() -> Fut + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let job = Job {
+            schedule: self.schedule,
+            jitter: self.jitter,
+            overlap: self.overlap,
+            queued: false,
+            point: SystemTime::UNIX_EPOCH,
+        };
+        let shutdown = self.scheduler.shutdown.subscribe();
+        let member = self.scheduler.jobs.clone();
+        drop(crate::spawn(job.run(f, shutdown, member)));
+    }
+}
+
+#[derive(Debug)]
+enum Schedule {
+    // period and next point
+    Every(Duration, Instant),
+    Wall(WallInterval),
+}
+
+struct Job {
+    schedule: Schedule,
+    jitter: Duration,
+    overlap: OverlapPolicy,
+    // a run is due right away
+    queued: bool,
+    // wall clock point of the last run
+    point: SystemTime,
+}
+
+impl Job {
+    async fn run<F, Fut>(mut self, mut f: F, mut shutdown: Receiver<bool>, _member: WaitGroup)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        while until_shutdown(&mut shutdown, self.wait()).await.is_some() {
+            // A run is not interrupted by the shutdown, it is waited for.
+            f().await;
+            self.after_run();
+        }
+    }
+
+    // Wait for the next point of the schedule, unless a run is queued.
+    async fn wait(&mut self) {
+        if std::mem::take(&mut self.queued) {
+            return;
+        }
+        match &mut self.schedule {
+            Schedule::Every(period, next) => {
+                sleep_until(*next).await;
+                *next += *period;
+            }
+            Schedule::Wall(interval) => self.point = interval.tick().await,
+        }
+        if !self.jitter.is_zero() {
+            let ratio = crate::utils::thread_rng_n(u32::MAX) as f64 / u32::MAX as f64;
+            sleep(self.jitter.mul_f64(ratio)).await;
+        }
+    }
+
+    // Skip the points missed during the run, and queue a run for them if
+    // asked to.
+    fn after_run(&mut self) {
+        let missed = match &mut self.schedule {
+            Schedule::Every(period, next) => {
+                let now = Instant::now();
+                let missed = *next <= now;
+                if missed {
+                    let count = (now - *next).as_nanos() / period.as_nanos() + 1;
+                    *next += Duration::from_nanos((period.as_nanos() * count) as u64);
+                }
+                missed
+            }
+            // The interval skips the missed points by itself.
+            Schedule::Wall(interval) => SystemTime::now()
+                .duration_since(self.point)
+                .is_ok_and(|elapsed| elapsed >= interval.period()),
+        };
+        self.queued = missed && self.overlap == OverlapPolicy::Queue;
+    }
+}
+
+// Returns None if the scheduler is shut down or dropped before `fut`
+// completes.
+async fn until_shutdown<F: Future>(shutdown: &mut Receiver<bool>, fut: F) -> Option<F::Output> {
+    if *shutdown.borrow() || shutdown.has_changed().is_err() {
+        return None;
+    }
+    let mut fut = std::pin::pin!(fut);
+    let mut changed = std::pin::pin!(shutdown.changed());
+    poll_fn(|cx| {
+        if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+            return Poll::Ready(Some(out));
+        }
+        if changed.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await
+}
@@ -0,0 +1,132 @@
+//! Wall-clock based scheduling.
+//!
+//! The time driver runs on the monotonic clock, so a plain [`sleep`] does not
+//! follow wall clock adjustments. The utilities here sleep in bounded steps
+//! and re-read the wall clock after each of them, so a jump of the system time
+//! is observed within [`MAX_WALL_STEP`].
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::time::{sleep, Duration};
+
+/// Max time to sleep before the wall clock is checked again.
+pub const MAX_WALL_STEP: Duration = Duration::from_secs(1);
+
+/// Waits until the system time reaches `deadline`.
+///
+/// Returns immediately if `deadline` is in the past. If the system time is
+/// adjusted while waiting, the new time is respected, with a delay of at most
+/// [`MAX_WALL_STEP`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::{Duration, SystemTime};
+///
+/// use monoio::time::sleep_until_system;
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     sleep_until_system(SystemTime::now() + Duration::from_secs(10)).await;
+/// }
+/// ```
+pub async fn sleep_until_system(deadline: SystemTime) {
+    loop {
+        match deadline.duration_since(SystemTime::now()) {
+            Ok(remaining) if !remaining.is_zero() => {
+                sleep(remaining.min(MAX_WALL_STEP)).await;
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Creates a [`WallInterval`] which fires at every wall clock time `t` such
+/// that `(t - UNIX_EPOCH - offset)` is a multiple of `period`, like a
+/// cron schedule.
+///
+/// For example, `at(Duration::from_secs(86400), Duration::from_secs(3 * 3600))`
+/// fires every day at 03:00 UTC, and `at(Duration::from_secs(60),
+/// Duration::ZERO)` fires at the start of every minute.
+///
+/// # Panics
+///
+/// This function panics if `period` is zero.
+pub fn at(period: Duration, offset: Duration) -> WallInterval {
+    assert!(!period.is_zero(), "`period` must be non-zero.");
+    WallInterval {
+        period: period.as_nanos(),
+        offset: offset.as_nanos() % period.as_nanos(),
+        last: None,
+    }
+}
+
+/// Wall clock schedule returned by [`at`].
+#[derive(Debug)]
+pub struct WallInterval {
+    period: u128,
+    offset: u128,
+    // last fired point, in nanoseconds since UNIX_EPOCH
+    last: Option<u128>,
+}
+
+impl WallInterval {
+    /// Waits until the next scheduled point and returns it.
+    ///
+    /// Points that are missed, e.g. because the task was busy or the clock
+    /// jumped forward, are skipped. A point is never returned twice, even if
+    /// the clock jumps backward.
+    pub async fn tick(&mut self) -> SystemTime {
+        let now = since_epoch(SystemTime::now());
+        let mut next = self.next_after(now);
+        if let Some(last) = self.last {
+            if next <= last {
+                next = last + self.period;
+            }
+        }
+        let point = UNIX_EPOCH + Duration::from_nanos(next as u64);
+        sleep_until_system(point).await;
+        self.last = Some(next);
+        point
+    }
+
+    pub(crate) fn period(&self) -> Duration {
+        Duration::from_nanos(self.period as u64)
+    }
+
+    // The first point strictly after `now`, unless `now` is exactly on one.
+    fn next_after(&self, now: u128) -> u128 {
+        let since = now + self.period - self.offset;
+        let rem = since % self.period;
+        if rem == 0 {
+            now
+        } else {
+            now + self.period - rem
+        }
+    }
+}
+
+fn since_epoch(t: SystemTime) -> u128 {
+    t.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_point() {
+        let minute = Duration::from_secs(60);
+        let i = at(minute, Duration::from_secs(15));
+        let s = |secs: u64| Duration::from_secs(secs).as_nanos();
+        assert_eq!(i.next_after(s(0)), s(15));
+        assert_eq!(i.next_after(s(15)), s(15));
+        assert_eq!(i.next_after(s(16)), s(75));
+        assert_eq!(i.next_after(s(3600)), s(3615));
+
+        let i = at(minute, minute + Duration::from_secs(1));
+        assert_eq!(i.next_after(s(2)), s(61));
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::poll_fn,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+const DEFAULT_CAPACITY: usize = 64;
+
+/// A task that owns a state and handles messages sent to its [`Addr`] one at
+/// a time.
+///
+/// This formalizes the "task owns the resource" pattern: instead of sharing
+/// a connection or a cache behind a `RefCell`, a single task owns it, and
+/// other tasks talk to it through messages. The mailbox is bounded, so
+/// senders wait when the actor falls behind.
+///
+/// The actor stops, dropping its state, when all its addresses are dropped
+/// and the mailbox is drained.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::utils::{LocalActor, Reply};
+///
+/// enum Msg {
+///     Incr(u64),
+///     Get(Reply<u64>),
+/// }
+///
+/// #[monoio::main]
+/// async fn main() {
+///     let addr = LocalActor::spawn(0_u64, async |count: &mut u64, msg: Msg| match msg {
+///         Msg::Incr(n) => *count += n,
+///         Msg::Get(reply) => reply.send(*count),
+///     });
+///     addr.send(Msg::Incr(2)).await.unwrap();
+///     assert_eq!(addr.call(Msg::Get).await.unwrap(), 2);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LocalActor(());
+
+impl LocalActor {
+    /// Spawn an actor owning `state` on the current runtime, with a mailbox
+    /// of 64 messages.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn spawn<S, M, H>(state: S, handler: H) -> Addr<M>
+    where
+        S: 'static,
+        M: 'static,
+        H: AsyncFnMut(&mut S, M) + 'static,
+    {
+        Self::spawn_with_capacity(DEFAULT_CAPACITY, state, handler)
+    }
+
+    /// Spawn an actor owning `state` on the current runtime, with a mailbox
+    /// of `capacity` messages.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `capacity` is zero, or if called outside a
+    /// monoio runtime.
+    pub fn spawn_with_capacity<S, M, H>(capacity: usize, mut state: S, mut handler: H) -> Addr<M>
+    where
+        S: 'static,
+        M: 'static,
+        H: AsyncFnMut(&mut S, M) + 'static,
+    {
+        assert!(capacity > 0, "capacity must be non-zero");
+        let mailbox = Rc::new(Mailbox {
+            queue: RefCell::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            addrs: Cell::new(1),
+            stopped: Cell::new(false),
+            actor: Cell::new(None),
+            senders: RefCell::new(Vec::new()),
+        });
+        let rx = mailbox.clone();
+        crate::spawn(async move {
+            let _stop = StopGuard(&rx);
+            while let Some(msg) = rx.recv().await {
+                handler(&mut state, msg).await;
+            }
+        });
+        Addr { mailbox }
+    }
+}
+
+struct Mailbox<M> {
+    queue: RefCell<VecDeque<M>>,
+    capacity: usize,
+    addrs: Cell<usize>,
+    stopped: Cell<bool>,
+    actor: Cell<Option<Waker>>,
+    senders: RefCell<Vec<Waker>>,
+}
+
+impl<M> Mailbox<M> {
+    async fn recv(&self) -> Option<M> {
+        poll_fn(|cx| {
+            let msg = self.queue.borrow_mut().pop_front();
+            if let Some(msg) = msg {
+                // wake the senders waiting for room
+                for waker in self.senders.take() {
+                    waker.wake();
+                }
+                return Poll::Ready(Some(msg));
+            }
+            if self.addrs.get() == 0 {
+                return Poll::Ready(None);
+            }
+            self.actor.set(Some(cx.waker().clone()));
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn wake_actor(&self) {
+        if let Some(waker) = self.actor.take() {
+            waker.wake();
+        }
+    }
+}
+
+// Marks the mailbox stopped when the actor task ends or is dropped.
+struct StopGuard<'a, M>(&'a Mailbox<M>);
+
+impl<M> Drop for StopGuard<'_, M> {
+    fn drop(&mut self) {
+        self.0.stopped.set(true);
+        // dropping the messages may drop replies, failing the calls
+        drop(self.0.queue.take());
+        for waker in self.0.senders.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Address of a [`LocalActor`], used to send it messages.
+pub struct Addr<M> {
+    mailbox: Rc<Mailbox<M>>,
+}
+
+impl<M> Addr<M> {
+    /// Send a message, waiting while the mailbox is full.
+    ///
+    /// Returns the message back if the actor is stopped.
+    pub async fn send(&self, msg: M) -> Result<(), SendError<M>> {
+        let mut msg = Some(msg);
+        poll_fn(|cx| match self.try_send(msg.take().unwrap()) {
+            Err(TrySendError::Full(m)) => {
+                msg = Some(m);
+                let mut senders = self.mailbox.senders.borrow_mut();
+                if !senders.iter().any(|w| w.will_wake(cx.waker())) {
+                    senders.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+            Err(TrySendError::Stopped(m)) => Poll::Ready(Err(SendError(m))),
+            Ok(()) => Poll::Ready(Ok(())),
+        })
+        .await
+    }
+
+    /// Send a message if there is room in the mailbox.
+    pub fn try_send(&self, msg: M) -> Result<(), TrySendError<M>> {
+        let mailbox = &self.mailbox;
+        if mailbox.stopped.get() {
+            return Err(TrySendError::Stopped(msg));
+        }
+        let mut queue = mailbox.queue.borrow_mut();
+        if queue.len() >= mailbox.capacity {
+            return Err(TrySendError::Full(msg));
+        }
+        queue.push_back(msg);
+        drop(queue);
+        mailbox.wake_actor();
+        Ok(())
+    }
+
+    /// Send a message carrying a [`Reply`] and wait for the answer.
+    ///
+    /// Returns an error if the actor is stopped or drops the reply without
+    /// answering.
+    pub async fn call<R, F>(&self, make_msg: F) -> Result<R, CallError>
+    where
+        F: FnOnce(Reply<R>) -> M,
+    {
+        let slot = Rc::new(ReplySlot {
+            value: Cell::new(None),
+            done: Cell::new(false),
+            waker: Cell::new(None),
+        });
+        let reply = Reply { slot: slot.clone() };
+        self.send(make_msg(reply))
+            .await
+            .map_err(|_| CallError(()))?;
+        poll_fn(|cx| {
+            if let Some(value) = slot.value.take() {
+                return Poll::Ready(Ok(value));
+            }
+            if slot.done.get() {
+                return Poll::Ready(Err(CallError(())));
+            }
+            slot.waker.set(Some(cx.waker().clone()));
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Returns true if the actor is stopped.
+    pub fn is_stopped(&self) -> bool {
+        self.mailbox.stopped.get()
+    }
+
+    /// Number of messages waiting in the mailbox.
+    pub fn pending(&self) -> usize {
+        self.mailbox.queue.borrow().len()
+    }
+}
+
+impl<M> Clone for Addr<M> {
+    fn clone(&self) -> Self {
+        self.mailbox.addrs.set(self.mailbox.addrs.get() + 1);
+        Self {
+            mailbox: self.mailbox.clone(),
+        }
+    }
+}
+
+impl<M> Drop for Addr<M> {
+    fn drop(&mut self) {
+        let addrs = self.mailbox.addrs.get() - 1;
+        self.mailbox.addrs.set(addrs);
+        if addrs == 0 {
+            self.mailbox.wake_actor();
+        }
+    }
+}
+
+impl<M> std::fmt::Debug for Addr<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Addr")
+            .field("pending", &self.pending())
+            .field("stopped", &self.is_stopped())
+            .finish()
+    }
+}
+
+struct ReplySlot<R> {
+    value: Cell<Option<R>>,
+    // the reply is sent or dropped
+    done: Cell<bool>,
+    waker: Cell<Option<Waker>>,
+}
+
+/// Answer handle of [`Addr::call`], to be put into the message.
+pub struct Reply<R> {
+    slot: Rc<ReplySlot<R>>,
+}
+
+impl<R> Reply<R> {
+    /// Answer the call.
+    pub fn send(self, value: R) {
+        self.slot.value.set(Some(value));
+    }
+}
+
+impl<R> Drop for Reply<R> {
+    fn drop(&mut self) {
+        self.slot.done.set(true);
+        if let Some(waker) = self.slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for Reply<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reply").finish()
+    }
+}
+
+/// Error returned by [`Addr::send`] when the actor is stopped.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct SendError<M>(pub M);
+
+impl<M> std::fmt::Debug for SendError<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SendError(..)")
+    }
+}
+
+impl<M> std::fmt::Display for SendError<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("actor stopped")
+    }
+}
+
+impl<M> std::error::Error for SendError<M> {}
+
+/// Error returned by [`Addr::try_send`].
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TrySendError<M> {
+    /// The mailbox is full.
+    Full(M),
+    /// The actor is stopped.
+    Stopped(M),
+}
+
+impl<M> std::fmt::Debug for TrySendError<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Stopped(_) => f.write_str("Stopped(..)"),
+        }
+    }
+}
+
+impl<M> std::fmt::Display for TrySendError<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("mailbox full"),
+            TrySendError::Stopped(_) => f.write_str("actor stopped"),
+        }
+    }
+}
+
+impl<M> std::error::Error for TrySendError<M> {}
+
+/// Error returned by [`Addr::call`] when the actor is stopped or drops the
+/// reply.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CallError(());
+
+impl std::fmt::Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("actor stopped or dropped the reply")
+    }
+}
+
+impl std::error::Error for CallError {}
@@ -0,0 +1,270 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+/// Local circuit breaker protecting an upstream dependency.
+///
+/// The breaker starts closed and lets every call through. After
+/// `failure_threshold` consecutive failures it opens, and calls are rejected
+/// without reaching the dependency. Once `open_timeout` elapsed it becomes
+/// half-open and lets up to `half_open_probes` calls through as probes: a
+/// failed probe opens it again, and when all of them succeed it closes.
+///
+/// The transition out of the open state is driven by a task sleeping on the
+/// timer, so the timer must be enabled. Clones share the same state.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monoio::{
+///     net::TcpStream,
+///     utils::{Breaker, BreakerError},
+/// };
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let breaker = Breaker::new(5, Duration::from_secs(10));
+///     match breaker.call(TcpStream::connect("127.0.0.1:8080")).await {
+///         Ok(_stream) => {}
+///         Err(BreakerError::Open) => println!("upstream is down"),
+///         Err(BreakerError::Inner(e)) => println!("connect failed: {e}"),
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Breaker {
+    shared: Rc<Shared>,
+}
+
+struct Shared {
+    failure_threshold: u32,
+    open_timeout: Duration,
+    half_open_probes: u32,
+    state: Cell<BreakerState>,
+    // Bumped on every transition, so outcomes of permits acquired in a
+    // previous state are ignored.
+    epoch: Cell<u64>,
+    failures: Cell<u32>,
+    probes_running: Cell<u32>,
+    probes_succeeded: Cell<u32>,
+}
+
+/// State of a [`Breaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Calls go through.
+    Closed,
+    /// Calls are rejected.
+    Open,
+    /// A limited number of probe calls go through.
+    HalfOpen,
+}
+
+impl Breaker {
+    /// Create a breaker opening after `failure_threshold` consecutive
+    /// failures, for `open_timeout`. It closes again after a single
+    /// successful probe, see [`half_open_probes`](Self::half_open_probes).
+    pub fn new(failure_threshold: u32, open_timeout: Duration) -> Self {
+        Self {
+            shared: Rc::new(Shared {
+                failure_threshold: failure_threshold.max(1),
+                open_timeout,
+                half_open_probes: 1,
+                state: Cell::new(BreakerState::Closed),
+                epoch: Cell::new(0),
+                failures: Cell::new(0),
+                probes_running: Cell::new(0),
+                probes_succeeded: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Set the number of successful probes needed to close the breaker,
+    /// which is also the number of probes allowed at the same time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the breaker has been cloned.
+    #[must_use]
+    pub fn half_open_probes(mut self, probes: u32) -> Self {
+        Rc::get_mut(&mut self.shared)
+            .expect("breaker must be configured before it is cloned")
+            .half_open_probes = probes.max(1);
+        self
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> BreakerState {
+        self.shared.state.get()
+    }
+
+    /// Run `fut` if the breaker allows it, and record its outcome.
+    ///
+    /// If `fut` is dropped before completion, nothing is recorded.
+    pub async fn call<T, E, F>(&self, fut: F) -> Result<T, BreakerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        let permit = self.try_acquire().ok_or(BreakerError::Open)?;
+        match fut.await {
+            Ok(v) => {
+                permit.success();
+                Ok(v)
+            }
+            Err(e) => {
+                permit.failure();
+                Err(BreakerError::Inner(e))
+            }
+        }
+    }
+
+    /// Acquire a permit to make a call, or `None` if the breaker rejects it.
+    /// The outcome of the call is reported through the permit.
+    pub fn try_acquire(&self) -> Option<Permit> {
+        let shared = &self.shared;
+        match shared.state.get() {
+            BreakerState::Closed => {}
+            BreakerState::Open => return None,
+            BreakerState::HalfOpen => {
+                let running = shared.probes_running.get() + shared.probes_succeeded.get();
+                if running >= shared.half_open_probes {
+                    return None;
+                }
+                shared.probes_running.set(shared.probes_running.get() + 1);
+            }
+        }
+        Some(Permit {
+            shared: self.shared.clone(),
+            epoch: shared.epoch.get(),
+            probe: shared.state.get() == BreakerState::HalfOpen,
+        })
+    }
+}
+
+impl std::fmt::Debug for Breaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Breaker")
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+impl Shared {
+    fn transition(self: &Rc<Self>, state: BreakerState) {
+        self.state.set(state);
+        self.epoch.set(self.epoch.get() + 1);
+        self.failures.set(0);
+        self.probes_running.set(0);
+        self.probes_succeeded.set(0);
+        if state == BreakerState::Open {
+            crate::spawn(half_open_after(
+                Rc::downgrade(self),
+                self.epoch.get(),
+                self.open_timeout,
+            ));
+        }
+    }
+}
+
+async fn half_open_after(shared: Weak<Shared>, epoch: u64, timeout: Duration) {
+    crate::time::sleep(timeout).await;
+    if let Some(shared) = shared.upgrade() {
+        if shared.epoch.get() == epoch {
+            shared.transition(BreakerState::HalfOpen);
+        }
+    }
+}
+
+/// Permission to make a call through a [`Breaker`].
+///
+/// Dropping it without reporting an outcome records nothing.
+pub struct Permit {
+    shared: Rc<Shared>,
+    epoch: u64,
+    probe: bool,
+}
+
+impl Permit {
+    /// Report a successful call.
+    pub fn success(self) {
+        let shared = &self.shared;
+        if shared.epoch.get() != self.epoch {
+            return;
+        }
+        if self.probe {
+            shared
+                .probes_succeeded
+                .set(shared.probes_succeeded.get() + 1);
+            if shared.probes_succeeded.get() >= shared.half_open_probes {
+                shared.transition(BreakerState::Closed);
+            }
+        } else {
+            shared.failures.set(0);
+        }
+    }
+
+    /// Report a failed call.
+    pub fn failure(self) {
+        let shared = &self.shared;
+        if shared.epoch.get() != self.epoch {
+            return;
+        }
+        if self.probe {
+            shared.transition(BreakerState::Open);
+        } else {
+            shared.failures.set(shared.failures.get() + 1);
+            if shared.failures.get() >= shared.failure_threshold {
+                shared.transition(BreakerState::Open);
+            }
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let shared = &self.shared;
+        if self.probe && shared.epoch.get() == self.epoch {
+            shared.probes_running.set(shared.probes_running.get() - 1);
+        }
+    }
+}
+
+impl std::fmt::Debug for Permit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Permit")
+            .field("probe", &self.probe)
+            .finish()
+    }
+}
+
+/// Error returned by [`Breaker::call`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BreakerError<E> {
+    /// The breaker rejected the call.
+    Open,
+    /// The call failed.
+    Inner(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for BreakerError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakerError::Open => f.write_str("circuit breaker open"),
+            BreakerError::Inner(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for BreakerError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BreakerError::Open => None,
+            BreakerError::Inner(e) => Some(e),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::{poll_fn, Future},
+    hash::Hash,
+    rc::{Rc, Weak},
+    task::{Poll, Waker},
+    time::{Duration, Instant},
+};
+
+/// A per-core cache with time based eviction and request coalescing.
+///
+/// Entries expire after the time-to-live since insertion, or the
+/// time-to-idle since the last access, whichever comes first. Expired
+/// entries are never returned, and are removed by a background task driven
+/// by the timer.
+///
+/// [`get_or_insert_with`](LocalCache::get_or_insert_with) runs the loader
+/// only once for concurrent misses of the same key; the other callers wait
+/// and share its result.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monoio::utils::LocalCache;
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let cache = LocalCache::new()
+///         .ttl(Duration::from_secs(60))
+///         .tti(Duration::from_secs(10));
+///     let ip = cache
+///         .get_or_insert_with("example.com", || async {
+///             // resolve it
+///             [93, 184, 216, 34]
+///         })
+///         .await;
+///     assert_eq!(cache.get(&"example.com"), Some(ip));
+/// }
+/// ```
+pub struct LocalCache<K, V> {
+    inner: Rc<Inner<K, V>>,
+}
+
+struct Inner<K, V> {
+    entries: RefCell<HashMap<K, Entry<V>>>,
+    ttl: Option<Duration>,
+    tti: Option<Duration>,
+    // keys being loaded, with the tasks waiting for them
+    loading: RefCell<HashMap<K, Vec<Waker>>>,
+    sweeper: Cell<bool>,
+}
+
+struct Entry<V> {
+    value: V,
+    inserted: Instant,
+    accessed: Cell<Instant>,
+}
+
+impl<K, V> Inner<K, V> {
+    fn is_expired(&self, entry: &Entry<V>, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.duration_since(entry.inserted) >= ttl)
+            || self
+                .tti
+                .is_some_and(|tti| now.duration_since(entry.accessed.get()) >= tti)
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: Clone + 'static> LocalCache<K, V> {
+    /// Create a cache without expiration.
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                entries: RefCell::new(HashMap::new()),
+                ttl: None,
+                tti: None,
+                loading: RefCell::new(HashMap::new()),
+                sweeper: Cell::new(false),
+            }),
+        }
+    }
+
+    /// Set the time-to-live: entries expire after `ttl` since insertion.
+    ///
+    /// Must be called before the cache is used or cloned.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the cache is cloned.
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        Rc::get_mut(&mut self.inner)
+            .expect("ttl must be set before the cache is cloned")
+            .ttl = Some(ttl);
+        self
+    }
+
+    /// Set the time-to-idle: entries expire after `tti` since the last
+    /// access.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the cache is cloned.
+    #[must_use]
+    pub fn tti(mut self, tti: Duration) -> Self {
+        Rc::get_mut(&mut self.inner)
+            .expect("tti must be set before the cache is cloned")
+            .tti = Some(tti);
+        self
+    }
+
+    /// Get a clone of the value of `key` if it is present and not expired.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let now = Instant::now();
+        let mut entries = self.inner.entries.borrow_mut();
+        let entry = entries.get(key)?;
+        if self.inner.is_expired(entry, now) {
+            entries.remove(key);
+            return None;
+        }
+        entry.accessed.set(now);
+        Some(entry.value.clone())
+    }
+
+    /// Insert a value, returning the previous one if it was not expired.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.start_sweeper();
+        let now = Instant::now();
+        let prev = self.inner.entries.borrow_mut().insert(
+            key,
+            Entry {
+                value,
+                inserted: now,
+                accessed: Cell::new(now),
+            },
+        );
+        prev.filter(|e| !self.inner.is_expired(e, now))
+            .map(|e| e.value)
+    }
+
+    /// Remove the value of `key`, returning it if it was not expired.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let entry = self.inner.entries.borrow_mut().remove(key)?;
+        (!self.inner.is_expired(&entry, Instant::now())).then_some(entry.value)
+    }
+
+    /// Remove all the values.
+    pub fn clear(&self) {
+        self.inner.entries.borrow_mut().clear();
+    }
+
+    /// Number of entries, which may include expired ones not removed yet.
+    pub fn len(&self) -> usize {
+        self.inner.entries.borrow().len()
+    }
+
+    /// Returns true if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.inner.entries.borrow().is_empty()
+    }
+
+    /// Get the value of `key`, or load and insert it with `load`.
+    ///
+    /// If the key is already being loaded by another task, wait for it
+    /// instead of loading it again.
+    pub async fn get_or_insert_with<F, Fut>(&self, key: K, load: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        match self
+            .try_get_or_insert_with(key, || async {
+                Ok::<_, std::convert::Infallible>(load().await)
+            })
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Like [`get_or_insert_with`](LocalCache::get_or_insert_with), with a
+    /// fallible loader. Errors are not cached: the error is returned to the
+    /// loading task, and a waiting task retries the load by itself.
+    pub async fn try_get_or_insert_with<F, Fut, E>(&self, key: K, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        loop {
+            if let Some(v) = self.get(&key) {
+                return Ok(v);
+            }
+            let loading = self.inner.loading.borrow().contains_key(&key);
+            if !loading {
+                break;
+            }
+            self.wait_loaded(&key).await;
+        }
+
+        self.inner
+            .loading
+            .borrow_mut()
+            .insert(key.clone(), Vec::new());
+        // wakes the waiters even if the load is canceled
+        let _guard = LoadingGuard {
+            inner: &self.inner,
+            key: &key,
+        };
+        let value = load().await?;
+        self.insert(key.clone(), value.clone());
+        Ok(value)
+    }
+
+    async fn wait_loaded(&self, key: &K) {
+        poll_fn(|cx| match self.inner.loading.borrow_mut().get_mut(key) {
+            Some(waiters) => {
+                if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                    waiters.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+            None => Poll::Ready(()),
+        })
+        .await
+    }
+
+    fn start_sweeper(&self) {
+        let period = match (self.inner.ttl, self.inner.tti) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => return,
+        };
+        if self.inner.sweeper.replace(true) {
+            return;
+        }
+        let period = period.max(Duration::from_millis(1));
+        crate::spawn(sweep(Rc::downgrade(&self.inner), period));
+    }
+}
+
+struct LoadingGuard<'a, K: Hash + Eq, V> {
+    inner: &'a Inner<K, V>,
+    key: &'a K,
+}
+
+impl<K: Hash + Eq, V> Drop for LoadingGuard<'_, K, V> {
+    fn drop(&mut self) {
+        if let Some(waiters) = self.inner.loading.borrow_mut().remove(self.key) {
+            for waker in waiters {
+                waker.wake();
+            }
+        }
+    }
+}
+
+async fn sweep<K: Hash + Eq, V>(inner: Weak<Inner<K, V>>, period: Duration) {
+    loop {
+        crate::time::sleep(period).await;
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        let now = Instant::now();
+        inner
+            .entries
+            .borrow_mut()
+            .retain(|_, entry| !inner.is_expired(entry, now));
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: Clone + 'static> Default for LocalCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for LocalCache<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<K, V> std::fmt::Debug for LocalCache<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalCache")
+            .field("len", &self.inner.entries.borrow().len())
+            .field("ttl", &self.inner.ttl)
+            .field("tti", &self.inner.tti)
+            .finish()
+    }
+}
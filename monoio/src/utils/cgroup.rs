@@ -0,0 +1,130 @@
+//! cgroup v2 helpers.
+//!
+//! These read and write cgroupfs pseudo-files with blocking syscalls, which
+//! never wait on a disk.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Move the current thread into the cgroup v2 at `cgroup`, a directory of
+/// the cgroup2 mount like `/sys/fs/cgroup/monoio/worker0`.
+///
+/// Threads and io_uring workers created by the thread afterwards start in
+/// the same cgroup. Moving a single thread requires the cgroup to be
+/// threaded (`echo threaded > cgroup.type`), a domain cgroup only accepts
+/// whole processes.
+pub fn move_current_thread(cgroup: impl AsRef<Path>) -> io::Result<()> {
+    let tid = unsafe { libc::syscall(libc::SYS_gettid) };
+    fs::write(cgroup.as_ref().join("cgroup.threads"), tid.to_string())
+}
+
+/// Returns the cgroup v2 of the current thread, as a path under the
+/// cgroup2 mount.
+pub fn current_cgroup() -> io::Result<PathBuf> {
+    // cgroup v2 has a single line "0::/path".
+    let content = fs::read_to_string("/proc/thread-self/cgroup")?;
+    let path = content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "not in a cgroup v2"))?;
+    Ok(mount_point()?.join(path.trim_start_matches('/')))
+}
+
+/// Returns where cgroup2 is mounted, `/sys/fs/cgroup` on most systems, or
+/// `/sys/fs/cgroup/unified` with the hybrid hierarchy.
+pub fn mount_point() -> io::Result<PathBuf> {
+    let mounts = fs::read_to_string("/proc/self/mounts")?;
+    mounts
+        .lines()
+        .find_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            (fields.next()? == "cgroup2").then(|| PathBuf::from(mount_point))
+        })
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "cgroup2 is not mounted"))
+}
+
+/// Pressure stall information of a resource, as reported by PSI.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pressure {
+    /// Share of time some tasks were stalled on the resource.
+    pub some: PressureLine,
+    /// Share of time all non-idle tasks were stalled at once. Not reported
+    /// for cpu at the system level before Linux 5.13.
+    pub full: Option<PressureLine>,
+}
+
+/// One line of a PSI file.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PressureLine {
+    /// Percentage of stalled time over the last 10 seconds.
+    pub avg10: f64,
+    /// Percentage of stalled time over the last 60 seconds.
+    pub avg60: f64,
+    /// Percentage of stalled time over the last 300 seconds.
+    pub avg300: f64,
+    /// Total stalled time.
+    pub total: Duration,
+}
+
+impl Pressure {
+    /// Read the cpu pressure of the cgroup v2 at `cgroup`.
+    ///
+    /// This is cheap enough to be polled periodically to feed load
+    /// shedding, see [`LoadShedder`](super::LoadShedder).
+    pub fn cpu(cgroup: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(cgroup.as_ref().join("cpu.pressure"))
+    }
+
+    /// Read the cpu pressure of the whole system.
+    pub fn system_cpu() -> io::Result<Self> {
+        Self::read("/proc/pressure/cpu")
+    }
+
+    /// Read a PSI file, like `memory.pressure` of a cgroup or
+    /// `/proc/pressure/io`.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed PSI file"))
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut pressure = Pressure::default();
+        let mut some = false;
+        for line in content.lines() {
+            let (kind, fields) = line.split_once(' ')?;
+            let parsed = PressureLine::parse(fields)?;
+            match kind {
+                "some" => {
+                    pressure.some = parsed;
+                    some = true;
+                }
+                "full" => pressure.full = Some(parsed),
+                _ => return None,
+            }
+        }
+        some.then_some(pressure)
+    }
+}
+
+impl PressureLine {
+    // Parse "avg10=0.00 avg60=0.00 avg300=0.00 total=0".
+    fn parse(fields: &str) -> Option<Self> {
+        let mut line = PressureLine::default();
+        for field in fields.split_whitespace() {
+            let (key, value) = field.split_once('=')?;
+            match key {
+                "avg10" => line.avg10 = value.parse().ok()?,
+                "avg60" => line.avg60 = value.parse().ok()?,
+                "avg300" => line.avg300 = value.parse().ok()?,
+                "total" => line.total = Duration::from_micros(value.parse().ok()?),
+                _ => {}
+            }
+        }
+        Some(line)
+    }
+}
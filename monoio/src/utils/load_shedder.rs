@@ -0,0 +1,188 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    rc::{Rc, Weak},
+    task::{Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use crate::io::stream::Stream;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Overload protection driven by scheduler lag.
+///
+/// A background task periodically spawns a probe task and measures how long
+/// it waits in the run queue before being polled. When the smoothed lag
+/// goes above the threshold, the runtime is considered overloaded, and new
+/// work should be rejected or deferred. It recovers when the lag drops below
+/// half of the threshold.
+///
+/// The probe sleeps between samples, so the timer must be enabled. It stops
+/// when the `LoadShedder` and all its clones are dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monoio::{net::TcpListener, utils::LoadShedder};
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() {
+///     let shedder = LoadShedder::new(Duration::from_millis(5));
+///     let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+///     // Stop accepting while the runtime is overloaded.
+///     while let Some(Ok((stream, _))) = shedder.accept(&mut listener).await {
+///         monoio::spawn(async move {
+///             // handle the connection
+///             drop(stream);
+///         });
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LoadShedder {
+    shared: Rc<Shared>,
+}
+
+struct Shared {
+    threshold: Duration,
+    lag: Cell<Duration>,
+    overloaded: Cell<bool>,
+    waiters: RefCell<Vec<Waker>>,
+}
+
+impl LoadShedder {
+    /// Create a `LoadShedder` sampling every 10ms and spawn its probe on the
+    /// current runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn new(threshold: Duration) -> Self {
+        Self::with_interval(threshold, DEFAULT_INTERVAL)
+    }
+
+    /// Create a `LoadShedder` with the given sampling interval and spawn its
+    /// probe on the current runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside a monoio runtime.
+    pub fn with_interval(threshold: Duration, interval: Duration) -> Self {
+        let shared = Rc::new(Shared {
+            threshold,
+            lag: Cell::new(Duration::ZERO),
+            overloaded: Cell::new(false),
+            waiters: RefCell::new(Vec::new()),
+        });
+        crate::spawn(probe(Rc::downgrade(&shared), interval));
+        Self { shared }
+    }
+
+    /// Smoothed scheduling delay, the time from wake to poll.
+    #[inline]
+    pub fn lag(&self) -> Duration {
+        self.shared.lag.get()
+    }
+
+    /// Returns true if the runtime is overloaded and new work should be shed.
+    #[inline]
+    pub fn is_overloaded(&self) -> bool {
+        self.shared.overloaded.get()
+    }
+
+    /// Wait until the runtime is not overloaded.
+    pub async fn ready(&self) {
+        poll_fn(|cx| {
+            if !self.shared.overloaded.get() {
+                return Poll::Ready(());
+            }
+            let mut waiters = self.shared.waiters.borrow_mut();
+            if !waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait until the runtime is not overloaded, then pull the next item,
+    /// e.g. accept the next connection of a listener.
+    ///
+    /// Pending connections stay in the kernel backlog while overloaded.
+    pub async fn accept<S: Stream>(&self, incoming: &mut S) -> Option<S::Item> {
+        self.ready().await;
+        incoming.next().await
+    }
+}
+
+impl Shared {
+    fn record(&self, sample: Duration) {
+        let lag = (self.lag.get() * 7 + sample) / 8;
+        self.lag.set(lag);
+        if lag > self.threshold {
+            self.overloaded.set(true);
+        } else if lag < self.threshold / 2 && self.overloaded.replace(false) {
+            for waker in self.waiters.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for LoadShedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadShedder")
+            .field("threshold", &self.shared.threshold)
+            .field("lag", &self.lag())
+            .field("overloaded", &self.is_overloaded())
+            .finish()
+    }
+}
+
+async fn probe(shared: Weak<Shared>, interval: Duration) {
+    loop {
+        crate::time::sleep(interval).await;
+        if shared.strong_count() == 0 {
+            return;
+        }
+        // A new task is pushed to the back of the run queue, so it is polled
+        // after all tasks already woken.
+        let begin = Instant::now();
+        let sample = crate::spawn(async move { begin.elapsed() }).await;
+        match shared.upgrade() {
+            Some(shared) => shared.record(sample),
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hysteresis() {
+        let shared = Shared {
+            threshold: Duration::from_millis(8),
+            lag: Cell::new(Duration::ZERO),
+            overloaded: Cell::new(false),
+            waiters: RefCell::new(Vec::new()),
+        };
+        for _ in 0..32 {
+            shared.record(Duration::from_millis(20));
+        }
+        assert!(shared.overloaded.get());
+        // between half threshold and threshold: still overloaded
+        for _ in 0..32 {
+            shared.record(Duration::from_millis(5));
+        }
+        assert!(shared.overloaded.get());
+        for _ in 0..32 {
+            shared.record(Duration::ZERO);
+        }
+        assert!(!shared.overloaded.get());
+    }
+}
@@ -1,7 +1,13 @@
 //! Common utils
 
+mod actor;
 pub(crate) mod box_into_inner;
+mod breaker;
+mod cache;
 pub(crate) mod linked_list;
+mod load_shedder;
+mod retry;
+mod runtime_features;
 #[allow(dead_code)]
 pub(crate) mod slab;
 #[allow(dead_code)]
@@ -9,11 +15,24 @@ pub(crate) mod thread_id;
 pub(crate) mod uring_detect;
 
 mod rand;
+pub use actor::{Addr, CallError, LocalActor, Reply, SendError, TrySendError};
+pub use breaker::{Breaker, BreakerError, BreakerState, Permit};
+pub use cache::LocalCache;
+pub use load_shedder::LoadShedder;
 pub use rand::thread_rng_n;
-pub use uring_detect::detect_uring;
+pub use retry::{is_transient, retry, retry_if, RetryBudget, RetryPolicy};
+pub use runtime_features::{runtime_features, DriverKind, RuntimeFeatures};
+pub use uring_detect::{detect_uring, uring_features, UringFeatures};
 
 pub use crate::driver::op::is_legacy;
 
+#[cfg(target_os = "linux")]
+pub mod cgroup;
+#[cfg(target_os = "linux")]
+mod pinned_cpu;
+#[cfg(target_os = "linux")]
+pub use pinned_cpu::pinned_cpu;
+
 #[cfg(feature = "signal")]
 mod ctrlc;
 #[cfg(feature = "signal")]
@@ -0,0 +1,15 @@
+//! The CPU current thread is pinned to.
+
+/// Returns the CPU current thread is pinned to, which is the only CPU in its
+/// affinity mask. Returns `None` if it may run on several CPUs.
+///
+/// Thread-per-core runtimes pin each thread with `bind_to_cpu_set` or
+/// `taskset`, so this is the core the runtime serves.
+pub fn pinned_cpu() -> Option<usize> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let ret = unsafe { libc::sched_getaffinity(0, std::mem::size_of_val(&set), &mut set) };
+    if ret != 0 || unsafe { libc::CPU_COUNT(&set) } != 1 {
+        return None;
+    }
+    (0..libc::CPU_SETSIZE as usize).find(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+}
@@ -0,0 +1,281 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    io,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{io::CancelHandle, utils::thread_rng_n};
+
+/// Retry a fallible async operation with the given policy.
+///
+/// Every error is considered retryable; use [`retry_if`] to classify them.
+/// The last error is returned when the policy gives up. The backoff sleeps
+/// use the timer, so it must be enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use monoio::{
+///     net::TcpStream,
+///     utils::{is_transient, retry_if, RetryPolicy},
+/// };
+///
+/// #[monoio::main(timer_enabled = true)]
+/// async fn main() -> std::io::Result<()> {
+///     let policy = RetryPolicy::new()
+///         .max_attempts(5)
+///         .initial_delay(Duration::from_millis(50));
+///     let stream = retry_if(
+///         &policy,
+///         || TcpStream::connect("127.0.0.1:8080"),
+///         is_transient,
+///     )
+///     .await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(policy, op, |_| true).await
+}
+
+/// Retry a fallible async operation while `should_retry` returns true for
+/// its error.
+pub async fn retry_if<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    mut op: F,
+    mut should_retry: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    if let Some(budget) = &policy.budget {
+        budget.deposit();
+    }
+    let mut attempt = 1;
+    let mut delay = policy.initial_delay;
+    loop {
+        let err = match op().await {
+            Ok(v) => return Ok(v),
+            Err(e) => e,
+        };
+        if !should_retry(&err) || attempt >= policy.max_attempts {
+            return Err(err);
+        }
+        if policy.cancel.as_ref().is_some_and(|c| c.canceled()) {
+            return Err(err);
+        }
+        let sleep = policy.jittered(delay);
+        if policy
+            .deadline
+            .is_some_and(|deadline| Instant::now() + sleep >= deadline)
+        {
+            return Err(err);
+        }
+        if let Some(budget) = &policy.budget {
+            if !budget.withdraw() {
+                return Err(err);
+            }
+        }
+        crate::time::sleep(sleep).await;
+        if policy.cancel.as_ref().is_some_and(|c| c.canceled()) {
+            return Err(err);
+        }
+        attempt += 1;
+        delay = delay.mul_f64(policy.multiplier).min(policy.max_delay);
+    }
+}
+
+/// Returns true for io errors which are usually worth retrying, like
+/// timeouts, refused or reset connections.
+pub fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::AddrNotAvailable
+    )
+}
+
+/// Exponential backoff policy of [`retry`].
+///
+/// By default it makes at most 3 attempts, starting with a 100ms delay which
+/// doubles up to 10s, with 50% jitter.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: f64,
+    deadline: Option<Instant>,
+    budget: Option<RetryBudget>,
+    cancel: Option<CancelHandle>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.5,
+            deadline: None,
+            budget: None,
+            cancel: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("deadline", &self.deadline)
+            .field("budget", &self.budget)
+            .field("cancelable", &self.cancel.is_some())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Max number of attempts, including the first one.
+    #[must_use]
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry.
+    #[must_use]
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Upper bound of the delay between attempts.
+    #[must_use]
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Factor applied to the delay after each retry.
+    #[must_use]
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Fraction of the delay which is randomized, between 0 and 1. A delay
+    /// `d` with jitter `j` becomes a random value in `d * (1 - j)..=d`.
+    #[must_use]
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Give up instead of sleeping past `deadline`.
+    #[must_use]
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Share a [`RetryBudget`] limiting the ratio of retries.
+    #[must_use]
+    pub fn budget(mut self, budget: RetryBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Stop retrying when the [`Canceller`](crate::io::Canceller) of
+    /// `handle` is canceled. The handle can be passed to the operation too,
+    /// so the running attempt is canceled as well.
+    #[must_use]
+    pub fn cancel_handle(mut self, handle: CancelHandle) -> Self {
+        self.cancel = Some(handle);
+        self
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        const SCALE: u32 = 1 << 16;
+        let rand = thread_rng_n(SCALE) as f64 / SCALE as f64;
+        delay.mul_f64(1.0 - self.jitter * rand)
+    }
+}
+
+/// Limit retries to a ratio of the calls, so a failing dependency is not
+/// overwhelmed by retries.
+///
+/// Each call deposits `ratio` tokens, up to `max_tokens`, and each retry
+/// withdraws one token. A retry without token is not made. Clones share the
+/// same budget, so it is usually shared by all calls to a dependency.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    inner: Rc<Budget>,
+}
+
+#[derive(Debug)]
+struct Budget {
+    tokens: Cell<f64>,
+    max_tokens: f64,
+    ratio: f64,
+}
+
+impl RetryBudget {
+    /// Create a budget allowing `ratio` retries per call, with a burst of
+    /// `max_tokens` retries. It starts full.
+    pub fn new(ratio: f64, max_tokens: u32) -> Self {
+        Self {
+            inner: Rc::new(Budget {
+                tokens: Cell::new(max_tokens as f64),
+                max_tokens: max_tokens as f64,
+                ratio: ratio.max(0.0),
+            }),
+        }
+    }
+
+    /// Number of retries currently allowed.
+    pub fn available(&self) -> u32 {
+        self.inner.tokens.get() as u32
+    }
+
+    fn deposit(&self) {
+        let b = &self.inner;
+        b.tokens.set((b.tokens.get() + b.ratio).min(b.max_tokens));
+    }
+
+    fn withdraw(&self) -> bool {
+        let b = &self.inner;
+        if b.tokens.get() < 1.0 {
+            return false;
+        }
+        b.tokens.set(b.tokens.get() - 1.0);
+        true
+    }
+}
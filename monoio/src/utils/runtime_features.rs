@@ -0,0 +1,118 @@
+//! Query features of current build and runtime.
+
+/// Driver current thread runs on, or to build with
+/// [`RuntimeBuilder::with_driver`](crate::RuntimeBuilder).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriverKind {
+    /// The io_uring driver if the kernel supports it, the legacy driver
+    /// otherwise. Only used to build a runtime, a running one never reports
+    /// it.
+    #[default]
+    Auto,
+    /// The io_uring driver.
+    IoUring,
+    /// The epoll/kqueue based legacy driver.
+    Legacy,
+}
+
+/// Cargo features enabled in this build and capabilities of the runtime
+/// current thread runs on, returned by [`runtime_features`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFeatures {
+    driver: Option<DriverKind>,
+    timer: bool,
+    fixed_buffers: bool,
+}
+
+impl RuntimeFeatures {
+    /// If the `iouring` feature is enabled.
+    #[inline]
+    pub const fn iouring(&self) -> bool {
+        cfg!(all(target_os = "linux", feature = "iouring"))
+    }
+
+    /// If the `legacy` feature is enabled.
+    #[inline]
+    pub const fn legacy(&self) -> bool {
+        cfg!(feature = "legacy")
+    }
+
+    /// If the `sync` feature is enabled, so wakers may be sent across threads
+    /// and `spawn_blocking` is available.
+    #[inline]
+    pub const fn sync(&self) -> bool {
+        cfg!(feature = "sync")
+    }
+
+    /// If the `zero-copy` feature is enabled.
+    #[inline]
+    pub const fn zero_copy(&self) -> bool {
+        cfg!(feature = "zero-copy")
+    }
+
+    /// If the `splice` feature is enabled.
+    #[inline]
+    pub const fn splice(&self) -> bool {
+        cfg!(feature = "splice")
+    }
+
+    /// If the `async-cancel` feature is enabled.
+    #[inline]
+    pub const fn async_cancel(&self) -> bool {
+        cfg!(feature = "async-cancel")
+    }
+
+    /// Driver of current runtime, or `None` outside of a runtime.
+    #[inline]
+    pub const fn driver(&self) -> Option<DriverKind> {
+        self.driver
+    }
+
+    /// If current runtime has the timer enabled.
+    #[inline]
+    pub const fn timer(&self) -> bool {
+        self.timer
+    }
+
+    /// If current runtime runs on io_uring and the kernel supports the fixed
+    /// buffer ops `READ_FIXED` and `WRITE_FIXED`.
+    #[inline]
+    pub const fn fixed_buffers(&self) -> bool {
+        self.fixed_buffers
+    }
+}
+
+/// Returns the cargo features enabled in this build and the capabilities of
+/// the runtime current thread runs on, so libraries can adapt instead of
+/// failing at first use.
+///
+/// ```
+/// let features = monoio::runtime_features();
+/// assert_eq!(features.driver(), None);
+/// ```
+pub fn runtime_features() -> RuntimeFeatures {
+    let driver = crate::driver::CURRENT.is_set().then(|| {
+        if crate::utils::is_legacy() {
+            DriverKind::Legacy
+        } else {
+            DriverKind::IoUring
+        }
+    });
+    let timer = crate::runtime::CURRENT.is_set()
+        && crate::runtime::CURRENT.with(|ctx| ctx.time_handle.is_some());
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    let fixed_buffers = driver == Some(DriverKind::IoUring) && {
+        use io_uring::opcode::{ReadFixed, WriteFixed};
+        let features = crate::utils::uring_features();
+        features.is_supported(ReadFixed::CODE) && features.is_supported(WriteFixed::CODE)
+    };
+    #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+    let fixed_buffers = false;
+
+    RuntimeFeatures {
+        driver,
+        timer,
+        fixed_buffers,
+    }
+}
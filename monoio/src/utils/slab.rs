@@ -43,6 +43,53 @@ impl<T> Slab<T> {
         })
     }
 
+    /// Allocate pages for `capacity` elements. Empty pages may be dropped
+    /// again on compaction.
+    #[allow(unused)]
+    pub(crate) fn reserve(&mut self, capacity: usize) {
+        let mut total = 0;
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            if total >= capacity {
+                break;
+            }
+            let size = PAGE_INITIAL_SIZE << i;
+            page.get_or_insert_with(|| Page::new(size, size - PAGE_INITIAL_SIZE));
+            total += size;
+        }
+    }
+
+    /// Get the size in bytes of the allocated pages.
+    #[allow(unused)]
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.pages.iter().flatten().fold(0, |acc, page| {
+            acc + page.slots.len() * std::mem::size_of::<Entry<T>>()
+        })
+    }
+
+    /// Call `f` on each element.
+    #[allow(unused)]
+    pub(crate) fn for_each(&self, mut f: impl FnMut(&T)) {
+        for page in self.pages.iter().flatten() {
+            for slot in 0..page.initialized {
+                if let Some(val) = page.get(slot) {
+                    f(val);
+                }
+            }
+        }
+    }
+
+    /// Call `f` on the key of each element.
+    #[allow(unused)]
+    pub(crate) fn for_each_key(&self, mut f: impl FnMut(usize)) {
+        for page in self.pages.iter().flatten() {
+            for slot in 0..page.initialized {
+                if page.get(slot).is_some() {
+                    f(slot + page.prev_len);
+                }
+            }
+        }
+    }
+
     pub(crate) fn get(&mut self, key: usize) -> Option<Ref<'_, T>> {
         let page_id = get_page_id(key);
         // here we make 2 mut ref so we must make it safe.
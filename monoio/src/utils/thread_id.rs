@@ -19,3 +19,9 @@ pub(crate) fn get_current_thread_id() -> usize {
 pub(crate) fn try_get_current_thread_id() -> Option<usize> {
     crate::runtime::CURRENT.try_with(|maybe_ctx| maybe_ctx.map(|ctx| ctx.thread_id))
 }
+
+/// Kernel thread id of the current thread.
+#[cfg(target_os = "linux")]
+pub(crate) fn gettid() -> libc::pid_t {
+    unsafe { libc::syscall(libc::SYS_gettid) as libc::pid_t }
+}
@@ -1,16 +1,5 @@
 //! Detect if current platform support io_uring.
 
-#[cfg(all(target_os = "linux", feature = "iouring"))]
-macro_rules! err_to_false {
-    ($e: expr) => {
-        match $e {
-            Ok(x) => x,
-            Err(_) => {
-                return false;
-            }
-        }
-    };
-}
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 fn detect_uring_inner() -> bool {
     let val = std::env::var("MONOIO_FORCE_LEGACY_DRIVER");
@@ -46,10 +35,125 @@ fn detect_uring_inner() -> bool {
         ];
     }
 
-    let uring = err_to_false!(io_uring::IoUring::new(2));
+    let features = uring_features();
+    features.is_available() && USED_OP.iter().all(|op| features.is_supported(*op))
+}
+
+/// Opcodes and features supported by io_uring on the running kernel.
+///
+/// It is probed with `IORING_REGISTER_PROBE` on a temporary ring, so it does
+/// not depend on the driver the runtime uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UringFeatures {
+    available: bool,
+    ops: [u64; 4],
+    nodrop: bool,
+    submit_stable: bool,
+    fast_poll: bool,
+    ext_arg: bool,
+    native_workers: bool,
+    skip_cqe_on_success: bool,
+    linked_file: bool,
+}
+
+impl UringFeatures {
+    /// If io_uring can be set up on current platform.
+    #[inline]
+    pub const fn is_available(&self) -> bool {
+        self.available
+    }
+
+    /// If the opcode is supported, e.g. `io_uring::opcode::Splice::CODE`.
+    #[inline]
+    pub const fn is_supported(&self, opcode: u8) -> bool {
+        self.ops[(opcode / 64) as usize] & (1 << (opcode % 64)) != 0
+    }
+
+    /// `IORING_FEAT_NODROP`: completion events will not be dropped on overflow.
+    #[inline]
+    pub const fn is_feature_nodrop(&self) -> bool {
+        self.nodrop
+    }
+
+    /// `IORING_FEAT_SUBMIT_STABLE`: data is consumed on submission.
+    #[inline]
+    pub const fn is_feature_submit_stable(&self) -> bool {
+        self.submit_stable
+    }
+
+    /// `IORING_FEAT_FAST_POLL`: internal poll is used for pollable files.
+    #[inline]
+    pub const fn is_feature_fast_poll(&self) -> bool {
+        self.fast_poll
+    }
+
+    /// `IORING_FEAT_EXT_ARG`: wait with timeout through enter args.
+    #[inline]
+    pub const fn is_feature_ext_arg(&self) -> bool {
+        self.ext_arg
+    }
+
+    /// `IORING_FEAT_NATIVE_WORKERS`: async workers are native threads.
+    #[inline]
+    pub const fn is_feature_native_workers(&self) -> bool {
+        self.native_workers
+    }
+
+    /// `IORING_FEAT_CQE_SKIP`: `IOSQE_CQE_SKIP_SUCCESS` is supported.
+    #[inline]
+    pub const fn is_feature_skip_cqe_on_success(&self) -> bool {
+        self.skip_cqe_on_success
+    }
+
+    /// `IORING_FEAT_LINKED_FILE`: files of linked ops are assigned lazily.
+    #[inline]
+    pub const fn is_feature_linked_file(&self) -> bool {
+        self.linked_file
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+fn uring_features_inner() -> UringFeatures {
+    let mut features = UringFeatures::default();
+    let uring = match io_uring::IoUring::new(2) {
+        Ok(uring) => uring,
+        Err(_) => return features,
+    };
     let mut probe = io_uring::Probe::new();
-    err_to_false!(uring.submitter().register_probe(&mut probe));
-    USED_OP.iter().all(|op| probe.is_supported(*op))
+    if uring.submitter().register_probe(&mut probe).is_err() {
+        return features;
+    }
+
+    features.available = true;
+    for op in 0..=u8::MAX {
+        if probe.is_supported(op) {
+            features.ops[(op / 64) as usize] |= 1 << (op % 64);
+        }
+    }
+    let params = uring.params();
+    features.nodrop = params.is_feature_nodrop();
+    features.submit_stable = params.is_feature_submit_stable();
+    features.fast_poll = params.is_feature_fast_poll();
+    features.ext_arg = params.is_feature_ext_arg();
+    features.native_workers = params.is_feature_native_workers();
+    features.skip_cqe_on_success = params.is_feature_skip_cqe_on_success();
+    features.linked_file = params.is_feature_linked_file();
+    features
+}
+
+/// Probe opcodes and features io_uring supports on current platform.
+/// The result is cached.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub fn uring_features() -> UringFeatures {
+    static FEATURES: std::sync::OnceLock<UringFeatures> = std::sync::OnceLock::new();
+    *FEATURES.get_or_init(uring_features_inner)
+}
+
+/// Probe opcodes and features io_uring supports on current platform.
+/// The result is cached.
+#[cfg(not(all(target_os = "linux", feature = "iouring")))]
+pub fn uring_features() -> UringFeatures {
+    UringFeatures::default()
 }
 
 /// Detect if current platform supports our needed uring ops.
@@ -82,4 +186,13 @@ mod tests {
             "io_uring or ops not supported on current platform"
         )
     }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[test]
+    fn test_features() {
+        let features = super::uring_features();
+        assert!(features.is_available());
+        assert!(features.is_supported(io_uring::opcode::Read::CODE));
+        assert_eq!(features, super::uring_features());
+    }
 }
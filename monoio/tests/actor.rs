@@ -0,0 +1,68 @@
+use std::{cell::Cell, rc::Rc};
+
+use monoio::utils::{LocalActor, Reply, TrySendError};
+
+enum Msg {
+    Incr(u64),
+    Get(Reply<u64>),
+    Ignore(Reply<u64>),
+}
+
+#[monoio::test_all]
+async fn send_and_call() {
+    let addr = LocalActor::spawn(0_u64, async |count: &mut u64, msg: Msg| match msg {
+        Msg::Incr(n) => *count += n,
+        Msg::Get(reply) => reply.send(*count),
+        Msg::Ignore(reply) => drop(reply),
+    });
+    for i in 1..=10 {
+        addr.send(Msg::Incr(i)).await.unwrap();
+    }
+    assert_eq!(addr.call(Msg::Get).await.unwrap(), 55);
+    // the reply is dropped without an answer
+    assert!(addr.call(Msg::Ignore).await.is_err());
+}
+
+#[monoio::test_all]
+async fn backpressure() {
+    let handled = Rc::new(Cell::new(0));
+    let h = handled.clone();
+    let addr = LocalActor::spawn_with_capacity(2, (), async move |_: &mut (), _: u32| {
+        h.set(h.get() + 1);
+    });
+    addr.try_send(1).unwrap();
+    addr.try_send(2).unwrap();
+    assert!(matches!(addr.try_send(3), Err(TrySendError::Full(3))));
+    // waits until the actor makes room
+    addr.send(3).await.unwrap();
+    addr.send(4).await.unwrap();
+    assert!(handled.get() >= 2);
+}
+
+#[monoio::test_all]
+async fn stop_when_addrs_dropped() {
+    struct State(Rc<Cell<bool>>);
+    impl Drop for State {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(false));
+    let handled = Rc::new(Cell::new(0));
+    let h = handled.clone();
+    let addr = LocalActor::spawn(State(dropped.clone()), async move |_: &mut State, _: ()| {
+        h.set(h.get() + 1);
+    });
+    let addr2 = addr.clone();
+    addr.send(()).await.unwrap();
+    addr2.send(()).await.unwrap();
+    drop(addr);
+    drop(addr2);
+    // let the actor drain its mailbox
+    for _ in 0..4 {
+        monoio::spawn(async {}).await;
+    }
+    assert_eq!(handled.get(), 2);
+    assert!(dropped.get());
+}
@@ -0,0 +1,36 @@
+#![cfg(unix)]
+
+use std::{
+    io::{Read, Write},
+    os::unix::net::UnixStream,
+};
+
+use monoio::io::{stream::Stream, AsyncFd, Interest};
+
+#[monoio::test_all]
+async fn ready_multi() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    a.set_nonblocking(true).unwrap();
+    let mut reader = a.try_clone().unwrap();
+    let fd = AsyncFd::new(a).unwrap();
+
+    fd.writable().await.unwrap();
+    let mut ready = fd.ready_multi(Interest::READABLE);
+    for i in 0..3u8 {
+        b.write_all(&[i]).unwrap();
+        let interest = ready.next().await.unwrap().unwrap();
+        assert!(interest.is_readable());
+        assert!(!interest.is_writable());
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], i);
+        assert_eq!(
+            reader.read(&mut buf).unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    let mut ready = fd.ready_multi(Interest::READABLE | Interest::WRITABLE);
+    let interest = ready.next().await.unwrap().unwrap();
+    assert!(interest.is_writable());
+}
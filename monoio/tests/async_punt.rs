@@ -0,0 +1,55 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::io::Write;
+
+use monoio::{fs::File, io::async_punt, IoUringDriver, RuntimeBuilder};
+
+fn read_punted(default: bool) {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_async_punt(default)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = File::open(tmp.path()).await.unwrap();
+        for enabled in [true, false] {
+            let (res, buf) = async_punt(enabled, file.read_at(vec![0; 6], 0)).await;
+            assert_eq!(res.unwrap(), 6);
+            assert_eq!(buf, b"monoio");
+        }
+        let (res, buf) = file.read_at(vec![0; 6], 0).await;
+        assert_eq!(res.unwrap(), 6);
+        assert_eq!(buf, b"monoio");
+    });
+}
+
+#[test]
+fn punt_reads() {
+    read_punted(false);
+    read_punted(true);
+}
+
+#[test]
+fn punt_blocking_read() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let (rx, mut tx) = std::os::unix::net::UnixStream::pair().unwrap();
+        let rx = monoio::net::UnixStream::from_std(rx).unwrap();
+        let read = monoio::spawn(async_punt(true, async move {
+            use monoio::io::AsyncReadRent;
+            let mut rx = rx;
+            rx.read(vec![0; 6]).await
+        }));
+        // The punted read waits in an io-wq worker until there is data.
+        monoio::time::sleep(std::time::Duration::from_millis(20)).await;
+        tx.write_all(b"monoio").unwrap();
+        let (res, buf) = read.await;
+        assert_eq!(res.unwrap(), 6);
+        assert_eq!(buf, b"monoio");
+    });
+}
@@ -0,0 +1,77 @@
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::UnixStream,
+    sync::local::bip_buffer,
+};
+
+#[monoio::test_all]
+async fn wrap_around() {
+    let (mut tx, mut rx) = bip_buffer(16);
+    let consumer = monoio::spawn(async move {
+        let mut out = Vec::new();
+        while let Some(grant) = rx.read().await {
+            // release in odd chunks to exercise partial releases
+            let used = grant.len().min(5);
+            out.extend_from_slice(&grant[..used]);
+            grant.release(used);
+        }
+        out
+    });
+
+    let mut expected = Vec::new();
+    for i in 0..200_u32 {
+        let len = (i % 7 + 1) as usize;
+        let mut grant = tx.reserve(len).await.unwrap();
+        for (j, b) in grant.iter_mut().enumerate() {
+            *b = (i as usize * 31 + j) as u8;
+        }
+        expected.extend_from_slice(&grant[..len - 1]);
+        grant.commit(len - 1);
+    }
+    drop(tx);
+    assert_eq!(consumer.await, expected);
+}
+
+#[monoio::test_all]
+async fn closed() {
+    let (mut tx, rx) = bip_buffer(8);
+    drop(rx);
+    assert!(tx.reserve(4).await.is_none());
+
+    let (mut tx, mut rx) = bip_buffer(8);
+    tx.reserve(3).await.unwrap().copy_from_slice(b"abc");
+    let mut grant = tx.reserve(3).await.unwrap();
+    grant.copy_from_slice(b"abc");
+    grant.commit(3);
+    // uncommitted bytes are not visible
+    drop(tx);
+    let grant = rx.read().await.unwrap();
+    assert_eq!(&*grant, b"abc");
+    grant.release(3);
+    assert!(rx.read().await.is_none());
+}
+
+#[monoio::test_all]
+async fn io_in_place() {
+    const MSG: &[u8] = b"lent out to the kernel";
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let (mut c, mut d) = UnixStream::pair().unwrap();
+    let (mut tx, mut rx) = bip_buffer(64);
+
+    a.write_all(MSG).await.0.unwrap();
+    let grant = tx.reserve(32).await.unwrap();
+    let (res, grant) = b.read(grant).await;
+    let n = res.unwrap();
+    assert_eq!(n, MSG.len());
+    assert_eq!(grant.written(), n);
+    grant.commit(n);
+
+    let grant = rx.read().await.unwrap();
+    let (res, grant) = c.write_all(grant).await;
+    res.unwrap();
+    grant.release(MSG.len());
+
+    let buf = vec![0; 64];
+    let (res, buf) = d.read(buf).await;
+    assert_eq!(&buf[..res.unwrap()], MSG);
+}
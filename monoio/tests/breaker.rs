@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use monoio::utils::{Breaker, BreakerError, BreakerState};
+
+async fn fail(breaker: &Breaker) -> Result<(), BreakerError<&'static str>> {
+    breaker.call(async { Err::<(), _>("down") }).await
+}
+
+async fn succeed(breaker: &Breaker) -> Result<(), BreakerError<&'static str>> {
+    breaker.call(async { Ok(()) }).await
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn breaker_opens_and_recovers() {
+    let breaker = Breaker::new(3, Duration::from_millis(50));
+    fail(&breaker).await.unwrap_err();
+    fail(&breaker).await.unwrap_err();
+    // a success resets the consecutive failures
+    succeed(&breaker).await.unwrap();
+    fail(&breaker).await.unwrap_err();
+    fail(&breaker).await.unwrap_err();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert_eq!(fail(&breaker).await, Err(BreakerError::Inner("down")));
+    assert_eq!(breaker.state(), BreakerState::Open);
+    assert_eq!(succeed(&breaker).await, Err(BreakerError::Open));
+
+    monoio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+    // a failed probe opens it again
+    assert_eq!(fail(&breaker).await, Err(BreakerError::Inner("down")));
+    assert_eq!(breaker.state(), BreakerState::Open);
+
+    monoio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+    succeed(&breaker).await.unwrap();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn breaker_limits_probes() {
+    let breaker = Breaker::new(1, Duration::from_millis(10)).half_open_probes(2);
+    fail(&breaker).await.unwrap_err();
+    monoio::time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+    let p1 = breaker.try_acquire().unwrap();
+    let p2 = breaker.try_acquire().unwrap();
+    assert!(breaker.try_acquire().is_none());
+    // dropping a permit without outcome frees its probe slot
+    drop(p2);
+    let p2 = breaker.try_acquire().unwrap();
+    p1.success();
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+    assert!(breaker.try_acquire().is_none());
+    p2.success();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
@@ -0,0 +1,53 @@
+#![cfg(target_os = "linux")]
+
+use std::time::Duration;
+
+use monoio::utils::cgroup::{current_cgroup, Pressure, PressureLine};
+
+#[test]
+fn read_pressure() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    std::io::Write::write_all(
+        &mut file,
+        b"some avg10=1.50 avg60=0.25 avg300=0.00 total=1234\nfull avg10=0.00 avg60=0.00 \
+          avg300=0.00 total=0\n",
+    )
+    .unwrap();
+    let pressure = Pressure::read(file.path()).unwrap();
+    assert_eq!(
+        pressure.some,
+        PressureLine {
+            avg10: 1.5,
+            avg60: 0.25,
+            avg300: 0.0,
+            total: Duration::from_micros(1234),
+        }
+    );
+    assert_eq!(pressure.full, Some(PressureLine::default()));
+
+    std::fs::write(file.path(), "bogus").unwrap();
+    assert_eq!(
+        Pressure::read(file.path()).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn current() {
+    // Not every environment has cgroup v2 or PSI.
+    let Ok(cgroup) = current_cgroup() else {
+        return;
+    };
+    assert!(cgroup.is_dir());
+    if let Ok(pressure) = Pressure::cpu(&cgroup) {
+        assert!(pressure.some.avg10 >= 0.0);
+    }
+}
+
+#[test]
+fn runtime_with_missing_cgroup() {
+    let res = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_cgroup("/nonexistent/cgroup")
+        .build();
+    assert!(res.is_err());
+}
@@ -0,0 +1,78 @@
+use std::io;
+
+use monoio::{
+    io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt, ChunkCipher, ChunkedCipherStream},
+    net::{TcpListener, TcpStream},
+};
+
+/// XorCipher is NOT secure, it only makes sealed bytes differ from the plain
+/// ones and appends a checksum byte.
+struct XorCipher(u8);
+
+impl ChunkCipher for XorCipher {
+    fn seal(&mut self, plain: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+        let sum = plain.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        out.extend(plain.iter().map(|b| b ^ self.0));
+        out.push(sum);
+        Ok(())
+    }
+
+    fn open(&mut self, sealed: &[u8], out: &mut Vec<u8>) -> io::Result<()> {
+        let (data, sum) = sealed
+            .split_last()
+            .map(|(sum, data)| (data, *sum))
+            .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))?;
+        let start = out.len();
+        out.extend(data.iter().map(|b| b ^ self.0));
+        let actual = out[start..].iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        if actual != sum {
+            return Err(io::ErrorKind::InvalidData.into());
+        }
+        Ok(())
+    }
+}
+
+#[monoio::test_all]
+async fn chunked_cipher_roundtrip() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    let msg: Vec<u8> = (0..10000).map(|i| (i % 251) as u8).collect();
+    let expected = msg.clone();
+    let client = monoio::spawn(async move {
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let mut stream = ChunkedCipherStream::new(stream, XorCipher(0x5a)).with_max_chunk(1000);
+        let (res, _) = stream.write_all(msg).await;
+        assert_eq!(res.unwrap(), 10000);
+        let (stream, _) = stream.into_inner();
+        drop(stream);
+    });
+
+    let (stream, _) = srv.accept().await.unwrap();
+    let mut stream = ChunkedCipherStream::new(stream, XorCipher(0x5a));
+    let (res, buf) = stream.read_exact(vec![0; 10000]).await;
+    assert_eq!(res.unwrap(), 10000);
+    assert_eq!(buf, expected);
+    client.await;
+
+    // eof at chunk boundary
+    let (res, _) = stream.read(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[monoio::test_all]
+async fn chunked_cipher_reject_bad_chunk() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    monoio::spawn(async move {
+        let stream = TcpStream::connect(&addr).await.unwrap();
+        let mut stream = ChunkedCipherStream::new(stream, XorCipher(1));
+        let _ = stream.write_all(b"hello").await;
+    });
+
+    let (stream, _) = srv.accept().await.unwrap();
+    let mut stream = ChunkedCipherStream::new(stream, XorCipher(2));
+    let (res, _) = stream.read(vec![0; 16]).await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,32 @@
+#![cfg(unix)]
+use monoio::{
+    io::{
+        AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt, CountedStream, IoCounters, Splitable,
+    },
+    net::UnixStream,
+};
+
+#[monoio::test_all]
+async fn count_bytes() {
+    let (a, b) = UnixStream::pair().unwrap();
+    let tenant = IoCounters::new();
+    let mut a = CountedStream::with_counters(a, tenant.clone());
+    let mut b = CountedStream::new(b);
+
+    a.write_all(b"hello world").await.0.unwrap();
+    let (res, _) = b.read_exact(vec![0; 11]).await;
+    res.unwrap();
+    assert_eq!(tenant.written_bytes(), 11);
+    assert_eq!(tenant.read_bytes(), 0);
+    assert_eq!(b.counters().read_bytes(), 11);
+
+    // split halves share the counters
+    let (mut rd, mut wr) = a.into_split();
+    wr.write_all(b"ping").await.0.unwrap();
+    b.write_all(b"pong!").await.0.unwrap();
+    let (res, _) = rd.read(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(tenant.take(), (5, 15));
+    assert_eq!(tenant.written_bytes(), 0);
+    assert_eq!(b.counters().written_bytes(), 5);
+}
@@ -0,0 +1,64 @@
+#![cfg(target_os = "linux")]
+
+use std::time::Duration;
+
+use monoio::net::{ListenerOpts, TcpListener, TcpStream};
+
+// Pin current thread to the first CPU it may run on.
+fn pin() -> usize {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        assert_eq!(
+            libc::sched_getaffinity(0, std::mem::size_of_val(&set), &mut set),
+            0
+        );
+        let cpu = (0..libc::CPU_SETSIZE as usize)
+            .find(|&cpu| libc::CPU_ISSET(cpu, &set))
+            .unwrap();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+        assert_eq!(
+            libc::sched_setaffinity(0, std::mem::size_of_val(&set), &set),
+            0
+        );
+        cpu
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn steer_to_current_cpu() {
+    let cpu = pin();
+    assert_eq!(monoio::utils::pinned_cpu(), Some(cpu));
+
+    let opts = ListenerOpts::new().steer_to_current_cpu(2);
+    assert_eq!(opts.incoming_cpu, Some(cpu));
+    let first = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap();
+    let addr = first.local_addr().unwrap();
+    let second = TcpListener::bind_with_config(addr, &opts).unwrap();
+    let (expected, other) = if cpu.is_multiple_of(2) {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    // Loopback packets are processed on the sending CPU.
+    for _ in 0..8 {
+        let _client = TcpStream::connect(addr).await.unwrap();
+        monoio::time::timeout(Duration::from_secs(5), expected.accept())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    assert!(
+        monoio::time::timeout(Duration::from_millis(50), other.accept())
+            .await
+            .is_err()
+    );
+}
+
+#[monoio::test_all]
+async fn steering_zero_listeners() {
+    let opts = ListenerOpts::new().cpu_steering(0);
+    let err = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,42 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::io::Write;
+
+use monoio::{fs::File, IoUringDriver, RuntimeBuilder};
+
+#[test]
+fn cq_overflow() {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    // The CQ holds 512 completions, submitting 4096 reads in batches of 256
+    // between two parks overflows it.
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(256)
+        .with_cq_size(512)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = std::rc::Rc::new(File::open(tmp.path()).await.unwrap());
+        let tasks: Vec<_> = (0..4096)
+            .map(|_| {
+                let file = file.clone();
+                monoio::spawn(async move {
+                    let (res, buf) = file.read_at(vec![0; 6], 0).await;
+                    assert_eq!(res.unwrap(), 6);
+                    assert_eq!(buf, b"monoio");
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await;
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let metrics = monoio::metrics::RuntimeMetrics::current();
+            assert!(metrics.cq_overflows > 0);
+            assert_eq!(metrics.cq_dropped, 0);
+        }
+    });
+}
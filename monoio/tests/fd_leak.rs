@@ -0,0 +1,98 @@
+//! Check that fds of dropped or canceled ops are closed. The tests count the
+//! open fds of the process, so they are run one at a time.
+#![cfg(target_os = "linux")]
+
+use std::{
+    future::Future,
+    net::SocketAddr,
+    sync::{Mutex, MutexGuard},
+    task::Poll,
+    time::Duration,
+};
+
+use monoio::net::{TcpConnectOpts, TcpListener, TcpStream};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn open_fds() -> usize {
+    std::fs::read_dir("/proc/self/fd").unwrap().count()
+}
+
+#[cfg(feature = "iouring")]
+#[test]
+fn dropped_bind_async_closes_socket() {
+    let _guard = lock();
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let before = open_fds();
+        for _ in 0..8 {
+            // Poll once so the socket op is in flight, then drop it.
+            let mut bind = std::pin::pin!(TcpListener::bind_async("127.0.0.1:0"));
+            std::future::poll_fn(|cx| {
+                let _ = bind.as_mut().poll(cx);
+                Poll::Ready(())
+            })
+            .await;
+        }
+        monoio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(open_fds(), before);
+    });
+}
+
+// The losers of a happy eyeballs race are canceled with their sockets.
+async fn race_closes_losers() {
+    // SYNs are dropped once the accept queue of the listener is full.
+    let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::STREAM, None).unwrap();
+    socket
+        .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+        .unwrap();
+    socket.listen(0).unwrap();
+    let stalled = socket.local_addr().unwrap().as_socket().unwrap();
+    let mut fillers = Vec::new();
+    while let Ok(stream) = TcpStream::connect_timeout(stalled, Duration::from_millis(100)).await {
+        fillers.push(stream);
+    }
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    monoio::time::sleep(Duration::from_millis(50)).await;
+
+    let before = open_fds();
+    let opts = TcpConnectOpts::new().attempt_delay(Duration::from_millis(20));
+    for _ in 0..4 {
+        let stream = TcpStream::connect_with_config(&[stalled, stalled, stalled, addr][..], &opts)
+            .await
+            .unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+    }
+    monoio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(open_fds(), before);
+}
+
+#[cfg(feature = "iouring")]
+#[test]
+fn happy_eyeballs_closes_losers_uring() {
+    let _guard = lock();
+    monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap()
+        .block_on(race_closes_losers());
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn happy_eyeballs_closes_losers_legacy() {
+    let _guard = lock();
+    monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap()
+        .block_on(race_closes_losers());
+}
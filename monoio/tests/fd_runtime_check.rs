@@ -0,0 +1,24 @@
+#![cfg(all(unix, debug_assertions, feature = "debug"))]
+
+use monoio::{io::AsyncWriteRentExt, net::UnixStream, FusionDriver, RuntimeBuilder};
+
+#[test]
+#[should_panic(expected = "io objects must stay on the runtime they are created on")]
+fn use_on_other_runtime() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new().build().unwrap();
+    let (mut a, _b) = rt.block_on(async { UnixStream::pair().unwrap() });
+
+    let mut other = RuntimeBuilder::<FusionDriver>::new().build().unwrap();
+    other.block_on(async move {
+        let _ = a.write_all(b"hello").await;
+    });
+}
+
+#[test]
+fn use_on_same_runtime() {
+    let mut rt = RuntimeBuilder::<FusionDriver>::new().build().unwrap();
+    let (mut a, _b) = rt.block_on(async { UnixStream::pair().unwrap() });
+    rt.block_on(async move {
+        a.write_all(b"hello").await.0.unwrap();
+    });
+}
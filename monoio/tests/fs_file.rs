@@ -149,6 +149,139 @@ async fn sync_doesnt_kill_anything() {
     file.sync_data().await.unwrap();
 }
 
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn send_to_socket() {
+    use monoio::{io::AsyncReadRentExt, net::UnixStream};
+
+    // larger than the internal pipe, so several rounds are needed
+    let data: Vec<u8> = (0..200 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut tempfile = tempfile();
+    tempfile.write_all(&data).unwrap();
+    tempfile.as_file_mut().sync_data().unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    let (mut tx, mut rx) = UnixStream::pair().unwrap();
+    let expected = data[10..].to_vec();
+    let reader = monoio::spawn(async move {
+        let buf = Vec::with_capacity(expected.len());
+        let (res, buf) = rx.read_exact(buf).await;
+        res.unwrap();
+        assert!(buf == expected);
+    });
+    // stops at the end of file
+    let sent = file.send_to(&mut tx, 10, u64::MAX).await.unwrap();
+    assert_eq!(sent, data.len() as u64 - 10);
+    reader.await;
+
+    let sent = file.send_to(&mut tx, data.len() as u64, 16).await.unwrap();
+    assert_eq!(sent, 0);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn copy_file() {
+    let data: Vec<u8> = (0..300 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut src = tempfile();
+    src.write_all(&data).unwrap();
+    src.as_file_mut().sync_data().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let dst = dir.path().join("copied");
+
+    let copied = monoio::fs::copy(src.path(), &dst).await.unwrap();
+    assert_eq!(copied, data.len() as u64);
+    assert!(std::fs::read(&dst).unwrap() == data);
+
+    // the destination is truncated
+    std::fs::write(src.path(), HELLO).unwrap();
+    let copied = monoio::fs::copy(src.path(), &dst).await.unwrap();
+    assert_eq!(copied, HELLO.len() as u64);
+    assert_eq!(std::fs::read(&dst).unwrap(), HELLO);
+
+    assert!(monoio::fs::copy(dir.path(), &dst).await.is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn copy_file_yields() {
+    use std::{cell::Cell, rc::Rc};
+
+    // several copy_file_range chunks
+    let data = vec![7u8; 9 * 1024 * 1024];
+    let mut src = tempfile();
+    src.write_all(&data).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let dst = dir.path().join("copied");
+
+    let ran = Rc::new(Cell::new(false));
+    let task_ran = ran.clone();
+    monoio::spawn(async move { task_ran.set(true) });
+    let copied = monoio::fs::copy(src.path(), &dst).await.unwrap();
+    assert_eq!(copied, data.len() as u64);
+    // the other task ran during the copy
+    assert!(ran.get());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn allocate() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.allocate(0, 8192, 0).await.unwrap();
+    assert_eq!(std::fs::metadata(tempfile.path()).unwrap().len(), 8192);
+
+    file.write_all_at(&[1_u8; 8192][..], 0).await.0.unwrap();
+    let mode = libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE;
+    match file.allocate(0, 4096, mode).await {
+        Ok(()) => {
+            let data = std::fs::read(tempfile.path()).unwrap();
+            assert_eq!(data.len(), 8192);
+            assert!(data[..4096].iter().all(|b| *b == 0));
+            assert!(data[4096..].iter().all(|b| *b == 1));
+        }
+        // not every filesystem supports punching holes
+        Err(e) => assert_eq!(e.raw_os_error(), Some(libc::EOPNOTSUPP)),
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn advise() {
+    use monoio::{buf::advise, fs::Advice};
+
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_all_at(&[1_u8; 8192][..], 0).await.0.unwrap();
+    file.advise(.., Advice::Sequential).await.unwrap();
+    file.advise(0..4096, Advice::WillNeed).await.unwrap();
+    file.advise(4096..=8191, Advice::DontNeed).await.unwrap();
+    let data = std::fs::read(tempfile.path()).unwrap();
+    assert!(data.len() == 8192 && data.iter().all(|b| *b == 1));
+
+    let (res, buf) = advise(vec![7_u8; 64 * 1024], Advice::WillNeed).await;
+    res.unwrap();
+    assert!(buf.iter().all(|b| *b == 7));
+    let (res, _) = advise(vec![0_u8; 16], Advice::NoReuse).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn sync_range() {
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_all_at(&[1_u8; 8192][..], 0).await.0.unwrap();
+    file.sync_range(0, 4096, libc::SYNC_FILE_RANGE_WRITE)
+        .await
+        .unwrap();
+    let flags = libc::SYNC_FILE_RANGE_WAIT_BEFORE
+        | libc::SYNC_FILE_RANGE_WRITE
+        | libc::SYNC_FILE_RANGE_WAIT_AFTER;
+    file.sync_range(0, 0, flags).await.unwrap();
+    let err = file.sync_range(0, 4096, 0xff).await.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
 fn tempfile() -> NamedTempFile {
     NamedTempFile::new().expect("unable to create tempfile")
 }
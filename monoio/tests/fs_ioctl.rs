@@ -0,0 +1,43 @@
+#![cfg(all(unix, feature = "sync"))]
+
+use std::io::Write;
+
+use monoio::blocking::DefaultThreadPool;
+
+async fn fionread() {
+    let (mut tx, rx) = std::os::unix::net::UnixStream::pair().unwrap();
+    tx.write_all(b"hello").unwrap();
+
+    // FIONREAD writes an int.
+    let (_, buf) = unsafe { monoio::fs::ioctl(&rx, libc::FIONREAD as _, vec![0; 4]) }
+        .await
+        .unwrap();
+    assert_eq!(i32::from_ne_bytes(buf[..4].try_into().unwrap()), 5);
+
+    // TIOCGWINSZ writes a struct winsize.
+    let winsize = vec![0; std::mem::size_of::<libc::winsize>()];
+    let err = unsafe { monoio::fs::ioctl(&rx, libc::TIOCGWINSZ as _, winsize) }
+        .await
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOTTY));
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn ioctl_uring() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(fionread());
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn ioctl_legacy() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(fionread());
+}
@@ -0,0 +1,48 @@
+#![cfg(target_os = "linux")]
+
+use std::{io::Write, os::unix::fs::MetadataExt};
+
+use monoio::fs::File;
+
+#[monoio::test_all]
+async fn metadata_of_path_and_file() {
+    let mut tempfile = tempfile::NamedTempFile::new().unwrap();
+    tempfile.write_all(b"hello world").unwrap();
+    let std_meta = std::fs::metadata(tempfile.path()).unwrap();
+
+    let meta = monoio::fs::metadata(tempfile.path()).await.unwrap();
+    assert!(meta.is_file());
+    assert!(!meta.is_dir());
+    assert_eq!(meta.len(), 11);
+    assert_eq!(meta.ino(), std_meta.ino());
+    assert_eq!(meta.dev(), std_meta.dev());
+    assert_eq!(meta.mode(), std_meta.mode());
+    assert_eq!(meta.uid(), std_meta.uid());
+    assert_eq!(meta.modified().unwrap(), std_meta.modified().unwrap());
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    let by_fd = file.metadata().await.unwrap();
+    assert_eq!(by_fd.ino(), meta.ino());
+    assert_eq!(by_fd.mount_id(), meta.mount_id());
+
+    let dir = tempfile::tempdir().unwrap();
+    assert!(monoio::fs::metadata(dir.path()).await.unwrap().is_dir());
+    let err = monoio::fs::metadata(dir.path().join("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn symlink_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target");
+    let link = dir.path().join("link");
+    std::fs::write(&target, b"data").unwrap();
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    assert!(monoio::fs::metadata(&link).await.unwrap().is_file());
+    let meta = monoio::fs::symlink_metadata(&link).await.unwrap();
+    assert!(meta.is_symlink());
+    assert!(!meta.is_file());
+}
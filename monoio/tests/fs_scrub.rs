@@ -0,0 +1,49 @@
+#![cfg(target_os = "linux")]
+
+use std::io::Write;
+
+use tempfile::NamedTempFile;
+
+fn file_with(content: &[u8]) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content).unwrap();
+    file
+}
+
+#[monoio::test_all]
+async fn scrub() {
+    let big: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    let files = [file_with(&big), file_with(b""), file_with(b"monoio")];
+    let paths: Vec<_> = files.iter().map(|f| f.path().to_owned()).collect();
+
+    let mut contents = vec![Vec::new(); 3];
+    let mut lasts = 0;
+    let total = monoio::fs::scrub(&paths, 4, 1024, |chunk| {
+        let idx = paths.iter().position(|p| p == chunk.path).unwrap();
+        assert_eq!(chunk.offset, contents[idx].len() as u64);
+        contents[idx].extend_from_slice(chunk.data);
+        if chunk.last {
+            lasts += 1;
+        }
+        Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(total, 10_006);
+    assert_eq!(lasts, 3);
+    assert_eq!(contents[0], big);
+    assert!(contents[1].is_empty());
+    assert_eq!(contents[2], b"monoio");
+
+    // the callback aborts the scrub
+    let err = monoio::fs::scrub(&paths, 4, 1024, |_| Err(std::io::Error::other("stop")))
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "stop");
+
+    let err = monoio::fs::scrub(["/nonexistent"], 4, 1024, |_| Ok(()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(err.to_string().starts_with("/nonexistent: "));
+}
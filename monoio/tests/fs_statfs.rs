@@ -0,0 +1,38 @@
+#![cfg(all(unix, feature = "sync"))]
+
+use monoio::blocking::DefaultThreadPool;
+
+async fn temp_dir_stats() {
+    let dir = tempfile::tempdir().unwrap();
+    let stats = monoio::fs::statfs(dir.path()).await.unwrap();
+    assert!(stats.block_size() > 0);
+    assert!(stats.total_space() >= stats.free_space());
+    assert!(stats.free_space() >= stats.available_space());
+    assert!(stats.max_name_len() > 0);
+    assert!(!stats.is_read_only());
+
+    let err = monoio::fs::statfs(dir.path().join("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn statfs_uring() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(temp_dir_stats());
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn statfs_legacy() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .attach_thread_pool(Box::new(DefaultThreadPool::new(1)))
+        .build()
+        .unwrap();
+    rt.block_on(temp_dir_stats());
+}
@@ -0,0 +1,53 @@
+#![cfg(target_os = "linux")]
+
+use std::time::Duration;
+
+use monoio::fs::{tempdir_in, tempfile_in};
+
+#[monoio::test_all]
+async fn anonymous_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = tempfile_in(dir.path()).await.unwrap();
+    let (res, _) = file.write_all_at(&b"staged data"[..], 0).await;
+    res.unwrap();
+    let (res, buf) = file.read_exact_at(vec![0; 11], 0).await;
+    res.unwrap();
+    assert_eq!(buf, b"staged data");
+
+    // The file has no name.
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    file.close().await.unwrap();
+}
+
+#[monoio::test_all]
+async fn close_dir() {
+    let parent = tempfile::tempdir().unwrap();
+    let dir = tempdir_in(parent.path()).await.unwrap();
+    let path = dir.path().to_path_buf();
+    assert!(path.starts_with(parent.path()));
+    std::fs::create_dir(path.join("nested")).unwrap();
+    std::fs::write(path.join("nested/data"), b"data").unwrap();
+    std::fs::write(path.join("data"), b"data").unwrap();
+
+    dir.close().await.unwrap();
+    assert!(!path.exists());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn drop_dir() {
+    let parent = tempfile::tempdir().unwrap();
+    let dir = tempdir_in(parent.path()).await.unwrap();
+    let path = dir.path().to_path_buf();
+    std::fs::write(path.join("data"), b"data").unwrap();
+
+    drop(dir);
+    monoio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!path.exists());
+}
+
+#[monoio::test_all]
+async fn keep_dir() {
+    let parent = tempfile::tempdir().unwrap();
+    let path = tempdir_in(parent.path()).await.unwrap().into_path();
+    assert!(path.is_dir());
+}
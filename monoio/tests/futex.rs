@@ -0,0 +1,55 @@
+#![cfg(target_os = "linux")]
+
+use std::{sync::atomic::Ordering, time::Duration};
+
+use monoio::sync::futex::Futex;
+
+#[cfg(feature = "iouring")]
+#[monoio::test(driver = "uring")]
+async fn wait_wake_from_thread() {
+    let futex = Futex::new(0);
+    let notifier = futex.clone();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        notifier.value().store(1, Ordering::Release);
+        notifier.wake_blocking(1).unwrap();
+    });
+    while futex.value().load(Ordering::Acquire) == 0 {
+        futex.wait(0).await.unwrap();
+    }
+    handle.join().unwrap();
+
+    // a value which is not the expected one returns at once
+    futex.wait(0).await.unwrap();
+}
+
+#[cfg(feature = "iouring")]
+#[monoio::test(driver = "uring")]
+async fn wake_from_runtime() {
+    let futex = Futex::new(0);
+    let notifier = futex.clone();
+    let handle = std::thread::spawn(move || {
+        let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .enable_timer()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            monoio::time::sleep(Duration::from_millis(20)).await;
+            notifier.value().store(1, Ordering::Release);
+            notifier.wake(u32::MAX).await.unwrap()
+        })
+    });
+    while futex.value().load(Ordering::Acquire) == 0 {
+        futex.wait(0).await.unwrap();
+    }
+    assert!(handle.join().unwrap() <= 1);
+}
+
+#[cfg(feature = "legacy")]
+#[monoio::test(driver = "legacy")]
+async fn legacy_wait_unsupported() {
+    let futex = Futex::new(0);
+    let err = futex.wait(0).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    assert_eq!(futex.wake(1).await.unwrap(), 0);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "interceptor")]
+
+use std::{
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use monoio::{
+    interceptor::{Interceptor, OpInfo, OpKind},
+    Buildable, Driver, RuntimeBuilder,
+};
+
+#[derive(Default)]
+struct Policy {
+    submitted: AtomicU64,
+    written: AtomicU64,
+}
+
+impl Interceptor for Policy {
+    fn on_submit(&self, op: &OpInfo<'_>) -> io::Result<u64> {
+        self.submitted.fetch_add(1, Ordering::Relaxed);
+        if let Some(path) = op.path() {
+            if path.starts_with("/etc") {
+                return Err(io::ErrorKind::PermissionDenied.into());
+            }
+        }
+        Ok(42)
+    }
+
+    fn on_complete(&self, kind: OpKind, tag: u64, result: &io::Result<u32>) {
+        assert_eq!(tag, 42);
+        if let (OpKind::Write, Ok(n)) = (kind, result) {
+            self.written.fetch_add(*n as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+fn run<D: Buildable + Driver>() {
+    let policy = Arc::new(Policy::default());
+    let builder = RuntimeBuilder::<D>::new().with_interceptor(policy.clone());
+    let mut rt = Buildable::build(builder).unwrap();
+    rt.block_on(async {
+        let err = monoio::fs::File::open("/etc/hostname").await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data");
+        let file = monoio::fs::File::create(&path).await.unwrap();
+        file.write_all_at(b"hello".to_vec(), 0).await.0.unwrap();
+        file.close().await.unwrap();
+        assert_eq!(std::fs::read(Path::new(&path)).unwrap(), b"hello");
+    });
+    assert!(policy.submitted.load(Ordering::Relaxed) >= 3);
+    assert_eq!(policy.written.load(Ordering::Relaxed), 5);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn uring_interceptor() {
+    run::<monoio::IoUringDriver>();
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_interceptor() {
+    run::<monoio::LegacyDriver>();
+}
@@ -0,0 +1,43 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use monoio::{
+    net::{TcpListener, TcpStream},
+    utils::LoadShedder,
+};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn shed_on_lag() {
+    let shedder = LoadShedder::with_interval(Duration::from_millis(2), Duration::from_millis(1));
+    assert!(!shedder.is_overloaded());
+
+    // Tasks hogging the thread delay the probe.
+    let stop = Rc::new(Cell::new(false));
+    for _ in 0..4 {
+        let stop = stop.clone();
+        monoio::spawn(async move {
+            while !stop.get() {
+                let begin = Instant::now();
+                while begin.elapsed() < Duration::from_millis(3) {}
+                monoio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+    }
+    let begin = Instant::now();
+    while !shedder.is_overloaded() {
+        assert!(begin.elapsed() < Duration::from_secs(5), "{:?}", shedder);
+        monoio::time::sleep(Duration::from_millis(1)).await;
+    }
+    assert!(shedder.lag() > Duration::from_millis(2));
+    stop.set(true);
+
+    // The accept layer waits until the runtime recovers.
+    let mut listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    monoio::spawn(async move { TcpStream::connect(addr).await });
+    assert!(shedder.accept(&mut listener).await.unwrap().is_ok());
+    assert!(!shedder.is_overloaded());
+}
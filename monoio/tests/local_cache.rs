@@ -0,0 +1,75 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::utils::LocalCache;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn expiration() {
+    let cache = LocalCache::new().ttl(Duration::from_millis(30));
+    cache.insert(1, "a");
+    assert_eq!(cache.get(&1), Some("a"));
+    monoio::time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(cache.get(&1), None);
+
+    let cache = LocalCache::new().tti(Duration::from_millis(30));
+    cache.insert(1, "a");
+    cache.insert(2, "b");
+    for _ in 0..4 {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        // keeps the entry alive
+        assert_eq!(cache.get(&1), Some("a"));
+    }
+    // removed by the sweeper without being accessed
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(&2), None);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn coalesce_loads() {
+    let cache = LocalCache::new();
+    let loads = Rc::new(Cell::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let cache = cache.clone();
+        let loads = loads.clone();
+        handles.push(monoio::spawn(async move {
+            cache
+                .get_or_insert_with("key", || async move {
+                    loads.set(loads.get() + 1);
+                    monoio::time::sleep(Duration::from_millis(5)).await;
+                    42
+                })
+                .await
+        }));
+    }
+    for h in handles {
+        assert_eq!(h.await, 42);
+    }
+    assert_eq!(loads.get(), 1);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn errors_are_not_cached() {
+    let cache = LocalCache::<&str, u32>::new();
+    let res: Result<u32, &str> = cache
+        .try_get_or_insert_with("key", || async { Err("failed") })
+        .await;
+    assert_eq!(res, Err("failed"));
+    assert!(cache.is_empty());
+
+    // a waiter retries when the loader fails
+    let c = cache.clone();
+    let leader = monoio::spawn(async move {
+        c.try_get_or_insert_with("key", || async {
+            monoio::time::sleep(Duration::from_millis(5)).await;
+            Err("failed")
+        })
+        .await
+    });
+    monoio::spawn(async {}).await;
+    let res: Result<u32, &str> = cache
+        .try_get_or_insert_with("key", || async { Ok(7) })
+        .await;
+    assert_eq!(res, Ok(7));
+    assert_eq!(leader.await, Err("failed"));
+    assert_eq!(cache.get(&"key"), Some(7));
+}
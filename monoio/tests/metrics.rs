@@ -0,0 +1,109 @@
+#![cfg(feature = "metrics")]
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    metrics::RuntimeMetrics,
+    net::{TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn metrics_snapshot() {
+    let before = RuntimeMetrics::current();
+    monoio::spawn(async {}).await;
+    let after = RuntimeMetrics::current();
+    assert_eq!(before.thread_id, after.thread_id);
+    assert!(after.tasks_polled > before.tasks_polled);
+    assert_eq!(after.timers, 0);
+
+    let mut out = String::new();
+    after.encode_prometheus(&mut out);
+    assert!(out.contains("# TYPE monoio_tasks_polled_total counter\n"));
+    assert!(out.contains(&format!(
+        "monoio_tasks_polled_total{{thread=\"{}\"}} {}\n",
+        after.thread_id, after.tasks_polled
+    )));
+}
+
+#[monoio::test_all]
+async fn serve_prometheus() {
+    // find a free port
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    monoio::spawn(monoio::metrics::serve_prometheus(addr));
+    monoio::spawn(async {}).await;
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let req = b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    stream.write_all(req.to_vec()).await.0.unwrap();
+    let mut resp = Vec::new();
+    loop {
+        let (res, buf) = stream.read(Vec::with_capacity(1024)).await;
+        if res.unwrap() == 0 {
+            break;
+        }
+        resp.extend_from_slice(&buf);
+    }
+    let resp = String::from_utf8(resp).unwrap();
+    assert!(resp.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(resp.contains("monoio_parks_total{thread="));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn utilization() {
+    use std::time::Duration;
+
+    let begin = RuntimeMetrics::current();
+    // parked
+    monoio::time::sleep(Duration::from_millis(100)).await;
+    let parked = RuntimeMetrics::current();
+    assert!(parked.park_duration - begin.park_duration >= Duration::from_millis(90));
+    assert!(parked.utilization_since(&begin) < 0.5);
+
+    // busy
+    std::thread::sleep(Duration::from_millis(100));
+    let busy = RuntimeMetrics::current();
+    assert!(busy.busy_duration - parked.busy_duration >= Duration::from_millis(100));
+    assert!(busy.utilization_since(&parked) > 0.5);
+    assert!((0.0..=1.0).contains(&busy.utilization()));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn memory_report() {
+    use monoio::metrics::MemoryReport;
+
+    let before = MemoryReport::current();
+    let (tx, rx) = local_sync::oneshot::channel::<()>();
+    let task = monoio::spawn(async move {
+        let _ = rx.await;
+    });
+    let sleep = monoio::spawn(monoio::time::sleep(std::time::Duration::from_millis(10)));
+    monoio::spawn(async {}).await;
+
+    let during = MemoryReport::current();
+    assert_eq!(during.tasks, before.tasks + 2);
+    assert!(during.task_bytes > before.task_bytes);
+    assert_eq!(during.timers, before.timers + 1);
+    assert!(during.timer_bytes > before.timer_bytes);
+    assert!(during.total_bytes() > before.total_bytes());
+
+    let _ = tx.send(());
+    task.await;
+    sleep.await;
+    monoio::spawn(async {}).await;
+    let after = MemoryReport::current();
+    assert_eq!(after.tasks, before.tasks);
+    assert_eq!(after.task_bytes, before.task_bytes);
+}
+
+#[test]
+fn runtime_memory_report() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    let idle = rt.memory_report();
+    assert_eq!(idle.inflight_ops, 0);
+    assert!(idle.task_queue_bytes > 0);
+    rt.block_on(async {});
+}
@@ -0,0 +1,74 @@
+use std::{future::Future, pin::pin, task::Poll};
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+// Poll the future once, it must be pending.
+async fn poll_once<F: Future>(fut: std::pin::Pin<&mut F>) {
+    let mut fut = Some(fut);
+    std::future::poll_fn(|cx| {
+        assert!(fut.take().unwrap().poll(cx).is_pending());
+        Poll::Ready(())
+    })
+    .await
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn panic_with_read_in_flight() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (mut conn, _) = listener.accept().await.unwrap();
+
+    let res = monoio::task::catch_unwind(async {
+        let read = pin!(conn.read(vec![0; 64]));
+        poll_once(read).await;
+        panic!("boom");
+    })
+    .await;
+    assert_eq!(*res.unwrap_err().downcast::<&str>().unwrap(), "boom");
+
+    // The kernel completes the parked read once data arrives.
+    let (res, _) = client.write_all(b"hello").await;
+    res.unwrap();
+    monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let res = monoio::task::catch_unwind(async { 1 }).await;
+    assert_eq!(res.unwrap(), 1);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn drop_runtime_with_read_in_flight() {
+    let (listener, client, conn) = {
+        let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap();
+            let client = TcpStream::connect(addr).await.unwrap();
+            let (conn, _) = listener.accept().await.unwrap();
+            (listener, client, conn)
+        })
+    };
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .build()
+        .unwrap();
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        rt.block_on(async {
+            let mut conn = conn;
+            let read = pin!(conn.read(vec![0; 64]));
+            poll_once(read).await;
+            panic!("boom");
+        })
+    }));
+    assert!(res.is_err());
+    // The in-flight read is canceled and completed before its buffer is
+    // freed, without waiting for the drain timeout.
+    let begin = std::time::Instant::now();
+    drop(rt);
+    assert!(begin.elapsed() < std::time::Duration::from_millis(500));
+    drop((listener, client));
+}
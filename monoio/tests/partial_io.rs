@@ -0,0 +1,69 @@
+#![cfg(unix)]
+use std::io;
+
+use monoio::{
+    buf::{IoBuf, IoVecBuf},
+    io::{AsyncReadRentExt, AsyncWriteRent, AsyncWriteRentExt},
+    net::UnixStream,
+    BufResult,
+};
+
+// Accepts up to 3 bytes per write, and fails once `room` bytes are written.
+struct Limited {
+    room: usize,
+}
+
+impl AsyncWriteRent for Limited {
+    async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        if self.room == 0 {
+            return (Err(io::ErrorKind::BrokenPipe.into()), buf);
+        }
+        let n = buf.bytes_init().min(3).min(self.room);
+        self.room -= n;
+        (Ok(n), buf)
+    }
+
+    async fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> BufResult<usize, T> {
+        (Err(io::ErrorKind::Unsupported.into()), buf_vec)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[monoio::test_all]
+async fn write_all_progress() {
+    let mut w = Limited { room: 7 };
+    let (res, buf) = w.write_all_partial(b"hello world").await;
+    let e = res.unwrap_err();
+    assert_eq!(e.written(), 7);
+    assert_eq!(e.error().kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(buf, b"hello world");
+
+    // plain write_all keeps returning the io error
+    let (res, _) = Limited { room: 2 }.write_all(b"hello").await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+
+    let (res, _) = Limited { room: 16 }.write_all_partial(b"hello").await;
+    assert_eq!(res.unwrap(), 5);
+}
+
+#[monoio::test_all]
+async fn read_exact_progress() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    a.write_all(b"hello").await.0.unwrap();
+    drop(a);
+
+    let (res, buf) = b.read_exact_partial(vec![0; 11]).await;
+    let e = res.unwrap_err();
+    assert_eq!(e.read(), 5);
+    assert_eq!(e.error().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(buf, b"hello");
+    let e: io::Error = e.into();
+    assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+}
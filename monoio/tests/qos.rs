@@ -0,0 +1,84 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{io::Write, time::Duration};
+
+use monoio::{
+    fs::File,
+    io::{with_qos, AsyncReadRent, AsyncWriteRentExt, Qos},
+    net::UnixStream,
+    IoUringDriver, RuntimeBuilder,
+};
+
+fn bulk_reads(bulk: io_uring::Builder) {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_bulk_ring(bulk, 64)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = std::rc::Rc::new(File::open(tmp.path()).await.unwrap());
+        let tasks: Vec<_> = (0..128)
+            .map(|i| {
+                let file = file.clone();
+                let qos = if i % 2 == 0 { Qos::Bulk } else { Qos::Latency };
+                monoio::spawn(with_qos(qos, async move {
+                    let (res, buf) = file.read_at(vec![0; 6], 0).await;
+                    assert_eq!(res.unwrap(), 6);
+                    assert_eq!(buf, b"monoio");
+                }))
+            })
+            .collect();
+        for task in tasks {
+            task.await;
+        }
+    });
+}
+
+#[test]
+fn bulk_ring() {
+    bulk_reads(io_uring::IoUring::builder());
+}
+
+#[test]
+fn bulk_ring_sqpoll() {
+    let mut urb = io_uring::IoUring::builder();
+    urb.setup_sqpoll(1000);
+    bulk_reads(urb);
+}
+
+#[test]
+fn bulk_wake_and_cancel() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_bulk_ring(io_uring::IoUring::builder(), 64)
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let (mut a, mut b) = UnixStream::pair().unwrap();
+
+        // The driver parks until the bulk op completes.
+        let read = monoio::spawn(with_qos(Qos::Bulk, async move {
+            let (res, buf) = b.read(vec![0; 6]).await;
+            assert_eq!(res.unwrap(), 6);
+            assert_eq!(buf, b"monoio");
+            b
+        }));
+        monoio::time::sleep(Duration::from_millis(20)).await;
+        a.write_all(b"monoio").await.0.unwrap();
+        let mut b = read.await;
+
+        // A canceled bulk op is canceled in the bulk ring.
+        let res = with_qos(
+            Qos::Bulk,
+            monoio::time::timeout(Duration::from_millis(20), b.read(vec![0; 6])),
+        )
+        .await;
+        assert!(res.is_err());
+        a.write_all(b"again").await.0.unwrap();
+        let (res, buf) = b.read(vec![0; 5]).await;
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(buf, b"again");
+    });
+}
@@ -0,0 +1,100 @@
+#![cfg(target_os = "linux")]
+
+use monoio::{
+    io::{stream::Stream, AsyncWriteRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream, UnixStream},
+};
+
+#[monoio::test_all]
+async fn tcp_recv_bundle() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    let (conn, _) = listener.accept().await.unwrap();
+
+    let data: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+    let (res, _) = client.write_all(data.clone()).await;
+    res.unwrap();
+    client.shutdown().await.unwrap();
+
+    let mut stream = conn.recv_bundle(8, 1024).unwrap();
+    let mut received = Vec::new();
+    let mut max_chunks = 0;
+    while let Some(bundle) = stream.next().await {
+        let bundle = bundle.unwrap();
+        assert!(bundle.chunks().all(|chunk| chunk.len() <= 1024));
+        max_chunks = max_chunks.max(bundle.chunks().count());
+        bundle.copy_to(&mut received);
+    }
+    assert_eq!(received, data);
+    // Data queued before the first recv is received into several buffers.
+    if !monoio::utils::is_legacy() {
+        assert!(max_chunks > 1);
+    }
+}
+
+#[monoio::test_all]
+async fn unix_recv_bundle_held_buffers() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    let mut stream = a.recv_bundle(2, 16).unwrap();
+
+    let mut held = Vec::new();
+    let mut received = Vec::new();
+    for i in 0..8u8 {
+        let (res, _) = b.write_all(vec![i; 16]).await;
+        res.unwrap();
+        let bundle = stream.next().await.unwrap().unwrap();
+        assert_eq!(bundle.len(), 16);
+        bundle.copy_to(&mut received);
+        // Hold one buffer, the stream waits for the other.
+        held.push(bundle);
+        if held.len() == 2 {
+            held.remove(0);
+        }
+    }
+    let expected: Vec<u8> = (0..8u8).flat_map(|i| [i; 16]).collect();
+    assert_eq!(received, expected);
+}
+
+#[monoio::test_all]
+async fn recv_bundle_invalid() {
+    let (a, _b) = UnixStream::pair().unwrap();
+    assert!(a.recv_bundle(0, 16).is_err());
+    assert!(a.recv_bundle(4, 0).is_err());
+}
+
+#[monoio::test_all]
+async fn recv_bundle_incremental() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    let mut stream = a.recv_bundle(2, 4096).unwrap().incremental();
+
+    // Small receives share the buffers, so more of them than buffers can be
+    // held at once.
+    let count = if stream.is_incremental() { 16 } else { 2 };
+    let mut held = Vec::new();
+    for i in 0..count {
+        let (res, _) = b.write_all(vec![i as u8; 100]).await;
+        res.unwrap();
+        let bundle = stream.next().await.unwrap().unwrap();
+        assert_eq!(bundle.len(), 100);
+        held.push(bundle);
+    }
+    for (i, bundle) in held.iter().enumerate() {
+        let mut received = Vec::new();
+        bundle.copy_to(&mut received);
+        assert_eq!(received, vec![i as u8; 100]);
+    }
+    drop(held);
+
+    // Receives spanning the end of a buffer, after the held ones are given
+    // back.
+    let data: Vec<u8> = (0..20000).map(|i| i as u8).collect();
+    let (res, _) = b.write_all(data.clone()).await;
+    res.unwrap();
+    b.shutdown().await.unwrap();
+    let mut received = Vec::new();
+    while let Some(bundle) = stream.next().await {
+        bundle.unwrap().copy_to(&mut received);
+    }
+    assert_eq!(received, data);
+}
@@ -0,0 +1,85 @@
+#![cfg(target_os = "linux")]
+
+use std::{os::unix::prelude::AsRawFd, time::Duration};
+
+use monoio::{
+    io::stream::Stream,
+    net::{udp::UdpSocket, UnixDatagram},
+};
+
+#[monoio::test_all]
+async fn udp_recv_msg_multi() {
+    let rx = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let tx = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let rx_addr = rx.local_addr().unwrap();
+    let tx_addr = tx.local_addr().unwrap();
+    let on: libc::c_int = 1;
+    let ret = unsafe {
+        libc::setsockopt(
+            rx.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMP,
+            &on as *const _ as *const _,
+            std::mem::size_of_val(&on) as _,
+        )
+    };
+    assert_eq!(ret, 0);
+
+    let mut stream = rx.recv_msg_multi(4, 256, 64).unwrap();
+    for i in 0..16u8 {
+        tx.send_to(vec![i; 8], rx_addr).await.0.unwrap();
+        let msg = stream.next().await.unwrap().unwrap();
+        assert_eq!(msg.data(), &[i; 8]);
+        assert!(!msg.is_truncated());
+        assert_eq!(msg.addr(), Some(tx_addr));
+        let cmsg = msg.control_messages().next().unwrap();
+        assert_eq!(cmsg.level, libc::SOL_SOCKET);
+        assert_eq!(cmsg.ty, libc::SCM_TIMESTAMP);
+        assert_eq!(cmsg.data.len(), std::mem::size_of::<libc::timeval>());
+    }
+
+    // longer than the buffer
+    tx.send_to(vec![1; 1024], rx_addr).await.0.unwrap();
+    let msg = stream.next().await.unwrap().unwrap();
+    assert!(msg.is_truncated());
+    assert!(msg.data().len() < 256);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn unix_recv_msg_multi_held_buffers() {
+    let (rx, tx) = UnixDatagram::pair().unwrap();
+    let mut stream = rx.recv_msg_multi(2, 256, 0).unwrap();
+
+    for i in 0..3u8 {
+        tx.send(vec![i; 4]).await.0.unwrap();
+    }
+    let first = stream.next().await.unwrap().unwrap();
+    let second = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.data(), &[0; 4]);
+    assert_eq!(second.data(), &[1; 4]);
+    assert!(first.addr().unwrap().is_unnamed());
+
+    // Both buffers are held, the third datagram waits for one.
+    drop(first);
+    let third = monoio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(third.data(), &[2; 4]);
+    drop(second);
+
+    // the stream can be dropped with a recvmsg in flight
+    drop(stream);
+    drop(third);
+    let mut stream = rx.recv_msg_multi(2, 256, 0).unwrap();
+    tx.send(vec![3; 4]).await.0.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap().data(), &[3; 4]);
+}
+
+#[monoio::test_all]
+async fn recv_msg_multi_small_buffers() {
+    let (rx, _tx) = UnixDatagram::pair().unwrap();
+    let err = rx.recv_msg_multi(2, 64, 0).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
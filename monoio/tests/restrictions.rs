@@ -0,0 +1,54 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::io::Write;
+
+use io_uring::opcode;
+use monoio::{fs::File, IoUringDriver, RuntimeBuilder};
+
+#[test]
+fn restrict_opcodes() {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .restrict_opcodes(&[opcode::OpenAt::CODE, opcode::Close::CODE])
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = File::open(tmp.path()).await.unwrap();
+        let (res, _) = file.write_at(b"uring", 0).await;
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EACCES));
+        file.close().await.unwrap();
+
+        // ops of the driver itself are still allowed
+        monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+    });
+}
+
+#[test]
+fn restrict_bulk_ring() {
+    use monoio::io::{with_qos, Qos};
+
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_bulk_ring(io_uring::IoUring::builder(), 64)
+        .restrict_opcodes(&[
+            opcode::OpenAt::CODE,
+            opcode::Close::CODE,
+            opcode::Read::CODE,
+        ])
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = File::open(tmp.path()).await.unwrap();
+        let (res, buf) = with_qos(Qos::Bulk, file.read_at(vec![0; 6], 0)).await;
+        assert_eq!(res.unwrap(), 6);
+        assert_eq!(buf, b"monoio");
+        let (res, _) = with_qos(Qos::Bulk, file.write_at(b"uring", 0)).await;
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EACCES));
+        file.close().await.unwrap();
+    });
+}
@@ -0,0 +1,121 @@
+use std::{
+    cell::Cell,
+    io,
+    time::{Duration, Instant},
+};
+
+use monoio::{
+    io::Canceller,
+    utils::{is_transient, retry, retry_if, RetryBudget, RetryPolicy},
+};
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .initial_delay(Duration::from_millis(1))
+        .max_delay(Duration::from_millis(5))
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_until_ok() {
+    let calls = Cell::new(0);
+    let policy = fast_policy().max_attempts(5);
+    let res: Result<u32, &str> = retry(&policy, || async {
+        calls.set(calls.get() + 1);
+        if calls.get() < 3 {
+            Err("not yet")
+        } else {
+            Ok(calls.get())
+        }
+    })
+    .await;
+    assert_eq!(res, Ok(3));
+
+    calls.set(0);
+    let res: Result<(), &str> = retry(&policy, || async {
+        calls.set(calls.get() + 1);
+        Err("never")
+    })
+    .await;
+    assert_eq!(res, Err("never"));
+    assert_eq!(calls.get(), 5);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_classified() {
+    let calls = Cell::new(0);
+    let policy = fast_policy().max_attempts(5);
+    let res: io::Result<()> = retry_if(
+        &policy,
+        || async {
+            calls.set(calls.get() + 1);
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        },
+        is_transient,
+    )
+    .await;
+    assert_eq!(res.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+    assert_eq!(calls.get(), 1);
+
+    calls.set(0);
+    let res: io::Result<()> = retry_if(
+        &policy,
+        || async {
+            calls.set(calls.get() + 1);
+            Err(io::Error::from(io::ErrorKind::ConnectionRefused))
+        },
+        is_transient,
+    )
+    .await;
+    assert!(res.is_err());
+    assert_eq!(calls.get(), 5);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_deadline_and_cancel() {
+    let calls = Cell::new(0);
+    let policy = RetryPolicy::new()
+        .max_attempts(100)
+        .initial_delay(Duration::from_millis(50))
+        .jitter(0.0)
+        .deadline(Instant::now() + Duration::from_millis(120));
+    let res: Result<(), ()> = retry(&policy, || async {
+        calls.set(calls.get() + 1);
+        Err(())
+    })
+    .await;
+    assert!(res.is_err());
+    // 0ms, 50ms, then the 100ms delay would pass the deadline
+    assert_eq!(calls.get(), 2);
+
+    let canceller = Canceller::new();
+    let policy = fast_policy()
+        .max_attempts(100)
+        .cancel_handle(canceller.handle());
+    calls.set(0);
+    let canceller = Cell::new(Some(canceller));
+    let res: Result<(), ()> = retry(&policy, || async {
+        calls.set(calls.get() + 1);
+        if calls.get() == 2 {
+            canceller.take().unwrap().cancel();
+        }
+        Err(())
+    })
+    .await;
+    assert!(res.is_err());
+    assert_eq!(calls.get(), 2);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn retry_budget() {
+    let budget = RetryBudget::new(0.0, 2);
+    let policy = fast_policy().max_attempts(10).budget(budget.clone());
+    let calls = Cell::new(0);
+    let res: Result<(), ()> = retry(&policy, || async {
+        calls.set(calls.get() + 1);
+        Err(())
+    })
+    .await;
+    assert!(res.is_err());
+    assert_eq!(calls.get(), 3);
+    assert_eq!(budget.available(), 0);
+}
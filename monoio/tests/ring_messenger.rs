@@ -0,0 +1,69 @@
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn ring_messenger() {
+    use monoio::RingMessenger;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let receiver = std::thread::spawn(move || {
+        let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            tx.send(RingMessenger::current().unwrap()).unwrap();
+            let mut received = Vec::new();
+            for _ in 0..3 {
+                received.push(RingMessenger::recv().await.unwrap());
+            }
+            received
+        })
+    });
+
+    let messenger = rx.recv().unwrap();
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        messenger.wake().await.unwrap();
+        for data in [1, 2, u32::MAX] {
+            messenger.send(data).await.unwrap();
+        }
+    });
+    assert_eq!(receiver.join().unwrap(), vec![1, 2, u32::MAX]);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
+#[test]
+fn ring_messenger_legacy_unsupported() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let err = monoio::RingMessenger::current().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        let err = monoio::RingMessenger::recv().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+    });
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn ring_messenger_dropped_runtime() {
+    use monoio::RingMessenger;
+
+    let messenger = std::thread::spawn(|| {
+        let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async { RingMessenger::current().unwrap() })
+    })
+    .join()
+    .unwrap();
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        let err = messenger.send(1).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotConnected);
+    });
+}
@@ -0,0 +1,85 @@
+use monoio::utils::DriverKind;
+
+#[test]
+fn outside_runtime() {
+    let features = monoio::runtime_features();
+    assert_eq!(features.driver(), None);
+    assert!(!features.timer());
+    assert!(!features.fixed_buffers());
+    assert_eq!(features.legacy(), cfg!(feature = "legacy"));
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn uring_runtime() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let features = monoio::runtime_features();
+        assert_eq!(features.driver(), Some(DriverKind::IoUring));
+        assert!(features.timer());
+        assert!(features.iouring());
+    });
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn legacy_runtime() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let features = monoio::runtime_features();
+        assert_eq!(features.driver(), Some(DriverKind::Legacy));
+        assert!(!features.timer());
+        assert!(!features.fixed_buffers());
+    });
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "legacy"))]
+#[test]
+fn fusion_with_driver() {
+    for kind in [DriverKind::IoUring, DriverKind::Legacy] {
+        let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .with_driver(kind)
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            assert_eq!(monoio::runtime_features().driver(), Some(kind));
+        });
+        let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .enable_timer()
+            .with_driver(kind)
+            .build()
+            .unwrap();
+        rt.block_on(async move {
+            assert_eq!(monoio::runtime_features().driver(), Some(kind));
+        });
+    }
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_driver(DriverKind::Auto)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let expected = if monoio::utils::detect_uring() {
+            DriverKind::IoUring
+        } else {
+            DriverKind::Legacy
+        };
+        assert_eq!(monoio::runtime_features().driver(), Some(expected));
+    });
+}
+
+#[cfg(all(feature = "legacy", not(all(target_os = "linux", feature = "iouring"))))]
+#[test]
+fn fusion_driver_not_enabled() {
+    let err = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_driver(DriverKind::IoUring)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+}
@@ -0,0 +1,114 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use monoio::time::{sleep, Instant, OverlapPolicy, Scheduler};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn fixed_interval() {
+    let scheduler = Scheduler::new();
+    let runs = Rc::new(RefCell::new(0));
+    let counter = runs.clone();
+    scheduler.every(Duration::from_millis(20)).spawn(move || {
+        let counter = counter.clone();
+        async move { *counter.borrow_mut() += 1 }
+    });
+    assert_eq!(scheduler.job_count(), 1);
+
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(*runs.borrow(), 0);
+    sleep(Duration::from_millis(80)).await;
+    let count = *runs.borrow();
+    assert!((3..=5).contains(&count), "{count} runs");
+
+    scheduler.shutdown().await;
+    sleep(Duration::from_millis(40)).await;
+    assert_eq!(*runs.borrow(), count);
+}
+
+// Start times of the runs of a job taking 1.5 period.
+async fn slow_job_starts(overlap: OverlapPolicy) -> Vec<Duration> {
+    let scheduler = Scheduler::new();
+    let start = Instant::now();
+    let starts = Rc::new(RefCell::new(Vec::new()));
+    let log = starts.clone();
+    scheduler
+        .every(Duration::from_millis(50))
+        .overlap(overlap)
+        .spawn(move || {
+            log.borrow_mut().push(start.elapsed());
+            sleep(Duration::from_millis(75))
+        });
+    sleep(Duration::from_millis(190)).await;
+    scheduler.shutdown().await;
+    starts.take()
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn overlap_policy() {
+    // The point at 100ms is missed, the next run starts at 150ms.
+    let starts = slow_job_starts(OverlapPolicy::Skip).await;
+    assert!(starts.len() >= 2);
+    assert!(starts[1] - starts[0] >= Duration::from_millis(95));
+
+    // The missed point is run once the previous run completed, at 125ms.
+    let starts = slow_job_starts(OverlapPolicy::Queue).await;
+    assert!(starts.len() >= 2);
+    assert!(starts[1] - starts[0] < Duration::from_millis(95));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn shutdown_waits_for_run() {
+    let scheduler = Scheduler::new();
+    let done = Rc::new(RefCell::new(false));
+    let flag = done.clone();
+    scheduler.every(Duration::from_millis(10)).spawn(move || {
+        let flag = flag.clone();
+        async move {
+            sleep(Duration::from_millis(30)).await;
+            *flag.borrow_mut() = true;
+        }
+    });
+    sleep(Duration::from_millis(15)).await;
+    assert!(!*done.borrow());
+    scheduler.shutdown().await;
+    assert!(*done.borrow());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn drop_stops_jobs() {
+    let scheduler = Scheduler::new();
+    let runs = Rc::new(RefCell::new(0));
+    let counter = runs.clone();
+    scheduler
+        .every(Duration::from_millis(10))
+        .jitter(Duration::from_millis(2))
+        .spawn(move || {
+            let counter = counter.clone();
+            async move { *counter.borrow_mut() += 1 }
+        });
+    drop(scheduler);
+    sleep(Duration::from_millis(40)).await;
+    assert_eq!(*runs.borrow(), 0);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn wall_clock_points() {
+    let scheduler = Scheduler::new();
+    let points = Rc::new(RefCell::new(Vec::new()));
+    let log = points.clone();
+    scheduler
+        .at(Duration::from_millis(20), Duration::ZERO)
+        .spawn(move || {
+            log.borrow_mut().push(std::time::SystemTime::now());
+            async {}
+        });
+    sleep(Duration::from_millis(90)).await;
+    scheduler.shutdown().await;
+
+    let points = points.take();
+    assert!((3..=5).contains(&points.len()), "{} runs", points.len());
+    for point in points {
+        let since = point.duration_since(std::time::UNIX_EPOCH).unwrap();
+        // Each run starts shortly after a multiple of the period.
+        assert!(since.as_millis() % 20 < 10, "{since:?}");
+    }
+}
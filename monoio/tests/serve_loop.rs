@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use monoio::net::{ServeEvent, TcpListener, TcpStream};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn accept_and_tick() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut serve = listener.serve_loop().tick(Duration::from_millis(20));
+
+    // A tick does not drop the accept in flight.
+    assert!(matches!(
+        serve.next_event().await,
+        Some(ServeEvent::Tick(_))
+    ));
+    let cli = TcpStream::connect(&addr).await.unwrap();
+    match serve.next_event().await {
+        Some(ServeEvent::Accept(Ok((srv, _)))) => {
+            assert_eq!(cli.local_addr().unwrap(), srv.peer_addr().unwrap());
+        }
+        event => panic!("unexpected event {event:?}"),
+    }
+    assert!(matches!(
+        serve.next_event().await,
+        Some(ServeEvent::Tick(_))
+    ));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn shutdown() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let (tx, rx) = local_sync::oneshot::channel::<()>();
+    let mut serve = listener.serve_loop().shutdown(async {
+        let _ = rx.await;
+    });
+    monoio::spawn(async move {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        let _ = tx.send(());
+    });
+    assert!(serve.next_event().await.is_none());
+    assert!(serve.is_shutdown());
+    assert!(serve.next_event().await.is_none());
+    drop(serve);
+
+    // The canceled accept did not take a connection from the listener.
+    let addr = listener.local_addr().unwrap();
+    let cli = TcpStream::connect(&addr).await.unwrap();
+    let (srv, _) = listener.accept().await.unwrap();
+    assert_eq!(cli.local_addr().unwrap(), srv.peer_addr().unwrap());
+}
+
+#[monoio::test_all]
+async fn shutdown_first() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let _cli = TcpStream::connect(&addr).await.unwrap();
+    let mut serve = listener.serve_loop().shutdown(async {});
+    // The shutdown signal wins over a ready connection.
+    assert!(serve.next_event().await.is_none());
+}
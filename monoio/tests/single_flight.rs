@@ -0,0 +1,54 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use monoio::sync::local::SingleFlight;
+
+#[monoio::test_all(timer_enabled = true)]
+async fn share_result() {
+    let group = Rc::new(SingleFlight::new());
+    let runs = Rc::new(Cell::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let group = group.clone();
+        let runs = runs.clone();
+        handles.push(monoio::spawn(async move {
+            group
+                .run(1, || async move {
+                    runs.set(runs.get() + 1);
+                    monoio::time::sleep(Duration::from_millis(5)).await;
+                    String::from("shared")
+                })
+                .await
+        }));
+    }
+    for h in handles {
+        assert_eq!(h.await, "shared");
+    }
+    assert_eq!(runs.get(), 1);
+    assert!(!group.is_running(&1));
+
+    // not cached
+    assert_eq!(
+        group.run(1, || async { String::from("again") }).await,
+        "again"
+    );
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn leader_canceled() {
+    let group = Rc::new(SingleFlight::new());
+    let g = group.clone();
+    let leader = monoio::spawn(async move {
+        monoio::select! {
+            _ = g.run(1, || async {
+                monoio::time::sleep(Duration::from_secs(10)).await;
+                0
+            }) => unreachable!(),
+            _ = monoio::time::sleep(Duration::from_millis(5)) => {}
+        }
+    });
+    monoio::spawn(async {}).await;
+    assert!(group.is_running(&1));
+    // takes over after the leader gives up
+    assert_eq!(group.run(1, || async { 7 }).await, 7);
+    leader.await;
+}
@@ -0,0 +1,93 @@
+use std::{cell::RefCell, rc::Rc};
+
+use monoio::{
+    buf::{IoBuf, IoVecBuf},
+    io::{forward, sink::Sink, sink_from_writer, stream::Stream, AsyncWriteRent, WriterSink},
+    BufResult,
+};
+
+#[derive(Default, Clone)]
+struct Recorder {
+    writes: Rc<RefCell<Vec<Vec<u8>>>>,
+    shutdown: Rc<RefCell<bool>>,
+}
+
+impl AsyncWriteRent for Recorder {
+    async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let data = unsafe { std::slice::from_raw_parts(buf.read_ptr(), buf.bytes_init()) };
+        self.writes.borrow_mut().push(data.to_vec());
+        (Ok(data.len()), buf)
+    }
+
+    async fn writev<T: IoVecBuf>(&mut self, _buf: T) -> BufResult<usize, T> {
+        unimplemented!()
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> std::io::Result<()> {
+        *self.shutdown.borrow_mut() = true;
+        Ok(())
+    }
+}
+
+/// Yields its items, stalling once before `stall_at`.
+struct Stalling {
+    items: Vec<&'static str>,
+    pos: usize,
+    stall_at: usize,
+}
+
+impl Stream for Stalling {
+    type Item = &'static str;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.stall_at {
+            monoio::spawn(async {}).await;
+        }
+        let item = self.items.get(self.pos).copied();
+        self.pos += 1;
+        item
+    }
+}
+
+#[monoio::test_all]
+async fn writer_sink_batches() {
+    let recorder = Recorder::default();
+    let mut sink = WriterSink::with_capacity(8, recorder.clone());
+    sink.send("abc").await.unwrap();
+    sink.send("def").await.unwrap();
+    assert!(recorder.writes.borrow().is_empty());
+    // does not fit, the batch is written first
+    sink.send("ghi").await.unwrap();
+    assert_eq!(*recorder.writes.borrow(), vec![b"abcdef".to_vec()]);
+    // larger than the capacity, written directly
+    sink.send(vec![b'x'; 16]).await.unwrap();
+    Sink::<Vec<u8>>::close(&mut sink).await.unwrap();
+    assert_eq!(
+        *recorder.writes.borrow(),
+        vec![b"abcdef".to_vec(), b"ghi".to_vec(), vec![b'x'; 16]]
+    );
+    assert!(*recorder.shutdown.borrow());
+}
+
+#[monoio::test_all]
+async fn forward_flushes_when_stalled() {
+    let recorder = Recorder::default();
+    let stream = Stalling {
+        items: vec!["a", "b", "c", "d"],
+        pos: 0,
+        stall_at: 2,
+    };
+    let sent = forward(stream, sink_from_writer(recorder.clone()))
+        .await
+        .unwrap();
+    assert_eq!(sent, 4);
+    assert_eq!(
+        *recorder.writes.borrow(),
+        vec![b"ab".to_vec(), b"cd".to_vec()]
+    );
+    assert!(!*recorder.shutdown.borrow());
+}
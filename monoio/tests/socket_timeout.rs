@@ -0,0 +1,113 @@
+use std::{
+    io::ErrorKind,
+    time::{Duration, Instant},
+};
+
+use monoio::{
+    buf::VecBuf,
+    io::{AsyncReadRent, AsyncWriteRent, CancelableAsyncReadRent, Canceller},
+    net::{TcpListener, TcpStream, UnixStream},
+};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn tcp_read_timeout() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut server, _) = srv.accept().await.unwrap();
+
+    assert_eq!(client.read_timeout().unwrap(), None);
+    let err = client.set_read_timeout(Some(Duration::ZERO)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    client
+        .set_read_timeout(Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(
+        client.read_timeout().unwrap(),
+        Some(Duration::from_millis(100))
+    );
+
+    // nothing to read
+    let begin = Instant::now();
+    let (res, buf) = client.read(vec![0; 8]).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::TimedOut);
+    assert!(begin.elapsed() >= Duration::from_millis(100));
+    let (res, buf) = client.readv(VecBuf::from(vec![buf])).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::TimedOut);
+
+    // the connection is still usable after timeout
+    server.write(b"hello").await.0.unwrap();
+    let (res, buf) = client.readv(buf).await;
+    assert_eq!(res.unwrap(), 5);
+    let mut buf: Vec<Vec<u8>> = buf.into();
+    assert_eq!(&buf[0][..5], b"hello");
+
+    client.set_read_timeout(None).unwrap();
+    assert_eq!(client.read_timeout().unwrap(), None);
+    server.write(b"world").await.0.unwrap();
+    let (res, buf) = client.read(buf.pop().unwrap()).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(&buf[..5], b"world");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn tcp_write_timeout() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (_server, _) = srv.accept().await.unwrap();
+
+    client
+        .set_write_timeout(Some(Duration::from_millis(100)))
+        .unwrap();
+    assert_eq!(
+        client.write_timeout().unwrap(),
+        Some(Duration::from_millis(100))
+    );
+
+    // the peer never reads, so the buffers fill up
+    let mut buf = vec![0; 64 * 1024];
+    for _ in 0..1024 {
+        let (res, b) = client.write(buf).await;
+        buf = b;
+        match res {
+            Ok(_) => continue,
+            Err(e) => {
+                assert_eq!(e.kind(), ErrorKind::TimedOut);
+                return;
+            }
+        }
+    }
+    panic!("write never timed out");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn unix_read_timeout() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    a.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+
+    let (res, buf) = a.read(vec![0; 8]).await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::TimedOut);
+
+    b.write(b"hi").await.0.unwrap();
+    let (res, buf) = a.read(buf).await;
+    assert_eq!(res.unwrap(), 2);
+    assert_eq!(&buf[..2], b"hi");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn cancel_before_read_timeout() {
+    let (mut a, _b) = UnixStream::pair().unwrap();
+    a.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+    let canceller = Canceller::new();
+    let handle = canceller.handle();
+    monoio::spawn(async move {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        canceller.cancel();
+    });
+    let (res, _) = a.cancelable_read(vec![0; 8], handle).await;
+    let err = res.unwrap_err();
+    assert_ne!(err.kind(), ErrorKind::TimedOut);
+    assert!(monoio::io::is_canceled(&err));
+}
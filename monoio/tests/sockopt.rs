@@ -0,0 +1,162 @@
+#![cfg(unix)]
+
+use monoio::net::{udp::UdpSocket, Level, Name, TcpListener, TcpStream, UnixDatagram};
+
+#[monoio::test_all]
+async fn tcp_options() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let client = TcpStream::connect(&addr).await.unwrap();
+
+    client.set_option(Level::Tcp, Name::NODELAY, true).unwrap();
+    assert!(client.option::<bool>(Level::Tcp, Name::NODELAY).unwrap());
+    assert!(client.nodelay().unwrap());
+    client.set_option(Level::Tcp, Name::NODELAY, false).unwrap();
+    assert!(!client.nodelay().unwrap());
+
+    srv.set_option(Level::Socket, Name::REUSEADDR, 1).unwrap();
+    assert_eq!(
+        srv.option::<i32>(Level::Socket, Name::REUSEADDR).unwrap(),
+        1
+    );
+
+    #[cfg(target_os = "linux")]
+    {
+        // reno is always available
+        client
+            .set_option(Level::Tcp, Name::CONGESTION, "reno")
+            .unwrap();
+        let congestion: String = client.option(Level::Tcp, Name::CONGESTION).unwrap();
+        assert_eq!(congestion, "reno");
+        let cpu: i32 = client.option(Level::Socket, Name::INCOMING_CPU).unwrap();
+        assert!(cpu >= -1);
+    }
+}
+
+#[monoio::test_all]
+async fn datagram_options() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_option(Level::Ip, Name::TTL, 42).unwrap();
+    assert_eq!(socket.option::<u32>(Level::Ip, Name::TTL).unwrap(), 42);
+    assert_eq!(
+        socket
+            .option::<i32>(Level::Raw(libc::SOL_SOCKET), Name::from_raw(libc::SO_TYPE))
+            .unwrap(),
+        libc::SOCK_DGRAM
+    );
+
+    let (a, _b) = UnixDatagram::pair().unwrap();
+    a.set_option(Level::Socket, Name::SNDBUF, 64 * 1024)
+        .unwrap();
+    assert!(a.option::<i32>(Level::Socket, Name::SNDBUF).unwrap() >= 64 * 1024);
+    let err = a
+        .set_option(Level::Socket, Name::from_raw(-1), 1)
+        .unwrap_err();
+    assert!(err.raw_os_error().is_some());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn tcp_defer_accept_quickack() {
+    use std::time::Duration;
+
+    use monoio::{
+        io::{AsyncReadRentExt, AsyncWriteRentExt},
+        net::ListenerOpts,
+    };
+
+    let opts = ListenerOpts::new().defer_accept(Duration::from_secs(3));
+    let srv = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap();
+    assert!(srv.defer_accept().unwrap().unwrap() >= Duration::from_secs(3));
+    let addr = srv.local_addr().unwrap();
+
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    client.set_quickack(true).unwrap();
+    assert!(client.quickack().unwrap());
+    client.set_quickack(false).unwrap();
+    assert!(!client.quickack().unwrap());
+
+    // The connection is only accepted once the client has sent data.
+    let (res, _) = client.write_all(b"ping").await;
+    res.unwrap();
+    let (mut conn, _) = srv.accept().await.unwrap();
+    let (res, buf) = conn.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+
+    srv.set_defer_accept(None).unwrap();
+    assert_eq!(srv.defer_accept().unwrap(), None);
+}
+
+#[monoio::test_all]
+async fn dedicated_options() {
+    use std::time::Duration;
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    srv.set_ttl(42).unwrap();
+    assert_eq!(srv.ttl().unwrap(), 42);
+    srv.set_nodelay(true).unwrap();
+    assert!(srv.nodelay().unwrap());
+    let client = TcpStream::connect(srv.local_addr().unwrap()).await.unwrap();
+
+    client.set_ttl(7).unwrap();
+    assert_eq!(client.ttl().unwrap(), 7);
+    client.set_linger(Some(Duration::from_secs(1))).unwrap();
+    assert_eq!(client.linger().unwrap(), Some(Duration::from_secs(1)));
+    client.set_send_buffer_size(64 * 1024).unwrap();
+    assert!(client.send_buffer_size().unwrap() >= 64 * 1024);
+    client.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(client.recv_buffer_size().unwrap() >= 64 * 1024);
+    client.set_tos(0x10).unwrap();
+    assert_eq!(client.tos().unwrap(), 0x10);
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_ttl(9).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 9);
+    socket.set_tos(0x08).unwrap();
+    assert_eq!(socket.tos().unwrap(), 0x08);
+
+    #[cfg(target_os = "linux")]
+    {
+        assert_eq!(socket.incoming_cpu().unwrap(), None);
+        socket.set_incoming_cpu(0).unwrap();
+        assert_eq!(socket.incoming_cpu().unwrap(), Some(0));
+        srv.set_incoming_cpu(0).unwrap();
+        assert_eq!(srv.incoming_cpu().unwrap(), Some(0));
+
+        let socket = UdpSocket::bind("[::1]:0").unwrap();
+        socket.set_tclass_v6(0x20).unwrap();
+        assert_eq!(socket.tclass_v6().unwrap(), 0x20);
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn bind_device() {
+    let srv = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert_eq!(client.device().unwrap(), None);
+    client.bind_device(Some("lo")).unwrap();
+    assert_eq!(client.device().unwrap().as_deref(), Some("lo"));
+
+    // Loopback traffic still flows through the bound interface.
+    client
+        .send_to(b"ping", srv.local_addr().unwrap())
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = srv.recv_from(vec![0; 4]).await;
+    assert_eq!(res.unwrap().0, 4);
+    assert_eq!(&buf, b"ping");
+
+    // Changing a bound interface needs CAP_NET_RAW.
+    match client.bind_device(None) {
+        Ok(()) => assert_eq!(client.device().unwrap(), None),
+        Err(e) => assert_eq!(e.kind(), std::io::ErrorKind::PermissionDenied),
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.bind_device(Some("lo")).unwrap();
+    assert_eq!(listener.device().unwrap().as_deref(), Some("lo"));
+    assert!(listener.bind_device(Some("monoio-missing0")).is_err());
+}
@@ -0,0 +1,42 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{io::Write, time::Duration};
+
+use monoio::{fs::File, IoUringDriver, RuntimeBuilder, SubmitPolicy};
+
+fn read_many(policy: SubmitPolicy) {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_submit_policy(policy)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = std::rc::Rc::new(File::open(tmp.path()).await.unwrap());
+        let tasks: Vec<_> = (0..64)
+            .map(|_| {
+                let file = file.clone();
+                monoio::spawn(async move {
+                    let (res, buf) = file.read_at(vec![0; 6], 0).await;
+                    assert_eq!(res.unwrap(), 6);
+                    assert_eq!(buf, b"monoio");
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await;
+        }
+    });
+}
+
+#[test]
+fn submit_policies() {
+    read_many(SubmitPolicy::Round);
+    read_many(SubmitPolicy::Eager);
+    read_many(SubmitPolicy::OnPark);
+    read_many(SubmitPolicy::Batch {
+        max_ops: 16,
+        max_delay: Duration::from_micros(50),
+    });
+}
@@ -29,3 +29,62 @@ test_accept! {
     (str_port_tuple, ("127.0.0.1", 0)),
     (ip_port_tuple, ("127.0.0.1".parse::<IpAddr>().unwrap(), 0)),
 }
+
+#[monoio::test_all(timer_enabled = true)]
+async fn cancel_accept() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let canceller = monoio::io::Canceller::new();
+    let handle = canceller.handle();
+    monoio::spawn(async move {
+        monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+        canceller.cancel();
+    });
+    let err = listener.cancelable_accept(handle).await.unwrap_err();
+    assert!(monoio::io::is_canceled(&err));
+}
+
+#[monoio::test_all]
+async fn cancel_connect() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let opts = monoio::net::TcpConnectOpts::default();
+
+    // canceled before connect
+    let canceller = monoio::io::Canceller::new();
+    let handle = canceller.handle();
+    let canceller = canceller.cancel();
+    assert!(handle.canceled());
+    let err = TcpStream::cancelable_connect_addr(addr, &opts, handle)
+        .await
+        .unwrap_err();
+    assert!(monoio::io::is_canceled(&err));
+
+    // completed before cancel
+    let stream = TcpStream::cancelable_connect_addr(addr, &opts, canceller.handle())
+        .await
+        .unwrap();
+    canceller.cancel();
+    let (srv, _) = listener.accept().await.unwrap();
+    assert_eq!(stream.local_addr().unwrap(), srv.peer_addr().unwrap());
+}
+
+#[monoio::test_all]
+async fn incoming() {
+    use futures::StreamExt as _;
+    use monoio::io::stream::Stream;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut incoming = listener.incoming();
+
+    let cli = TcpStream::connect(&addr).await.unwrap();
+    let (srv, peer) = Stream::next(&mut incoming).await.unwrap().unwrap();
+    assert_eq!(cli.local_addr().unwrap(), peer);
+    assert_eq!(srv.peer_addr().unwrap(), peer);
+
+    // futures_core::Stream, composed with combinators
+    let _cli = TcpStream::connect(&addr).await.unwrap();
+    let _cli2 = TcpStream::connect(&addr).await.unwrap();
+    let accepted: Vec<_> = incoming.by_ref().take(2).collect().await;
+    assert!(accepted.iter().all(Result::is_ok));
+}
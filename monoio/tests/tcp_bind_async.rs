@@ -0,0 +1,36 @@
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{ListenerOpts, TcpListener, TcpStream},
+};
+
+#[monoio::test_all]
+async fn bind_async_accept() {
+    let listener = TcpListener::bind_async("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    assert_ne!(addr.port(), 0);
+
+    let client = monoio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let (res, _) = stream.write_all(b"hello").await;
+        res.unwrap();
+    });
+    let (mut conn, _) = listener.accept().await.unwrap();
+    let (res, buf) = conn.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    client.await;
+}
+
+#[monoio::test_all]
+async fn bind_async_in_use() {
+    let opts = ListenerOpts::new().reuse_port(false).reuse_addr(false);
+    let listener = TcpListener::bind_with_config_async("127.0.0.1:0", &opts)
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let err = TcpListener::bind_with_config_async(addr, &opts)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+}
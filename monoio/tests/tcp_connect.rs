@@ -176,3 +176,95 @@ impl DropFlag {
         assert!(*self.0.borrow());
     }
 }
+
+#[monoio::test_all(timer_enabled = true)]
+async fn connect_timeout() {
+    use std::time::Duration;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect_timeout(addr, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+
+    let err = TcpStream::connect_timeout(addr, Duration::ZERO)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all(timer_enabled = true)]
+async fn connect_timeout_expires() {
+    use std::time::{Duration, Instant};
+
+    // SYNs are dropped once the accept queue of the listener is full.
+    let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::STREAM, None).unwrap();
+    socket
+        .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+        .unwrap();
+    socket.listen(0).unwrap();
+    let addr = socket.local_addr().unwrap().as_socket().unwrap();
+
+    let mut streams = Vec::new();
+    for _ in 0..8 {
+        let begin = Instant::now();
+        match TcpStream::connect_timeout(addr, Duration::from_millis(100)).await {
+            Ok(stream) => streams.push(stream),
+            Err(e) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::TimedOut);
+                assert!(begin.elapsed() < Duration::from_secs(1));
+                return;
+            }
+        }
+    }
+    panic!("connect never timed out");
+}
+
+#[monoio::test_all]
+async fn connect_next_addr_on_failure() {
+    // find a closed port
+    let closed = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let stream = TcpStream::connect(&[closed, addr][..]).await.unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+    let err = TcpStream::connect(&[closed, closed][..]).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all(timer_enabled = true)]
+async fn connect_happy_eyeballs() {
+    use std::time::{Duration, Instant};
+
+    use monoio::net::TcpConnectOpts;
+
+    // SYNs are dropped once the accept queue of the listener is full.
+    let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::STREAM, None).unwrap();
+    socket
+        .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+        .unwrap();
+    socket.listen(0).unwrap();
+    let stalled = socket.local_addr().unwrap().as_socket().unwrap();
+    let mut fillers = Vec::new();
+    while let Ok(stream) = TcpStream::connect_timeout(stalled, Duration::from_millis(100)).await {
+        fillers.push(stream);
+    }
+
+    let listener = TcpListener::bind("[::1]:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let opts = TcpConnectOpts::new().attempt_delay(Duration::from_millis(50));
+    let begin = Instant::now();
+    let stream = TcpStream::connect_with_config(&[stalled, stalled, addr][..], &opts)
+        .await
+        .unwrap();
+    // The IPv6 address is tried second.
+    assert!(begin.elapsed() < Duration::from_secs(1));
+    assert_eq!(stream.peer_addr().unwrap(), addr);
+}
@@ -122,3 +122,133 @@ async fn echo_tfo() {
     let active_addr = rx.await.unwrap();
     assert_eq!(active.local_addr().unwrap(), active_addr);
 }
+
+#[monoio::test_all]
+async fn echo_tfo_connect_with_data() {
+    use std::net::SocketAddr;
+
+    let bind_addr = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let opts = monoio::net::ListenerOpts::default().tcp_fastopen(16);
+    let listener = TcpListener::bind_with_config(bind_addr, &opts).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = local_sync::oneshot::channel();
+    monoio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let (res, buf) = socket.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        socket.write_all(buf).await.0.unwrap();
+        assert!(tx.send(()).is_ok());
+    });
+    let (res, _) = TcpStream::connect_with_data(addr, b"hello").await;
+    let mut active = res.unwrap();
+    let (res, buf) = active.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(&buf, b"hello");
+    rx.await.unwrap();
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn read_with_deadline() {
+    use std::time::{Duration, Instant};
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut server, _) = srv.accept().await.unwrap();
+
+    // nothing to read, the read should be canceled
+    let begin = Instant::now();
+    let deadline = begin + Duration::from_millis(100);
+    let (res, buf) = client.read_with_deadline(vec![0; 8], deadline).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    assert!(begin.elapsed() >= Duration::from_millis(100));
+
+    // the connection is still usable after timeout
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let (res, _) = server.write_with_deadline(b"hello", deadline).await;
+    assert_eq!(res.unwrap(), 5);
+    let (res, buf) = client.read_with_deadline(buf, deadline).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn peek() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut server, _) = srv.accept().await.unwrap();
+    assert!(client.write_all(b"\x16\x03\x01hello").await.0.is_ok());
+
+    // peeking does not consume the data
+    let (res, buf) = server.peek(vec![0; 3]).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(&buf[..], b"\x16\x03\x01");
+    let (res, buf) = server.read_exact(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), 8);
+    assert_eq!(&buf[..], b"\x16\x03\x01hello");
+
+    drop(client);
+    let (res, _) = server.peek(vec![0; 3]).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn echo_defer_taskrun() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .with_coop_taskrun()
+        .with_defer_taskrun()
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = srv.local_addr().unwrap();
+        let client = monoio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            for _ in 0..64 {
+                assert!(stream.write_all("ping").await.0.is_ok());
+                let (res, buf) = stream.read_exact(vec![0; 4]).await;
+                assert!(res.is_ok());
+                assert_eq!(&buf, b"ping");
+            }
+        });
+
+        let (stream, _) = srv.accept().await.unwrap();
+        let (mut rd, mut wr) = stream.into_split();
+        monoio::spawn(async move { io::copy(&mut rd, &mut wr).await });
+        monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+        client.await;
+    });
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn echo_registered_ring_fd() {
+    // Rings are unregistered on drop, so the per thread slots are reused.
+    for registered in [true, false].into_iter().cycle().take(40) {
+        let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+            .with_registered_ring_fd(registered)
+            .enable_timer()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = srv.local_addr().unwrap();
+            let client = monoio::spawn(async move {
+                let mut stream = TcpStream::connect(addr).await.unwrap();
+                assert!(stream.write_all("ping").await.0.is_ok());
+            });
+            let (mut stream, _) = srv.accept().await.unwrap();
+            let (res, buf) = stream.read_exact(vec![0; 4]).await;
+            assert!(res.is_ok());
+            assert_eq!(&buf, b"ping");
+            monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+            client.await;
+        });
+    }
+}
@@ -124,3 +124,23 @@ async fn drop_write() -> Result<()> {
     handle.join().unwrap().unwrap();
     Ok(())
 }
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn peek_read_half() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    let mut client = TcpStream::connect(&addr).await?;
+    let (server, _) = listener.accept().await?;
+    client.write_all(b"GET /").await.0?;
+
+    let (mut read_half, _write_half) = server.into_split();
+    let (res, buf) = read_half.peek(vec![0; 3]).await;
+    assert_eq!(res?, 3);
+    assert_eq!(&buf[..], b"GET");
+    let (res, buf) = read_half.read(vec![0; 5]).await;
+    assert_eq!(res?, 5);
+    assert_eq!(&buf[..], b"GET /");
+    Ok(())
+}
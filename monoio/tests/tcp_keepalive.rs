@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use monoio::net::{KeepAlive, TcpListener, TcpStream};
+
+#[monoio::test_all]
+async fn keepalive() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    assert_eq!(client.keepalive().unwrap(), None);
+
+    let keepalive = KeepAlive::new()
+        .time(Duration::from_secs(60))
+        .interval(Duration::from_secs(10))
+        .retries(5);
+    client.set_keepalive(Some(keepalive)).unwrap();
+    let got = client.keepalive().unwrap().unwrap();
+    #[cfg(target_os = "linux")]
+    assert_eq!(got, keepalive);
+    #[cfg(not(target_os = "linux"))]
+    let _ = got;
+
+    client.set_keepalive(None).unwrap();
+    assert_eq!(client.keepalive().unwrap(), None);
+}
@@ -0,0 +1,90 @@
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::TcpSocket,
+};
+
+#[monoio::test_all]
+async fn socket_listen_connect() {
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    #[cfg(unix)]
+    {
+        socket.set_reuseport(true).unwrap();
+        assert!(socket.reuseport().unwrap());
+    }
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let addr = socket.local_addr().unwrap();
+    let listener = socket.listen(128).unwrap();
+    assert_eq!(listener.local_addr().unwrap(), addr);
+
+    let client = TcpSocket::new_for_addr(addr).unwrap();
+    client.set_nodelay(true).unwrap();
+    client.set_send_buffer_size(64 * 1024).unwrap();
+    client.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let local = client.local_addr().unwrap();
+    let mut client = client.connect(addr).await.unwrap();
+    assert!(client.nodelay().unwrap());
+
+    let (mut conn, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, local);
+    let (res, _) = client.write_all(b"monoio".to_vec()).await;
+    res.unwrap();
+    let (res, buf) = conn.read(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap(), 6);
+    assert_eq!(buf, b"monoio");
+}
+
+#[monoio::test_all]
+async fn socket_connect_refused() {
+    // find a free port
+    let addr = {
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        socket.local_addr().unwrap()
+    };
+    let socket = TcpSocket::new_v4().unwrap();
+    assert!(socket.connect(addr).await.is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn socket_raw_option() {
+    use monoio::net::{Level, Name};
+
+    let socket = TcpSocket::new_v4().unwrap();
+    let transparent: libc::c_int = socket.option(Level::Ip, Name::TRANSPARENT).unwrap();
+    assert_eq!(transparent, 0);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all(timer_enabled = true)]
+async fn socket_reuseport_group() {
+    use std::time::Duration;
+
+    use monoio::net::TcpStream;
+
+    let mut listeners = Vec::new();
+    let mut addr = "127.0.0.1:0".parse().unwrap();
+    for _ in 0..2 {
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.set_reuseport(true).unwrap();
+        socket.bind(addr).unwrap();
+        addr = socket.local_addr().unwrap();
+        listeners.push(socket.listen(128).unwrap());
+    }
+    listeners[0].attach_cpu_steering(1).unwrap();
+
+    // With a single slot, the first listener of the group gets everything.
+    for _ in 0..4 {
+        let _client = TcpStream::connect(addr).await.unwrap();
+        monoio::time::timeout(Duration::from_secs(5), listeners[0].accept())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+    assert!(
+        monoio::time::timeout(Duration::from_millis(50), listeners[1].accept())
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,32 @@
+#![cfg(target_os = "linux")]
+
+#[test]
+fn runtime_thread_name() {
+    // Build on a fresh thread, not to rename the thread of the test harness.
+    std::thread::spawn(|| {
+        let rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .with_thread_name("monoio-worker-0")
+            .build()
+            .unwrap();
+        let comm = std::fs::read_to_string("/proc/thread-self/comm").unwrap();
+        assert_eq!(comm.trim_end(), "monoio-worker-0");
+
+        let tid = unsafe { libc::syscall(libc::SYS_gettid) } as libc::pid_t;
+        assert_eq!(rt.os_thread_id(), tid);
+
+        let other = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .build()
+            .unwrap();
+        assert_ne!(rt.thread_id(), other.thread_id());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn invalid_thread_name() {
+    let res = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .with_thread_name("monoio\0worker")
+        .build();
+    assert_eq!(res.err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,107 @@
+use std::{future::Future, time::Duration};
+
+use monoio::time::{sleep, timer_count};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn count_timers() {
+    assert_eq!(timer_count(), 0);
+    let mut sleeps: Vec<_> = (1..=100)
+        .map(|i| Box::pin(sleep(Duration::from_secs(i))))
+        .collect();
+    // timers are registered on first poll
+    std::future::poll_fn(|cx| {
+        for s in sleeps.iter_mut() {
+            assert!(s.as_mut().poll(cx).is_pending());
+        }
+        std::task::Poll::Ready(())
+    })
+    .await;
+    assert_eq!(timer_count(), 100);
+    sleeps.truncate(40);
+    assert_eq!(timer_count(), 40);
+    drop(sleeps);
+    assert_eq!(timer_count(), 0);
+
+    sleep(Duration::from_millis(5)).await;
+    assert_eq!(timer_count(), 0);
+}
+
+#[monoio::test_all]
+async fn count_without_timer() {
+    assert_eq!(timer_count(), 0);
+}
+
+#[test]
+fn wheel_levels() {
+    for levels in [1, 2, 10] {
+        let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+            .enable_timer()
+            .with_timer_levels(levels)
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            // beyond the range of one level, wraps around in the top level
+            let far = Box::pin(sleep(Duration::from_secs(3600)));
+            let begin = std::time::Instant::now();
+            sleep(Duration::from_millis(100)).await;
+            assert!(begin.elapsed() >= Duration::from_millis(100));
+            monoio::select! {
+                _ = far => unreachable!(),
+                _ = sleep(Duration::from_millis(10)) => {},
+            }
+        });
+    }
+}
+
+#[test]
+#[should_panic]
+fn wheel_levels_out_of_range() {
+    let _ = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .with_timer_levels(11);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn wall_clock() {
+    use std::time::SystemTime;
+
+    let begin = std::time::Instant::now();
+    monoio::time::sleep_until_system(SystemTime::now() - Duration::from_secs(1)).await;
+    monoio::time::sleep_until_system(SystemTime::now() + Duration::from_millis(20)).await;
+    assert!(begin.elapsed() >= Duration::from_millis(19));
+
+    let mut every = monoio::time::at(Duration::from_millis(10), Duration::ZERO);
+    let first = every.tick().await;
+    let second = every.tick().await;
+    assert!(SystemTime::now() >= second);
+    let step = second.duration_since(first).unwrap();
+    assert_eq!(step.as_nanos() % Duration::from_millis(10).as_nanos(), 0);
+    assert!(!step.is_zero());
+}
+
+#[test]
+fn custom_clock() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    struct CountingClock(Arc<AtomicUsize>);
+    impl monoio::time::ClockSource for CountingClock {
+        fn now(&self) -> monoio::time::Instant {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            monoio::time::Instant::now()
+        }
+    }
+
+    let reads = Arc::new(AtomicUsize::new(0));
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .with_clock(CountingClock(reads.clone()))
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        sleep(Duration::from_millis(10)).await;
+    });
+    assert!(reads.load(Ordering::Relaxed) > 0);
+}
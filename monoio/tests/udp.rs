@@ -96,3 +96,136 @@ async fn cancel_recv_from() {
         }
     }
 }
+
+#[monoio::test_all(timer_enabled = true)]
+async fn flow_demux() {
+    use std::time::Duration;
+
+    use monoio::net::udp::{FlowOpts, UdpListener};
+
+    let opts = FlowOpts::new().idle_timeout(Duration::from_millis(50));
+    let listener = UdpListener::from_socket(UdpSocket::bind("127.0.0.1:0").unwrap(), opts);
+    let addr = listener.local_addr().unwrap();
+
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+    a.send_to("a1", addr).await.0.unwrap();
+    b.send_to("b1", addr).await.0.unwrap();
+    a.send_to("a2", addr).await.0.unwrap();
+
+    let (flow_a, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, a.local_addr().unwrap());
+    let (flow_b, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, b.local_addr().unwrap());
+    assert_eq!(listener.flows(), 2);
+    assert_eq!(flow_a.recv().await.unwrap(), b"a1");
+    assert_eq!(flow_a.recv().await.unwrap(), b"a2");
+    assert_eq!(flow_b.recv().await.unwrap(), b"b1");
+
+    flow_b.send("pong").await.0.unwrap();
+    let (res, buf) = b.recv_from(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap().1, addr);
+    assert_eq!(buf, b"pong");
+
+    // a dropped flow is opened again by the next datagram
+    drop(flow_b);
+    assert_eq!(listener.flows(), 1);
+    b.send_to("b2", addr).await.0.unwrap();
+    let (flow_b, _) = listener.accept().await.unwrap();
+    assert_eq!(flow_b.recv().await.unwrap(), b"b2");
+
+    // idle flows expire
+    monoio::time::sleep(Duration::from_millis(150)).await;
+    assert!(flow_a.is_closed());
+    assert_eq!(flow_a.recv().await, None);
+    assert_eq!(listener.flows(), 0);
+
+    drop(listener);
+    assert_eq!(flow_b.recv().await, None);
+    // the socket is closed once the receiving task stopped
+    drop((flow_a, flow_b));
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    UdpSocket::bind(addr).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn batch() {
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let server_addr = server.local_addr().unwrap();
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    let (res, _) = a
+        .send_to_batch(vec![("one", server_addr), ("two", server_addr)])
+        .await;
+    assert_eq!(res.unwrap(), 2);
+    b.send_to("three", server_addr).await.0.unwrap();
+
+    let mut received = Vec::new();
+    while received.len() < 3 {
+        let bufs = (0..4).map(|_| Vec::with_capacity(16)).collect();
+        let (res, bufs) = server.recv_from_batch(bufs).await;
+        for ((n, addr), buf) in res.unwrap().into_iter().zip(bufs) {
+            assert_eq!(n, buf.len());
+            received.push((buf, addr));
+        }
+    }
+    assert_eq!(received[0], (b"one".to_vec(), a.local_addr().unwrap()));
+    assert_eq!(received[1], (b"two".to_vec(), a.local_addr().unwrap()));
+    assert_eq!(received[2], (b"three".to_vec(), b.local_addr().unwrap()));
+
+    // reply to both peers in a single batch
+    let replies = vec![
+        (b"ra".to_vec(), a.local_addr().unwrap()),
+        (b"rb".to_vec(), b.local_addr().unwrap()),
+    ];
+    let (res, _) = server.send_to_batch(replies).await;
+    assert_eq!(res.unwrap(), 2);
+    let (res, buf) = a.recv_from(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap().1, server_addr);
+    assert_eq!(buf, b"ra");
+    let (res, buf) = b.recv_from(Vec::with_capacity(16)).await;
+    assert_eq!(res.unwrap().1, server_addr);
+    assert_eq!(buf, b"rb");
+
+    let (res, _) = server.send_to_batch(Vec::<(Vec<u8>, _)>::new()).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn multicast_v4() {
+    use std::{
+        net::{Ipv4Addr, SocketAddr},
+        time::Duration,
+    };
+
+    let group = Ipv4Addr::new(239, 255, 0, 1);
+    let receiver = UdpSocket::bind("0.0.0.0:0").unwrap();
+    let port = receiver.local_addr().unwrap().port();
+    receiver
+        .join_multicast_v4(&group, &Ipv4Addr::LOCALHOST)
+        .unwrap();
+
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    sender.set_multicast_if_v4(&Ipv4Addr::LOCALHOST).unwrap();
+    assert_eq!(sender.multicast_if_v4().unwrap(), Ipv4Addr::LOCALHOST);
+    sender.set_multicast_loop_v4(true).unwrap();
+    assert!(sender.multicast_loop_v4().unwrap());
+    sender.set_multicast_ttl_v4(1).unwrap();
+    assert_eq!(sender.multicast_ttl_v4().unwrap(), 1);
+
+    let (res, _) = sender
+        .send_to(b"discover", SocketAddr::from((group, port)))
+        .await;
+    assert_eq!(res.unwrap(), 8);
+    let recv = monoio::time::timeout(Duration::from_secs(5), receiver.recv_from(vec![0; 16]));
+    let (res, buf) = recv.await.unwrap();
+    let (n, from) = res.unwrap();
+    assert_eq!(&buf[..n], b"discover");
+    assert_eq!(from, sender.local_addr().unwrap());
+
+    receiver
+        .leave_multicast_v4(&group, &Ipv4Addr::LOCALHOST)
+        .unwrap();
+}
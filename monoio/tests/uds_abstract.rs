@@ -0,0 +1,65 @@
+#![cfg(any(target_os = "linux", target_os = "android"))]
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{unix::SocketAddr, ListenerOpts, UnixDatagram, UnixListener, UnixStream},
+};
+
+fn name(kind: &str) -> String {
+    format!(
+        "monoio-{kind}-{}-{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    )
+}
+
+#[monoio::test_all]
+async fn stream_abstract() -> std::io::Result<()> {
+    let name = name("stream");
+    let listener = UnixListener::bind(format!("\0{name}"))?;
+
+    let mut client = UnixStream::connect(format!("\0{name}")).await?;
+    let (mut server, _) = listener.accept().await?;
+    let peer = client.peer_addr()?;
+    assert_eq!(peer.as_abstract_namespace(), Some(name.as_bytes()));
+    assert!(peer.as_pathname().is_none());
+
+    client.write_all(b"ping").await.0?;
+    let (res, buf) = server.read_exact(vec![0; 4]).await;
+    res?;
+    assert_eq!(buf, b"ping");
+    Ok(())
+}
+
+#[monoio::test_all]
+async fn addr_abstract() -> std::io::Result<()> {
+    let name = name("addr");
+    let addr = SocketAddr::from_abstract_name(&name)?;
+    assert_eq!(addr.as_abstract_namespace(), Some(name.as_bytes()));
+
+    let listener = UnixListener::bind_addr(addr.clone(), &ListenerOpts::default())?;
+    let client = UnixStream::connect_addr(addr).await?;
+    let (server, _) = listener.accept().await?;
+    assert_eq!(
+        server.local_addr()?.as_abstract_namespace(),
+        Some(name.as_bytes())
+    );
+    drop(client);
+    Ok(())
+}
+
+#[monoio::test_all]
+async fn datagram_abstract() -> std::io::Result<()> {
+    let name = name("dgram");
+    let server = UnixDatagram::bind(format!("\0{name}"))?;
+    let client = UnixDatagram::bind_addr(SocketAddr::from_abstract_name(format!("{name}-c"))?)?;
+
+    client.send_to(b"hello", format!("\0{name}")).await.0?;
+    let (res, buf) = server.recv_from(vec![0; 16]).await;
+    let (n, from) = res?;
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(
+        from.as_abstract_namespace(),
+        Some(format!("{name}-c").as_bytes())
+    );
+    Ok(())
+}
@@ -15,3 +15,20 @@ async fn test_socket_pair() {
     assert_eq!(cred_a.uid(), uid);
     assert_eq!(cred_a.gid(), gid);
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[monoio::test_all]
+async fn test_accepted_peer() {
+    use monoio::net::{unix::UCred, UnixListener};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cred.sock");
+    let listener = UnixListener::bind(&path).unwrap();
+    let client = UnixStream::connect(&path).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+
+    let cred: UCred = server.peer_cred().unwrap();
+    assert_eq!(cred.pid(), Some(std::process::id() as libc::pid_t));
+    assert_eq!(cred.uid(), unsafe { geteuid() });
+    assert_eq!(client.peer_cred().unwrap(), cred);
+}
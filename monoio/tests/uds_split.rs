@@ -29,6 +29,23 @@ async fn split() -> std::io::Result<()> {
     Ok(())
 }
 
+#[monoio::test_all]
+async fn reunite() -> std::io::Result<()> {
+    let (a, b) = UnixStream::pair()?;
+
+    let (a_read, a_write) = a.into_split();
+    let (_, b_write) = b.into_split();
+    assert!(a_write.local_addr().is_ok());
+    assert!(a_write.peer_addr().is_ok());
+
+    let a_read = match a_read.reunite(b_write) {
+        Ok(_) => panic!("Reunite should not succeed"),
+        Err(err) => err.0,
+    };
+    a_read.reunite(a_write).expect("Reunite should succeed");
+    Ok(())
+}
+
 async fn send_recv_all<R: AsyncReadRent, W: AsyncWriteRent>(
     read: &mut R,
     write: &mut W,
@@ -63,3 +63,58 @@ async fn shutdown() -> std::io::Result<()> {
     assert_eq!(n, 0);
     Ok(())
 }
+
+#[monoio::test_all]
+async fn cancel_connect() {
+    let canceller = monoio::io::Canceller::new();
+    let handle = canceller.handle();
+    canceller.cancel();
+    let err = UnixStream::cancelable_connect("/nonexistent.sock", handle)
+        .await
+        .unwrap_err();
+    assert!(monoio::io::is_canceled(&err));
+}
+
+#[monoio::test_all]
+async fn pass_fds() -> std::io::Result<()> {
+    use std::{
+        io::Write,
+        os::unix::{fs::FileExt, io::AsRawFd},
+    };
+
+    let (mut a, mut b) = UnixStream::pair()?;
+    let mut file = tempfile::tempfile()?;
+    file.write_all(b"shared")?;
+    let (r, w) = std::os::unix::net::UnixStream::pair()?;
+
+    let (res, _) = a
+        .send_with_fds(b"fds", &[file.as_raw_fd(), w.as_raw_fd()])
+        .await;
+    assert_eq!(res?, 3);
+    let (res, buf) = b.recv_with_fds(Vec::with_capacity(16)).await;
+    let (n, mut fds, truncated) = res?;
+    assert_eq!(&buf[..n], b"fds");
+    assert_eq!(fds.len(), 2);
+    assert!(!truncated);
+
+    let received = std::fs::File::from(fds.remove(0));
+    let mut data = [0; 6];
+    received.read_exact_at(&mut data, 0)?;
+    assert_eq!(&data, b"shared");
+
+    let mut writer = std::os::unix::net::UnixStream::from(fds.remove(0));
+    drop(w);
+    writer.write_all(b"!")?;
+    let mut byte = [0; 1];
+    std::io::Read::read_exact(&mut &r, &mut byte)?;
+    assert_eq!(&byte, b"!");
+
+    // plain data carries no fds
+    a.write_all(b"plain").await.0?;
+    let (res, buf) = b.recv_with_fds(Vec::with_capacity(16)).await;
+    let (n, fds, truncated) = res?;
+    assert_eq!(&buf[..n], b"plain");
+    assert!(fds.is_empty());
+    assert!(!truncated);
+    Ok(())
+}
@@ -43,3 +43,45 @@ async fn addr_type() -> std::io::Result<()> {
     assert_eq!(_res.unwrap().1.as_pathname(), Some(sock_path1.as_path()));
     Ok(())
 }
+
+#[monoio::test_all]
+async fn reply_to_origin() -> std::io::Result<()> {
+    let dir = tempfile::Builder::new()
+        .prefix("monoio-unix-datagram-tests")
+        .tempdir()
+        .unwrap();
+    let server_path = dir.path().join("server.sock");
+    let client_path = dir.path().join("client.sock");
+
+    let server = UnixDatagram::bind(&server_path)?;
+    let client = UnixDatagram::bind(&client_path)?;
+    client.connect_to(&server_path)?;
+    assert_eq!(
+        client.peer_addr()?.as_pathname(),
+        Some(server_path.as_path())
+    );
+
+    client.send(b"ping").await.0?;
+    let (res, _) = server.recv_from(vec![0; 16]).await;
+    let (_, origin) = res?;
+    server.send_to_addr(b"pong", origin).await.0?;
+    let (res, buf) = client.recv(vec![0; 16]).await;
+    assert_eq!(res?, 4);
+    assert_eq!(buf, b"pong");
+    Ok(())
+}
+
+#[monoio::test_all]
+async fn pair_duplex() -> std::io::Result<()> {
+    let (a, b) = UnixDatagram::pair()?;
+    assert!(a.peer_addr()?.is_unnamed());
+
+    a.send(b"ping").await.0?;
+    let (res, buf) = b.recv(vec![0; 16]).await;
+    assert_eq!(&buf[..res?], b"ping");
+
+    b.send(b"pong").await.0?;
+    let (res, buf) = a.recv(vec![0; 16]).await;
+    assert_eq!(&buf[..res?], b"pong");
+    Ok(())
+}
@@ -0,0 +1,50 @@
+#![cfg(target_os = "linux")]
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{VsockAddr, VsockListener, VsockStream},
+};
+
+// Without the vsock module, or the loopback transport for local
+// connections, the tests do nothing.
+fn listener() -> Option<VsockListener> {
+    VsockListener::bind(VsockAddr::new(VsockAddr::CID_ANY, VsockAddr::PORT_ANY)).ok()
+}
+
+#[monoio::test_all]
+async fn bind_any_port() {
+    let Some(listener) = listener() else {
+        return;
+    };
+    let addr = listener.local_addr().unwrap();
+    assert_eq!(addr.cid(), VsockAddr::CID_ANY);
+    assert_ne!(addr.port(), VsockAddr::PORT_ANY);
+}
+
+#[monoio::test_all]
+async fn echo_loopback() {
+    if !std::path::Path::new("/sys/module/vsock_loopback").exists() {
+        return;
+    }
+    let Some(listener) = listener() else {
+        return;
+    };
+    let port = listener.local_addr().unwrap().port();
+
+    let server = monoio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        stream.write_all(buf).await.0.unwrap();
+    });
+
+    let mut client = VsockStream::connect(VsockAddr::new(VsockAddr::CID_LOCAL, port))
+        .await
+        .unwrap();
+    assert_eq!(client.peer_addr().unwrap().port(), port);
+    client.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = client.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    server.await;
+}
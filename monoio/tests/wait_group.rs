@@ -0,0 +1,80 @@
+use std::{cell::Cell, rc::Rc};
+
+use monoio::sync::local::{Barrier, WaitGroup};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn local_wait_group() {
+    let wg = WaitGroup::new();
+    let done = Rc::new(Cell::new(0));
+    for _ in 0..4 {
+        let wg = wg.clone();
+        let done = done.clone();
+        monoio::spawn(async move {
+            monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+            done.set(done.get() + 1);
+            drop(wg);
+        });
+    }
+    assert_eq!(wg.count(), 5);
+    wg.wait().await;
+    assert_eq!(done.get(), 4);
+
+    // no other member
+    WaitGroup::new().wait().await;
+}
+
+#[monoio::test_all]
+async fn local_barrier() {
+    let barrier = Rc::new(Barrier::new(3));
+    let leaders = Rc::new(Cell::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..6 {
+        let barrier = barrier.clone();
+        let leaders = leaders.clone();
+        handles.push(monoio::spawn(async move {
+            // two rounds of three
+            if barrier.wait().await.is_leader() {
+                leaders.set(leaders.get() + 1);
+            }
+        }));
+    }
+    for h in handles {
+        h.await;
+    }
+    assert_eq!(leaders.get(), 2);
+    assert!(Barrier::new(0).wait().await.is_leader());
+}
+
+#[cfg(feature = "sync")]
+#[monoio::test_all]
+async fn cross_thread_wait_group() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let wg = monoio::sync::WaitGroup::new();
+    let ready = Arc::new(AtomicUsize::new(0));
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let wg = wg.clone();
+            let ready = ready.clone();
+            std::thread::spawn(move || {
+                let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+                    .enable_timer()
+                    .build()
+                    .unwrap();
+                rt.block_on(async move {
+                    monoio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    ready.fetch_add(1, Ordering::SeqCst);
+                    drop(wg);
+                });
+            })
+        })
+        .collect();
+    wg.wait().await;
+    assert_eq!(ready.load(Ordering::SeqCst), 4);
+    for t in threads {
+        t.join().unwrap();
+    }
+}
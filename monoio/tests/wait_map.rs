@@ -0,0 +1,66 @@
+use std::{
+    future::{poll_fn, Future},
+    rc::Rc,
+    task::Poll,
+};
+
+use monoio::sync::local::WaitMap;
+
+#[monoio::test_all]
+async fn complete() {
+    let map = Rc::new(WaitMap::new());
+    let a = map.wait(1);
+    let b = map.wait(2);
+    assert_eq!(map.len(), 2);
+
+    let producer = map.clone();
+    monoio::spawn(async move {
+        producer.complete(&2, "b").unwrap();
+        producer.complete(&1, "a").unwrap();
+        // already completed
+        assert_eq!(producer.complete(&1, "c"), Err("c"));
+    });
+    assert_eq!(b.await, Some("b"));
+    assert_eq!(a.await, Some("a"));
+    assert!(map.is_empty());
+    // nobody is waiting
+    assert_eq!(map.complete(&3, "d"), Err("d"));
+}
+
+#[monoio::test_all]
+async fn cancel() {
+    let map = WaitMap::<u32, ()>::new();
+    let a = map.wait(1);
+    let b = map.wait(2);
+    let c = map.wait(3);
+    assert!(map.cancel(&1));
+    assert!(!map.cancel(&4));
+    assert_eq!(a.await, None);
+    map.cancel_all();
+    assert_eq!(b.await, None);
+    assert_eq!(c.await, None);
+    assert!(map.is_empty());
+}
+
+#[monoio::test_all]
+async fn drop_removes_waiter() {
+    let map = WaitMap::<u32, u32>::new();
+    let mut a = Box::pin(map.wait(1));
+    // poll once so a waker is registered
+    poll_fn(|cx| {
+        assert!(a.as_mut().poll(cx).is_pending());
+        Poll::Ready(())
+    })
+    .await;
+    assert!(map.contains_key(&1));
+    drop(a);
+    assert!(map.is_empty());
+    assert_eq!(map.complete(&1, 7), Err(7));
+
+    // a second waiter on the same key replaces the first one
+    let first = map.wait(1);
+    let second = map.wait(1);
+    assert_eq!(first.await, None);
+    map.complete(&1, 8).unwrap();
+    assert_eq!(second.await, Some(8));
+}
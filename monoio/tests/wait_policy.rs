@@ -0,0 +1,87 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use monoio::{fs::File, IoUringDriver, RuntimeBuilder, WaitPolicy};
+
+fn read_many(policy: WaitPolicy) {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_wait_policy(policy)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let file = std::rc::Rc::new(File::open(tmp.path()).await.unwrap());
+        let tasks: Vec<_> = (0..64)
+            .map(|_| {
+                let file = file.clone();
+                monoio::spawn(async move {
+                    let (res, buf) = file.read_at(vec![0; 6], 0).await;
+                    assert_eq!(res.unwrap(), 6);
+                    assert_eq!(buf, b"monoio");
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await;
+        }
+    });
+}
+
+#[test]
+fn wait_policies() {
+    read_many(WaitPolicy::new());
+    read_many(WaitPolicy::new().min_complete(16));
+    read_many(WaitPolicy::new().max_wait(Duration::from_micros(50)));
+    read_many(
+        WaitPolicy::new()
+            .min_complete(1024)
+            .max_wait(Duration::from_millis(1)),
+    );
+}
+
+#[test]
+fn wait_policy_timer() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .enable_timer()
+        .with_wait_policy(
+            WaitPolicy::new()
+                .min_complete(64)
+                .max_wait(Duration::from_secs(3600)),
+        )
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // The timer deadline bounds the wait for more completions.
+        let begin = Instant::now();
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        assert!(begin.elapsed() < Duration::from_secs(1));
+    });
+}
+
+#[test]
+fn wait_policy_without_max_wait() {
+    let mut tmp = tempfile::NamedTempFile::new().unwrap();
+    tmp.write_all(b"monoio").unwrap();
+
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_wait_policy(WaitPolicy::new().min_complete(2))
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // The accept never completes, the driver must not wait for it.
+        let listener = monoio::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let accept = monoio::spawn(async move {
+            let _ = listener.accept().await;
+        });
+        let file = File::open(tmp.path()).await.unwrap();
+        let (res, _) = file.read_at(vec![0; 6], 0).await;
+        assert_eq!(res.unwrap(), 6);
+        drop(accept);
+    });
+}
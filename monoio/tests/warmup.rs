@@ -0,0 +1,35 @@
+use std::{cell::RefCell, rc::Rc};
+
+use monoio::RuntimeBuilder;
+
+#[test]
+fn warmup_then_serve() {
+    let mut rt = RuntimeBuilder::<monoio::FusionDriver>::new()
+        .enable_timer()
+        .build()
+        .unwrap();
+    let cache = Rc::new(RefCell::new(Vec::new()));
+    let filled = rt.warmup(|| {
+        let cache = cache.clone();
+        async move {
+            monoio::spawn(async move { cache.borrow_mut().extend(0..16) }).await;
+            monoio::time::sleep(std::time::Duration::from_millis(1)).await;
+            true
+        }
+    });
+    assert!(filled);
+    rt.block_on(async move {
+        assert_eq!(cache.borrow().len(), 16);
+    });
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "metrics"))]
+#[test]
+fn warmup_allocates_op_slots() {
+    let mut rt = RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .build()
+        .unwrap();
+    let before = rt.memory_report();
+    rt.warmup(|| async {});
+    assert!(rt.memory_report().op_bytes > before.op_bytes);
+}
@@ -0,0 +1,52 @@
+use monoio::sync::local::watch;
+
+#[monoio::test_all]
+async fn local_latest_value() {
+    let (tx, mut rx) = watch::channel(0);
+    assert!(!rx.has_changed().unwrap());
+    tx.send(1);
+    tx.send(2);
+    assert!(rx.has_changed().unwrap());
+    rx.changed().await.unwrap();
+    // only the latest value is observed
+    assert_eq!(*rx.borrow(), 2);
+    assert!(!rx.has_changed().unwrap());
+
+    let mut rx2 = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 2);
+    let waiter = monoio::spawn(async move {
+        rx2.changed().await.unwrap();
+        let v = *rx2.borrow_and_update();
+        // the sender is dropped
+        assert!(rx2.changed().await.is_err());
+        v
+    });
+    monoio::spawn(async move {
+        tx.send_modify(|v| *v += 1);
+    });
+    assert_eq!(waiter.await, 3);
+    assert!(rx.has_changed().is_err());
+    // the unseen value is still delivered after the sender is dropped
+    rx.changed().await.unwrap();
+    assert!(rx.changed().await.is_err());
+}
+
+#[cfg(feature = "sync")]
+#[monoio::test_all]
+async fn cross_thread() {
+    use monoio::sync::watch;
+
+    let (tx, mut rx) = watch::channel(String::from("v0"));
+    let handle = std::thread::spawn(move || {
+        for i in 1..=3 {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            tx.send(format!("v{i}"));
+        }
+    });
+    let mut last = String::new();
+    while rx.changed().await.is_ok() {
+        last = rx.borrow_and_update().clone();
+    }
+    assert_eq!(last, "v3");
+    handle.join().unwrap();
+}
@@ -61,3 +61,106 @@ async fn zero_copy_for_uds() {
     assert_eq!(zero_copy(&mut rx, &mut tx).await.unwrap(), MSG.len() as u64);
     c_tx.closed().await;
 }
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn splice_through_pipe() {
+    use monoio::{
+        buf::IoBufMut,
+        io::{AsyncReadRentExt, AsyncWriteRentExt, SpliceFlags},
+        net::{Pipe, UnixStream},
+    };
+
+    const MSG: &[u8] = b"splice through pipe";
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let (mut c, mut d) = UnixStream::pair().unwrap();
+    let (mut pr, mut pw) = Pipe::new().unwrap();
+
+    a.write_all(MSG).await.0.unwrap();
+    let n = b
+        .splice_to(&mut pw, MSG.len() as u32, SpliceFlags::default())
+        .await
+        .unwrap();
+    assert_eq!(n, MSG.len() as u32);
+    let n = c
+        .splice_from(&mut pr, n, SpliceFlags::MOVE | SpliceFlags::MORE)
+        .await
+        .unwrap();
+    assert_eq!(n, MSG.len() as u32);
+
+    let buf = Vec::<u8>::with_capacity(MSG.len()).slice_mut(0..MSG.len());
+    let (res, buf) = d.read_exact(buf).await;
+    res.unwrap();
+    assert_eq!(&buf.into_inner(), MSG);
+
+    drop(a);
+    let n = b.splice_to(&mut pw, 1024, SpliceFlags::NONE).await.unwrap();
+    assert_eq!(n, 0);
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn tee_pipe() {
+    use monoio::{
+        buf::IoBufMut,
+        io::{AsyncReadRentExt, AsyncWriteRentExt, SpliceFlags},
+        net::{Pipe, UnixStream},
+    };
+
+    const MSG: &[u8] = b"tee between pipes";
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let (mut pr, mut pw) = Pipe::new().unwrap();
+    let (mut mirror_r, mut mirror_w) = Pipe::new().unwrap();
+
+    a.write_all(MSG).await.0.unwrap();
+    let n = b
+        .splice_to(&mut pw, MSG.len() as u32, SpliceFlags::default())
+        .await
+        .unwrap();
+    assert_eq!(n, MSG.len() as u32);
+    let n = pr.tee(&mut mirror_w, n, SpliceFlags::NONE).await.unwrap();
+    assert_eq!(n, MSG.len() as u32);
+
+    // both pipes hold the data now
+    for pipe in [&mut pr, &mut mirror_r] {
+        let (mut c, mut d) = UnixStream::pair().unwrap();
+        let n = c
+            .splice_from(pipe, MSG.len() as u32, SpliceFlags::default())
+            .await
+            .unwrap();
+        assert_eq!(n, MSG.len() as u32);
+        let buf = Vec::<u8>::with_capacity(MSG.len()).slice_mut(0..MSG.len());
+        let (res, buf) = d.read_exact(buf).await;
+        res.unwrap();
+        assert_eq!(&buf.into_inner(), MSG);
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn splice_child_stdout() {
+    use std::process::{Command, Stdio};
+
+    use monoio::{
+        io::{AsyncReadRentExt, SpliceFlags},
+        net::{Pipe, UnixStream},
+    };
+
+    let mut child = Command::new("printf")
+        .arg("log line")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = Pipe::try_from(child.stdout.take().unwrap()).unwrap();
+    assert!(child.wait().unwrap().success());
+
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let n = a
+        .splice_from(&mut stdout, 1024, SpliceFlags::default())
+        .await
+        .unwrap();
+    assert_eq!(n, 8);
+    let (res, buf) = b.read_exact(vec![0; 8]).await;
+    res.unwrap();
+    assert_eq!(buf, b"log line");
+}